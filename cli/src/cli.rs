@@ -144,6 +144,14 @@ pub struct RunCmd {
 	/// **Dangerous!** Do not touch unless explicitly adviced to.
 	#[arg(long)]
 	pub overseer_channel_capacity_override: Option<usize>,
+
+	/// Keep the session info of all past sessions in the parachains database.
+	///
+	/// By default, session info is only kept for the sessions of the rolling session window.
+	/// Keeping all of it allows dealing with disputes and inspecting old sessions, even once
+	/// their session info got pruned from the runtime state.
+	#[arg(long)]
+	pub keep_session_info: bool,
 }
 
 #[allow(missing_docs)]
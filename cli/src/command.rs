@@ -347,6 +347,7 @@ where
 			false,
 			overseer_gen,
 			cli.run.overseer_channel_capacity_override,
+			cli.run.keep_session_info,
 			maybe_malus_finality_delay,
 			hwbench,
 		)
@@ -23,6 +23,7 @@ use polkadot_primitives::{
 	runtime_api::ParachainHost,
 	v2::{AccountId, Balance, Block, BlockNumber, Hash, Header, Nonce},
 };
+use polkadot_runtime_common::auctions::AuctionsApi;
 use sc_client_api::{AuxStore, Backend as BackendT, BlockchainEvents, KeyIterator, UsageProvider};
 use sc_executor::NativeElseWasmExecutor;
 use sp_api::{CallApiAt, Encode, NumberFor, ProvideRuntimeApi};
@@ -126,6 +127,7 @@ pub trait RuntimeApiCollection:
 	+ sp_consensus_babe::BabeApi<Block>
 	+ sp_finality_grandpa::GrandpaApi<Block>
 	+ ParachainHost<Block>
+	+ AuctionsApi<Block, AccountId, Balance, BlockNumber>
 	+ sp_block_builder::BlockBuilder<Block>
 	+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 	+ sp_mmr_primitives::MmrApi<Block, <Block as BlockT>::Hash, BlockNumber>
@@ -147,6 +149,7 @@ where
 		+ sp_consensus_babe::BabeApi<Block>
 		+ sp_finality_grandpa::GrandpaApi<Block>
 		+ ParachainHost<Block>
+		+ AuctionsApi<Block, AccountId, Balance, BlockNumber>
 		+ sp_block_builder::BlockBuilder<Block>
 		+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 		+ sp_mmr_primitives::MmrApi<Block, <Block as BlockT>::Hash, BlockNumber>
@@ -23,7 +23,10 @@ use crate::{
 };
 use polkadot_node_subsystem_util::database::Database;
 use polkadot_primitives::v2::Id as ParaId;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::Arc,
+};
 
 use ::test_helpers::{dummy_candidate_receipt, dummy_candidate_receipt_bad_sig, dummy_hash};
 
@@ -425,7 +428,12 @@ fn canonicalize_works() {
 	]);
 
 	let mut overlay_db = OverlayedBackend::new(&db);
-	canonicalize(&mut overlay_db, 3, block_hash_c1).unwrap();
+	let deleted_candidates: HashSet<_> = canonicalize(&mut overlay_db, 3, block_hash_c1)
+		.unwrap()
+		.into_iter()
+		.map(|entry| entry.candidate.hash())
+		.collect();
+	assert_eq!(deleted_candidates, [cand_hash_1, cand_hash_2].into_iter().collect());
 	let write_ops = overlay_db.into_write_ops();
 	db.write(write_ops).unwrap();
 
@@ -0,0 +1,98 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tallies of the approval votes observed per session, used for rewarding approval checking.
+//!
+//! Approvals of a candidate are counted once its entry is pruned on finality. Once the first
+//! block of a later session got finalized, all candidates of a session have been counted and
+//! its tally can be submitted to the runtime, which credits approval work from the median of
+//! the tallies of all validators.
+//!
+//! Tallies are only kept in memory. The tally of the session we were started in is incomplete
+//! and thus never submitted.
+
+use bitvec::{order::Lsb0 as BitOrderLsb0, slice::BitSlice};
+use polkadot_primitives::v2::SessionIndex;
+
+use std::collections::BTreeMap;
+
+/// The approval votes observed per validator, for every session with pruned candidates.
+#[derive(Debug, Default)]
+pub struct ApprovalsTallies {
+	/// The number of approval votes per validator, indexed by `ValidatorIndex`.
+	tallies: BTreeMap<SessionIndex, Vec<u32>>,
+	/// The first session whose candidates we have all seen, set on the first finalized block.
+	first_complete_session: Option<SessionIndex>,
+}
+
+impl ApprovalsTallies {
+	/// Count the approvals of a candidate of the given session.
+	pub fn note_approvals(
+		&mut self,
+		session: SessionIndex,
+		approvals: &BitSlice<u8, BitOrderLsb0>,
+	) {
+		let tally = self.tallies.entry(session).or_default();
+		for validator_index in approvals.iter_ones() {
+			if tally.len() <= validator_index {
+				tally.resize(validator_index + 1, 0);
+			}
+			tally[validator_index] = tally[validator_index].saturating_add(1);
+		}
+	}
+
+	/// Take the tallies of all sessions before the session of the latest finalized block.
+	///
+	/// Only the tallies of sessions we have seen all candidates of are returned, all other
+	/// tallies before the given session are dropped.
+	pub fn take_completed(
+		&mut self,
+		finalized_session: SessionIndex,
+	) -> Vec<(SessionIndex, Vec<u32>)> {
+		let first_complete_session =
+			*self.first_complete_session.get_or_insert(finalized_session.saturating_add(1));
+
+		let pending = self.tallies.split_off(&finalized_session);
+		std::mem::replace(&mut self.tallies, pending)
+			.into_iter()
+			.filter(|(session, _)| *session >= first_complete_session)
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bitvec::bitvec;
+
+	#[test]
+	fn tallies_of_the_first_session_are_dropped() {
+		let mut tallies = ApprovalsTallies::default();
+
+		tallies.note_approvals(1, &bitvec![u8, BitOrderLsb0; 1, 1]);
+		assert!(tallies.take_completed(1).is_empty());
+
+		tallies.note_approvals(1, &bitvec![u8, BitOrderLsb0; 0, 1]);
+		tallies.note_approvals(2, &bitvec![u8, BitOrderLsb0; 0, 0, 1]);
+		assert!(tallies.take_completed(2).is_empty());
+
+		tallies.note_approvals(2, &bitvec![u8, BitOrderLsb0; 1, 0, 1]);
+		tallies.note_approvals(3, &bitvec![u8, BitOrderLsb0; 1]);
+		assert_eq!(tallies.take_completed(3), vec![(2, vec![1, 0, 2])]);
+		assert!(tallies.take_completed(3).is_empty());
+		assert_eq!(tallies.take_completed(4), vec![(3, vec![1])]);
+	}
+}
@@ -163,7 +163,7 @@ async fn imported_block_info<Context>(
 		if env
 			.session_window
 			.as_ref()
-			.map_or(true, |s| session_index < s.earliest_session())
+			.map_or(true, |s| session_index < s.earliest_session() && !s.keeps_session_info())
 		{
 			gum::debug!(
 				target: LOG_TARGET,
@@ -212,7 +212,10 @@ async fn imported_block_info<Context>(
 		}
 	};
 
-	let session_info = match env.session_window.as_ref().and_then(|s| s.session_info(session_index))
+	let session_info = match env
+		.session_window
+		.as_ref()
+		.and_then(|s| s.session_info_or_cached(session_index))
 	{
 		Some(s) => s,
 		None => {
@@ -238,7 +241,7 @@ async fn imported_block_info<Context>(
 						let assignments = env.assignment_criteria.compute_assignments(
 							&env.keystore,
 							relay_vrf.clone(),
-							&crate::criteria::Config::from(session_info),
+							&crate::criteria::Config::from(&*session_info),
 							included_candidates
 								.iter()
 								.map(|(c_hash, _, core, group)| (*c_hash, *core, *group))
@@ -462,7 +465,7 @@ pub(crate) async fn handle_new_head<Context, B: Backend>(
 		let session_info = state
 			.session_window
 			.as_ref()
-			.and_then(|s| s.session_info(session_index))
+			.and_then(|s| s.session_info_or_cached(session_index))
 			.expect("imported_block_info requires session info to be available; qed");
 
 		let (block_tick, no_show_duration) = {
@@ -663,6 +666,8 @@ pub(crate) mod tests {
 			assignment_criteria: Box::new(MockAssignmentCriteria),
 			db,
 			db_config: TEST_CONFIG,
+			keep_session_info: false,
+			approvals_tallies: Default::default(),
 		}
 	}
 
@@ -698,7 +703,8 @@ pub(crate) mod tests {
 			_relay_vrf_story: polkadot_node_primitives::approval::RelayVRFStory,
 			_assignment: &polkadot_node_primitives::approval::AssignmentCert,
 			_backing_group: polkadot_primitives::v2::GroupIndex,
-		) -> Result<polkadot_node_primitives::approval::DelayTranche, criteria::InvalidAssignment> {
+		) -> Result<polkadot_node_primitives::approval::DelayTranche, criteria::InvalidAssignment>
+		{
 			Ok(0)
 		}
 	}
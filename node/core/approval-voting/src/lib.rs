@@ -48,10 +48,13 @@ use polkadot_node_subsystem_util::{
 	},
 	TimeoutExt,
 };
-use polkadot_primitives::v2::{
-	ApprovalVote, BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt, DisputeStatement,
-	GroupIndex, Hash, SessionIndex, SessionInfo, ValidDisputeStatementKind, ValidatorId,
-	ValidatorIndex, ValidatorPair, ValidatorSignature,
+use polkadot_primitives::{
+	v2::{
+		ApprovalVote, BlockNumber, CandidateHash, CandidateIndex, CandidateReceipt,
+		DisputeStatement, GroupIndex, Hash, SessionIndex, SessionInfo, ValidDisputeStatementKind,
+		ValidatorId, ValidatorIndex, ValidatorPair, ValidatorSignature,
+	},
+	vstaging::ApprovalsTally,
 };
 use sc_keystore::LocalKeystore;
 use sp_application_crypto::Pair;
@@ -66,6 +69,7 @@ use futures::{
 };
 
 use std::{
+	borrow::Cow,
 	collections::{
 		btree_map::Entry as BTMEntry, hash_map::Entry as HMEntry, BTreeMap, HashMap, HashSet,
 	},
@@ -75,12 +79,14 @@ use std::{
 };
 
 use approval_checking::RequiredTranches;
+use approvals_tally::ApprovalsTallies;
 use criteria::{AssignmentCriteria, RealAssignmentCriteria};
 use persisted_entries::{ApprovalEntry, BlockEntry, CandidateEntry};
 use time::{slot_number_to_tick, Clock, ClockExt, SystemClock, Tick};
 
 mod approval_checking;
 mod approval_db;
+mod approvals_tally;
 mod backend;
 mod criteria;
 mod import;
@@ -121,6 +127,8 @@ pub struct Config {
 	/// The slot duration of the consensus algorithm, in milliseconds. Should be evenly
 	/// divisible by 500.
 	pub slot_duration_millis: u64,
+	/// Keep the cached session info of all sessions instead of pruning it with the session window.
+	pub keep_session_info: bool,
 }
 
 // The mode of the approval voting subsystem. It should start in a `Syncing` mode when it first
@@ -145,6 +153,7 @@ pub struct ApprovalVotingSubsystem {
 	keystore: Arc<LocalKeystore>,
 	db_config: DatabaseConfig,
 	slot_duration_millis: u64,
+	keep_session_info: bool,
 	db: Arc<dyn Database>,
 	mode: Mode,
 	metrics: Metrics,
@@ -356,6 +365,7 @@ impl ApprovalVotingSubsystem {
 		ApprovalVotingSubsystem {
 			keystore,
 			slot_duration_millis: config.slot_duration_millis,
+			keep_session_info: config.keep_session_info,
 			db,
 			db_config: DatabaseConfig {
 				col_approval_data: config.col_approval_data,
@@ -639,12 +649,15 @@ struct State {
 	// Require for `RollingSessionWindow`.
 	db_config: DatabaseConfig,
 	db: Arc<dyn Database>,
+	keep_session_info: bool,
+	// Approval votes observed per session, submitted for rewarding approval checking.
+	approvals_tallies: ApprovalsTallies,
 }
 
 #[overseer::contextbounds(ApprovalVoting, prefix = self::overseer)]
 impl State {
-	fn session_info(&self, i: SessionIndex) -> Option<&SessionInfo> {
-		self.session_window.as_ref().and_then(|w| w.session_info(i))
+	fn session_info(&self, i: SessionIndex) -> Option<Cow<'_, SessionInfo>> {
+		self.session_window.as_ref().and_then(|w| w.session_info_or_cached(i))
 	}
 
 	/// Bring `session_window` up to date.
@@ -667,6 +680,7 @@ impl State {
 						DatabaseParams {
 							db: self.db.clone(),
 							db_column: self.db_config.col_session_data,
+							keep_session_info: self.keep_session_info,
 						},
 					)
 					.await?,
@@ -777,6 +791,8 @@ where
 		assignment_criteria,
 		db_config: subsystem.db_config,
 		db: subsystem.db,
+		keep_session_info: subsystem.keep_session_info,
+		approvals_tallies: ApprovalsTallies::default(),
 	};
 
 	let mut wakeups = Wakeups::default();
@@ -1196,9 +1212,19 @@ async fn handle_from_overseer<Context>(
 			gum::debug!(target: LOG_TARGET, ?block_hash, ?block_number, "Block finalized");
 			*last_finalized_height = Some(block_number);
 
-			crate::ops::canonicalize(db, block_number, block_hash)
+			let finalized_session = db.load_block_entry(&block_hash)?.map(|entry| entry.session());
+			let pruned_candidates = crate::ops::canonicalize(db, block_number, block_hash)
 				.map_err(|e| SubsystemError::with_origin("db", e))?;
 
+			for candidate_entry in &pruned_candidates {
+				state
+					.approvals_tallies
+					.note_approvals(candidate_entry.session, candidate_entry.approvals());
+			}
+			if let Some(finalized_session) = finalized_session {
+				submit_approvals_tallies(ctx.sender(), state, block_hash, finalized_session).await;
+			}
+
 			wakeups.prune_finalized_wakeups(block_number);
 
 			Vec::new()
@@ -1735,7 +1761,7 @@ fn check_and_import_assignment(
 		let res = state.assignment_criteria.check_assignment_cert(
 			claimed_core_index,
 			assignment.validator,
-			&criteria::Config::from(session_info),
+			&criteria::Config::from(&*session_info),
 			block_entry.relay_vrf_story(),
 			&assignment.cert,
 			approval_entry.backing_group(),
@@ -2606,6 +2632,63 @@ fn sign_approval(
 	Some(key.sign(&payload[..]))
 }
 
+/// Sign and submit the tallies of the approval votes observed in all completed sessions before
+/// the session of the finalized block, for the sessions we were a validator in.
+async fn submit_approvals_tallies<Sender>(
+	sender: &mut Sender,
+	state: &mut State,
+	finalized_hash: Hash,
+	finalized_session: SessionIndex,
+) where
+	Sender: overseer::ApprovalVotingSenderTrait,
+{
+	let completed = state.approvals_tallies.take_completed(finalized_session);
+
+	for (session_index, mut approvals) in completed {
+		let session_info = match state.session_info(session_index) {
+			Some(session_info) => session_info,
+			None => {
+				gum::debug!(
+					target: LOG_TARGET,
+					session = session_index,
+					"Missing session info, not submitting approvals tally",
+				);
+				continue
+			},
+		};
+
+		let signing_key = session_info.validators.iter().enumerate().find_map(|(i, public)| {
+			let pair = state.keystore.key_pair::<ValidatorPair>(public).ok().flatten()?;
+			Some((ValidatorIndex(i as _), pair))
+		});
+		let (validator_index, pair) = match signing_key {
+			Some(signing_key) => signing_key,
+			// We were not a validator in this session.
+			None => continue,
+		};
+
+		approvals.resize(session_info.validators.len(), 0);
+		let tally = ApprovalsTally { session_index, validator_index, approvals };
+		let signature = pair.sign(&tally.signing_payload()[..]);
+
+		gum::debug!(
+			target: LOG_TARGET,
+			session = session_index,
+			?validator_index,
+			"Submitting approvals tally",
+		);
+
+		// The submission is fire-and-forget, there is nothing we could do about a failure.
+		let (tx, _rx) = oneshot::channel();
+		sender
+			.send_message(RuntimeApiMessage::Request(
+				finalized_hash,
+				RuntimeApiRequest::SubmitApprovalsTally(tally, signature, tx),
+			))
+			.await;
+	}
+}
+
 /// Send `IssueLocalStatement` to dispute-coordinator.
 fn issue_local_invalid_statement<Sender>(
 	sender: &mut Sender,
@@ -81,14 +81,17 @@ fn visit_and_remove_block_entry(
 
 /// Canonicalize some particular block, pruning everything before it and
 /// pruning any competing branches at the same height.
+///
+/// Returns the candidate entries which were deleted, as they are not referenced by any block
+/// anymore.
 pub fn canonicalize(
 	overlay_db: &mut OverlayedBackend<'_, impl Backend>,
 	canon_number: BlockNumber,
 	canon_hash: Hash,
-) -> SubsystemResult<()> {
+) -> SubsystemResult<Vec<CandidateEntry>> {
 	let range = match overlay_db.load_stored_blocks()? {
-		None => return Ok(()),
-		Some(range) if range.0 >= canon_number => return Ok(()),
+		None => return Ok(Vec::new()),
+		Some(range) if range.0 >= canon_number => return Ok(Vec::new()),
 		Some(range) => range,
 	};
 
@@ -153,9 +156,11 @@ pub fn canonicalize(
 	}
 
 	// Update all `CandidateEntry`s, deleting all those which now have empty `block_assignments`.
+	let mut deleted_candidates = Vec::new();
 	for (candidate_hash, candidate) in visited_candidates.into_iter() {
 		if candidate.block_assignments.is_empty() {
 			overlay_db.delete_candidate_entry(&candidate_hash);
+			deleted_candidates.push(candidate);
 		} else {
 			overlay_db.write_candidate_entry(candidate);
 		}
@@ -177,7 +182,7 @@ pub fn canonicalize(
 
 	overlay_db.write_stored_block_range(new_range);
 
-	Ok(())
+	Ok(deleted_candidates)
 }
 
 /// Record a new block entry.
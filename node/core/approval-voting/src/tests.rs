@@ -495,6 +495,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 				col_approval_data: test_constants::TEST_CONFIG.col_approval_data,
 				slot_duration_millis: SLOT_DURATION_MILLIS,
 				col_session_data: TEST_CONFIG.col_session_data,
+				keep_session_info: false,
 			},
 			Arc::new(db),
 			Arc::new(keystore),
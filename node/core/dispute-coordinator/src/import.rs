@@ -26,7 +26,10 @@
 //! got imported on top of an existing `CandidateVoteState` and reveals "dynamic" information, like whether
 //! due to the import a dispute was raised/got confirmed, ...
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+	borrow::Cow,
+	collections::{BTreeMap, HashMap, HashSet},
+};
 
 use polkadot_node_primitives::{CandidateVotes, DisputeStatus, SignedDisputeStatement, Timestamp};
 use polkadot_node_subsystem_util::rolling_session_window::RollingSessionWindow;
@@ -43,7 +46,7 @@ pub struct CandidateEnvironment<'a> {
 	/// The session the candidate appeared in.
 	session_index: SessionIndex,
 	/// Session for above index.
-	session: &'a SessionInfo,
+	session: Cow<'a, SessionInfo>,
 	/// Validator indices controlled by this node.
 	controlled_indices: HashSet<ValidatorIndex>,
 }
@@ -51,13 +54,14 @@ pub struct CandidateEnvironment<'a> {
 impl<'a> CandidateEnvironment<'a> {
 	/// Create `CandidateEnvironment`.
 	///
-	/// Return: `None` in case session is outside of session window.
+	/// Return: `None` in case session is outside of session window and its `SessionInfo` is not
+	/// kept in the session info cache.
 	pub fn new(
 		keystore: &LocalKeystore,
 		session_window: &'a RollingSessionWindow,
 		session_index: SessionIndex,
 	) -> Option<Self> {
-		let session = session_window.session_info(session_index)?;
+		let session = session_window.session_info_or_cached(session_index)?;
 		let controlled_indices = find_controlled_validator_indices(keystore, &session.validators);
 		Some(Self { session_index, session, controlled_indices })
 	}
@@ -61,6 +61,7 @@ use super::{
 		WorkerMessageReceiver,
 	},
 	scraping::ChainScraper,
+	slashing::SlashingReporter,
 	spam_slots::SpamSlots,
 	OverlayedBackend,
 };
@@ -78,6 +79,7 @@ pub struct Initialized {
 	participation: Participation,
 	scraper: ChainScraper,
 	participation_receiver: WorkerMessageReceiver,
+	slashing_reporter: SlashingReporter,
 	metrics: Metrics,
 	// This tracks only rolling session window failures.
 	// It can be a `Vec` if the need to track more arises.
@@ -107,6 +109,7 @@ impl Initialized {
 			scraper,
 			participation,
 			participation_receiver,
+			slashing_reporter: SlashingReporter::new(),
 			metrics,
 			error: None,
 		}
@@ -274,6 +277,7 @@ impl Initialized {
 		self.participation.process_active_leaves_update(ctx, &update).await?;
 
 		if let Some(new_leaf) = update.activated {
+			let mut session_advanced = false;
 			match self
 				.rolling_session_window
 				.cache_session_info_for_head(ctx.sender(), new_leaf.hash)
@@ -298,6 +302,7 @@ impl Initialized {
 						gum::trace!(target: LOG_TARGET, session, "Observed new session. Pruning");
 
 						self.highest_session = session;
+						session_advanced = true;
 
 						db::v1::note_earliest_session(overlay_db, new_window_start)?;
 						self.spam_slots.prune_old(new_window_start);
@@ -306,6 +311,14 @@ impl Initialized {
 				Ok(SessionWindowUpdate::Unchanged) => {},
 			};
 
+			if self.error.is_none() {
+				self.slashing_reporter.note_leaf(
+					self.rolling_session_window.latest_session(),
+					new_leaf.hash,
+					self.rolling_session_window.earliest_session(),
+				);
+			}
+
 			// The `runtime-api` subsystem has an internal queue which serializes the execution,
 			// so there is no point in running these in parallel.
 			for votes in on_chain_votes {
@@ -319,6 +332,25 @@ impl Initialized {
 					},
 				);
 			}
+
+			if self.slashing_reporter.should_check(session_advanced, new_leaf.number) {
+				let _ = self
+					.slashing_reporter
+					.process_unapplied_slashes(
+						ctx.sender(),
+						overlay_db,
+						new_leaf.hash,
+						new_leaf.number,
+					)
+					.await
+					.map_err(|error| {
+						gum::warn!(
+							target: LOG_TARGET,
+							?error,
+							"Failed to submit dispute slashing reports",
+						);
+					});
+			}
 		}
 
 		Ok(())
@@ -340,21 +372,22 @@ impl Initialized {
 		}
 
 		// Obtain the session info, for sake of `ValidatorId`s
-		// either from the rolling session window.
+		// either from the rolling session window or the session info cache.
 		// Must be called _after_ `fn cache_session_info_for_head`
 		// which guarantees that the session info is available
 		// for the current session.
-		let session_info: SessionInfo =
-			if let Some(session_info) = self.rolling_session_window.session_info(session) {
-				session_info.clone()
-			} else {
-				gum::warn!(
-					target: LOG_TARGET,
-					?session,
-					"Could not retrieve session info from rolling session window",
-				);
-				return Ok(())
-			};
+		let session_info: SessionInfo = if let Some(session_info) =
+			self.rolling_session_window.session_info_or_cached(session)
+		{
+			session_info.into_owned()
+		} else {
+			gum::warn!(
+				target: LOG_TARGET,
+				?session,
+				"Could not retrieve session info from rolling session window",
+			);
+			return Ok(())
+		};
 
 		// Scraped on-chain backing votes for the candidates with
 		// the new active leaf as if we received them via gossip.
@@ -459,9 +492,9 @@ impl Initialized {
 				.into_iter()
 				.filter_map(|(dispute_statement, validator_index, validator_signature)| {
 					let session_info: SessionInfo = if let Some(session_info) =
-						self.rolling_session_window.session_info(session)
+						self.rolling_session_window.session_info_or_cached(session)
 					{
-						session_info.clone()
+						session_info.into_owned()
 					} else {
 						gum::warn!(
 								target: LOG_TARGET,
@@ -708,8 +741,12 @@ impl Initialized {
 		now: Timestamp,
 	) -> Result<ImportStatementsResult> {
 		gum::trace!(target: LOG_TARGET, ?statements, "In handle import statements");
-		if !self.rolling_session_window.contains(session) {
-			// It is not valid to participate in an ancient dispute (spam?) or too new.
+		if session > self.rolling_session_window.latest_session() ||
+			(session < self.rolling_session_window.earliest_session() &&
+				!self.rolling_session_window.keeps_session_info())
+		{
+			// It is not valid to participate in an ancient dispute (spam?) or too new. Ancient
+			// sessions are only served if their `SessionInfo` is kept in the session info cache.
 			return Ok(ImportStatementsResult::InvalidImport)
 		}
 
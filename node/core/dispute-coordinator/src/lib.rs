@@ -99,6 +99,10 @@ mod metrics;
 /// Status tracking of disputes (`DisputeStatus`).
 mod status;
 
+/// Submission of slashing reports for validators who lost a dispute about a past session
+/// candidate.
+mod slashing;
+
 use crate::status::Clock;
 
 #[cfg(test)]
@@ -121,6 +125,8 @@ pub struct Config {
 	pub col_dispute_data: u32,
 	/// The data column in the store to use for session data.
 	pub col_session_data: u32,
+	/// Keep the cached session info of all sessions instead of pruning it with the session window.
+	pub keep_session_info: bool,
 }
 
 impl Config {
@@ -205,8 +211,11 @@ impl DisputeCoordinatorSubsystem {
 		B: Backend + 'static,
 	{
 		loop {
-			let db_params =
-				DatabaseParams { db: self.store.clone(), db_column: self.config.col_session_data };
+			let db_params = DatabaseParams {
+				db: self.store.clone(),
+				db_column: self.config.col_session_data,
+				keep_session_info: self.config.keep_session_info,
+			};
 
 			let (first_leaf, rolling_session_window) =
 				match get_rolling_session_window(ctx, db_params).await {
@@ -302,7 +311,7 @@ impl DisputeCoordinatorSubsystem {
 					},
 				};
 
-			let validators = match rolling_session_window.session_info(session) {
+			let validators = match rolling_session_window.session_info_or_cached(session) {
 				None => {
 					gum::warn!(
 						target: LOG_TARGET,
@@ -0,0 +1,233 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Submission of slashing reports for disputes concluded about past session candidates.
+//!
+//! When a dispute concludes about a candidate of a past session, the runtime can't identify the
+//! offenders on its own and records the losers as unapplied slashes instead. Any block producer
+//! can then apply those by submitting an unsigned transaction carrying a key ownership proof of
+//! the offender.
+//!
+//! On new leaves we fetch the unapplied slashes and for every offender we have not reported
+//! recently we fetch a key ownership proof and submit the report through the local transaction
+//! pool. Reports are retried every `SLASHING_REPORT_RETRY_INTERVAL` blocks for as long as they
+//! stay pending on chain.
+//!
+//! Key ownership proofs only prove membership in the session current at the block they are
+//! generated at, so they are generated at a block of the session of the offence: the relay parent
+//! of the disputed candidate, if we still have its receipt, or a leaf of that session we saw.
+//!
+//! Every validator submits the reports on its own. The transactions are not propagated, so
+//! only a report submitted by the block author gets included. Deduplication across validators
+//! happens on chain: once a report is applied the slash is removed from the unapplied slashes,
+//! and all other copies of the report become stale and are dropped from the transaction pools.
+
+use std::collections::{BTreeMap, HashMap};
+
+use polkadot_node_subsystem::{errors::RuntimeApiError, overseer};
+use polkadot_node_subsystem_util::runtime::{
+	self, get_unapplied_slashes, key_ownership_proof, submit_report_dispute_lost,
+};
+use polkadot_primitives::{
+	v2::{BlockNumber, CandidateHash, Hash, SessionIndex, ValidatorIndex},
+	vstaging::slashing::{DisputeProof, DisputesTimeSlot, PendingSlashes},
+};
+
+use crate::{
+	backend::{Backend, OverlayedBackend},
+	error::Result,
+	LOG_TARGET,
+};
+
+/// How many blocks to wait before submitting a slashing report again, if it is still pending.
+pub const SLASHING_REPORT_RETRY_INTERVAL: BlockNumber = 10;
+
+/// Tracks submitted slashing reports and decides when to check for pending slashes again.
+pub struct SlashingReporter {
+	/// Block numbers at which reports were last submitted, per offence.
+	submitted: HashMap<(SessionIndex, CandidateHash, ValidatorIndex), BlockNumber>,
+	/// The block number from which on pending slashes should be checked again, if any.
+	next_check_at: Option<BlockNumber>,
+	/// The first leaf we saw of every session in the session window.
+	session_leaves: BTreeMap<SessionIndex, Hash>,
+}
+
+impl SlashingReporter {
+	/// Create a new reporter that hasn't submitted anything yet.
+	///
+	/// Pending slashes are checked on the first leaf processed, so we don't have to wait for the
+	/// next session change after a restart.
+	pub fn new() -> Self {
+		Self { submitted: HashMap::new(), next_check_at: Some(0), session_leaves: BTreeMap::new() }
+	}
+
+	/// Note a leaf whose state has `session` as the current session.
+	///
+	/// Leaves of sessions before `earliest_session` are forgotten.
+	pub fn note_leaf(
+		&mut self,
+		session: SessionIndex,
+		leaf_hash: Hash,
+		earliest_session: SessionIndex,
+	) {
+		self.session_leaves.entry(session).or_insert(leaf_hash);
+		self.session_leaves = self.session_leaves.split_off(&earliest_session);
+	}
+
+	/// Returns a block of the given session to generate key ownership proofs at.
+	fn block_in_session(
+		&self,
+		overlay_db: &OverlayedBackend<'_, impl Backend>,
+		session_index: SessionIndex,
+		candidate_hash: CandidateHash,
+	) -> Result<Option<Hash>> {
+		if let Some(votes) = overlay_db.load_candidate_votes(session_index, &candidate_hash)? {
+			return Ok(Some(votes.candidate_receipt.descriptor.relay_parent))
+		}
+		Ok(self.session_leaves.get(&session_index).copied())
+	}
+
+	/// Whether pending slashes should be checked at a leaf with the given number.
+	///
+	/// New unapplied slashes can only appear on a session change, so in between we only need to
+	/// look again when there are reports to retry.
+	pub fn should_check(&self, session_advanced: bool, leaf_number: BlockNumber) -> bool {
+		session_advanced || self.next_check_at.map_or(false, |at| leaf_number >= at)
+	}
+
+	/// Fetch the unapplied slashes at the given leaf and submit reports for all offenders which
+	/// were not reported within the last `SLASHING_REPORT_RETRY_INTERVAL` blocks.
+	pub async fn process_unapplied_slashes<Sender>(
+		&mut self,
+		sender: &mut Sender,
+		overlay_db: &OverlayedBackend<'_, impl Backend>,
+		leaf_hash: Hash,
+		leaf_number: BlockNumber,
+	) -> Result<()>
+	where
+		Sender: overseer::DisputeCoordinatorSenderTrait,
+	{
+		let pending = match get_unapplied_slashes(sender, leaf_hash).await {
+			Ok(pending) => pending,
+			Err(runtime::Error::RuntimeRequest(RuntimeApiError::NotSupported { .. })) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?leaf_hash,
+					"Runtime does not support unapplied slashes, skipping slashing reports",
+				);
+				self.next_check_at = None;
+				return Ok(())
+			},
+			Err(err) => return Err(err.into()),
+		};
+
+		// Forget about reports which are not pending anymore.
+		self.submitted.retain(|(session_index, candidate_hash, validator_index), _| {
+			pending.iter().any(|(s, c, slashes)| {
+				s == session_index &&
+					c == candidate_hash &&
+					slashes.keys.contains_key(validator_index)
+			})
+		});
+
+		for (session_index, candidate_hash, PendingSlashes { keys, kind }) in pending {
+			let proof_at = match self.block_in_session(overlay_db, session_index, candidate_hash)? {
+				Some(hash) => hash,
+				None => {
+					gum::debug!(
+						target: LOG_TARGET,
+						?session_index,
+						?candidate_hash,
+						"No block of the offence session known, can't report pending slashes",
+					);
+					continue
+				},
+			};
+
+			for (validator_index, validator_id) in keys {
+				let key = (session_index, candidate_hash, validator_index);
+				let recently_submitted = self.submitted.get(&key).map_or(false, |submitted_at| {
+					leaf_number < submitted_at.saturating_add(SLASHING_REPORT_RETRY_INTERVAL)
+				});
+				if recently_submitted {
+					continue
+				}
+
+				let key_ownership_proof =
+					match key_ownership_proof(sender, proof_at, validator_id.clone()).await? {
+						Some(proof) => proof,
+						None => {
+							// The validator has rotated its keys within the session or the
+							// historical session data got pruned, nothing we can prove.
+							gum::debug!(
+								target: LOG_TARGET,
+								?session_index,
+								?candidate_hash,
+								?validator_id,
+								"Could not generate a key ownership proof for a pending slash",
+							);
+							continue
+						},
+					};
+
+				let dispute_proof = DisputeProof {
+					time_slot: DisputesTimeSlot::new(session_index, candidate_hash),
+					kind,
+					validator_index,
+					validator_id,
+				};
+
+				match submit_report_dispute_lost(
+					sender,
+					leaf_hash,
+					dispute_proof,
+					key_ownership_proof,
+				)
+				.await?
+				{
+					Some(()) => {
+						gum::info!(
+							target: LOG_TARGET,
+							?session_index,
+							?candidate_hash,
+							?validator_index,
+							"Submitted dispute slashing report",
+						);
+					},
+					None => {
+						gum::warn!(
+							target: LOG_TARGET,
+							?session_index,
+							?candidate_hash,
+							?validator_index,
+							"Failed to submit dispute slashing report",
+						);
+					},
+				}
+				// Also back off on failures, we retry after the interval either way.
+				self.submitted.insert(key, leaf_number);
+			}
+		}
+
+		self.next_check_at = self
+			.submitted
+			.values()
+			.min()
+			.map(|submitted_at| submitted_at.saturating_add(SLASHING_REPORT_RETRY_INTERVAL));
+
+		Ok(())
+	}
+}
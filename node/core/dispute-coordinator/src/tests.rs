@@ -60,11 +60,14 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_test_helpers::{
 	make_buffered_subsystem_context, TestSubsystemContextHandle,
 };
-use polkadot_primitives::v2::{
-	ApprovalVote, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash,
-	CandidateReceipt, CoreIndex, DisputeStatement, GroupIndex, Hash, HeadData, Header, IndexedVec,
-	MultiDisputeStatementSet, ScrapedOnChainVotes, SessionIndex, SessionInfo, SigningContext,
-	ValidDisputeStatementKind, ValidatorId, ValidatorIndex, ValidatorSignature,
+use polkadot_primitives::{
+	v2::{
+		ApprovalVote, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash,
+		CandidateReceipt, CoreIndex, DisputeStatement, GroupIndex, Hash, HeadData, Header,
+		IndexedVec, MultiDisputeStatementSet, ScrapedOnChainVotes, SessionIndex, SessionInfo,
+		SigningContext, ValidDisputeStatementKind, ValidatorId, ValidatorIndex, ValidatorSignature,
+	},
+	vstaging::slashing::{OpaqueKeyOwnershipProof, PendingSlashes, SlashingOffenceKind},
 };
 
 use crate::{
@@ -140,6 +143,12 @@ struct TestState {
 	last_block: Hash,
 	// last session the subsystem knows about.
 	known_session: Option<SessionIndex>,
+	// whether the subsystem has checked for unapplied slashes since it was started.
+	checked_unapplied_slashes: bool,
+	// unapplied slashes returned by the runtime.
+	unapplied_slashes: Vec<(SessionIndex, CandidateHash, PendingSlashes)>,
+	// blocks at which key ownership proofs for slashing reports were requested.
+	key_ownership_proofs_at: Vec<Hash>,
 }
 
 impl Default for TestState {
@@ -180,7 +189,7 @@ impl Default for TestState {
 		let db = kvdb_memorydb::create(1);
 		let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[]);
 		let db = Arc::new(db);
-		let config = Config { col_dispute_data: 0, col_session_data: 1 };
+		let config = Config { col_dispute_data: 0, col_session_data: 1, keep_session_info: false };
 
 		let genesis_header = Header {
 			parent_hash: Hash::zero(),
@@ -206,6 +215,9 @@ impl Default for TestState {
 			headers,
 			last_block,
 			known_session: None,
+			checked_unapplied_slashes: false,
+			unapplied_slashes: Vec::new(),
+			key_ownership_proofs_at: Vec::new(),
 		}
 	}
 }
@@ -279,6 +291,11 @@ impl TestState {
 		}
 
 		let mut finished_steps = FinishedSteps::new();
+		// Pending slashes are checked on the first leaf after startup and on every session change.
+		// The very first leaf is processed while initializing, where no checks happen.
+		let expect_unapplied_slashes = self
+			.known_session
+			.map_or(false, |known| !self.checked_unapplied_slashes || known < session);
 
 		while !finished_steps.is_done() {
 			let recv = overseer_recv(virtual_overseer).await;
@@ -393,6 +410,47 @@ impl TestState {
 				},
 			}
 		}
+
+		if expect_unapplied_slashes {
+			self.checked_unapplied_slashes = true;
+			assert_matches!(
+				overseer_recv(virtual_overseer).await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					h,
+					RuntimeApiRequest::UnappliedSlashes(tx),
+				)) => {
+					assert_eq!(h, block_hash);
+					tx.send(Ok(self.unapplied_slashes.clone())).unwrap();
+				}
+			);
+
+			for (_, _, pending) in self.unapplied_slashes.clone() {
+				for validator_id in pending.keys.into_values() {
+					assert_matches!(
+						overseer_recv(virtual_overseer).await,
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							h,
+							RuntimeApiRequest::KeyOwnershipProof(id, tx),
+						)) => {
+							assert_eq!(id, validator_id);
+							self.key_ownership_proofs_at.push(h);
+							tx.send(Ok(Some(OpaqueKeyOwnershipProof::new(Vec::new())))).unwrap();
+						}
+					);
+					assert_matches!(
+						overseer_recv(virtual_overseer).await,
+						AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+							h,
+							RuntimeApiRequest::SubmitReportDisputeLost(proof, _, tx),
+						)) => {
+							assert_eq!(h, block_hash);
+							assert_eq!(proof.validator_id, validator_id);
+							tx.send(Ok(Some(()))).unwrap();
+						}
+					);
+				}
+			}
+		}
 	}
 
 	async fn handle_resume_sync(
@@ -527,6 +585,7 @@ impl TestState {
 		F: FnOnce(TestState, VirtualOverseer) -> BoxFuture<'static, TestState>,
 	{
 		self.known_session = None;
+		self.checked_unapplied_slashes = false;
 		let (ctx, ctx_handle) = make_buffered_subsystem_context(TaskExecutor::new(), 1);
 		let subsystem = DisputeCoordinatorSubsystem::new(
 			self.db.clone(),
@@ -2868,6 +2927,72 @@ fn negative_issue_local_statement_only_triggers_import() {
 	});
 }
 
+#[test]
+fn slashing_reports_prove_key_ownership_in_the_offence_session() {
+	test_harness(|mut test_state, mut virtual_overseer| {
+		Box::pin(async move {
+			let session = 1;
+
+			test_state.handle_resume_sync(&mut virtual_overseer, session).await;
+			test_state
+				.activate_leaf_at_session(&mut virtual_overseer, session, 1, Vec::new())
+				.await;
+
+			// A candidate of session 1 whose receipt we know.
+			let relay_parent = Hash::repeat_byte(7);
+			let mut candidate_receipt = dummy_candidate_receipt_bad_sig(relay_parent, dummy_hash());
+			candidate_receipt.commitments_hash = CandidateCommitments::default().hash();
+			let candidate_hash = candidate_receipt.hash();
+
+			let valid_vote = test_state
+				.issue_backing_statement_with_index(ValidatorIndex(1), candidate_hash, session)
+				.await;
+			let (tx, rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_receipt,
+						session,
+						statements: vec![(valid_vote, ValidatorIndex(1))],
+						pending_confirmation: Some(tx),
+					},
+				})
+				.await;
+			rx.await.unwrap();
+
+			test_state
+				.activate_leaf_at_session(&mut virtual_overseer, session + 1, 2, Vec::new())
+				.await;
+			let session_2_leaf = test_state.last_block;
+
+			// Offences of both past sessions are reported at a leaf of session 3. The candidate
+			// of session 2 is unknown, so the first leaf we saw of that session is used.
+			let pending = |index: u32| PendingSlashes {
+				keys: vec![(
+					ValidatorIndex(index),
+					test_state.validator_public[ValidatorIndex(index)].clone(),
+				)]
+				.into_iter()
+				.collect(),
+				kind: SlashingOffenceKind::ForInvalid,
+			};
+			test_state.unapplied_slashes = vec![
+				(session, candidate_hash, pending(1)),
+				(session + 1, CandidateHash(Hash::repeat_byte(42)), pending(2)),
+			];
+			test_state
+				.activate_leaf_at_session(&mut virtual_overseer, session + 2, 3, Vec::new())
+				.await;
+			assert_eq!(test_state.key_ownership_proofs_at, vec![relay_parent, session_2_leaf]);
+
+			virtual_overseer.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
+			assert!(virtual_overseer.try_recv().await.is_none());
+
+			test_state
+		})
+	});
+}
+
 #[test]
 fn redundant_votes_ignored() {
 	test_harness(|mut test_state, mut virtual_overseer| {
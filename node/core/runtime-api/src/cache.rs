@@ -19,12 +19,18 @@ use std::{collections::btree_map::BTreeMap, num::NonZeroUsize};
 use lru::LruCache;
 use sp_consensus_babe::Epoch;
 
-use polkadot_primitives::v2::{
-	AuthorityDiscoveryId, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash,
-	CommittedCandidateReceipt, CoreState, DisputeState, GroupRotationInfo, Hash, Id as ParaId,
-	InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption, PersistedValidationData,
-	PvfCheckStatement, ScrapedOnChainVotes, SessionIndex, SessionInfo, ValidationCode,
-	ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
+use polkadot_primitives::{
+	v2::{
+		AuthorityDiscoveryId, BlockNumber, CandidateCommitments, CandidateEvent, CandidateHash,
+		CommittedCandidateReceipt, CoreState, DisputeState, GroupRotationInfo, Hash, Id as ParaId,
+		InboundDownwardMessage, InboundHrmpMessage, OccupiedCoreAssumption,
+		PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes, SessionIndex, SessionInfo,
+		ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
+	},
+	vstaging::{
+		slashing::{DisputeProof, OpaqueKeyOwnershipProof, PendingSlashes},
+		ApprovalsTally, HrmpTopology,
+	},
 };
 
 /// For consistency we have the same capacity for all caches. We use 128 as we'll only need that
@@ -62,6 +68,9 @@ pub(crate) struct RequestResultCache {
 		LruCache<(Hash, ParaId, OccupiedCoreAssumption), Option<ValidationCodeHash>>,
 	version: LruCache<Hash, u32>,
 	disputes: LruCache<Hash, Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)>>,
+	hrmp_topology: LruCache<Hash, HrmpTopology<BlockNumber>>,
+	unapplied_slashes: LruCache<Hash, Vec<(SessionIndex, CandidateHash, PendingSlashes)>>,
+	key_ownership_proof: LruCache<(Hash, ValidatorId), Option<OpaqueKeyOwnershipProof>>,
 }
 
 impl Default for RequestResultCache {
@@ -88,6 +97,9 @@ impl Default for RequestResultCache {
 			validation_code_hash: LruCache::new(DEFAULT_CACHE_CAP),
 			version: LruCache::new(DEFAULT_CACHE_CAP),
 			disputes: LruCache::new(DEFAULT_CACHE_CAP),
+			hrmp_topology: LruCache::new(DEFAULT_CACHE_CAP),
+			unapplied_slashes: LruCache::new(DEFAULT_CACHE_CAP),
+			key_ownership_proof: LruCache::new(DEFAULT_CACHE_CAP),
 		}
 	}
 }
@@ -368,6 +380,51 @@ impl RequestResultCache {
 	) {
 		self.disputes.put(relay_parent, value);
 	}
+
+	pub(crate) fn hrmp_topology(
+		&mut self,
+		relay_parent: &Hash,
+	) -> Option<&HrmpTopology<BlockNumber>> {
+		self.hrmp_topology.get(relay_parent)
+	}
+
+	pub(crate) fn cache_hrmp_topology(
+		&mut self,
+		relay_parent: Hash,
+		value: HrmpTopology<BlockNumber>,
+	) {
+		self.hrmp_topology.put(relay_parent, value);
+	}
+
+	pub(crate) fn unapplied_slashes(
+		&mut self,
+		relay_parent: &Hash,
+	) -> Option<&Vec<(SessionIndex, CandidateHash, PendingSlashes)>> {
+		self.unapplied_slashes.get(relay_parent)
+	}
+
+	pub(crate) fn cache_unapplied_slashes(
+		&mut self,
+		relay_parent: Hash,
+		value: Vec<(SessionIndex, CandidateHash, PendingSlashes)>,
+	) {
+		self.unapplied_slashes.put(relay_parent, value);
+	}
+
+	pub(crate) fn key_ownership_proof(
+		&mut self,
+		key: (Hash, ValidatorId),
+	) -> Option<&Option<OpaqueKeyOwnershipProof>> {
+		self.key_ownership_proof.get(&key)
+	}
+
+	pub(crate) fn cache_key_ownership_proof(
+		&mut self,
+		key: (Hash, ValidatorId),
+		value: Option<OpaqueKeyOwnershipProof>,
+	) {
+		self.key_ownership_proof.put(key, value);
+	}
 }
 
 pub(crate) enum RequestResult {
@@ -404,4 +461,11 @@ pub(crate) enum RequestResult {
 	ValidationCodeHash(Hash, ParaId, OccupiedCoreAssumption, Option<ValidationCodeHash>),
 	Version(Hash, u32),
 	Disputes(Hash, Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)>),
+	HrmpTopology(Hash, HrmpTopology<BlockNumber>),
+	UnappliedSlashes(Hash, Vec<(SessionIndex, CandidateHash, PendingSlashes)>),
+	KeyOwnershipProof(Hash, ValidatorId, Option<OpaqueKeyOwnershipProof>),
+	// This is a request with side-effects.
+	SubmitReportDisputeLost(Hash, DisputeProof, OpaqueKeyOwnershipProof, Option<()>),
+	// This is a request with side-effects and no result, hence ().
+	SubmitApprovalsTally(Hash, ApprovalsTally, ValidatorSignature, ()),
 }
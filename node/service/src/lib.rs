@@ -744,6 +744,7 @@ pub fn new_full<RuntimeApi, ExecutorDispatch, OverseerGenerator>(
 	overseer_enable_anyways: bool,
 	overseer_gen: OverseerGenerator,
 	overseer_message_channel_capacity_override: Option<usize>,
+	keep_session_info: bool,
 	_malus_finality_delay: Option<u32>,
 	hwbench: Option<sc_sysinfo::HwBench>,
 ) -> Result<NewFull<Arc<FullClient<RuntimeApi, ExecutorDispatch>>>, Error>
@@ -948,6 +949,7 @@ where
 		col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
 		slot_duration_millis: slot_duration.as_millis() as u64,
+		keep_session_info,
 	};
 
 	let candidate_validation_config = CandidateValidationConfig {
@@ -971,6 +973,7 @@ where
 	let dispute_coordinator_config = DisputeCoordinatorConfig {
 		col_dispute_data: parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
+		keep_session_info,
 	};
 
 	let rpc_handlers = service::spawn_tasks(service::SpawnTasksParams {
@@ -1397,6 +1400,7 @@ pub fn build_full(
 	overseer_enable_anyways: bool,
 	overseer_gen: impl OverseerGen,
 	overseer_message_channel_override: Option<usize>,
+	keep_session_info: bool,
 	malus_finality_delay: Option<u32>,
 	hwbench: Option<sc_sysinfo::HwBench>,
 ) -> Result<NewFull<Client>, Error> {
@@ -1416,6 +1420,7 @@ pub fn build_full(
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override,
+			keep_session_info,
 			malus_finality_delay,
 			hwbench,
 		)
@@ -1435,6 +1440,7 @@ pub fn build_full(
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override,
+			keep_session_info,
 			malus_finality_delay,
 			hwbench,
 		)
@@ -1454,6 +1460,7 @@ pub fn build_full(
 			overseer_enable_anyways,
 			overseer_gen,
 			overseer_message_channel_override,
+			keep_session_info,
 			malus_finality_delay,
 			hwbench,
 		)
@@ -1476,6 +1483,7 @@ pub fn build_full(
 				gum::warn!("Channel capacity should _never_ be tampered with on polkadot!");
 				capacity
 			}),
+			keep_session_info,
 			malus_finality_delay,
 			hwbench,
 		)
@@ -1545,6 +1553,7 @@ fn revert_approval_voting(db: Arc<dyn Database>, hash: Hash) -> sp_blockchain::R
 		col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
 		slot_duration_millis: Default::default(),
+		keep_session_info: false,
 	};
 
 	let approval_voting = approval_voting_subsystem::ApprovalVotingSubsystem::with_config(
@@ -38,14 +38,20 @@ use polkadot_node_primitives::{
 	CollationSecondedSignal, DisputeMessage, DisputeStatus, ErasureChunk, PoV,
 	SignedDisputeStatement, SignedFullStatement, ValidationResult,
 };
-use polkadot_primitives::v2::{
-	AuthorityDiscoveryId, BackedCandidate, BlockNumber, CandidateEvent, CandidateHash,
-	CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt, CoreState,
-	DisputeState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader, Id as ParaId,
-	InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet, OccupiedCoreAssumption,
-	PersistedValidationData, PvfCheckStatement, SessionIndex, SessionInfo,
-	SignedAvailabilityBitfield, SignedAvailabilityBitfields, ValidationCode, ValidationCodeHash,
-	ValidatorId, ValidatorIndex, ValidatorSignature,
+use polkadot_primitives::{
+	v2::{
+		AuthorityDiscoveryId, BackedCandidate, BlockNumber, CandidateEvent, CandidateHash,
+		CandidateIndex, CandidateReceipt, CollatorId, CommittedCandidateReceipt, CoreState,
+		DisputeState, GroupIndex, GroupRotationInfo, Hash, Header as BlockHeader, Id as ParaId,
+		InboundDownwardMessage, InboundHrmpMessage, MultiDisputeStatementSet,
+		OccupiedCoreAssumption, PersistedValidationData, PvfCheckStatement, SessionIndex,
+		SessionInfo, SignedAvailabilityBitfield, SignedAvailabilityBitfields, ValidationCode,
+		ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
+	},
+	vstaging::{
+		slashing::{DisputeProof, OpaqueKeyOwnershipProof, PendingSlashes},
+		ApprovalsTally, HrmpTopology,
+	},
 };
 use polkadot_statement_table::v2::Misbehavior;
 use std::{
@@ -698,6 +704,20 @@ pub enum RuntimeApiRequest {
 	),
 	/// Returns all on-chain disputes at given block number. Available in `v3`.
 	Disputes(RuntimeApiSender<Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)>>),
+	/// Get all open HRMP channels along with their queue fill levels and the pending open and
+	/// close channel requests. Available in `v3`.
+	HrmpTopology(RuntimeApiSender<HrmpTopology<BlockNumber>>),
+	/// Returns a list of validators that lost a past session dispute and need to be slashed.
+	/// Available in `v3`.
+	UnappliedSlashes(RuntimeApiSender<Vec<(SessionIndex, CandidateHash, PendingSlashes)>>),
+	/// Returns a merkle proof of a validator session key. Available in `v3`.
+	KeyOwnershipProof(ValidatorId, RuntimeApiSender<Option<OpaqueKeyOwnershipProof>>),
+	/// Submits an unsigned extrinsic to slash validator who lost a past session dispute into the
+	/// transaction pool. Available in `v3`.
+	SubmitReportDisputeLost(DisputeProof, OpaqueKeyOwnershipProof, RuntimeApiSender<Option<()>>),
+	/// Submits a tally of the approval votes observed in a past session into the transaction
+	/// pool. Available in `v3`.
+	SubmitApprovalsTally(ApprovalsTally, ValidatorSignature, RuntimeApiSender<()>),
 }
 
 impl RuntimeApiRequest {
@@ -705,6 +725,21 @@ impl RuntimeApiRequest {
 
 	/// `Disputes`
 	pub const DISPUTES_RUNTIME_REQUIREMENT: u32 = 3;
+
+	/// `HrmpTopology`
+	pub const HRMP_TOPOLOGY_RUNTIME_REQUIREMENT: u32 = 3;
+
+	/// `UnappliedSlashes`
+	pub const UNAPPLIED_SLASHES_RUNTIME_REQUIREMENT: u32 = 3;
+
+	/// `KeyOwnershipProof`
+	pub const KEY_OWNERSHIP_PROOF_RUNTIME_REQUIREMENT: u32 = 3;
+
+	/// `SubmitReportDisputeLost`
+	pub const SUBMIT_REPORT_DISPUTE_LOST_RUNTIME_REQUIREMENT: u32 = 3;
+
+	/// `SubmitApprovalsTally`
+	pub const SUBMIT_APPROVALS_TALLY_RUNTIME_REQUIREMENT: u32 = 3;
 }
 
 /// A message to the Runtime API subsystem.
@@ -24,6 +24,7 @@ use polkadot_primitives::{
 		PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes, SessionIndex, SessionInfo,
 		ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
 	},
+	vstaging::{self, HrmpTopology},
 };
 use sp_api::{ApiError, ApiExt, ProvideRuntimeApi};
 use sp_authority_discovery::AuthorityDiscoveryApi;
@@ -191,6 +192,46 @@ pub trait RuntimeApiSubsystemClient {
 		at: Hash,
 	) -> Result<Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)>, ApiError>;
 
+	/// Returns all open HRMP channels with their queue fill levels, as well as all pending open
+	/// and close channel requests.
+	/// This is a staging method! Do not use on production runtimes!
+	async fn hrmp_topology(&self, at: Hash) -> Result<HrmpTopology<BlockNumber>, ApiError>;
+
+	/// Returns a list of validators that lost a past session dispute and need to be slashed.
+	/// This is a staging method! Do not use on production runtimes!
+	async fn unapplied_slashes(
+		&self,
+		at: Hash,
+	) -> Result<Vec<(SessionIndex, CandidateHash, vstaging::slashing::PendingSlashes)>, ApiError>;
+
+	/// Returns a merkle proof of a validator session key in a past session.
+	/// This is a staging method! Do not use on production runtimes!
+	async fn key_ownership_proof(
+		&self,
+		at: Hash,
+		validator_id: ValidatorId,
+	) -> Result<Option<vstaging::slashing::OpaqueKeyOwnershipProof>, ApiError>;
+
+	/// Submits an unsigned extrinsic to slash validators who lost a dispute about
+	/// a candidate of a past session.
+	/// This is a staging method! Do not use on production runtimes!
+	async fn submit_report_dispute_lost(
+		&self,
+		at: Hash,
+		dispute_proof: vstaging::slashing::DisputeProof,
+		key_ownership_proof: vstaging::slashing::OpaqueKeyOwnershipProof,
+	) -> Result<Option<()>, ApiError>;
+
+	/// Submits a tally of the approval votes observed in a past session into the transaction
+	/// pool.
+	/// This is a staging method! Do not use on production runtimes!
+	async fn submit_approvals_tally(
+		&self,
+		at: Hash,
+		tally: vstaging::ApprovalsTally,
+		signature: ValidatorSignature,
+	) -> Result<(), ApiError>;
+
 	// === BABE API ===
 
 	/// Returns information regarding the current epoch.
@@ -381,4 +422,45 @@ where
 	) -> Result<Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)>, ApiError> {
 		self.runtime_api().disputes(&BlockId::Hash(at))
 	}
+
+	async fn hrmp_topology(&self, at: Hash) -> Result<HrmpTopology<BlockNumber>, ApiError> {
+		self.runtime_api().hrmp_topology(&BlockId::Hash(at))
+	}
+
+	async fn unapplied_slashes(
+		&self,
+		at: Hash,
+	) -> Result<Vec<(SessionIndex, CandidateHash, vstaging::slashing::PendingSlashes)>, ApiError> {
+		self.runtime_api().unapplied_slashes(&BlockId::Hash(at))
+	}
+
+	async fn key_ownership_proof(
+		&self,
+		at: Hash,
+		validator_id: ValidatorId,
+	) -> Result<Option<vstaging::slashing::OpaqueKeyOwnershipProof>, ApiError> {
+		self.runtime_api().key_ownership_proof(&BlockId::Hash(at), validator_id)
+	}
+
+	async fn submit_report_dispute_lost(
+		&self,
+		at: Hash,
+		dispute_proof: vstaging::slashing::DisputeProof,
+		key_ownership_proof: vstaging::slashing::OpaqueKeyOwnershipProof,
+	) -> Result<Option<()>, ApiError> {
+		self.runtime_api().submit_report_dispute_lost(
+			&BlockId::Hash(at),
+			dispute_proof,
+			key_ownership_proof,
+		)
+	}
+
+	async fn submit_approvals_tally(
+		&self,
+		at: Hash,
+		tally: vstaging::ApprovalsTally,
+		signature: ValidatorSignature,
+	) -> Result<(), ApiError> {
+		self.runtime_api().submit_approvals_tally(&BlockId::Hash(at), tally, signature)
+	}
 }
@@ -25,7 +25,7 @@ use kvdb::{DBKey, DBOp};
 use parity_scale_codec::{Decode, Encode};
 pub use polkadot_node_primitives::{new_session_window_size, SessionWindowSize};
 use polkadot_primitives::v2::{BlockNumber, Hash, SessionIndex, SessionInfo};
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use futures::channel::oneshot;
 use polkadot_node_subsystem::{
@@ -39,6 +39,8 @@ use polkadot_node_subsystem::{
 const SESSION_WINDOW_SIZE: SessionWindowSize = new_session_window_size!(6);
 const LOG_TARGET: &str = "parachain::rolling-session-window";
 const STORED_ROLLING_SESSION_WINDOW: &[u8] = b"Rolling_session_window";
+const STORED_SESSION_INFO_PREFIX: &[u8] = b"Rolling_session_info";
+const STORED_EARLIEST_CACHED_SESSION: &[u8] = b"Earliest_cached_session_info";
 
 /// Sessions unavailable in state to cache.
 #[derive(Debug, Clone, thiserror::Error)]
@@ -110,7 +112,11 @@ pub struct DatabaseParams {
 	pub db: Arc<dyn Database>,
 	/// The column which stores the rolling session info.
 	pub db_column: u32,
+	/// Keep the cached `SessionInfo` of all sessions ever seen instead of pruning it together
+	/// with the window.
+	pub keep_session_info: bool,
 }
+
 /// A rolling window of sessions and cached session info.
 pub struct RollingSessionWindow {
 	earliest_session: SessionIndex,
@@ -190,6 +196,7 @@ impl RollingSessionWindow {
 		let sessions = if sessions_missing_count > 0 {
 			match extend_sessions_from_chain_state(
 				stored_sessions,
+				Some(&db_params),
 				&mut sender,
 				block_hash,
 				&mut window_start,
@@ -242,16 +249,34 @@ impl RollingSessionWindow {
 	}
 
 	// Saves/Updates all sessions in the database.
+	// Cached session infos of sessions which left the window are pruned, unless we keep them.
 	// TODO: https://github.com/paritytech/polkadot/issues/6144
 	fn db_save(&mut self, stored_window: StoredWindow) {
 		if let Some(db_params) = self.db_params.as_ref() {
-			match db_params.db.write(DBTransaction {
-				ops: vec![DBOp::Insert {
-					col: db_params.db_column,
-					key: DBKey::from_slice(STORED_ROLLING_SESSION_WINDOW),
-					value: stored_window.encode(),
-				}],
+			let mut ops = vec![DBOp::Insert {
+				col: db_params.db_column,
+				key: DBKey::from_slice(STORED_ROLLING_SESSION_WINDOW),
+				value: stored_window.encode(),
+			}];
+
+			let earliest_cached = db_load_earliest_cached_session(db_params);
+			if let Some(earliest_cached) = earliest_cached.filter(|earliest_cached| {
+				!db_params.keep_session_info && *earliest_cached < stored_window.earliest_session
 			}) {
+				ops.extend((earliest_cached..stored_window.earliest_session).map(|index| {
+					DBOp::Delete {
+						col: db_params.db_column,
+						key: DBKey::from_slice(&session_info_key(index)),
+					}
+				}));
+				ops.push(DBOp::Insert {
+					col: db_params.db_column,
+					key: DBKey::from_slice(STORED_EARLIEST_CACHED_SESSION),
+					value: stored_window.earliest_session.encode(),
+				});
+			}
+
+			match db_params.db.write(DBTransaction { ops }) {
 				Ok(_) => {},
 				Err(err) => {
 					gum::warn!(target: LOG_TARGET, ?err, "Failed writing db entry");
@@ -284,6 +309,28 @@ impl RollingSessionWindow {
 		}
 	}
 
+	/// Access the session info for the given session index.
+	///
+	/// Sessions which already left the window are loaded from the database cache, if the window
+	/// was created with `keep_session_info` set.
+	pub fn session_info_or_cached(&self, index: SessionIndex) -> Option<Cow<'_, SessionInfo>> {
+		if index >= self.earliest_session {
+			return self.session_info(index).map(Cow::Borrowed)
+		}
+
+		self.db_params
+			.as_ref()
+			.filter(|db_params| db_params.keep_session_info)
+			.and_then(|db_params| load_cached_session_info(db_params, index))
+			.map(Cow::Owned)
+	}
+
+	/// Returns `true` if the session info of sessions which left the window is kept in the
+	/// database cache.
+	pub fn keeps_session_info(&self) -> bool {
+		self.db_params.as_ref().map_or(false, |db_params| db_params.keep_session_info)
+	}
+
 	/// Access the index of the earliest session.
 	pub fn earliest_session(&self) -> SessionIndex {
 		self.earliest_session
@@ -434,6 +481,7 @@ impl RollingSessionWindow {
 
 		match extend_sessions_from_chain_state(
 			sessions,
+			self.db_params.as_ref(),
 			sender,
 			block_hash,
 			&mut window_start,
@@ -510,10 +558,73 @@ async fn get_session_index_for_child(
 	}
 }
 
+fn session_info_key(session_index: SessionIndex) -> Vec<u8> {
+	let mut key = STORED_SESSION_INFO_PREFIX.to_vec();
+	key.extend_from_slice(&session_index.to_be_bytes());
+	key
+}
+
+fn db_load_earliest_cached_session(db_params: &DatabaseParams) -> Option<SessionIndex> {
+	let raw = db_params.db.get(db_params.db_column, STORED_EARLIEST_CACHED_SESSION).ok()??;
+	SessionIndex::decode(&mut &raw[..]).ok()
+}
+
+/// Load the `SessionInfo` of the given session from the cache in the parachains db.
+///
+/// Sessions are cached as they are fetched from chain state. Cached sessions which left the
+/// rolling window are pruned, unless `keep_session_info` is set in the `DatabaseParams`.
+pub fn load_cached_session_info(
+	db_params: &DatabaseParams,
+	session_index: SessionIndex,
+) -> Option<SessionInfo> {
+	let raw = db_params.db.get(db_params.db_column, &session_info_key(session_index)).ok()??;
+	match SessionInfo::decode(&mut &raw[..]) {
+		Ok(session_info) => Some(session_info),
+		Err(err) => {
+			gum::warn!(
+				target: LOG_TARGET,
+				?err,
+				session = ?session_index,
+				"Failed decoding cached session info"
+			);
+			None
+		},
+	}
+}
+
+// Cache the `SessionInfo` of a session fetched from chain state in the parachains db.
+fn db_store_session_info(
+	db_params: &DatabaseParams,
+	session_index: SessionIndex,
+	session_info: &SessionInfo,
+) {
+	let mut ops = vec![DBOp::Insert {
+		col: db_params.db_column,
+		key: DBKey::from_slice(&session_info_key(session_index)),
+		value: session_info.encode(),
+	}];
+
+	if db_load_earliest_cached_session(db_params)
+		.map_or(true, |earliest_cached| session_index < earliest_cached)
+	{
+		ops.push(DBOp::Insert {
+			col: db_params.db_column,
+			key: DBKey::from_slice(STORED_EARLIEST_CACHED_SESSION),
+			value: session_index.encode(),
+		});
+	}
+
+	if let Err(err) = db_params.db.write(DBTransaction { ops }) {
+		gum::warn!(target: LOG_TARGET, ?err, "Failed writing cached session info");
+	}
+}
+
 /// Attempts to extend db stored sessions with sessions missing between `start` and up to `end_inclusive`.
-/// Runtime session info fetching errors are ignored if that doesn't create a gap in the window.
+/// Sessions unavailable in chain state are served from the session info cache, if possible.
+/// Remaining session info fetching errors are ignored if that doesn't create a gap in the window.
 async fn extend_sessions_from_chain_state(
 	stored_sessions: Vec<SessionInfo>,
+	db_params: Option<&DatabaseParams>,
 	sender: &mut impl overseer::SubsystemSender<RuntimeApiMessage>,
 	block_hash: Hash,
 	window_start: &mut SessionIndex,
@@ -536,48 +647,52 @@ async fn extend_sessions_from_chain_state(
 			))
 			.await;
 
-		match rx.await {
+		let fetched = match rx.await {
 			Ok(Ok(Some(session_info))) => {
+				if let Some(db_params) = db_params {
+					db_store_session_info(db_params, i, &session_info);
+				}
+				Ok(session_info)
+			},
+			Ok(Ok(None)) => Err(SessionsUnavailableReason::Missing(i)),
+			Ok(Err(e)) => Err(SessionsUnavailableReason::RuntimeApi(e)),
+			Err(canceled) => Err(SessionsUnavailableReason::RuntimeApiUnavailable(canceled)),
+		};
+
+		// Fall back to the cache, e.g. if the session info got pruned from chain state already.
+		let fetched = fetched.or_else(|reason| {
+			match db_params.and_then(|db_params| load_cached_session_info(db_params, i)) {
+				Some(session_info) => {
+					gum::debug!(
+						target: LOG_TARGET,
+						session = ?i,
+						?reason,
+						"Session info unavailable from runtime, using cached session info."
+					);
+					Ok(session_info)
+				},
+				None => Err(reason),
+			}
+		});
+
+		match fetched {
+			Ok(session_info) => {
 				// We do not allow failure anymore after having at least 1 session in window.
 				allow_failure = false;
 				sessions.push(session_info);
 			},
-			Ok(Ok(None)) if !allow_failure => return Err(SessionsUnavailableReason::Missing(i)),
-			Ok(Ok(None)) => {
+			Err(reason) if !allow_failure => return Err(reason),
+			Err(reason) => {
 				// Handle `allow_failure` true.
 				// If we didn't get the session, we advance window start.
 				*window_start += 1;
 				gum::debug!(
 					target: LOG_TARGET,
 					session = ?i,
-					"Session info missing from runtime."
-				);
-			},
-			Ok(Err(e)) if !allow_failure => return Err(SessionsUnavailableReason::RuntimeApi(e)),
-			Err(canceled) if !allow_failure =>
-				return Err(SessionsUnavailableReason::RuntimeApiUnavailable(canceled)),
-			Ok(Err(err)) => {
-				// Handle `allow_failure` true.
-				// If we didn't get the session, we advance window start.
-				*window_start += 1;
-				gum::debug!(
-					target: LOG_TARGET,
-					session = ?i,
-					?err,
+					?reason,
 					"Error while fetching session information."
 				);
 			},
-			Err(err) => {
-				// Handle `allow_failure` true.
-				// If we didn't get the session, we advance window start.
-				*window_start += 1;
-				gum::debug!(
-					target: LOG_TARGET,
-					session = ?i,
-					?err,
-					"Channel error while fetching session information."
-				);
-			},
 		};
 	}
 
@@ -605,7 +720,7 @@ mod tests {
 		let db = kvdb_memorydb::create(NUM_COLUMNS);
 		let db = DbAdapter::new(db, &[]);
 		let db: Arc<dyn Database> = Arc::new(db);
-		DatabaseParams { db, db_column: SESSION_DATA_COL }
+		DatabaseParams { db, db_column: SESSION_DATA_COL, keep_session_info: false }
 	}
 
 	fn dummy_session_info(index: SessionIndex) -> SessionInfo {
@@ -1361,4 +1476,137 @@ mod tests {
 
 		futures::executor::block_on(futures::future::join(test_fut, aux_fut));
 	}
+
+	#[test]
+	fn cached_session_info_is_pruned_with_the_window() {
+		let start = 10 - (SESSION_WINDOW_SIZE.get() - 1);
+
+		for keep_session_info in [false, true] {
+			let db_params = DatabaseParams { keep_session_info, ..dummy_db_params() };
+
+			let window = cache_session_info_test(start, 10, None, start, Some(db_params.clone()));
+			for i in start..=10 {
+				assert_eq!(load_cached_session_info(&db_params, i), Some(dummy_session_info(i)));
+			}
+
+			let window = cache_session_info_test(start + 1, 11, Some(window), 11, None);
+			assert_eq!(load_cached_session_info(&db_params, start).is_some(), keep_session_info);
+			assert_eq!(load_cached_session_info(&db_params, 11), Some(dummy_session_info(11)));
+
+			assert!(!window.contains(start));
+			assert_eq!(window.session_info(start), None);
+			assert_eq!(
+				window.session_info_or_cached(start).map(Cow::into_owned),
+				keep_session_info.then(|| dummy_session_info(start)),
+			);
+			assert_eq!(
+				window.session_info_or_cached(11).map(Cow::into_owned),
+				Some(dummy_session_info(11)),
+			);
+		}
+	}
+
+	#[test]
+	fn session_info_pruned_from_state_is_served_from_cache() {
+		let session: SessionIndex = 10;
+		let start = session - (SESSION_WINDOW_SIZE.get() - 1);
+		// The first sessions of the window are gone from chain state, but were cached before.
+		let pruned = start..start + 3;
+
+		let db_params = dummy_db_params();
+		for i in pruned.clone() {
+			db_store_session_info(&db_params, i, &dummy_session_info(i));
+		}
+
+		let header = Header {
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+			number: 5,
+			state_root: Default::default(),
+			parent_hash: Default::default(),
+		};
+
+		let pool = TaskExecutor::new();
+		let (mut ctx, mut handle) = make_subsystem_context::<(), _>(pool.clone());
+
+		let hash = header.hash();
+
+		let test_fut = {
+			Box::pin(async move {
+				let sender = ctx.sender().clone();
+				let window = RollingSessionWindow::new(sender, hash, db_params).await.unwrap();
+
+				assert_eq!(window.earliest_session, start);
+				assert_eq!(
+					window.session_info,
+					(start..=session).map(dummy_session_info).collect::<Vec<_>>(),
+				);
+			})
+		};
+
+		let aux_fut = Box::pin(async move {
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					h,
+					RuntimeApiRequest::SessionIndexForChild(s_tx),
+				)) => {
+					assert_eq!(h, hash);
+					let _ = s_tx.send(Ok(session));
+				}
+			);
+
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::ChainApi(ChainApiMessage::FinalizedBlockNumber(
+					s_tx,
+				)) => {
+					let _ = s_tx.send(Ok(header.number));
+				}
+			);
+
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::ChainApi(ChainApiMessage::FinalizedBlockHash(
+					block_number,
+					s_tx,
+				)) => {
+					assert_eq!(block_number, header.number);
+					let _ = s_tx.send(Ok(Some(hash)));
+				}
+			);
+
+			assert_matches!(
+				handle.recv().await,
+				AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+					h,
+					RuntimeApiRequest::SessionIndexForChild(s_tx),
+				)) => {
+					assert_eq!(h, hash);
+					let _ = s_tx.send(Ok(session));
+				}
+			);
+
+			for i in start..=session {
+				assert_matches!(
+					handle.recv().await,
+					AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+						h,
+						RuntimeApiRequest::SessionInfo(j, s_tx),
+					)) => {
+						assert_eq!(h, hash);
+						assert_eq!(i, j);
+
+						let _ = s_tx.send(Ok(if pruned.contains(&i) {
+							None
+						} else {
+							Some(dummy_session_info(i))
+						}));
+					}
+				);
+			}
+		});
+
+		futures::executor::block_on(futures::future::join(test_fut, aux_fut));
+	}
 }
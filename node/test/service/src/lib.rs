@@ -99,6 +99,7 @@ pub fn new_full(
 		false,
 		polkadot_service::RealOverseerGen,
 		None,
+		false,
 		None,
 		None,
 	)
@@ -0,0 +1,100 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use futures::{pin_mut, select, FutureExt};
+use polkadot_primitives::{
+	runtime_api::ParachainHost,
+	v2::{BlockId, CandidateHash, Hash, ValidatorId, ValidatorIndex},
+	vstaging::slashing::{PendingSlashes, SlashingOffenceKind},
+};
+use polkadot_runtime_parachains::disputes::slashing::UnappliedSlashes;
+use polkadot_test_service::*;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_keyring::Sr25519Keyring;
+use std::sync::Arc;
+use substrate_test_client::BlockchainEventsExt;
+
+/// How many blocks to wait at most for the slash to be applied.
+const MAX_BLOCKS: usize = 30;
+
+/// Seeds a pending slash for a dispute lost by Alice in the genesis session and checks that the
+/// validators report it, so that it gets applied on chain.
+#[substrate_test_utils::test(flavor = "multi_thread")]
+async fn pending_dispute_slashes_get_reported() {
+	let mut builder = sc_cli::LoggerBuilder::new("");
+	builder.with_colors(false);
+	builder.init().expect("Sets up logger");
+
+	let candidate_hash = CandidateHash(Hash::repeat_byte(42));
+	let seed_pending_slash = move || {
+		let validator_id: ValidatorId = Sr25519Keyring::Alice.public().into();
+		let pending = PendingSlashes {
+			keys: vec![(ValidatorIndex(0), validator_id)].into_iter().collect(),
+			kind: SlashingOffenceKind::ForInvalid,
+		};
+		UnappliedSlashes::<polkadot_test_runtime::Runtime>::insert(0, candidate_hash, pending);
+	};
+
+	let alice_config = node_config(
+		seed_pending_slash.clone(),
+		tokio::runtime::Handle::current(),
+		Sr25519Keyring::Alice,
+		Vec::new(),
+		true,
+	);
+	let mut alice = run_validator_node(alice_config, None);
+
+	let bob_config = node_config(
+		seed_pending_slash,
+		tokio::runtime::Handle::current(),
+		Sr25519Keyring::Bob,
+		vec![alice.addr.clone()],
+		true,
+	);
+	let mut bob = run_validator_node(bob_config, None);
+
+	let unapplied_slashes = |client: &Arc<Client>| {
+		let best = client.info().best_hash;
+		client
+			.runtime_api()
+			.unapplied_slashes(&BlockId::Hash(best))
+			.expect("test runtime supports the staging API")
+	};
+	assert_eq!(unapplied_slashes(&alice.client).len(), 1);
+
+	let client = alice.client.clone();
+	let slash_applied = async move {
+		for _ in 0..MAX_BLOCKS {
+			client.wait_for_blocks(1).await;
+			if unapplied_slashes(&client).is_empty() {
+				return true
+			}
+		}
+		false
+	}
+	.fuse();
+	let t2 = alice.task_manager.future().fuse();
+	let t3 = bob.task_manager.future().fuse();
+
+	pin_mut!(slash_applied, t2, t3);
+
+	select! {
+		applied = slash_applied => assert!(applied, "pending slash was not reported in time"),
+		_ = t2 => panic!("service Alice failed"),
+		_ = t3 => panic!("service Bob failed"),
+	}
+}
@@ -282,7 +282,7 @@ impl IsSystem for Sibling {
 /// identified by `(A, B)`. A channel with the same para id in sender and recipient is invalid. That
 /// is, however, not enforced.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, RuntimeDebug, TypeInfo)]
-#[cfg_attr(feature = "std", derive(Hash))]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Hash))]
 pub struct HrmpChannelId {
 	/// The para that acts as the sender in this channel.
 	pub sender: Id,
@@ -66,6 +66,7 @@ fn main() -> Result<()> {
 					false,
 					polkadot_service::RealOverseerGen,
 					None,
+					false,
 					None,
 					None,
 				)
@@ -110,7 +110,7 @@
 //! All staging API functions should use primitives from `vstaging`. They should be clearly separated
 //! from the stable primitives.
 
-use crate::v2;
+use crate::{v2, vstaging};
 use parity_scale_codec::{Decode, Encode};
 use polkadot_core_primitives as pcp;
 use polkadot_parachain::primitives as ppp;
@@ -221,5 +221,54 @@ sp_api::decl_runtime_apis! {
 		/// Returns all onchain disputes.
 		#[api_version(3)]
 		fn disputes() -> Vec<(v2::SessionIndex, v2::CandidateHash, v2::DisputeState<v2::BlockNumber>)>;
+
+		/// Returns all open HRMP channels with the fill levels of their queues and the
+		/// recipients' watermarks, as well as all pending open and close channel requests.
+		#[api_version(3)]
+		fn hrmp_topology() -> vstaging::HrmpTopology<N>;
+
+		/// Returns the state of the validation code upgrade pipeline of the given para, or `None`
+		/// if the para is not registered.
+		#[api_version(3)]
+		fn para_code_upgrade_pipeline(para_id: ppp::Id) -> Option<vstaging::CodeUpgradePipeline<N>>;
+
+		/// Returns the hashes of the most recent heads of the given para along with the relay-chain
+		/// block numbers at which they were noted, ordered ascending by block number.
+		#[api_version(3)]
+		fn para_head_history(para_id: ppp::Id) -> Vec<(N, v2::Hash)>;
+
+		/// Returns a list of validators that lost a past session dispute and need to be slashed.
+		#[api_version(3)]
+		fn unapplied_slashes() -> Vec<(v2::SessionIndex, v2::CandidateHash, vstaging::slashing::PendingSlashes)>;
+
+		/// Returns a merkle proof of a validator session key.
+		#[api_version(3)]
+		fn key_ownership_proof(
+			validator_id: v2::ValidatorId,
+		) -> Option<vstaging::slashing::OpaqueKeyOwnershipProof>;
+
+		/// Submit an unsigned extrinsic to slash validators who lost a dispute about
+		/// a candidate of a past session.
+		#[api_version(3)]
+		fn submit_report_dispute_lost(
+			dispute_proof: vstaging::slashing::DisputeProof,
+			key_ownership_proof: vstaging::slashing::OpaqueKeyOwnershipProof,
+		) -> Option<()>;
+
+		/// Submits a tally of the approval votes observed in a past session into the
+		/// transaction pool.
+		#[api_version(3)]
+		fn submit_approvals_tally(tally: vstaging::ApprovalsTally, signature: v2::ValidatorSignature);
+
+		/// Returns the paras expected to hold an assigned parachain slot in the current lease
+		/// period and in each of the following ones, `lease_periods` in total.
+		#[api_version(3)]
+		fn assigned_slots_schedule(lease_periods: u32) -> Vec<vstaging::AssignedSlotsSchedule<N>>;
+
+		/// Returns the pending host configuration changes along with the sessions at which they
+		/// become active, ordered ascending by session. Each entry holds the changes to the
+		/// configuration of the entry before it, or to the active configuration for the first one.
+		#[api_version(3)]
+		fn pending_configs() -> Vec<(v2::SessionIndex, Vec<vstaging::HostConfigurationChange<N>>)>;
 	}
 }
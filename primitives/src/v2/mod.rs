@@ -318,6 +318,23 @@ pub mod well_known_keys {
 		})
 	}
 
+	/// The head-data of the given para.
+	///
+	/// The storage entry stores a `HeadData` encoded value.
+	pub fn para_head(para_id: Id) -> Vec<u8> {
+		let prefix = hex!["cd710b30bd2eab0352ddcc26417aa1941b3c252fcb29d88eff4f3de5de4476c3"];
+
+		para_id.using_encoded(|para_id: &[u8]| {
+			prefix
+				.as_ref()
+				.iter()
+				.chain(twox_64(para_id).iter())
+				.chain(para_id.iter())
+				.cloned()
+				.collect()
+		})
+	}
+
 	/// The signal that indicates whether the parachain should go-ahead with the proposed validation
 	/// code upgrade.
 	///
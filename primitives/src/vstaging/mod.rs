@@ -17,3 +17,235 @@
 //! Staging Primitives.
 
 // Put any primitives used by staging APIs functions here
+
+pub mod slashing;
+
+use crate::v2::{
+	Balance, Hash, HrmpChannelId, Id, SessionIndex, UpgradeGoAhead, UpgradeRestriction,
+	ValidationCodeHash, ValidatorIndex,
+};
+use parity_scale_codec::{Decode, Encode};
+use primitives::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+use sp_weights::Weight;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// An open HRMP channel together with the fill level of its message queue.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HrmpChannelInfo<N> {
+	/// The sender and the recipient of the channel.
+	pub id: HrmpChannelId,
+	/// The maximum number of messages that can be pending in the channel at once.
+	pub max_capacity: u32,
+	/// The maximum total size of the messages that can be pending in the channel at once.
+	pub max_total_size: u32,
+	/// The maximum message size that could be put into the channel.
+	pub max_message_size: u32,
+	/// The current number of messages pending in the channel.
+	pub msg_count: u32,
+	/// The total size in bytes of all message payloads pending in the channel.
+	pub total_size: u32,
+	/// The head of the Message Queue Chain for this channel.
+	pub mqc_head: Option<Hash>,
+	/// The amount that the sender supplied as a deposit when opening this channel.
+	pub sender_deposit: Balance,
+	/// The amount that the recipient supplied as a deposit when accepting this channel.
+	pub recipient_deposit: Balance,
+	/// The fee the sender pays to the recipient for each message sent over this channel.
+	pub fee_per_message: Balance,
+	/// The relay-chain block number up to which the recipient has processed its inbound
+	/// messages. `None` if the recipient has not advanced its watermark yet.
+	pub recipient_watermark: Option<N>,
+}
+
+/// A pending request to open an HRMP channel.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HrmpOpenChannelRequestInfo {
+	/// The sender and the recipient of the requested channel.
+	pub id: HrmpChannelId,
+	/// Indicates if this request was confirmed by the recipient.
+	pub confirmed: bool,
+	/// The maximum number of messages that can be pending in the channel at once.
+	pub max_capacity: u32,
+	/// The maximum total size of the messages that can be pending in the channel at once.
+	pub max_total_size: u32,
+	/// The maximum message size that could be put into the channel.
+	pub max_message_size: u32,
+	/// The amount that the sender supplied at the time of creation of this request.
+	pub sender_deposit: Balance,
+	/// The amount that the recipient supplied when confirming this request.
+	pub recipient_deposit: Balance,
+	/// The fee per message set by the recipient when confirming this request.
+	pub fee_per_message: Balance,
+}
+
+/// The full HRMP topology: all open channels and all pending open and close requests.
+///
+/// All the lists are sorted by the channel id.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HrmpTopology<N> {
+	/// All open channels.
+	pub channels: Vec<HrmpChannelInfo<N>>,
+	/// All pending requests to open a channel. These channels are opened at the next session
+	/// change if confirmed.
+	pub open_requests: Vec<HrmpOpenChannelRequestInfo>,
+	/// All channels requested to be closed. These channels are closed at the next session change.
+	pub close_requests: Vec<HrmpChannelId>,
+}
+
+/// The state of the validation code upgrade pipeline of a para.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct CodeUpgradePipeline<N> {
+	/// The hash of the validation code currently used by the para.
+	pub current_code_hash: Option<ValidationCodeHash>,
+	/// The hash of the validation code the para is upgrading to, if an upgrade is in progress.
+	pub future_code_hash: Option<ValidationCodeHash>,
+	/// Whether the future validation code is still being pre-checked by the validators.
+	pub pvf_check_in_progress: bool,
+	/// The relay-chain block number at which the upgrade is expected to be applied. Set once the
+	/// future validation code passed pre-checking.
+	pub expected_at: Option<N>,
+	/// The go-ahead signal communicated to the para, if any.
+	pub go_ahead: Option<UpgradeGoAhead>,
+	/// The upgrade restriction signal communicated to the para, if any.
+	pub restriction: Option<UpgradeRestriction>,
+	/// The relay-chain block number until which the para is cooling down after its last upgrade.
+	pub cooldown_until: Option<N>,
+	/// The relay-chain block number until which code upgrades of the para are frozen by
+	/// governance.
+	pub frozen_until: Option<N>,
+}
+
+/// The approval votes a validator observed from every validator of a session.
+///
+/// Submitted by validators once the session is over, the runtime credits approval checking work
+/// from the median of all tallies it received for a session.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ApprovalsTally {
+	/// The session the approval votes were cast in.
+	pub session_index: SessionIndex,
+	/// The index of the validator who observed the votes, in the validator set of the session.
+	pub validator_index: ValidatorIndex,
+	/// The number of approval votes observed from every validator of the session, indexed by
+	/// `ValidatorIndex`.
+	pub approvals: Vec<u32>,
+}
+
+impl ApprovalsTally {
+	/// Produce the payload used for signing this type of tally.
+	pub fn signing_payload(&self) -> Vec<u8> {
+		const MAGIC: [u8; 4] = *b"APRT"; // for "approvals tally"
+		(MAGIC, self.session_index, self.validator_index, &self.approvals).encode()
+	}
+}
+
+/// The paras holding a slot assigned by the assigned slots module in some lease period.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AssignedSlotsSchedule<N> {
+	/// The lease period.
+	pub lease_period: N,
+	/// The paras holding a permanent slot, ordered by ID.
+	pub permanent: Vec<Id>,
+	/// The paras given a turn on a temporary slot, ordered by ID.
+	pub temporary: Vec<Id>,
+}
+
+/// A change of a single parameter of the host configuration, carrying the new value of the
+/// `HostConfiguration` field the variant is named after.
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum HostConfigurationChange<N> {
+	/// The maximum validation code size, in bytes.
+	MaxCodeSize(u32),
+	/// The maximum head-data size, in bytes.
+	MaxHeadDataSize(u32),
+	/// The maximum number of messages in the upward message queue of a para.
+	MaxUpwardQueueCount(u32),
+	/// The maximum total size of the messages in the upward message queue of a para.
+	MaxUpwardQueueSize(u32),
+	/// The maximum size of an upward message that can be sent by a candidate.
+	MaxUpwardMessageSize(u32),
+	/// The maximum number of upward messages that a candidate can contain.
+	MaxUpwardMessageNumPerCandidate(u32),
+	/// The maximum number of outbound HRMP messages that a candidate can contain.
+	HrmpMaxMessageNumPerCandidate(u32),
+	/// The minimum period, in blocks, between validation code upgrades of a para.
+	ValidationUpgradeCooldown(N),
+	/// The delay, in blocks, after which an upgrade of the validation code is applied.
+	ValidationUpgradeDelay(N),
+	/// The maximum PoV block size, in bytes.
+	MaxPovSize(u32),
+	/// The maximum size of a message that can be put in a downward message queue.
+	MaxDownwardMessageSize(u32),
+	/// The weight devoted to processing the dispatchable upward messages.
+	UmpServiceTotalWeight(Weight),
+	/// The maximum number of outbound HRMP channels a parachain is allowed to open.
+	HrmpMaxParachainOutboundChannels(u32),
+	/// The maximum number of outbound HRMP channels a parathread is allowed to open.
+	HrmpMaxParathreadOutboundChannels(u32),
+	/// The deposit that the sender should provide for opening an HRMP channel.
+	HrmpSenderDeposit(Balance),
+	/// The deposit that the recipient should provide for accepting an HRMP channel.
+	HrmpRecipientDeposit(Balance),
+	/// The maximum number of messages allowed in an HRMP channel at once.
+	HrmpChannelMaxCapacity(u32),
+	/// The maximum total size of messages allowed in an HRMP channel at once.
+	HrmpChannelMaxTotalSize(u32),
+	/// The maximum number of inbound HRMP channels a parachain is allowed to accept.
+	HrmpMaxParachainInboundChannels(u32),
+	/// The maximum number of inbound HRMP channels a parathread is allowed to accept.
+	HrmpMaxParathreadInboundChannels(u32),
+	/// The maximum size of a message that could ever be put into an HRMP channel.
+	HrmpChannelMaxMessageSize(u32),
+	/// How long to keep code on-chain, in blocks.
+	CodeRetentionPeriod(N),
+	/// The number of execution cores dedicated to parathread execution.
+	ParathreadCores(u32),
+	/// The number of retries that a parathread author has to submit their block.
+	ParathreadRetries(u32),
+	/// How often parachain groups are rotated across parachains.
+	GroupRotationFrequency(N),
+	/// The availability period, in blocks, for parachains.
+	ChainAvailabilityPeriod(N),
+	/// The availability period, in blocks, for parathreads.
+	ThreadAvailabilityPeriod(N),
+	/// The number of blocks ahead to schedule parachains and parathreads.
+	SchedulingLookahead(u32),
+	/// The maximum number of validators per core, `None` meaning no maximum.
+	MaxValidatorsPerCore(Option<u32>),
+	/// The maximum number of validators used for parachain consensus, `None` meaning no maximum.
+	MaxValidators(Option<u32>),
+	/// The number of sessions to keep for disputes.
+	DisputePeriod(SessionIndex),
+	/// How long after dispute conclusion statements are accepted.
+	DisputePostConclusionAcceptancePeriod(N),
+	/// The maximum number of dispute spam slots.
+	DisputeMaxSpamSlots(u32),
+	/// How long it takes for a dispute to conclude by time-out.
+	DisputeConclusionByTimeOutPeriod(N),
+	/// The number of consensus slots after which an approval checker is considered a no-show.
+	NoShowSlots(u32),
+	/// The number of delay tranches in total.
+	NDelayTranches(u32),
+	/// The width of the zeroth delay tranche for approval assignments.
+	ZerothDelayTrancheWidth(u32),
+	/// The number of validators needed to approve a block.
+	NeededApprovals(u32),
+	/// The number of samples to do of the `RelayVRFModulo` approval assignment criterion.
+	RelayVrfModuloSamples(u32),
+	/// The maximum weight any individual upward message may consume.
+	UmpMaxIndividualWeight(Weight),
+	/// Whether PVF pre-checking is enabled.
+	PvfCheckingEnabled(bool),
+	/// The number of sessions after which an active PVF pre-checking vote is rejected.
+	PvfVotingTtl(SessionIndex),
+	/// The lower bound number of blocks an upgrade can be scheduled.
+	MinimumValidationUpgradeDelay(N),
+}
@@ -0,0 +1,295 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPCs exposing the parachains state of the relay chain.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::Decode;
+use polkadot_primitives::{
+	runtime_api::ParachainHost,
+	v2::{
+		well_known_keys, AccountId, Balance, Block, BlockId, BlockNumber, Hash, HeadData,
+		Id as ParaId,
+	},
+	vstaging::{AssignedSlotsSchedule, HrmpTopology},
+};
+use polkadot_runtime_common::auctions::{AuctionState, AuctionsApi};
+use sc_client_api::{Backend, ProofProvider, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, Bytes};
+
+/// The error code returned if a runtime call failed.
+const RUNTIME_ERROR: i32 = 1;
+/// The error code returned if the runtime doesn't expose the requested API.
+const NOT_SUPPORTED_ERROR: i32 = 2;
+/// The error code returned if the state of the requested block couldn't be read.
+const CLIENT_ERROR: i32 = 3;
+
+/// The minimal version of the `ParachainHost` API exposing `hrmp_topology`.
+const HRMP_TOPOLOGY_RUNTIME_REQUIREMENT: u32 = 3;
+/// The minimal version of the `ParachainHost` API exposing `para_head_history`.
+const PARA_HEAD_HISTORY_RUNTIME_REQUIREMENT: u32 = 3;
+/// The minimal version of the `ParachainHost` API exposing `assigned_slots_schedule`.
+const ASSIGNED_SLOTS_SCHEDULE_RUNTIME_REQUIREMENT: u32 = 3;
+
+/// The head of a para at some relay-chain block, along with a proof of it against the state root
+/// of that block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParaHeadProof {
+	/// The hash of the relay-chain block the head was read at.
+	pub at: Hash,
+	/// The state root of the relay-chain block the head was read at.
+	pub state_root: Hash,
+	/// The head-data of the para.
+	pub head: HeadData,
+	/// The trie nodes proving the `paras::Heads` entry of the para against `state_root`.
+	pub proof: Vec<Bytes>,
+}
+
+/// Parachains RPC methods.
+#[rpc(server)]
+pub trait ParachainsApi {
+	/// Returns all open HRMP channels along with the fill levels of their queues and the
+	/// watermarks of their recipients, as well as all pending open and close channel requests.
+	///
+	/// The state of the best block is queried if `at` is not specified.
+	#[method(name = "parachains_hrmpTopology")]
+	fn hrmp_topology(&self, at: Option<Hash>) -> RpcResult<HrmpTopology<BlockNumber>>;
+
+	/// Returns the hashes of the most recent heads of the given para along with the relay-chain
+	/// block numbers at which they were noted, ordered ascending by block number.
+	///
+	/// The state of the best block is queried if `at` is not specified.
+	#[method(name = "parachains_paraHeadHistory")]
+	fn para_head_history(
+		&self,
+		para_id: ParaId,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<(BlockNumber, Hash)>>;
+
+	/// Returns the head of the given para at the given relay-chain block along with a read proof
+	/// against the state root of that block, or `None` if the para has no head at that block.
+	///
+	/// Combined with `parachains_paraHeadHistory`, this allows light clients to obtain and verify
+	/// the head of a para at any recent relay-chain block. The best block is used if `at` is not
+	/// specified.
+	#[method(name = "parachains_paraHeadWithProof")]
+	fn para_head_with_proof(
+		&self,
+		para_id: ParaId,
+		at: Option<Hash>,
+	) -> RpcResult<Option<ParaHeadProof>>;
+
+	/// Returns the state of the current parachain slot auction, or of the most recent one if no
+	/// auction is in progress: the bids currently winning each range, the winners at each sample
+	/// of the ending period, the most recent bids and the amounts reserved by each bidder.
+	///
+	/// At most `max_samples` samples of the ending period are returned, starting at the offset
+	/// `first_sample`. The runtime caps the number of samples returned by a single query.
+	///
+	/// The state of the best block is queried if `at` is not specified.
+	#[method(name = "parachains_auctionState")]
+	fn auction_state(
+		&self,
+		first_sample: BlockNumber,
+		max_samples: u32,
+		at: Option<Hash>,
+	) -> RpcResult<Option<AuctionState<AccountId, Balance, BlockNumber>>>;
+
+	/// Returns the paras expected to hold a parachain slot assigned by the assigned slots module
+	/// in the current lease period and in each of the following ones, `lease_periods` in total.
+	///
+	/// The state of the best block is queried if `at` is not specified.
+	#[method(name = "parachains_assignedSlotsSchedule")]
+	fn assigned_slots_schedule(
+		&self,
+		lease_periods: u32,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<AssignedSlotsSchedule<BlockNumber>>>;
+}
+
+/// Implements the [`ParachainsApiServer`] RPC trait for interacting with the parachains runtime
+/// APIs.
+pub struct Parachains<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Parachains<C, B> {
+	/// Create a new `Parachains` RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, B> ParachainsApiServer for Parachains<C, B>
+where
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, B>
+		+ ProofProvider<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: ParachainHost<Block>,
+	C::Api: AuctionsApi<Block, AccountId, Balance, BlockNumber>,
+	B: Backend<Block> + Send + Sync + 'static,
+{
+	fn hrmp_topology(&self, at: Option<Hash>) -> RpcResult<HrmpTopology<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let version = api
+			.api_version::<dyn ParachainHost<Block>>(&at)
+			.map_err(runtime_error_into_rpc_error)?
+			.unwrap_or_default();
+		if version < HRMP_TOPOLOGY_RUNTIME_REQUIREMENT {
+			return Err(not_supported_error("hrmp_topology"))
+		}
+
+		api.hrmp_topology(&at).map_err(runtime_error_into_rpc_error)
+	}
+
+	fn para_head_history(
+		&self,
+		para_id: ParaId,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<(BlockNumber, Hash)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let version = api
+			.api_version::<dyn ParachainHost<Block>>(&at)
+			.map_err(runtime_error_into_rpc_error)?
+			.unwrap_or_default();
+		if version < PARA_HEAD_HISTORY_RUNTIME_REQUIREMENT {
+			return Err(not_supported_error("para_head_history"))
+		}
+
+		api.para_head_history(&at, para_id).map_err(runtime_error_into_rpc_error)
+	}
+
+	fn para_head_with_proof(
+		&self,
+		para_id: ParaId,
+		at: Option<Hash>,
+	) -> RpcResult<Option<ParaHeadProof>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let header = self
+			.client
+			.header(BlockId::hash(at))
+			.map_err(client_error_into_rpc_error)?
+			.ok_or_else(|| client_error_into_rpc_error(format!("Unknown block {}", at)))?;
+
+		let key = well_known_keys::para_head(para_id);
+		let head = match self
+			.client
+			.storage(at, &StorageKey(key.clone()))
+			.map_err(client_error_into_rpc_error)?
+		{
+			Some(data) =>
+				HeadData::decode(&mut &data.0[..]).map_err(client_error_into_rpc_error)?,
+			None => return Ok(None),
+		};
+
+		let proof = self
+			.client
+			.read_proof(at, &mut std::iter::once(&key[..]))
+			.map_err(client_error_into_rpc_error)?;
+
+		Ok(Some(ParaHeadProof {
+			at,
+			state_root: header.state_root,
+			head,
+			proof: proof.into_iter_nodes().map(Into::into).collect(),
+		}))
+	}
+
+	fn auction_state(
+		&self,
+		first_sample: BlockNumber,
+		max_samples: u32,
+		at: Option<Hash>,
+	) -> RpcResult<Option<AuctionState<AccountId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let supported = api
+			.has_api::<dyn AuctionsApi<Block, AccountId, Balance, BlockNumber>>(&at)
+			.map_err(runtime_error_into_rpc_error)?;
+		if !supported {
+			return Err(not_supported_error("auction_state"))
+		}
+
+		api.auction_state(&at, first_sample, max_samples)
+			.map_err(runtime_error_into_rpc_error)
+	}
+
+	fn assigned_slots_schedule(
+		&self,
+		lease_periods: u32,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<AssignedSlotsSchedule<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let version = api
+			.api_version::<dyn ParachainHost<Block>>(&at)
+			.map_err(runtime_error_into_rpc_error)?
+			.unwrap_or_default();
+		if version < ASSIGNED_SLOTS_SCHEDULE_RUNTIME_REQUIREMENT {
+			return Err(not_supported_error("assigned_slots_schedule"))
+		}
+
+		api.assigned_slots_schedule(&at, lease_periods)
+			.map_err(runtime_error_into_rpc_error)
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Converts a failure to read the state of a block into an RPC error.
+fn client_error_into_rpc_error(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(CLIENT_ERROR, "Client error", Some(format!("{:?}", err))))
+		.into()
+}
+
+/// Returns an RPC error signalling that the runtime doesn't support the given API.
+fn not_supported_error(runtime_api_name: &str) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		NOT_SUPPORTED_ERROR,
+		"Runtime API not supported",
+		Some(runtime_api_name.to_string()),
+	))
+	.into()
+}
@@ -18,6 +18,8 @@
 //! (short-lived) parachain slots to paras, leveraging the existing
 //! parachain slot lease mechanism. Temporary slots are given turns
 //! in a fair (though best-effort) manner.
+//! Each temporary slot has a priority, which weighs its share of the
+//! turns, and the rotation can be reset with `reshuffle_temporary_slots`.
 //! The dispatchables must be called from the configured origin
 //! (typically `Sudo` or a governance origin).
 //! This pallet should not be used on a production relay chain,
@@ -32,7 +34,7 @@ use frame_support::{pallet_prelude::*, traits::Currency};
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
-use primitives::v2::Id as ParaId;
+use primitives::{v2::Id as ParaId, vstaging::AssignedSlotsSchedule};
 use runtime_parachains::{
 	configuration,
 	paras::{self},
@@ -72,6 +74,14 @@ type BalanceOf<T> = <<<T as Config>::Leaser as Leaser<<T as frame_system::Config
 >>::Balance;
 type LeasePeriodOf<T> =
 	<<T as Config>::Leaser as Leaser<<T as frame_system::Config>::BlockNumber>>::LeasePeriod;
+type TemporarySlotOf<T> =
+	ParachainTemporarySlot<<T as frame_system::Config>::AccountId, LeasePeriodOf<T>>;
+
+/// The priority of temporary slots which weren't given one explicitly.
+pub const DEFAULT_TEMPORARY_SLOT_PRIORITY: u32 = 1;
+
+/// The maximum number of lease periods [`Pallet::slot_schedule`] looks ahead.
+pub const MAX_SCHEDULE_LEASE_PERIODS: u32 = 64;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -150,6 +160,12 @@ pub mod pallet {
 	#[pallet::getter(fn active_temporary_slot_count)]
 	pub type ActiveTemporarySlotCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+	/// Priorities of temporary slots which were given one other than
+	/// `DEFAULT_TEMPORARY_SLOT_PRIORITY`.
+	#[pallet::storage]
+	pub type TemporarySlotPriorities<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, u32, OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -157,6 +173,10 @@ pub mod pallet {
 		PermanentSlotAssigned(ParaId),
 		/// A para was assigned a temporary parachain slot
 		TemporarySlotAssigned(ParaId),
+		/// The priority of a temporary parachain slot was set
+		TemporarySlotPrioritySet(ParaId, u32),
+		/// The rotation of temporary parachain slots was reset
+		TemporarySlotsReshuffled,
 	}
 
 	#[pallet::error]
@@ -179,6 +199,8 @@ pub mod pallet {
 		MaxPermanentSlotsExceeded,
 		// Maximum number of temporary slots exceeded
 		MaxTemporarySlotsExceeded,
+		/// The priority of a temporary slot must not be zero.
+		ZeroPriority,
 	}
 
 	#[pallet::hooks]
@@ -361,6 +383,7 @@ pub mod pallet {
 				<PermanentSlotCount<T>>::mutate(|count| *count = count.saturating_sub(One::one()));
 			} else if TemporarySlots::<T>::contains_key(id) {
 				TemporarySlots::<T>::remove(id);
+				TemporarySlotPriorities::<T>::remove(id);
 				<TemporarySlotCount<T>>::mutate(|count| *count = count.saturating_sub(One::one()));
 				if is_parachain {
 					<ActiveTemporarySlotCount<T>>::mutate(|active_count| {
@@ -384,32 +407,124 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		// TODO: Benchmark this
+		/// Set the priority of a temporary parachain slot.
+		///
+		/// Temporary slots are given turns in proportion to their priority: a slot with priority 2
+		/// gets about twice as many lease periods as a slot with priority 1. Among slots which had
+		/// the same share of turns, the one with the highest priority goes first.
+		#[pallet::weight(((MAXIMUM_BLOCK_WEIGHT / 10) as Weight, DispatchClass::Operational))]
+		pub fn set_temporary_slot_priority(
+			origin: OriginFor<T>,
+			id: ParaId,
+			priority: u32,
+		) -> DispatchResult {
+			T::AssignSlotOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::has_temporary_slot(id), Error::<T>::SlotNotAssigned);
+			ensure!(!priority.is_zero(), Error::<T>::ZeroPriority);
+
+			if priority == DEFAULT_TEMPORARY_SLOT_PRIORITY {
+				TemporarySlotPriorities::<T>::remove(id);
+			} else {
+				TemporarySlotPriorities::<T>::insert(id, priority);
+			}
+
+			Self::deposit_event(Event::<T>::TemporarySlotPrioritySet(id, priority));
+			Ok(())
+		}
+
+		// TODO: Benchmark this
+		/// Reset the rotation of temporary parachain slots.
+		///
+		/// The turns every temporary slot had so far are forgotten, so from the next lease period
+		/// on slots are scheduled as if they were all newly assigned, by priority and then para ID.
+		/// Ongoing slot leases are not affected.
+		#[pallet::weight(((MAXIMUM_BLOCK_WEIGHT / 10) as Weight, DispatchClass::Operational))]
+		pub fn reshuffle_temporary_slots(origin: OriginFor<T>) -> DispatchResult {
+			T::AssignSlotOrigin::ensure_origin(origin)?;
+
+			let current_lease_period = Self::current_lease_period_index();
+			TemporarySlots::<T>::translate_values::<TemporarySlotOf<T>, _>(|mut slot| {
+				if !Self::is_active_temporary_slot(&slot, current_lease_period) {
+					slot.last_lease = None;
+				}
+				slot.lease_count = 0;
+				Some(slot)
+			});
+
+			Self::deposit_event(Event::<T>::TemporarySlotsReshuffled);
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
 	/// Allocate temporary slot leases up to `MaxTemporarySlotPerLeasePeriod` per lease period.
-	/// Beyond the already active temporary slot leases, this function will activate more leases
-	/// in the following order of preference:
+	///
+	/// The slots to be given a lease are chosen by `select_temporary_slots`. The function will call
+	/// out to `Leaser::lease_out` to create the appropriate slot leases.
+	fn allocate_temporary_slot_leases(lease_period_index: LeasePeriodOf<T>) -> DispatchResult {
+		let (active_temp_slots, slots_to_be_upgraded) =
+			Self::select_temporary_slots(TemporarySlots::<T>::iter(), lease_period_index);
+
+		let mut newly_created_lease = 0u32;
+		for (id, temp_slot) in slots_to_be_upgraded {
+			TemporarySlots::<T>::try_mutate::<_, _, Error<T>, _>(id, |s| {
+				// Configure temp slot lease
+				Self::configure_slot_lease(
+					id,
+					temp_slot.manager.clone(),
+					lease_period_index,
+					temp_slot.period_count,
+				)
+				.map_err(|_| Error::<T>::CannotUpgrade)?;
+
+				// Update temp slot lease info in storage
+				*s = Some(ParachainTemporarySlot {
+					manager: temp_slot.manager.clone(),
+					period_begin: temp_slot.period_begin,
+					period_count: temp_slot.period_count,
+					last_lease: Some(lease_period_index),
+					lease_count: temp_slot.lease_count + 1,
+				});
+
+				newly_created_lease += 1;
+
+				Ok(())
+			})?;
+		}
+
+		ActiveTemporarySlotCount::<T>::set(active_temp_slots + newly_created_lease);
+
+		Ok(())
+	}
+
+	/// Select the temporary slots to be given a lease starting at `lease_period_index`, up to
+	/// `MaxTemporarySlotPerLeasePeriod` active slots.
+	///
+	/// Returns the number of slots with an active lease, along with the selected slots.
+	///
+	/// Beyond the already active temporary slot leases, slots are selected in the following order
+	/// of preference:
 	/// - Assigned slots that didn't have a turn yet, though their `period_begin` has passed.
 	/// - Assigned slots that already had one (or more) turn(s): they will be considered for the
-	/// current slot lease if they weren't active in the preceding one, and will be ranked by
-	/// total number of lease (lower first), and then when they last a turn (older ones first).
+	/// current slot lease if they weren't active in the preceding one.
+	/// Slots are ranked by their number of leases relative to their priority (lower first), then
+	/// by priority (higher first), and then by when they last had a turn (older ones first).
 	/// If any remaining ex-aequo, we just take the para ID in ascending order as discriminator.
 	///
 	/// Assigned slots with a `period_begin` bigger than current lease period are not considered (yet).
-	///
-	/// The function will call out to `Leaser::lease_out` to create the appropriate slot leases.
-	fn allocate_temporary_slot_leases(lease_period_index: LeasePeriodOf<T>) -> DispatchResult {
+	fn select_temporary_slots(
+		temporary_slots: impl Iterator<Item = (ParaId, TemporarySlotOf<T>)>,
+		lease_period_index: LeasePeriodOf<T>,
+	) -> (u32, Vec<(ParaId, TemporarySlotOf<T>)>) {
 		let mut active_temp_slots = 0u32;
 		let mut pending_temp_slots = Vec::new();
-		TemporarySlots::<T>::iter().for_each(|(para, slot)| {
+		temporary_slots.for_each(|(para, slot)| {
 				match slot.last_lease {
-					Some(last_lease)
-						if last_lease <= lease_period_index &&
-							lease_period_index <
-								(last_lease.saturating_add(slot.period_count)) =>
-					{
+					Some(_) if Self::is_active_temporary_slot(&slot, lease_period_index) => {
 						// Active slot lease
 						active_temp_slots += 1;
 					}
@@ -428,54 +543,101 @@ impl<T: Config> Pallet<T> {
 				}
 		});
 
-		let mut newly_created_lease = 0u32;
-		if active_temp_slots < T::MaxTemporarySlotPerLeasePeriod::get() &&
-			!pending_temp_slots.is_empty()
-		{
-			// Sort by lease_count, favoring slots that had no or less turns first
-			// (then by last_lease index, and then Para ID)
-			pending_temp_slots.sort_by(|a, b| {
-				a.1.lease_count
-					.cmp(&b.1.lease_count)
-					.then_with(|| a.1.last_lease.cmp(&b.1.last_lease))
-					.then_with(|| a.0.cmp(&b.0))
-			});
+		let available = T::MaxTemporarySlotPerLeasePeriod::get().saturating_sub(active_temp_slots);
+		if available.is_zero() || pending_temp_slots.is_empty() {
+			return (active_temp_slots, Vec::new())
+		}
 
-			let slots_to_be_upgraded = pending_temp_slots.iter().take(
-				(T::MaxTemporarySlotPerLeasePeriod::get().saturating_sub(active_temp_slots))
-					as usize,
-			);
+		let mut pending_temp_slots: Vec<_> = pending_temp_slots
+			.into_iter()
+			.map(|(para, slot)| (Self::temporary_slot_priority(para), para, slot))
+			.collect();
+		// Sort by lease_count relative to priority, favoring slots that had no or less turns first
+		// (then by priority, last_lease index, and then Para ID)
+		pending_temp_slots.sort_by(|(a_priority, a_para, a), (b_priority, b_para, b)| {
+			(u64::from(a.lease_count) * u64::from(*b_priority))
+				.cmp(&(u64::from(b.lease_count) * u64::from(*a_priority)))
+				.then_with(|| b_priority.cmp(a_priority))
+				.then_with(|| a.last_lease.cmp(&b.last_lease))
+				.then_with(|| a_para.cmp(b_para))
+		});
 
-			for (id, temp_slot) in slots_to_be_upgraded {
-				TemporarySlots::<T>::try_mutate::<_, _, Error<T>, _>(id, |s| {
-					// Configure temp slot lease
-					Self::configure_slot_lease(
-						*id,
-						temp_slot.manager.clone(),
-						lease_period_index,
-						temp_slot.period_count,
-					)
-					.map_err(|_| Error::<T>::CannotUpgrade)?;
-
-					// Update temp slot lease info in storage
-					*s = Some(ParachainTemporarySlot {
-						manager: temp_slot.manager.clone(),
-						period_begin: temp_slot.period_begin,
-						period_count: temp_slot.period_count,
-						last_lease: Some(lease_period_index),
-						lease_count: temp_slot.lease_count + 1,
-					});
+		let selected = pending_temp_slots
+			.into_iter()
+			.take(available as usize)
+			.map(|(_, para, slot)| (para, slot))
+			.collect();
+
+		(active_temp_slots, selected)
+	}
 
-					newly_created_lease += 1;
+	/// Returns whether a temporary slot holds a lease in the given lease period.
+	fn is_active_temporary_slot(
+		slot: &TemporarySlotOf<T>,
+		lease_period_index: LeasePeriodOf<T>,
+	) -> bool {
+		slot.last_lease.map_or(false, |last_lease| {
+			last_lease <= lease_period_index &&
+				lease_period_index < last_lease.saturating_add(slot.period_count)
+		})
+	}
+
+	/// Returns the priority of a temporary slot.
+	pub fn temporary_slot_priority(id: ParaId) -> u32 {
+		TemporarySlotPriorities::<T>::get(id).unwrap_or(DEFAULT_TEMPORARY_SLOT_PRIORITY)
+	}
 
-					Ok(())
-				})?;
+	/// Returns the paras expected to hold an assigned slot lease in the current lease period and
+	/// in each of the `lease_periods - 1` following ones, up to `MAX_SCHEDULE_LEASE_PERIODS`.
+	///
+	/// The schedule of temporary slots is projected by running the rotation on the current state,
+	/// so it changes if slots are assigned, unassigned or reprioritized in the meantime.
+	pub fn slot_schedule(lease_periods: u32) -> Vec<AssignedSlotsSchedule<LeasePeriodOf<T>>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let current_lease_period = match Self::lease_period_index(now) {
+			Some((lease_period, _)) => lease_period,
+			None => return Vec::new(),
+		};
+
+		let permanent_slots: Vec<_> = PermanentSlots::<T>::iter().collect();
+		let mut temporary_slots: Vec<_> = TemporarySlots::<T>::iter().collect();
+		temporary_slots.sort_by_key(|(para, _)| *para);
+
+		let mut schedule = Vec::new();
+		for offset in 0..lease_periods.min(MAX_SCHEDULE_LEASE_PERIODS) {
+			let lease_period = current_lease_period.saturating_add(offset.into());
+
+			// The current lease period was allocated already.
+			if !offset.is_zero() {
+				let (_, selected) =
+					Self::select_temporary_slots(temporary_slots.iter().cloned(), lease_period);
+				for (id, _) in selected {
+					if let Ok(i) = temporary_slots.binary_search_by_key(&id, |(para, _)| *para) {
+						let slot = &mut temporary_slots[i].1;
+						slot.last_lease = Some(lease_period);
+						slot.lease_count.saturating_inc();
+					}
+				}
 			}
-		}
 
-		ActiveTemporarySlotCount::<T>::set(active_temp_slots + newly_created_lease);
+			let mut permanent: Vec<_> = permanent_slots
+				.iter()
+				.filter(|(_, (begin, count))| {
+					*begin <= lease_period && lease_period < begin.saturating_add(*count)
+				})
+				.map(|(para, _)| *para)
+				.collect();
+			permanent.sort();
+			let temporary = temporary_slots
+				.iter()
+				.filter(|(_, slot)| Self::is_active_temporary_slot(slot, lease_period))
+				.map(|(para, _)| *para)
+				.collect();
+
+			schedule.push(AssignedSlotsSchedule { lease_period, permanent, temporary });
+		}
 
-		Ok(())
+		schedule
 	}
 
 	/// Clear out all slot leases for both permanent & temporary slots.
@@ -637,13 +799,16 @@ mod tests {
 
 	parameter_types! {
 		pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+		pub const ParasHeadHistoryLength: u32 = 16;
 	}
 
 	impl parachains_paras::Config for Test {
 		type RuntimeEvent = RuntimeEvent;
 		type WeightInfo = parachains_paras::TestWeightInfo;
 		type UnsignedPriority = ParasUnsignedPriority;
+		type HeadHistoryLength = ParasHeadHistoryLength;
 		type NextSessionRotation = crate::mock::TestNextSessionRotation;
+		type OnboardingPvfCheckHandler = ();
 	}
 
 	impl parachains_shared::Config for Test {}
@@ -1318,4 +1483,186 @@ mod tests {
 			assert_eq!(Slots::already_leased(ParaId::from(1_u32), 0, 1), false);
 		});
 	}
+
+	fn register_temp_slots(paras: u32, lease_period_start: SlotLeasePeriodStart) {
+		for n in 0..paras {
+			assert_ok!(TestRegistrar::<Test>::register(
+				n as u64,
+				ParaId::from(n),
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+			assert_ok!(AssignedSlots::assign_temp_parachain_slot(
+				RuntimeOrigin::root(),
+				ParaId::from(n),
+				lease_period_start,
+			));
+		}
+	}
+
+	#[test]
+	fn set_temporary_slot_priority_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			assert_noop!(
+				AssignedSlots::set_temporary_slot_priority(
+					RuntimeOrigin::root(),
+					ParaId::from(0_u32),
+					2
+				),
+				Error::<Test>::SlotNotAssigned
+			);
+
+			register_temp_slots(1, SlotLeasePeriodStart::Next);
+
+			assert_noop!(
+				AssignedSlots::set_temporary_slot_priority(
+					RuntimeOrigin::signed(1),
+					ParaId::from(0_u32),
+					2
+				),
+				BadOrigin
+			);
+			assert_noop!(
+				AssignedSlots::set_temporary_slot_priority(
+					RuntimeOrigin::root(),
+					ParaId::from(0_u32),
+					0
+				),
+				Error::<Test>::ZeroPriority
+			);
+
+			assert_eq!(AssignedSlots::temporary_slot_priority(ParaId::from(0_u32)), 1);
+			assert_ok!(AssignedSlots::set_temporary_slot_priority(
+				RuntimeOrigin::root(),
+				ParaId::from(0_u32),
+				3
+			));
+			assert_eq!(AssignedSlots::temporary_slot_priority(ParaId::from(0_u32)), 3);
+			System::assert_last_event(
+				Event::<Test>::TemporarySlotPrioritySet(ParaId::from(0_u32), 3).into(),
+			);
+
+			// Back to the default priority.
+			assert_ok!(AssignedSlots::set_temporary_slot_priority(
+				RuntimeOrigin::root(),
+				ParaId::from(0_u32),
+				1
+			));
+			assert_eq!(TemporarySlotPriorities::<Test>::get(ParaId::from(0_u32)), None);
+
+			// Priorities are cleared along with the slot.
+			assert_ok!(AssignedSlots::set_temporary_slot_priority(
+				RuntimeOrigin::root(),
+				ParaId::from(0_u32),
+				3
+			));
+			assert_ok!(AssignedSlots::unassign_parachain_slot(
+				RuntimeOrigin::root(),
+				ParaId::from(0_u32),
+			));
+			assert_eq!(TemporarySlotPriorities::<Test>::get(ParaId::from(0_u32)), None);
+		});
+	}
+
+	#[test]
+	fn temporary_slots_are_scheduled_by_priority() {
+		new_test_ext().execute_with(|| {
+			// Block 1, Period 0
+			run_to_block(1);
+
+			register_temp_slots(3, SlotLeasePeriodStart::Next);
+			assert_ok!(AssignedSlots::set_temporary_slot_priority(
+				RuntimeOrigin::root(),
+				ParaId::from(2_u32),
+				2
+			));
+
+			// Para 3 gets a permanent slot for periods 0-2.
+			assert_ok!(TestRegistrar::<Test>::register(
+				3,
+				ParaId::from(3_u32),
+				dummy_head_data(),
+				dummy_validation_code(),
+			));
+			assert_ok!(AssignedSlots::assign_perm_parachain_slot(
+				RuntimeOrigin::root(),
+				ParaId::from(3_u32),
+			));
+
+			let ids = |ids: &[u32]| ids.iter().map(|id| ParaId::from(*id)).collect::<Vec<_>>();
+			let schedule =
+				|lease_period, permanent: &[u32], temporary: &[u32]| AssignedSlotsSchedule {
+					lease_period,
+					permanent: ids(permanent),
+					temporary: ids(temporary),
+				};
+			// Para 2 has the highest priority, so it gets a turn along with para 0 before para 1.
+			assert_eq!(
+				AssignedSlots::slot_schedule(5),
+				vec![
+					schedule(0, &[3], &[]),
+					schedule(1, &[3], &[0, 2]),
+					schedule(2, &[3], &[0, 2]),
+					schedule(3, &[], &[1]),
+					schedule(4, &[], &[1]),
+				]
+			);
+
+			// Block 3-8, Period 1-2
+			for n in 3..=8 {
+				run_to_block(n);
+				assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(0)), true);
+				assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(1_u32)), false);
+				assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(2_u32)), true);
+			}
+
+			// Block 9, Period 3
+			run_to_block(9);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(0)), false);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(1_u32)), true);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(2_u32)), false);
+			assert_eq!(
+				AssignedSlots::slot_schedule(2),
+				vec![schedule(3, &[], &[1]), schedule(4, &[], &[1])]
+			);
+		});
+	}
+
+	#[test]
+	fn reshuffle_temporary_slots_works() {
+		new_test_ext().execute_with(|| {
+			// Block 1, Period 0
+			run_to_block(1);
+
+			register_temp_slots(3, SlotLeasePeriodStart::Next);
+
+			// Paras 0 & 1 get periods 1-2, para 2 gets periods 3-4.
+			run_to_block(9);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(0)), false);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(1_u32)), false);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(2_u32)), true);
+
+			assert_noop!(
+				AssignedSlots::reshuffle_temporary_slots(RuntimeOrigin::signed(1)),
+				BadOrigin
+			);
+			assert_ok!(AssignedSlots::reshuffle_temporary_slots(RuntimeOrigin::root()));
+			System::assert_last_event(Event::<Test>::TemporarySlotsReshuffled.into());
+
+			// Past turns are forgotten, the ongoing lease is kept.
+			let slot = AssignedSlots::temporary_slots(ParaId::from(1_u32)).unwrap();
+			assert_eq!((slot.last_lease, slot.lease_count), (None, 0));
+			let slot = AssignedSlots::temporary_slots(ParaId::from(2_u32)).unwrap();
+			assert_eq!((slot.last_lease, slot.lease_count), (Some(3), 0));
+
+			// Block 12, Period 4: para 0 no longer waits for period 5 to get a turn.
+			run_to_block(12);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(0)), true);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(1_u32)), false);
+			assert_eq!(TestRegistrar::<Test>::is_parachain(ParaId::from(2_u32)), true);
+			assert_eq!(AssignedSlots::active_temporary_slot_count(), 2);
+		});
+	}
 }
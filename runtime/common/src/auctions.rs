@@ -29,9 +29,15 @@ use frame_support::{
 	weights::Weight,
 };
 pub use pallet::*;
-use parity_scale_codec::Decode;
+use parity_scale_codec::{Codec, Decode, Encode, MaxEncodedLen};
 use primitives::v2::Id as ParaId;
-use sp_runtime::traits::{CheckedSub, One, Saturating, Zero};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::{CheckedSub, One, Saturating, Zero},
+	RuntimeDebug,
+};
 use sp_std::{mem::swap, prelude::*};
 
 type CurrencyOf<T> =
@@ -77,6 +83,82 @@ type WinningData<T> = [Option<(<T as frame_system::Config>::AccountId, ParaId, B
 type WinnersData<T> =
 	Vec<(<T as frame_system::Config>::AccountId, ParaId, BalanceOf<T>, SlotRange)>;
 
+/// A bid accepted as the winner of its range, as kept in the bid history of an auction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct BidRecord<AccountId, Balance, BlockNumber> {
+	/// The block in which the bid was accepted.
+	pub block_number: BlockNumber,
+	/// The account funding the bid.
+	pub bidder: AccountId,
+	/// The para the bid was placed for.
+	pub para_id: ParaId,
+	/// The first lease period of the range bid on.
+	pub first_slot: BlockNumber,
+	/// The last lease period of the range bid on, inclusive.
+	pub last_slot: BlockNumber,
+	/// The amount bid.
+	pub amount: Balance,
+}
+
+/// A bid in a parachain slot auction.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuctionBid<AccountId, Balance, N> {
+	/// The account funding the bid.
+	pub bidder: AccountId,
+	/// The para the bid was placed for.
+	pub para_id: ParaId,
+	/// The first lease period of the range bid on.
+	pub first_slot: N,
+	/// The last lease period of the range bid on, inclusive.
+	pub last_slot: N,
+	/// The amount bid.
+	pub amount: Balance,
+}
+
+/// The state of a parachain slot auction, as reported by the `AuctionsApi` runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuctionState<AccountId, Balance, N> {
+	/// The index of the auction.
+	pub auction_index: u32,
+	/// The first lease period on auction and the block at which the ending period of the auction
+	/// begins. `None` once the auction has closed.
+	pub info: Option<(N, N)>,
+	/// The bids currently winning each slot range, or the bids of the chosen sample once the
+	/// auction has closed, ordered by range.
+	pub winning: Vec<AuctionBid<AccountId, Balance, N>>,
+	/// The bids winning each slot range at the requested samples taken so far in the ending
+	/// period, ordered by the sample offset. Empty until the ending period begins.
+	pub samples: Vec<(N, Vec<AuctionBid<AccountId, Balance, N>>)>,
+	/// The offset of the sample chosen as the end of the auction once it has closed.
+	pub winning_sample: Option<N>,
+	/// The most recent bids accepted in the auction along with the block they were accepted in,
+	/// oldest first.
+	pub bid_history: Vec<(N, AuctionBid<AccountId, Balance, N>)>,
+	/// The amounts currently reserved for each bidder and para.
+	pub reserved: Vec<(AccountId, ParaId, Balance)>,
+}
+
+/// The maximum number of ending-period samples returned by a single `auction_state` query.
+pub const MAX_AUCTION_STATE_SAMPLES: u32 = 64;
+
+type BidRecordOf<T> = BidRecord<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+type AuctionStateOf<T> = AuctionState<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+type AuctionBidOf<T> = AuctionBid<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -119,6 +201,11 @@ pub mod pallet {
 		/// The origin which may initiate auctions.
 		type InitiateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The maximum number of accepted bids kept in the history of an auction. Older bids are
+		/// dropped once the history is full.
+		#[pallet::constant]
+		type MaxBidHistory: Get<u32>;
+
 		/// Weight Information for the Extrinsics in the Pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -197,10 +284,34 @@ pub mod pallet {
 	/// The winning bids for each of the 10 ranges at each sample in the final Ending Period of
 	/// the current auction. The map's key is the 0-based index into the Sample Size. The
 	/// first sample of the ending period is 0; the last is `Sample Size - 1`.
+	///
+	/// The samples of a closed auction are kept until the next auction starts.
 	#[pallet::storage]
 	#[pallet::getter(fn winning)]
 	pub type Winning<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, WinningData<T>>;
 
+	/// The most recent bids accepted in an auction, oldest first.
+	///
+	/// The history of an auction is kept until the next auction starts.
+	#[pallet::storage]
+	#[pallet::getter(fn bid_history)]
+	pub type BidHistory<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		AuctionIndex,
+		BoundedVec<BidRecordOf<T>, T::MaxBidHistory>,
+		ValueQuery,
+	>;
+
+	/// The offset of the ending-period sample chosen as the end of a closed auction, along with
+	/// the first lease period that was on auction.
+	///
+	/// Kept until the next auction starts.
+	#[pallet::storage]
+	#[pallet::getter(fn winning_sample)]
+	pub type WinningSample<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionIndex, (T::BlockNumber, LeasePeriodOf<T>)>;
+
 	#[pallet::extra_constants]
 	impl<T: Config> Pallet<T> {
 		#[pallet::constant_name(SlotRangeCount)]
@@ -306,6 +417,7 @@ pub mod pallet {
 			#[allow(deprecated)]
 			Winning::<T>::remove_all(None);
 			AuctionInfo::<T>::kill();
+			BidHistory::<T>::remove(AuctionCounter::<T>::get());
 			Ok(())
 		}
 	}
@@ -398,6 +510,14 @@ impl<T: Config> Pallet<T> {
 			*n
 		});
 
+		// Clean up the history and the samples of the previous auction. This `remove_all`
+		// statement should remove at most `EndingPeriod` / `SampleLength` items, which should be
+		// bounded and sensibly configured in the runtime.
+		BidHistory::<T>::remove(n - 1);
+		WinningSample::<T>::remove(n - 1);
+		#[allow(deprecated)]
+		Winning::<T>::remove_all(None);
+
 		// Set the information.
 		let ending = frame_system::Pallet::<T>::block_number().saturating_add(duration);
 		AuctionInfo::<T>::put((lease_period_index, ending));
@@ -511,6 +631,20 @@ impl<T: Config> Pallet<T> {
 
 			// Update the range winner.
 			Winning::<T>::insert(offset, &current_winning);
+			BidHistory::<T>::mutate(auction_index, |history| {
+				// Make room for the new bid by dropping the oldest one.
+				if history.len() as u32 >= T::MaxBidHistory::get() && !history.is_empty() {
+					history.remove(0);
+				}
+				let _ = history.try_push(BidRecord {
+					block_number: frame_system::Pallet::<T>::block_number(),
+					bidder: bidder.clone(),
+					para_id: para,
+					first_slot,
+					last_slot,
+					amount,
+				});
+			});
 			Self::deposit_event(Event::<T>::BidAccepted {
 				bidder,
 				para_id: para,
@@ -526,8 +660,8 @@ impl<T: Config> Pallet<T> {
 	/// If `Some` then the block number must be at most the previous block and at least the
 	/// previous block minus `T::EndingPeriod::get()`.
 	///
-	/// This mutates the state, cleaning up `AuctionInfo` and `Winning` in the case of an auction
-	/// ending. An immediately subsequent call with the same argument will always return `None`.
+	/// This mutates the state, cleaning up `AuctionInfo` in the case of an auction ending. An
+	/// immediately subsequent call with the same argument will always return `None`.
 	fn check_auction_end(now: T::BlockNumber) -> Option<(WinningData<T>, LeasePeriodOf<T>)> {
 		if let Some((lease_period_index, early_end)) = AuctionInfo::<T>::get() {
 			let ending_period = T::EndingPeriod::get();
@@ -552,12 +686,11 @@ impl<T: Config> Pallet<T> {
 						auction_index: auction_counter,
 						block_number: offset,
 					});
+					WinningSample::<T>::insert(auction_counter, (offset, lease_period_index));
 					let res = Winning::<T>::get(offset)
 						.unwrap_or([Self::EMPTY; SlotRange::SLOT_RANGE_COUNT]);
-					// This `remove_all` statement should remove at most `EndingPeriod` / `SampleLength` items,
-					// which should be bounded and sensibly configured in the runtime.
-					#[allow(deprecated)]
-					Winning::<T>::remove_all(None);
+					// The samples are kept, so that the result of the auction can be inspected
+					// until the next auction starts.
 					AuctionInfo::<T>::kill();
 					return Some((res, lease_period_index))
 				}
@@ -618,6 +751,109 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	/// The state of the current auction, or of the most recent one if no auction is in progress.
+	///
+	/// At most `max_samples` samples of the ending period are returned, starting at the offset
+	/// `first_sample`, and never more than `MAX_AUCTION_STATE_SAMPLES`.
+	///
+	/// Returns `None` if no auction was started yet, or if the most recent one was cancelled.
+	pub fn auction_state(
+		first_sample: T::BlockNumber,
+		max_samples: u32,
+	) -> Option<AuctionStateOf<T>> {
+		let auction_index = AuctionCounter::<T>::get();
+		let info = AuctionInfo::<T>::get();
+		let bid_history = BidHistory::<T>::get(auction_index);
+		let winning_sample = WinningSample::<T>::get(auction_index);
+		if info.is_none() && bid_history.is_empty() && winning_sample.is_none() {
+			return None
+		}
+
+		// The samples are taken at contiguous offsets, and kept once the auction has closed.
+		let sample_length = T::SampleLength::get().max(One::one());
+		let last_sample = T::EndingPeriod::get().saturating_sub(One::one()) / sample_length;
+		let now = frame_system::Pallet::<T>::block_number();
+		let (lease_period, winning_offset, latest_sample) = match (info, Self::auction_status(now))
+		{
+			(Some((lease_period, _)), AuctionStatus::StartingPeriod) =>
+				(lease_period, Some(Zero::zero()), None),
+			(Some((lease_period, _)), AuctionStatus::EndingPeriod(offset, _)) =>
+				(lease_period, Some(offset), Some(offset)),
+			(Some((lease_period, _)), AuctionStatus::VrfDelay(_)) =>
+				(lease_period, Some(last_sample), Some(last_sample)),
+			_ => match winning_sample {
+				Some((offset, lease_period)) => (lease_period, Some(offset), Some(last_sample)),
+				None => (Zero::zero(), None, None),
+			},
+		};
+		let winning = winning_offset
+			.and_then(Winning::<T>::get)
+			.map(|winning| Self::winning_bids(lease_period, winning))
+			.unwrap_or_default();
+		let mut samples = Vec::new();
+		if let Some(latest_sample) = latest_sample {
+			let mut offset = first_sample;
+			while offset <= latest_sample &&
+				(samples.len() as u32) < max_samples.min(MAX_AUCTION_STATE_SAMPLES)
+			{
+				match Winning::<T>::get(offset) {
+					Some(winning) =>
+						samples.push((offset, Self::winning_bids(lease_period, winning))),
+					None => break,
+				}
+				offset = offset.saturating_add(One::one());
+			}
+		}
+
+		Some(AuctionState {
+			auction_index,
+			info,
+			winning,
+			samples,
+			winning_sample: winning_sample.map(|(offset, _)| offset),
+			bid_history: bid_history
+				.into_iter()
+				.map(|record| {
+					let bid = AuctionBid {
+						bidder: record.bidder,
+						para_id: record.para_id,
+						first_slot: record.first_slot,
+						last_slot: record.last_slot,
+						amount: record.amount,
+					};
+					(record.block_number, bid)
+				})
+				.collect(),
+			reserved: ReservedAmounts::<T>::iter()
+				.map(|((bidder, para), amount)| (bidder, para, amount))
+				.collect(),
+		})
+	}
+
+	/// Turn the winning data of an auction for the given first lease period into a list of bids,
+	/// ordered by range.
+	fn winning_bids(
+		lease_period: LeasePeriodOf<T>,
+		winning: WinningData<T>,
+	) -> Vec<AuctionBidOf<T>> {
+		winning
+			.into_iter()
+			.enumerate()
+			.filter_map(|(index, bid)| {
+				let (bidder, para_id, amount) = bid?;
+				let range = SlotRange::n(index as u8)?;
+				let (first, last) = range.as_pair();
+				Some(AuctionBid {
+					bidder,
+					para_id,
+					first_slot: lease_period + LeasePeriodOf::<T>::from(first as u32),
+					last_slot: lease_period + LeasePeriodOf::<T>::from(last as u32),
+					amount,
+				})
+			})
+			.collect()
+	}
+
 	/// Calculate the final winners from the winning slots.
 	///
 	/// This is a simple dynamic programming algorithm designed by Al, the original code is at:
@@ -667,6 +903,19 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+sp_api::decl_runtime_apis! {
+	/// The API for querying the state of parachain slot auctions.
+	pub trait AuctionsApi<AccountId: Codec, Balance: Codec, BlockNumber: Codec> {
+		/// Returns the state of the current auction, or of the most recent one if no auction is in
+		/// progress, with at most `max_samples` samples of the ending period starting at the
+		/// offset `first_sample`.
+		fn auction_state(
+			first_sample: BlockNumber,
+			max_samples: u32,
+		) -> Option<AuctionState<AccountId, Balance, BlockNumber>>;
+	}
+}
+
 /// tests for this module
 #[cfg(test)]
 mod tests {
@@ -864,6 +1113,7 @@ mod tests {
 	parameter_types! {
 		pub static EndingPeriod: BlockNumber = 3;
 		pub static SampleLength: BlockNumber = 1;
+		pub static MaxBidHistory: u32 = 3;
 	}
 
 	impl Config for Test {
@@ -874,6 +1124,7 @@ mod tests {
 		type SampleLength = SampleLength;
 		type Randomness = TestPastRandomness;
 		type InitiateOrigin = RootOrSix;
+		type MaxBidHistory = MaxBidHistory;
 		type WeightInfo = crate::auctions::TestWeightInfo;
 	}
 
@@ -1616,6 +1867,110 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn auction_state_works() {
+		new_test_ext().execute_with(|| {
+			EndingPeriod::set(30);
+			SampleLength::set(10);
+			let para_1 = ParaId::from(1_u32);
+			let para_2 = ParaId::from(2_u32);
+			let para_3 = ParaId::from(3_u32);
+			let bid = |bidder, para_id, first_slot, last_slot, amount| AuctionBid {
+				bidder,
+				para_id,
+				first_slot,
+				last_slot,
+				amount,
+			};
+			let reserved = || {
+				let mut reserved = Auctions::auction_state(0, 10).unwrap().reserved;
+				reserved.sort();
+				reserved
+			};
+
+			run_to_block(1);
+			assert_eq!(Auctions::auction_state(0, 10), None);
+			assert_ok!(Auctions::new_auction(RuntimeOrigin::signed(6), 9, 11));
+			assert_ok!(Auctions::bid(RuntimeOrigin::signed(1), para_1, 1, 11, 14, 10));
+			assert_ok!(Auctions::bid(RuntimeOrigin::signed(2), para_2, 1, 13, 14, 20));
+
+			// Only the current winners are known during the starting period.
+			let state = Auctions::auction_state(0, 10).unwrap();
+			assert_eq!(state.auction_index, 1);
+			assert_eq!(state.info, Some((11, 10)));
+			assert_eq!(state.winning, vec![bid(1, para_1, 11, 14, 10), bid(2, para_2, 13, 14, 20)]);
+			assert!(state.samples.is_empty());
+			assert_eq!(state.winning_sample, None);
+			assert_eq!(
+				state.bid_history,
+				vec![(1, bid(1, para_1, 11, 14, 10)), (1, bid(2, para_2, 13, 14, 20))]
+			);
+			assert_eq!(reserved(), vec![(1, para_1, 10), (2, para_2, 20)]);
+
+			run_to_block(10);
+			assert_ok!(Auctions::bid(RuntimeOrigin::signed(3), para_3, 1, 14, 14, 30));
+			run_to_block(20);
+			assert_ok!(Auctions::bid(RuntimeOrigin::signed(3), para_3, 1, 13, 14, 30));
+
+			// The samples of the ending period are exposed, and the oldest bid was dropped from the
+			// full history.
+			let state = Auctions::auction_state(0, 10).unwrap();
+			let first_sample = vec![
+				bid(1, para_1, 11, 14, 10),
+				bid(2, para_2, 13, 14, 20),
+				bid(3, para_3, 14, 14, 30),
+			];
+			let second_sample = vec![
+				bid(1, para_1, 11, 14, 10),
+				bid(3, para_3, 13, 14, 30),
+				bid(3, para_3, 14, 14, 30),
+			];
+			assert_eq!(state.winning, second_sample);
+			assert_eq!(state.samples, vec![(0, first_sample.clone()), (1, second_sample.clone())]);
+			assert_eq!(
+				state.bid_history,
+				vec![
+					(1, bid(2, para_2, 13, 14, 20)),
+					(10, bid(3, para_3, 14, 14, 30)),
+					(20, bid(3, para_3, 13, 14, 30)),
+				]
+			);
+			assert_eq!(reserved(), vec![(1, para_1, 10), (2, para_2, 20), (3, para_3, 30)]);
+
+			// The samples can be queried page by page.
+			let samples = |first_sample, max_samples| {
+				Auctions::auction_state(first_sample, max_samples).unwrap().samples
+			};
+			assert_eq!(samples(0, 1), vec![(0, first_sample.clone())]);
+			assert_eq!(samples(1, 1), vec![(1, second_sample.clone())]);
+			assert!(samples(2, 10).is_empty());
+			assert!(samples(0, 0).is_empty());
+
+			// Once closed, the history and the samples are kept, along with the chosen one.
+			set_last_random(H256::from([254; 32]), 40);
+			run_to_block(40);
+			let state = Auctions::auction_state(0, 10).unwrap();
+			assert_eq!(state.info, None);
+			assert_eq!(state.winning, second_sample);
+			assert_eq!(
+				state.samples,
+				vec![(0, first_sample), (1, second_sample.clone()), (2, second_sample)]
+			);
+			assert_eq!(state.winning_sample, Some(2));
+			assert_eq!(state.bid_history.len(), 3);
+			assert!(state.reserved.is_empty());
+
+			// Until the next auction starts.
+			assert_ok!(Auctions::new_auction(RuntimeOrigin::signed(6), 9, 12));
+			assert!(BidHistory::<Test>::get(1).is_empty());
+			assert_eq!(WinningSample::<Test>::get(1), None);
+			assert_eq!(Winning::<Test>::iter().count(), 0);
+			let state = Auctions::auction_state(0, 10).unwrap();
+			assert_eq!(state.auction_index, 2);
+			assert!(state.bid_history.is_empty());
+		});
+	}
+
 	#[test]
 	fn auction_status_works() {
 		new_test_ext().execute_with(|| {
@@ -1716,6 +2071,7 @@ mod tests {
 			assert_eq!(Balances::reserved_balance(1), 0);
 			assert_eq!(ReservedAmounts::<Test>::iter().count(), 0);
 			assert_eq!(Winning::<Test>::iter().count(), 0);
+			assert_eq!(Auctions::auction_state(0, 10), None);
 		});
 	}
 }
@@ -1723,7 +2079,10 @@ mod tests {
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking {
 	use super::{Pallet as Auctions, *};
-	use frame_support::traits::{EnsureOrigin, OnInitialize};
+	use frame_support::{
+		traits::{EnsureOrigin, OnInitialize},
+		BoundedVec,
+	};
 	use frame_system::RawOrigin;
 	use sp_runtime::{traits::Bounded, SaturatedConversion};
 
@@ -1780,12 +2139,18 @@ mod benchmarking {
 	benchmarks! {
 		where_clause { where T: pallet_babe::Config }
 
+		// Worst case scenario the samples of a previous auction fill the winning map.
 		new_auction {
 			let duration = T::BlockNumber::max_value();
 			let lease_period_index = LeasePeriodOf::<T>::max_value();
 			let origin = T::InitiateOrigin::successful_origin();
+			let winning_data = [Auctions::<T>::EMPTY; SlotRange::SLOT_RANGE_COUNT];
+			for i in 0u32 .. (T::EndingPeriod::get() / T::SampleLength::get()).saturated_into() {
+				Winning::<T>::insert(T::BlockNumber::from(i), winning_data.clone());
+			}
 		}: _<T::RuntimeOrigin>(origin, duration, lease_period_index)
 		verify {
+			assert!(Winning::<T>::iter().count().is_zero());
 			assert_last_event::<T>(Event::<T>::AuctionStarted {
 				auction_index: AuctionCounter::<T>::get(),
 				lease_period: LeasePeriodOf::<T>::max_value(),
@@ -1833,6 +2198,11 @@ mod benchmarking {
 				first_amount,
 			)?;
 
+			// Fill the bid history, so that the oldest bid needs to be dropped.
+			let record = BidHistory::<T>::get(auction_index)[0].clone();
+			let history = vec![record; T::MaxBidHistory::get() as usize];
+			BidHistory::<T>::insert(auction_index, BoundedVec::try_from(history).unwrap());
+
 			let caller: T::AccountId = whitelisted_caller();
 			CurrencyOf::<T>::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
 			let bigger_amount = CurrencyOf::<T>::minimum_balance().saturating_mul(10u32.into());
@@ -1844,7 +2214,7 @@ mod benchmarking {
 		}
 
 		// Worst case: 10 bidders taking all wining spots, and we need to calculate the winner for auction end.
-		// Entire winner map should be full and removed at the end of the benchmark.
+		// Entire winner map should be full, and is kept until the next auction.
 		on_initialize {
 			// If there is an offset, we need to be on that block to be able to do lease things.
 			let (lease_length, offset) = T::Leaser::lease_period_length();
@@ -1885,7 +2255,7 @@ mod benchmarking {
 		} verify {
 			let auction_index = AuctionCounter::<T>::get();
 			assert_last_event::<T>(Event::<T>::AuctionClosed { auction_index }.into());
-			assert!(Winning::<T>::iter().count().is_zero());
+			assert!(WinningSample::<T>::get(auction_index).is_some());
 		}
 
 		// Worst case: 10 bidders taking all wining spots, and winning data is full.
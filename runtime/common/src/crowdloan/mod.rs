@@ -48,6 +48,17 @@
 //! Successful funds remain tracked (in the `Funds` storage item and the associated child trie) as long as
 //! the parachain remains active. Users can withdraw their funds once the slot is completed and funds are
 //! returned to the crowdloan account.
+//!
+//! Holders of the relay chain token on child parachains may also contribute via XCM, using
+//! `contribute_from_location`. Such a contribution is paid from, and tracked against, the local
+//! account that the origin location converts to. Once the contribution is withdrawn or refunded, it
+//! is reserve-transferred back to the origin location instead of being left in that account.
+//!
+//! Once `refund` has been called for an ended fund, or the lease period range of the fund has
+//! passed, the remaining contributors are refunded automatically with the weight left over at the
+//! end of blocks. The fund's child trie is walked with a stored cursor, and the fund is dissolved
+//! once nothing is left in it. The progress of a refund can be queried through the `CrowdloanApi`
+//! runtime API.
 
 pub mod migration;
 
@@ -58,25 +69,31 @@ use crate::{
 use frame_support::{
 	ensure,
 	pallet_prelude::{DispatchResult, Weight},
-	storage::{child, ChildTriePrefixIterator},
+	storage::{child, with_transaction, ChildTriePrefixIterator},
 	traits::{
-		Currency,
+		Contains, Currency,
 		ExistenceRequirement::{self, AllowDeath, KeepAlive},
 		Get, ReservableCurrency,
 	},
 	Identity, PalletId,
 };
 pub use pallet::*;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Codec, Decode, Encode};
 use primitives::v2::Id as ParaId;
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{
-		AccountIdConversion, CheckedAdd, Hash, IdentifyAccount, One, Saturating, Verify, Zero,
+		AccountIdConversion, CheckedAdd, Hash, IdentifyAccount, One, SaturatedConversion,
+		Saturating, Verify, Zero,
 	},
-	MultiSignature, MultiSigner, RuntimeDebug,
+	DispatchError, MultiSignature, MultiSigner, RuntimeDebug, TransactionOutcome,
+};
+use sp_std::prelude::*;
+use xcm::{
+	latest::{MultiLocation, SendXcm},
+	VersionedMultiLocation,
 };
-use sp_std::vec::Vec;
+use xcm_executor::traits::Convert;
 
 type CurrencyOf<T> =
 	<<T as Config>::Auctioneer as Auctioneer<<T as frame_system::Config>::BlockNumber>>::Currency;
@@ -101,6 +118,8 @@ pub trait WeightInfo {
 	fn add_memo() -> Weight;
 	fn on_initialize(n: u32) -> Weight;
 	fn poke() -> Weight;
+	fn contribute_from_location() -> Weight;
+	fn withdraw_to_location() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -132,6 +151,29 @@ impl WeightInfo for TestWeightInfo {
 	fn poke() -> Weight {
 		Weight::zero()
 	}
+	fn contribute_from_location() -> Weight {
+		Weight::zero()
+	}
+	fn withdraw_to_location() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Matches the locations which may contribute to a crowdloan via XCM: child parachains and the
+/// accounts held on them.
+pub struct IsChildParachainContributor;
+impl Contains<MultiLocation> for IsChildParachainContributor {
+	fn contains(location: &MultiLocation) -> bool {
+		use xcm::latest::{Junction::*, Junctions::*};
+		matches!(
+			location,
+			MultiLocation { parents: 0, interior: X1(Parachain(_)) } |
+				MultiLocation {
+					parents: 0,
+					interior: X2(Parachain(_), AccountId32 { .. } | AccountKey20 { .. })
+				}
+		)
+	}
 }
 
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -176,6 +218,31 @@ pub struct FundInfo<AccountId, Balance, BlockNumber, LeasePeriod> {
 	pub fund_index: FundIndex,
 }
 
+/// The progress of refunding the contributors of a fund.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+pub struct RefundProgress<Balance> {
+	/// The raw child trie key of the last contribution visited by the automatic refund, which
+	/// resumes right after it. Empty if the automatic refund has not started yet.
+	pub cursor: Vec<u8>,
+	/// The number of contributors refunded so far.
+	pub refunded: u32,
+	/// The amount refunded so far.
+	pub amount: Balance,
+}
+
+/// The refund status of a fund, as reported by the `CrowdloanApi` runtime API.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RefundStatus<Balance> {
+	/// Whether the remaining contributors are being refunded automatically.
+	pub scheduled: bool,
+	/// The number of contributors refunded so far.
+	pub refunded: u32,
+	/// The amount refunded so far.
+	pub refunded_amount: Balance,
+	/// The amount yet to be refunded.
+	pub remaining: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -221,6 +288,20 @@ pub mod pallet {
 		/// The maximum length for the memo attached to a crowdloan contribution.
 		type MaxMemoLength: Get<u8>;
 
+		/// The origin which may contribute on behalf of a remote location, e.g. an account on a
+		/// child parachain dispatching `contribute_from_location` with an XCM `Transact`.
+		type XcmContributionOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = MultiLocation>;
+
+		/// Converts the location of a remote contributor into the local account which pays for and
+		/// tracks its contributions.
+		///
+		/// This should be the converter the XCM asset transactor uses, so that funds deposited for
+		/// a location are the ones its contributions are paid from.
+		type LocationToAccountId: Convert<MultiLocation, Self::AccountId>;
+
+		/// The router used to send the refunds of remote contributors back to them.
+		type XcmRouter: SendXcm;
+
 		/// Weight Information for the Extrinsics in the Pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -251,6 +332,32 @@ pub mod pallet {
 	#[pallet::getter(fn next_fund_index)]
 	pub(super) type NextFundIndex<T> = StorageValue<_, u32, ValueQuery>;
 
+	/// The funds being refunded, along with the progress made so far. The contributors of these
+	/// funds are refunded automatically with the weight left over at the end of blocks.
+	#[pallet::storage]
+	#[pallet::getter(fn refund_progress)]
+	pub(super) type Refunds<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, RefundProgress<BalanceOf<T>>>;
+
+	/// The raw key of the last fund checked for an ended lease period range, if `Funds` is being
+	/// walked. A walk starts at the beginning of each lease period and is continued in `on_idle`.
+	#[pallet::storage]
+	pub(super) type RefundScanCursor<T> = StorageValue<_, Vec<u8>>;
+
+	/// The remote locations of contributors who contributed via XCM, keyed by the fund index and
+	/// the local account their location converts to. Refunds to these contributors are sent back
+	/// to their location.
+	#[pallet::storage]
+	#[pallet::getter(fn xcm_contributor)]
+	pub(super) type XcmContributors<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		FundIndex,
+		Twox64Concat,
+		T::AccountId,
+		VersionedMultiLocation,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -275,6 +382,20 @@ pub mod pallet {
 		MemoUpdated { who: T::AccountId, para_id: ParaId, memo: Vec<u8> },
 		/// A parachain has been moved to `NewRaise`
 		AddedToNewRaise { para_id: ParaId },
+		/// Contributed to a crowd sale on behalf of a remote location.
+		ContributedFromLocation {
+			who: T::AccountId,
+			location: VersionedMultiLocation,
+			para_id: ParaId,
+			amount: BalanceOf<T>,
+		},
+		/// The refund of a remote contributor has been sent back to its location.
+		RefundSent { who: T::AccountId, location: VersionedMultiLocation, amount: BalanceOf<T> },
+		/// The refund of a remote contributor could not be sent back to its location and was left
+		/// in its local account instead.
+		RefundNotSent { who: T::AccountId, location: VersionedMultiLocation, amount: BalanceOf<T> },
+		/// Some contributors of a fund have been refunded automatically.
+		AutoRefunded { para_id: ParaId, count: u32, amount: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -325,12 +446,18 @@ pub mod pallet {
 		VrfDelayInProgress,
 		/// A lease period has not started yet, due to an offset in the starting block.
 		NoLeasePeriod,
+		/// The location of a remote contributor cannot be converted into a local account.
+		BadLocation,
+		/// The refund of a remote contributor could not be sent.
+		XcmSendFailed,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(num: T::BlockNumber) -> frame_support::weights::Weight {
-			if let Some((sample, sub_sample)) = T::Auctioneer::auction_status(num).is_ending() {
+			let weight = if let Some((sample, sub_sample)) =
+				T::Auctioneer::auction_status(num).is_ending()
+			{
 				// This is the very first block in the ending period
 				if sample.is_zero() && sub_sample.is_zero() {
 					// first block of ending period.
@@ -356,8 +483,20 @@ pub mod pallet {
 				T::WeightInfo::on_initialize(new_raise_len)
 			} else {
 				T::DbWeight::get().reads(1)
+			};
+			// Funds whose lease period range has passed are looked for in `on_idle`.
+			if let Some((_, true)) = T::Auctioneer::lease_period_index(num) {
+				RefundScanCursor::<T>::put(Vec::<u8>::new());
+				weight.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				weight
 			}
 		}
+
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let used = Self::schedule_ended_refunds(n, remaining_weight);
+			used.saturating_add(Self::auto_refund(remaining_weight.saturating_sub(used)))
+		}
 	}
 
 	#[pallet::call]
@@ -471,7 +610,7 @@ pub mod pallet {
 		///
 		/// - `who`: The account whose contribution should be withdrawn.
 		/// - `index`: The parachain to whose crowdloan the contribution was made.
-		#[pallet::weight(T::WeightInfo::withdraw())]
+		#[pallet::weight(T::WeightInfo::withdraw().max(T::WeightInfo::withdraw_to_location()))]
 		pub fn withdraw(
 			origin: OriginFor<T>,
 			who: T::AccountId,
@@ -487,7 +626,7 @@ pub mod pallet {
 			let (balance, _) = Self::contribution_get(fund.fund_index, &who);
 			ensure!(balance > Zero::zero(), Error::<T>::NoContributions);
 
-			CurrencyOf::<T>::transfer(&fund_account, &who, balance, AllowDeath)?;
+			Self::pay_out(fund.fund_index, &fund_account, &who, balance)?;
 
 			Self::contribution_kill(fund.fund_index, &who);
 			fund.raised = fund.raised.saturating_sub(balance);
@@ -502,8 +641,12 @@ pub mod pallet {
 		/// Due to weight restrictions, this function may need to be called multiple
 		/// times to fully refund all users. We will refund `RemoveKeysLimit` users at a time.
 		///
+		/// The fund is also scheduled for automatic refunding, so any remaining contributors are
+		/// refunded with the weight left over in the following blocks, after which the fund is
+		/// dissolved.
+		///
 		/// Origin must be signed, but can come from anyone.
-		#[pallet::weight(T::WeightInfo::refund(T::RemoveKeysLimit::get()))]
+		#[pallet::weight(Pallet::<T>::refund_weight(T::RemoveKeysLimit::get()))]
 		pub fn refund(
 			origin: OriginFor<T>,
 			#[pallet::compact] index: ParaId,
@@ -516,6 +659,7 @@ pub mod pallet {
 			Self::ensure_crowdloan_ended(now, &fund_account, &fund)?;
 
 			let mut refund_count = 0u32;
+			let mut refunded_amount: BalanceOf<T> = Zero::zero();
 			// Try killing the crowdloan child trie
 			let contributions = Self::contribution_iterator(fund.fund_index);
 			// Assume everyone will be refunded.
@@ -526,19 +670,25 @@ pub mod pallet {
 					all_refunded = false;
 					break
 				}
-				CurrencyOf::<T>::transfer(&fund_account, &who, balance, AllowDeath)?;
+				Self::pay_out(fund.fund_index, &fund_account, &who, balance)?;
 				Self::contribution_kill(fund.fund_index, &who);
 				fund.raised = fund.raised.saturating_sub(balance);
+				refunded_amount = refunded_amount.saturating_add(balance);
 				refund_count += 1;
 			}
 
 			// Save the changes.
 			Funds::<T>::insert(index, &fund);
+			Refunds::<T>::mutate(index, |progress| {
+				let progress = progress.get_or_insert_with(Default::default);
+				progress.refunded = progress.refunded.saturating_add(refund_count);
+				progress.amount = progress.amount.saturating_add(refunded_amount);
+			});
 
 			if all_refunded {
 				Self::deposit_event(Event::<T>::AllRefunded { para_id: index });
 				// Refund for unused refund count.
-				Ok(Some(T::WeightInfo::refund(refund_count)).into())
+				Ok(Some(Self::refund_weight(refund_count)).into())
 			} else {
 				Self::deposit_event(Event::<T>::PartiallyRefunded { para_id: index });
 				// No weight to refund since we did not finish the loop.
@@ -565,9 +715,7 @@ pub mod pallet {
 			// can take care of that.
 			debug_assert!(Self::contribution_iterator(fund.fund_index).count().is_zero());
 
-			CurrencyOf::<T>::unreserve(&fund.depositor, fund.deposit);
-			Funds::<T>::remove(index);
-			Self::deposit_event(Event::<T>::Dissolved { para_id: index });
+			Self::do_dissolve(index, &fund);
 			Ok(())
 		}
 
@@ -652,6 +800,43 @@ pub mod pallet {
 			let value = CurrencyOf::<T>::free_balance(&who);
 			Self::do_contribute(who, index, value, signature, AllowDeath)
 		}
+
+		/// Contribute to a crowd sale on behalf of a remote location, e.g. an account on a child
+		/// parachain dispatching this call with an XCM `Transact`.
+		///
+		/// The contribution is paid from, and tracked against, the local account the origin location
+		/// converts to, so the message should deposit the funds into that account beforehand. Once
+		/// the contribution is withdrawn or refunded, it is sent back to the origin location.
+		#[pallet::weight(T::WeightInfo::contribute_from_location())]
+		pub fn contribute_from_location(
+			origin: OriginFor<T>,
+			#[pallet::compact] index: ParaId,
+			#[pallet::compact] value: BalanceOf<T>,
+			signature: Option<MultiSignature>,
+		) -> DispatchResult {
+			let location = T::XcmContributionOrigin::ensure_origin(origin)?;
+			let who = T::LocationToAccountId::convert_ref(&location)
+				.map_err(|()| Error::<T>::BadLocation)?;
+			let fund_index = Self::funds(index).ok_or(Error::<T>::InvalidParaId)?.fund_index;
+
+			// The local account is only a way station for the funds of the remote location.
+			Self::do_contribute(who.clone(), index, value, signature, AllowDeath)?;
+
+			// A parachain contributing on its own behalf is refunded into its sovereign account, so
+			// there is nothing to send back.
+			let remote = location.interior.len() > 1;
+			let location = VersionedMultiLocation::from(location);
+			if remote {
+				XcmContributors::<T>::insert(fund_index, &who, &location);
+			}
+			Self::deposit_event(Event::<T>::ContributedFromLocation {
+				who,
+				location,
+				para_id: index,
+				amount: value,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -732,6 +917,230 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Remove the fund of `index` and return its deposit.
+	fn do_dissolve(
+		index: ParaId,
+		fund: &FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber, LeasePeriodOf<T>>,
+	) {
+		CurrencyOf::<T>::unreserve(&fund.depositor, fund.deposit);
+		Funds::<T>::remove(index);
+		Refunds::<T>::remove(index);
+		Self::deposit_event(Event::<T>::Dissolved { para_id: index });
+	}
+
+	/// Schedule the automatic refund of the funds whose lease period range has passed, walking
+	/// `Funds` from `RefundScanCursor` with the `remaining_weight` of a block.
+	///
+	/// The deposit of a lease which just ended is returned to the fund account at the start of the
+	/// lease period, so whether the fund can be refunded yet is left to `auto_refund` to check.
+	fn schedule_ended_refunds(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		let mut used = T::DbWeight::get().reads(1);
+		let cursor = match RefundScanCursor::<T>::get() {
+			Some(cursor) => cursor,
+			None => return used,
+		};
+		let current_lease_period = match T::Auctioneer::lease_period_index(now) {
+			Some((lease_period, _)) => lease_period,
+			None => return used,
+		};
+
+		let per_fund = T::DbWeight::get().reads_writes(2, 1);
+		let finish = T::DbWeight::get().writes(1);
+		let mut funds =
+			if cursor.is_empty() { Funds::<T>::iter() } else { Funds::<T>::iter_from(cursor) };
+		loop {
+			if used.saturating_add(per_fund).saturating_add(finish).any_gt(remaining_weight) {
+				RefundScanCursor::<T>::put(funds.last_raw_key().to_vec());
+				break
+			}
+			let (index, fund) = match funds.next() {
+				Some(next) => next,
+				None => {
+					RefundScanCursor::<T>::kill();
+					break
+				},
+			};
+			used = used.saturating_add(per_fund);
+			if fund.last_period < current_lease_period && !Refunds::<T>::contains_key(index) {
+				Refunds::<T>::insert(index, RefundProgress::default());
+			}
+		}
+		used.saturating_add(finish)
+	}
+
+	/// Continue refunding a fund scheduled in `Refunds` with the `remaining_weight` of a block.
+	///
+	/// Up to `RemoveKeysLimit` contributors are refunded, resuming after the stored cursor. Once the
+	/// end of the fund's child trie is reached, the fund is dissolved if everyone was refunded, and
+	/// dropped from `Refunds` otherwise; contributors whose refund failed can still `withdraw`.
+	fn auto_refund(remaining_weight: Weight) -> Weight {
+		let mut used = T::DbWeight::get().reads(2);
+		let (index, mut progress) = match Refunds::<T>::iter().next() {
+			Some(next) => next,
+			None => return used,
+		};
+		let base = T::WeightInfo::refund(0).saturating_add(T::WeightInfo::dissolve());
+		let per_contributor = Self::refund_weight(1).saturating_sub(T::WeightInfo::refund(0));
+		if used
+			.saturating_add(base)
+			.saturating_add(per_contributor)
+			.any_gt(remaining_weight)
+		{
+			return used
+		}
+		used = used.saturating_add(base);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut fund = match Self::funds(index) {
+			Some(fund) => fund,
+			None => {
+				Refunds::<T>::remove(index);
+				return used
+			},
+		};
+		let fund_account = Self::fund_account_id(fund.fund_index);
+		if Self::ensure_crowdloan_ended(now, &fund_account, &fund).is_err() {
+			// The funds are in use again, e.g. by a new lease; there is nothing to refund.
+			Refunds::<T>::remove(index);
+			return used
+		}
+		let child_info = Self::id_from_index(fund.fund_index);
+
+		let (mut count, mut amount) = (0u32, BalanceOf::<T>::zero());
+		let mut finished = false;
+		for _ in 0..T::RemoveKeysLimit::get() {
+			if used.saturating_add(per_contributor).any_gt(remaining_weight) {
+				break
+			}
+			let key = match sp_io::default_child_storage::next_key(
+				child_info.storage_key(),
+				&progress.cursor,
+			) {
+				Some(key) => key,
+				None => {
+					finished = true;
+					break
+				},
+			};
+			used = used.saturating_add(per_contributor);
+			progress.cursor = key;
+
+			let who = match T::AccountId::decode(&mut &progress.cursor[..]) {
+				Ok(who) => who,
+				Err(_) => continue,
+			};
+			let (balance, _) = Self::contribution_get(fund.fund_index, &who);
+			// A failed refund is skipped, the contributor can still withdraw it later.
+			if Self::pay_out(fund.fund_index, &fund_account, &who, balance).is_ok() {
+				Self::contribution_kill(fund.fund_index, &who);
+				fund.raised = fund.raised.saturating_sub(balance);
+				amount = amount.saturating_add(balance);
+				count += 1;
+			}
+		}
+
+		Funds::<T>::insert(index, &fund);
+		progress.refunded = progress.refunded.saturating_add(count);
+		progress.amount = progress.amount.saturating_add(amount);
+		if count > 0 {
+			Self::deposit_event(Event::<T>::AutoRefunded { para_id: index, count, amount });
+		}
+
+		if !finished {
+			Refunds::<T>::insert(index, progress);
+		} else if fund.raised.is_zero() {
+			Self::deposit_event(Event::<T>::AllRefunded { para_id: index });
+			Self::do_dissolve(index, &fund);
+		} else {
+			Self::deposit_event(Event::<T>::PartiallyRefunded { para_id: index });
+			Refunds::<T>::remove(index);
+		}
+		used
+	}
+
+	/// The refund status of the fund for `index`, if there is one.
+	pub fn refund_status(index: ParaId) -> Option<RefundStatus<BalanceOf<T>>> {
+		let fund = Self::funds(index)?;
+		let progress = Self::refund_progress(index);
+		Some(RefundStatus {
+			scheduled: progress.is_some(),
+			refunded: progress.as_ref().map_or(0, |p| p.refunded),
+			refunded_amount: progress.map_or_else(Zero::zero, |p| p.amount),
+			remaining: fund.raised,
+		})
+	}
+
+	/// The weight of refunding `k` contributors, assuming all of them contributed via XCM.
+	fn refund_weight(k: u32) -> Weight {
+		let xcm_overhead =
+			T::WeightInfo::withdraw_to_location().saturating_sub(T::WeightInfo::withdraw());
+		T::WeightInfo::refund(k).saturating_add(xcm_overhead.saturating_mul(k as u64))
+	}
+
+	/// Pay `balance` out of the fund account to the contributor `who`.
+	///
+	/// If `who` contributed via XCM, the funds are sent on to its remote location. Should that fail,
+	/// they are left in `who` for the remote location to claim later.
+	fn pay_out(
+		fund_index: FundIndex,
+		fund_account: &T::AccountId,
+		who: &T::AccountId,
+		balance: BalanceOf<T>,
+	) -> DispatchResult {
+		CurrencyOf::<T>::transfer(fund_account, who, balance, AllowDeath)?;
+
+		if let Some(location) = XcmContributors::<T>::take(fund_index, who) {
+			let sent = with_transaction(|| match Self::send_refund(who, &location, balance) {
+				Ok(()) => TransactionOutcome::Commit(Ok(())),
+				Err(e) => TransactionOutcome::Rollback(Err(e)),
+			});
+			let (who, amount) = (who.clone(), balance);
+			Self::deposit_event(match sent {
+				Ok(()) => Event::<T>::RefundSent { who, location, amount },
+				Err(_) => Event::<T>::RefundNotSent { who, location, amount },
+			});
+		}
+		Ok(())
+	}
+
+	/// Reserve-transfer `amount` held by `who` back to the remote `location` it belongs to.
+	///
+	/// The funds are moved into the sovereign account of the parachain holding `location` and the
+	/// parachain is told to deposit them into the account they were contributed from.
+	fn send_refund(
+		who: &T::AccountId,
+		location: &VersionedMultiLocation,
+		amount: BalanceOf<T>,
+	) -> Result<(), DispatchError> {
+		use xcm::latest::prelude::*;
+
+		let location =
+			MultiLocation::try_from(location.clone()).map_err(|()| Error::<T>::BadLocation)?;
+		let (beneficiary, para) = match (location.parents, location.interior.split_first()) {
+			(0, (beneficiary, Some(Parachain(para)))) if beneficiary != Here => (beneficiary, para),
+			_ => return Err(Error::<T>::BadLocation.into()),
+		};
+		let dest = MultiLocation::new(0, X1(Parachain(para)));
+		let sovereign =
+			T::LocationToAccountId::convert_ref(&dest).map_err(|()| Error::<T>::BadLocation)?;
+		CurrencyOf::<T>::transfer(who, &sovereign, amount, AllowDeath)?;
+
+		// Our native token, as seen from the parachain.
+		let asset: MultiAsset = (Parent, amount.saturated_into::<u128>()).into();
+		let message = Xcm(vec![
+			ReserveAssetDeposited(asset.clone().into()),
+			ClearOrigin,
+			BuyExecution { fees: asset, weight_limit: Unlimited },
+			DepositAsset {
+				assets: Wild(All),
+				max_assets: 1,
+				beneficiary: MultiLocation::new(0, beneficiary),
+			},
+		]);
+		T::XcmRouter::send_xcm(dest, message).map_err(|_| Error::<T>::XcmSendFailed)?;
+		Ok(())
+	}
+
 	fn do_contribute(
 		who: T::AccountId,
 		index: ParaId,
@@ -818,7 +1227,17 @@ impl<T: Config> Pallet<T> {
 
 impl<T: Config> crate::traits::OnSwap for Pallet<T> {
 	fn on_swap(one: ParaId, other: ParaId) {
-		Funds::<T>::mutate(one, |x| Funds::<T>::mutate(other, |y| sp_std::mem::swap(x, y)))
+		Funds::<T>::mutate(one, |x| Funds::<T>::mutate(other, |y| sp_std::mem::swap(x, y)));
+		Refunds::<T>::mutate(one, |x| Refunds::<T>::mutate(other, |y| sp_std::mem::swap(x, y)));
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for querying the refund progress of crowdloans.
+	pub trait CrowdloanApi<Balance: Codec> {
+		/// Returns the refund status of the crowdloan for `para_id`, or `None` if there is no such
+		/// crowdloan.
+		fn refund_status(para_id: ParaId) -> Option<RefundStatus<Balance>>;
 	}
 }
 
@@ -846,7 +1265,7 @@ mod tests {
 
 	use frame_support::{
 		assert_noop, assert_ok, parameter_types,
-		traits::{OnFinalize, OnInitialize},
+		traits::{EnsureOrigin, OnFinalize, OnIdle, OnInitialize},
 	};
 	use primitives::v2::Id as ParaId;
 	use sp_core::H256;
@@ -865,6 +1284,7 @@ mod tests {
 		traits::{BlakeTwo256, IdentityLookup, TrailingZeroInput},
 		DispatchResult,
 	};
+	use xcm::latest::prelude::*;
 
 	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 	type Block = frame_system::mocking::MockBlock<Test>;
@@ -945,6 +1365,8 @@ mod tests {
 		static ENDING_PERIOD: RefCell<u64> = RefCell::new(5);
 		static BIDS_PLACED: RefCell<Vec<BidPlaced>> = RefCell::new(Vec::new());
 		static HAS_WON: RefCell<BTreeMap<(ParaId, u64), bool>> = RefCell::new(BTreeMap::new());
+		static SENT_XCM: RefCell<Vec<(MultiLocation, Xcm<()>)>> = RefCell::new(Vec::new());
+		static XCM_ROUTER_FAILS: RefCell<bool> = RefCell::new(false);
 	}
 
 	#[allow(unused)]
@@ -960,6 +1382,12 @@ mod tests {
 	fn bids() -> Vec<BidPlaced> {
 		BIDS_PLACED.with(|p| p.borrow().clone())
 	}
+	fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+		SENT_XCM.with(|p| p.borrow().clone())
+	}
+	fn set_xcm_router_fails(fails: bool) {
+		XCM_ROUTER_FAILS.with(|p| *p.borrow_mut() = fails);
+	}
 	fn vrf_delay() -> u64 {
 		VRF_DELAY.with(|p| p.borrow().clone())
 	}
@@ -1071,6 +1499,60 @@ mod tests {
 		pub const MaxMemoLength: u8 = 32;
 	}
 
+	/// The parachain remote contributors live on.
+	const REMOTE_PARA: u32 = 2000;
+
+	/// Remote contributors are represented by signed origins: `who` stands for the account with
+	/// index `who` on `REMOTE_PARA`.
+	pub struct EnsureRemoteContributor;
+	impl EnsureOrigin<RuntimeOrigin> for EnsureRemoteContributor {
+		type Success = MultiLocation;
+
+		fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+			let raw: Result<frame_system::RawOrigin<u64>, _> = o.clone().into();
+			match raw {
+				Ok(frame_system::RawOrigin::Signed(who)) => Ok(MultiLocation::new(
+					0,
+					X2(Parachain(REMOTE_PARA), AccountIndex64 { network: Any, index: who }),
+				)),
+				_ => Err(o),
+			}
+		}
+
+		#[cfg(feature = "runtime-benchmarks")]
+		fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+			Ok(RuntimeOrigin::signed(0))
+		}
+	}
+
+	/// Parachain `p` has the sovereign account `1000 + p` and account `i` on a parachain is held
+	/// locally by `100 + i`.
+	pub struct RemoteToAccountId;
+	impl Convert<MultiLocation, u64> for RemoteToAccountId {
+		fn convert(location: MultiLocation) -> Result<u64, MultiLocation> {
+			match location {
+				MultiLocation { parents: 0, interior: X1(Parachain(p)) } => Ok(1000 + p as u64),
+				MultiLocation {
+					parents: 0,
+					interior: X2(Parachain(_), AccountIndex64 { index, .. }),
+				} => Ok(100 + index),
+				location => Err(location),
+			}
+		}
+	}
+
+	pub struct TestXcmRouter;
+	impl SendXcm for TestXcmRouter {
+		fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+			let dest = dest.into();
+			if XCM_ROUTER_FAILS.with(|p| *p.borrow()) {
+				return Err(SendError::CannotReachDestination(dest, msg))
+			}
+			SENT_XCM.with(|p| p.borrow_mut().push((dest, msg)));
+			Ok(())
+		}
+	}
+
 	impl Config for Test {
 		type RuntimeEvent = RuntimeEvent;
 		type SubmissionDeposit = SubmissionDeposit;
@@ -1080,6 +1562,9 @@ mod tests {
 		type Registrar = TestRegistrar<Test>;
 		type Auctioneer = TestAuctioneer;
 		type MaxMemoLength = MaxMemoLength;
+		type XcmContributionOrigin = EnsureRemoteContributor;
+		type LocationToAccountId = RemoteToAccountId;
+		type XcmRouter = TestXcmRouter;
 		type WeightInfo = crate::crowdloan::TestWeightInfo;
 	}
 
@@ -1696,6 +2181,265 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn contribute_from_location_works() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let index = NextFundIndex::<Test>::get();
+			let account_id = Crowdloan::fund_account_id(index);
+			let location = VersionedMultiLocation::from(MultiLocation::new(
+				0,
+				X2(Parachain(REMOTE_PARA), AccountIndex64 { network: Any, index: 2 }),
+			));
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 4, 9, None));
+			// The remote account of 2 is held locally by 102, which has no funds yet.
+			assert_noop!(
+				Crowdloan::contribute_from_location(RuntimeOrigin::signed(2), para, 49, None),
+				BalancesError::<Test, _>::InsufficientBalance
+			);
+
+			Balances::make_free_balance_be(&102, 49);
+			assert_ok!(Crowdloan::contribute_from_location(
+				RuntimeOrigin::signed(2),
+				para,
+				49,
+				None
+			));
+			assert_eq!(
+				last_event(),
+				super::Event::<Test>::ContributedFromLocation {
+					who: 102,
+					location: location.clone(),
+					para_id: para,
+					amount: 49,
+				}
+				.into()
+			);
+
+			// The contribution is tracked against the local account, which may be emptied.
+			assert_eq!(Crowdloan::contribution_get(index, &102).0, 49);
+			assert_eq!(Crowdloan::xcm_contributor(index, &102), Some(location));
+			assert_eq!(Balances::free_balance(102), 0);
+			assert_eq!(Balances::free_balance(account_id), 49);
+			assert_eq!(Crowdloan::funds(para).unwrap().raised, 49);
+			// Signed contributions of the same account are left alone.
+			assert_eq!(Crowdloan::contribution_get(index, &2).0, 0);
+		});
+	}
+
+	#[test]
+	fn refund_sends_remote_contributions_back() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let index = NextFundIndex::<Test>::get();
+			let account_id = Crowdloan::fund_account_id(index);
+			let sovereign = 1000 + REMOTE_PARA as u64;
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 1, 9, None));
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(1), para, 100, None));
+			Balances::make_free_balance_be(&102, 200);
+			assert_ok!(Crowdloan::contribute_from_location(
+				RuntimeOrigin::signed(2),
+				para,
+				200,
+				None
+			));
+			assert_eq!(Balances::free_balance(account_id), 300);
+
+			run_to_block(10);
+			assert_ok!(Crowdloan::refund(RuntimeOrigin::signed(1337), para));
+
+			// The local contributor is refunded as usual.
+			assert_eq!(Balances::free_balance(account_id), 0);
+			assert_eq!(Balances::free_balance(1), 1000 - 1);
+			// The remote contribution is moved into the sovereign account of its parachain...
+			assert_eq!(Balances::free_balance(102), 0);
+			assert_eq!(Balances::free_balance(sovereign), 200);
+			assert_eq!(Crowdloan::xcm_contributor(index, &102), None);
+			// ...which is told to deposit it back into the contributing account.
+			let asset: MultiAsset = (Parent, 200u128).into();
+			assert_eq!(
+				sent_xcm(),
+				vec![(
+					MultiLocation::new(0, X1(Parachain(REMOTE_PARA))),
+					Xcm(vec![
+						ReserveAssetDeposited(asset.clone().into()),
+						ClearOrigin,
+						BuyExecution { fees: asset, weight_limit: Unlimited },
+						DepositAsset {
+							assets: Wild(All),
+							max_assets: 1,
+							beneficiary: MultiLocation::new(
+								0,
+								X1(AccountIndex64 { network: Any, index: 2 })
+							),
+						},
+					]),
+				)]
+			);
+			assert!(System::events().iter().any(|r| r.event ==
+				super::Event::<Test>::RefundSent {
+					who: 102,
+					location: MultiLocation::new(
+						0,
+						X2(Parachain(REMOTE_PARA), AccountIndex64 { network: Any, index: 2 })
+					)
+					.into(),
+					amount: 200,
+				}
+				.into()));
+		});
+	}
+
+	#[test]
+	fn withdraw_keeps_remote_contribution_if_it_cannot_be_sent() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let index = NextFundIndex::<Test>::get();
+			let sovereign = 1000 + REMOTE_PARA as u64;
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 1, 9, None));
+			Balances::make_free_balance_be(&102, 200);
+			assert_ok!(Crowdloan::contribute_from_location(
+				RuntimeOrigin::signed(2),
+				para,
+				200,
+				None
+			));
+
+			run_to_block(10);
+			set_xcm_router_fails(true);
+			assert_ok!(Crowdloan::withdraw(RuntimeOrigin::signed(1337), 102, para));
+
+			// Nothing was sent, so the funds are left in the local account of the contributor.
+			assert!(sent_xcm().is_empty());
+			assert_eq!(Balances::free_balance(102), 200);
+			assert_eq!(Balances::free_balance(sovereign), 0);
+			assert_eq!(Crowdloan::xcm_contributor(index, &102), None);
+			assert_eq!(Crowdloan::contribution_get(index, &102).0, 0);
+			assert!(System::events().iter().any(|r| r.event ==
+				super::Event::<Test>::RefundNotSent {
+					who: 102,
+					location: MultiLocation::new(
+						0,
+						X2(Parachain(REMOTE_PARA), AccountIndex64 { network: Any, index: 2 })
+					)
+					.into(),
+					amount: 200,
+				}
+				.into()));
+		});
+	}
+
+	#[test]
+	fn refund_continues_on_idle_and_dissolves() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let index = NextFundIndex::<Test>::get();
+			let account_id = Crowdloan::fund_account_id(index);
+			let contributors = RemoveKeysLimit::get() * 2 + 5;
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 100000, 1, 1, 9, None));
+			for i in 1..=contributors {
+				Balances::make_free_balance_be(&i.into(), (1000 * i).into());
+				assert_ok!(Crowdloan::contribute(
+					RuntimeOrigin::signed(i.into()),
+					para,
+					(i * 100).into(),
+					None
+				));
+			}
+			let raised = Crowdloan::funds(para).unwrap().raised;
+			assert_eq!(
+				Crowdloan::refund_status(para),
+				Some(RefundStatus {
+					scheduled: false,
+					refunded: 0,
+					refunded_amount: 0,
+					remaining: raised
+				})
+			);
+
+			// Nothing is refunded before someone asks for it.
+			run_to_block(10);
+			Crowdloan::on_idle(10, Weight::MAX);
+			assert_eq!(Balances::free_balance(account_id), raised);
+
+			// The first batch is refunded right away.
+			assert_ok!(Crowdloan::refund(RuntimeOrigin::signed(1337), para));
+			let status = Crowdloan::refund_status(para).unwrap();
+			assert!(status.scheduled);
+			assert_eq!(status.refunded, RemoveKeysLimit::get());
+			assert_eq!(status.refunded_amount + status.remaining, raised);
+
+			// The others are refunded in batches when blocks have weight to spare.
+			Crowdloan::on_idle(10, Weight::MAX);
+			let amount = status.remaining - Crowdloan::funds(para).unwrap().raised;
+			assert_eq!(
+				last_event(),
+				super::Event::<Test>::AutoRefunded {
+					para_id: para,
+					count: RemoveKeysLimit::get(),
+					amount
+				}
+				.into()
+			);
+			assert_eq!(
+				Crowdloan::refund_status(para).unwrap().refunded,
+				RemoveKeysLimit::get() * 2
+			);
+			assert!(Crowdloan::funds(para).is_some());
+
+			// Until the fund is empty, at which point it is dissolved.
+			Crowdloan::on_idle(10, Weight::MAX);
+			assert_eq!(last_event(), super::Event::<Test>::Dissolved { para_id: para }.into());
+			assert_eq!(Crowdloan::funds(para), None);
+			assert_eq!(Crowdloan::refund_status(para), None);
+			assert_eq!(Crowdloan::refund_progress(para), None);
+			assert_eq!(Balances::free_balance(account_id), 0);
+			// The depositor got its deposit back.
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 1000 + 1);
+			for i in 2..=contributors {
+				assert_eq!(Balances::free_balance(&i.into()), i as u64 * 1000);
+			}
+		});
+	}
+
+	#[test]
+	fn refund_is_scheduled_once_the_lease_period_range_passed() {
+		new_test_ext().execute_with(|| {
+			let para = new_para();
+			let index = NextFundIndex::<Test>::get();
+			let account_id = Crowdloan::fund_account_id(index);
+
+			assert_ok!(Crowdloan::create(RuntimeOrigin::signed(1), para, 1000, 1, 1, 9, None));
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(2), para, 100, None));
+			assert_ok!(Crowdloan::contribute(RuntimeOrigin::signed(3), para, 150, None));
+
+			// The fund may still win lease period 1.
+			run_to_block(20);
+			Crowdloan::on_idle(20, Weight::MAX);
+			assert!(!Crowdloan::refund_status(para).unwrap().scheduled);
+			assert_eq!(Balances::free_balance(account_id), 250);
+
+			assert_eq!(RefundScanCursor::<Test>::get(), None);
+
+			// Lease period 2 starts, so the fund is refunded and dissolved without anyone asking.
+			run_to_block(40);
+			assert_eq!(RefundScanCursor::<Test>::get(), Some(Vec::new()));
+			assert!(!Crowdloan::refund_status(para).unwrap().scheduled);
+			Crowdloan::on_idle(40, Weight::MAX);
+			assert_eq!(RefundScanCursor::<Test>::get(), None);
+			assert_eq!(last_event(), super::Event::<Test>::Dissolved { para_id: para }.into());
+			assert_eq!(Crowdloan::funds(para), None);
+			assert_eq!(Balances::free_balance(account_id), 0);
+			assert_eq!(Balances::free_balance(2), 2000);
+			assert_eq!(Balances::free_balance(3), 3000);
+		});
+	}
+
 	#[test]
 	fn refund_and_dissolve_works() {
 		new_test_ext().execute_with(|| {
@@ -1934,7 +2678,8 @@ mod benchmarking {
 	use sp_runtime::traits::{Bounded, CheckedSub};
 	use sp_std::prelude::*;
 
-	use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+	use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+	use xcm::latest::prelude::*;
 
 	fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 		let events = frame_system::Pallet::<T>::events();
@@ -2054,6 +2799,53 @@ mod benchmarking {
 			assert_last_event::<T>(Event::<T>::Withdrew { who: contributor, fund_index, amount: T::MinContribution::get() }.into());
 		}
 
+		contribute_from_location {
+			let (lpl, offset) = T::Auctioneer::lease_period_length();
+			let end = lpl + offset;
+			let fund_index = create_fund::<T>(1, end);
+			let origin = T::XcmContributionOrigin::try_successful_origin()
+				.map_err(|_| BenchmarkError::Skip)?;
+			let location = T::XcmContributionOrigin::try_origin(origin.clone())
+				.map_err(|_| BenchmarkError::Skip)?;
+			let who = T::LocationToAccountId::convert_ref(&location)
+				.map_err(|()| BenchmarkError::Skip)?;
+			let contribution = T::MinContribution::get();
+			CurrencyOf::<T>::make_free_balance_be(&who, BalanceOf::<T>::max_value());
+
+			let pubkey = crypto::create_ed25519_pubkey(b"//verifier".to_vec());
+			let payload = (fund_index, &who, BalanceOf::<T>::default(), contribution);
+			let sig = crypto::create_ed25519_signature(&payload.encode(), pubkey);
+		}: _<T::RuntimeOrigin>(origin, fund_index, contribution, Some(sig))
+		verify {
+			assert_last_event::<T>(Event::<T>::ContributedFromLocation {
+				who,
+				location: location.into(),
+				para_id: fund_index,
+				amount: contribution,
+			}.into());
+		}
+
+		// Worst case: the withdrawn contribution is sent back to an account on a parachain.
+		withdraw_to_location {
+			let (lpl, offset) = T::Auctioneer::lease_period_length();
+			let end = lpl + offset;
+			let fund_index = create_fund::<T>(1337, end);
+			let caller: T::AccountId = whitelisted_caller();
+			let contributor = account("contributor", 0, 0);
+			contribute_fund::<T>(&contributor, fund_index);
+			let fund = Crowdloan::<T>::funds(fund_index).ok_or("fund not created")?;
+			let location = MultiLocation::new(
+				0,
+				X2(Parachain(1337), AccountId32 { network: Any, id: [0; 32] }),
+			);
+			XcmContributors::<T>::insert(fund.fund_index, &contributor, VersionedMultiLocation::from(location));
+			frame_system::Pallet::<T>::set_block_number(T::BlockNumber::max_value());
+		}: withdraw(RawOrigin::Signed(caller), contributor.clone(), fund_index)
+		verify {
+			assert!(Crowdloan::<T>::xcm_contributor(fund.fund_index, &contributor).is_none());
+			assert_eq!(Crowdloan::<T>::contribution_get(fund.fund_index, &contributor).0, Zero::zero());
+		}
+
 		// Worst case: Refund removes `RemoveKeysLimit` keys, and is fully refunded.
 		#[skip_meta]
 		refund {
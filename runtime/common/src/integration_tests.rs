@@ -24,7 +24,7 @@ use crate::{
 };
 use frame_support::{
 	assert_noop, assert_ok, parameter_types,
-	traits::{Currency, GenesisBuild, KeyOwnerProofSystem, OnFinalize, OnInitialize},
+	traits::{Currency, EnsureOrigin, GenesisBuild, KeyOwnerProofSystem, OnFinalize, OnInitialize},
 	weights::Weight,
 	PalletId,
 };
@@ -46,6 +46,12 @@ use sp_runtime::{
 	AccountId32,
 };
 use sp_std::sync::Arc;
+use xcm::latest::{
+	Junction::{self, Parachain},
+	Junctions::X2,
+	MultiLocation, NetworkId,
+};
+use xcm_builder::{Account32Hash, ChildParachainConvertsVia};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -200,18 +206,22 @@ impl origin::Config for Test {}
 
 parameter_types! {
 	pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const ParasHeadHistoryLength: u32 = 16;
 }
 
 impl paras::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = paras::TestWeightInfo;
 	type UnsignedPriority = ParasUnsignedPriority;
+	type HeadHistoryLength = ParasHeadHistoryLength;
 	type NextSessionRotation = crate::mock::TestNextSessionRotation;
+	type OnboardingPvfCheckHandler = Registrar;
 }
 
 parameter_types! {
 	pub const ParaDeposit: Balance = 500;
 	pub const DataDepositPerByte: Balance = 1;
+	pub const PvfRejectionFee: Balance = 10;
 }
 
 impl paras_registrar::Config for Test {
@@ -219,6 +229,7 @@ impl paras_registrar::Config for Test {
 	type OnSwap = (Crowdloan, Slots);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
+	type PvfRejectionFee = PvfRejectionFee;
 	type Currency = Balances;
 	type RuntimeOrigin = RuntimeOrigin;
 	type WeightInfo = crate::paras_registrar::TestWeightInfo;
@@ -227,6 +238,7 @@ impl paras_registrar::Config for Test {
 parameter_types! {
 	pub const EndingPeriod: BlockNumber = 10;
 	pub const SampleLength: BlockNumber = 1;
+	pub const MaxBidHistory: u32 = 10;
 }
 
 impl auctions::Config for Test {
@@ -237,6 +249,7 @@ impl auctions::Config for Test {
 	type SampleLength = SampleLength;
 	type Randomness = TestRandomness<Self>;
 	type InitiateOrigin = EnsureRoot<AccountId>;
+	type MaxBidHistory = MaxBidHistory;
 	type WeightInfo = crate::auctions::TestWeightInfo;
 }
 
@@ -261,6 +274,32 @@ parameter_types! {
 	pub const MinContribution: Balance = 1;
 	pub const RemoveKeysLimit: u32 = 100;
 	pub const MaxMemoLength: u8 = 32;
+	pub const AnyNetwork: NetworkId = NetworkId::Any;
+}
+
+/// Treats signed origins as the same account on parachain 2000, contributing via XCM.
+pub struct SignedAsRemoteContributor;
+impl EnsureOrigin<RuntimeOrigin> for SignedAsRemoteContributor {
+	type Success = MultiLocation;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<MultiLocation, RuntimeOrigin> {
+		let raw: Result<frame_system::RawOrigin<AccountId>, _> = o.clone().into();
+		match raw {
+			Ok(frame_system::RawOrigin::Signed(who)) => Ok(MultiLocation::new(
+				0,
+				X2(
+					Parachain(2000),
+					Junction::AccountId32 { network: NetworkId::Any, id: who.into() },
+				),
+			)),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(signed(0))
+	}
 }
 
 impl crowdloan::Config for Test {
@@ -272,6 +311,10 @@ impl crowdloan::Config for Test {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type XcmContributionOrigin = SignedAsRemoteContributor;
+	type LocationToAccountId =
+		(ChildParachainConvertsVia<ParaId, AccountId>, Account32Hash<AnyNetwork, AccountId>);
+	type XcmRouter = ();
 	type WeightInfo = crate::crowdloan::TestWeightInfo;
 }
 
@@ -129,6 +129,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type DataDepositPerByte: Get<BalanceOf<Self>>;
 
+		/// The fee withheld from the data deposit when the validation code of an onboarding para
+		/// is rejected by PVF pre-checking and its registration is rolled back.
+		#[pallet::constant]
+		type PvfRejectionFee: Get<BalanceOf<Self>>;
+
 		/// Weight Information for the Extrinsics in the Pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -136,9 +141,30 @@ pub mod pallet {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		Registered { para_id: ParaId, manager: T::AccountId },
-		Deregistered { para_id: ParaId },
-		Reserved { para_id: ParaId, who: T::AccountId },
+		Registered {
+			para_id: ParaId,
+			manager: T::AccountId,
+		},
+		Deregistered {
+			para_id: ParaId,
+		},
+		Reserved {
+			para_id: ParaId,
+			who: T::AccountId,
+		},
+		/// The validation code of a registered para passed PVF pre-checking and the para is
+		/// onboarding.
+		RegistrationAccepted {
+			para_id: ParaId,
+		},
+		/// The validation code of a registered para was rejected by PVF pre-checking. The
+		/// registration was rolled back to a reservation, `refunded` of the data deposit was
+		/// returned to the manager and `fee` was burned.
+		RegistrationRejected {
+			para_id: ParaId,
+			refunded: BalanceOf<T>,
+			fee: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -608,6 +634,34 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Roll back the registration of a para whose validation code was rejected by PVF
+	/// pre-checking.
+	///
+	/// The para ID stays reserved by its manager, who may register it again. Everything deposited
+	/// on top of the reservation deposit is refunded, minus `PvfRejectionFee`.
+	fn do_rollback_registration(id: ParaId) -> Weight {
+		let mut info = match Paras::<T>::get(id) {
+			Some(info) => info,
+			None => return T::DbWeight::get().reads(1),
+		};
+
+		let kept = T::ParaDeposit::get().min(info.deposit);
+		let refundable = info.deposit.saturating_sub(kept);
+		let fee = T::PvfRejectionFee::get().min(refundable);
+		let (_, unslashed) = <T as Config>::Currency::slash_reserved(&info.manager, fee);
+		let fee = fee.saturating_sub(unslashed);
+		let refunded = refundable.saturating_sub(fee);
+		<T as Config>::Currency::unreserve(&info.manager, refunded);
+
+		info.deposit = kept;
+		info.locked = false;
+		Paras::<T>::insert(id, info);
+		PendingSwap::<T>::remove(id);
+
+		Self::deposit_event(Event::<T>::RegistrationRejected { para_id: id, refunded, fee });
+		T::DbWeight::get().reads_writes(2, 4)
+	}
+
 	/// Verifies the onboarding data is valid for a para.
 	///
 	/// Returns `ParaGenesisArgs` and the deposit needed for the data.
@@ -642,6 +696,19 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> paras::OnboardingPvfCheckHandler for Pallet<T> {
+	fn accepted(id: ParaId) -> Weight {
+		if Paras::<T>::contains_key(id) {
+			Self::deposit_event(Event::<T>::RegistrationAccepted { para_id: id });
+		}
+		T::DbWeight::get().reads(1)
+	}
+
+	fn rejected(id: ParaId) -> Weight {
+		Self::do_rollback_registration(id)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -752,13 +819,16 @@ mod tests {
 
 	parameter_types! {
 		pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+		pub const ParasHeadHistoryLength: u32 = 16;
 	}
 
 	impl paras::Config for Test {
 		type RuntimeEvent = RuntimeEvent;
 		type WeightInfo = paras::TestWeightInfo;
 		type UnsignedPriority = ParasUnsignedPriority;
+		type HeadHistoryLength = ParasHeadHistoryLength;
 		type NextSessionRotation = crate::mock::TestNextSessionRotation;
+		type OnboardingPvfCheckHandler = Registrar;
 	}
 
 	impl configuration::Config for Test {
@@ -768,6 +838,7 @@ mod tests {
 	parameter_types! {
 		pub const ParaDeposit: Balance = 10;
 		pub const DataDepositPerByte: Balance = 1;
+		pub const PvfRejectionFee: Balance = 5;
 		pub const MaxRetries: u32 = 3;
 	}
 
@@ -778,6 +849,7 @@ mod tests {
 		type OnSwap = MockSwap;
 		type ParaDeposit = ParaDeposit;
 		type DataDepositPerByte = DataDepositPerByte;
+		type PvfRejectionFee = PvfRejectionFee;
 		type WeightInfo = TestWeightInfo;
 	}
 
@@ -940,6 +1012,63 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn pvf_check_rejection_rolls_back_registration() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			let mut config = Configuration::config();
+			config.pvf_checking_enabled = true;
+			configuration::Pallet::<Test>::force_set_active_config(config);
+
+			let para_id = LOWEST_PUBLIC_ID;
+			let free_balance = Balances::free_balance(&1);
+			assert_ok!(Registrar::reserve(RuntimeOrigin::signed(1)));
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(1),
+				para_id,
+				test_genesis_head(32),
+				test_validation_code(32),
+			));
+			assert_eq!(Parachains::lifecycle(para_id), Some(ParaLifecycle::Onboarding));
+
+			// Nobody votes, so the PVF check expires and the code is rejected.
+			run_to_session(4);
+			assert_eq!(Parachains::lifecycle(para_id), None);
+
+			let deposit = <Test as Config>::ParaDeposit::get();
+			let data_deposit = 64 * <Test as Config>::DataDepositPerByte::get();
+			let fee = <Test as Config>::PvfRejectionFee::get();
+			System::assert_has_event(
+				Event::<Test>::RegistrationRejected { para_id, refunded: data_deposit - fee, fee }
+					.into(),
+			);
+			// The ID stays reserved and only the fee is lost.
+			assert_eq!(Balances::reserved_balance(&1), deposit);
+			assert_eq!(Balances::free_balance(&1), free_balance - deposit - fee);
+			assert_eq!(
+				Paras::<Test>::get(para_id),
+				Some(ParaInfo { manager: 1, deposit, locked: false })
+			);
+
+			// The manager may register again.
+			configuration::Pallet::<Test>::force_set_active_config(
+				configuration::HostConfiguration {
+					pvf_checking_enabled: false,
+					..Configuration::config()
+				},
+			);
+			assert_ok!(Registrar::register(
+				RuntimeOrigin::signed(1),
+				para_id,
+				test_genesis_head(32),
+				test_validation_code(16),
+			));
+			System::assert_has_event(Event::<Test>::RegistrationAccepted { para_id }.into());
+			run_to_session(6);
+			assert!(Parachains::is_parathread(para_id));
+		});
+	}
+
 	#[test]
 	fn register_handles_basic_errors() {
 		new_test_ext().execute_with(|| {
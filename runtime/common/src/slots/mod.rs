@@ -16,7 +16,7 @@
 
 //! Parathread and parachains leasing system. Allows para IDs to be claimed, the code and data to be initialized and
 //! parachain slots (i.e. continuous scheduling) to be leased. Also allows for parachains and parathreads to be
-//! swapped.
+//! swapped, and for lease periods to be transferred between paras with the consent of both parties.
 //!
 //! This doesn't handle the mechanics of determining which para ID actually ends up with a parachain lease. This
 //! must handled by a separately, through the trait interface that this pallet provides or the root dispatchables.
@@ -26,14 +26,19 @@ pub mod migration;
 use crate::traits::{LeaseError, Leaser, Registrar};
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ReservableCurrency},
+	traits::{BalanceStatus, Currency, ReservableCurrency},
 	weights::Weight,
 };
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
+use parity_scale_codec::{Decode, Encode};
 use primitives::v2::Id as ParaId;
-use sp_runtime::traits::{CheckedConversion, CheckedSub, Saturating, Zero};
-use sp_std::prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{CheckedConversion, CheckedSub, Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
 
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -44,6 +49,7 @@ pub trait WeightInfo {
 	fn manage_lease_period_start(c: u32, t: u32) -> Weight;
 	fn clear_all_leases() -> Weight;
 	fn trigger_onboard() -> Weight;
+	fn transfer_lease() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -60,6 +66,22 @@ impl WeightInfo for TestWeightInfo {
 	fn trigger_onboard() -> Weight {
 		Weight::zero()
 	}
+	fn transfer_lease() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// A transfer of lease periods from one para to another, awaiting the consent of both parties.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PendingLeaseTransfer<LeasePeriod> {
+	/// The first lease period to transfer.
+	pub period_begin: LeasePeriod,
+	/// The number of lease periods to transfer.
+	pub period_count: LeasePeriod,
+	/// Whether the account holding the lease periods consented to the transfer.
+	pub holder_approved: bool,
+	/// Whether the manager of the receiving para consented to the transfer.
+	pub recipient_approved: bool,
 }
 
 #[frame_support::pallet]
@@ -118,6 +140,25 @@ pub mod pallet {
 	pub type Leases<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, Vec<Option<(T::AccountId, BalanceOf<T>)>>, ValueQuery>;
 
+	/// Lease transfers awaiting the consent of one of the two parties, keyed by the para giving up
+	/// the lease periods and the para receiving them.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_lease_transfer)]
+	pub type PendingLeaseTransfers<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ParaId,
+		Twox64Concat,
+		ParaId,
+		PendingLeaseTransfer<LeasePeriodOf<T>>,
+	>;
+
+	/// The index of `PendingLeaseTransfers` by the para receiving the lease periods, so that the
+	/// pending transfers of a para can be found from either side.
+	#[pallet::storage]
+	pub type PendingLeaseTransfersTo<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Twox64Concat, ParaId, ()>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -134,6 +175,23 @@ pub mod pallet {
 			extra_reserved: BalanceOf<T>,
 			total_amount: BalanceOf<T>,
 		},
+		/// One of the parties consented to transferring lease periods between two paras. The
+		/// transfer happens once the other party consents as well.
+		LeaseTransferApproved {
+			para_id: ParaId,
+			recipient: ParaId,
+			period_begin: LeasePeriodOf<T>,
+			period_count: LeasePeriodOf<T>,
+			who: T::AccountId,
+		},
+		/// Lease periods were transferred from one para to another, along with their deposit.
+		LeaseTransferred {
+			para_id: ParaId,
+			recipient: ParaId,
+			period_begin: LeasePeriodOf<T>,
+			period_count: LeasePeriodOf<T>,
+			leaser: T::AccountId,
+		},
 	}
 
 	#[pallet::error]
@@ -142,6 +200,16 @@ pub mod pallet {
 		ParaNotOnboarding,
 		/// There was an error with the lease.
 		LeaseError,
+		/// The lease periods to transfer are not all held by a single account, or are not all in
+		/// the future or the current lease period.
+		InvalidLeaseRange,
+		/// The receiving para is not registered, or is the same para as the one giving up the
+		/// lease periods.
+		InvalidRecipient,
+		/// The receiving para already holds a lease for some of the lease periods to transfer.
+		AlreadyLeased,
+		/// The caller neither holds the lease periods to transfer nor manages the receiving para.
+		NotTransferParty,
 	}
 
 	#[pallet::hooks]
@@ -218,6 +286,86 @@ pub mod pallet {
 			};
 			Ok(())
 		}
+
+		/// Transfer `period_count` lease periods of `para` starting at `period_begin` to the
+		/// registered para `recipient`, along with the deposit held for them.
+		///
+		/// Both the account holding the lease periods and the manager of `recipient` must call
+		/// this with the same arguments. The transfer happens once both of them consented, until
+		/// then it is stored in the pending lease transfers. Calling this with a `period_count` of
+		/// zero clears the pending transfer between the two paras.
+		///
+		/// The manager of `recipient` becomes the leaser of the transferred lease periods. The
+		/// deposit the former leaser no longer needs is moved to the manager, which reserves
+		/// whatever else is needed. If the current lease period is transferred, `recipient` is
+		/// onboarded as a parachain and `para` is downgraded to a parathread, failing the transfer
+		/// if either is not possible.
+		///
+		/// Origin must be signed.
+		#[pallet::weight(T::WeightInfo::transfer_lease())]
+		pub fn transfer_lease(
+			origin: OriginFor<T>,
+			para: ParaId,
+			recipient: ParaId,
+			period_begin: LeasePeriodOf<T>,
+			period_count: LeasePeriodOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				para != recipient && T::Registrar::is_registered(recipient),
+				Error::<T>::InvalidRecipient
+			);
+
+			if period_count.is_zero() {
+				let holder = Leases::<T>::get(para).into_iter().flatten().any(|(l, _)| l == who);
+				ensure!(
+					holder || T::Registrar::manager_of(recipient) == Some(who),
+					Error::<T>::NotTransferParty
+				);
+				Self::remove_pending_transfer(para, recipient);
+				return Ok(())
+			}
+
+			let (holder, offset, count) = Self::lease_holder(para, period_begin, period_count)?;
+			let is_holder = holder == who;
+			let is_recipient = T::Registrar::manager_of(recipient).as_ref() == Some(&who);
+			ensure!(is_holder || is_recipient, Error::<T>::NotTransferParty);
+
+			// Any consent given for a different range doesn't count.
+			let mut pending = PendingLeaseTransfers::<T>::get(para, recipient)
+				.filter(|p| p.period_begin == period_begin && p.period_count == period_count)
+				.unwrap_or(PendingLeaseTransfer {
+					period_begin,
+					period_count,
+					holder_approved: false,
+					recipient_approved: false,
+				});
+			pending.holder_approved |= is_holder;
+			pending.recipient_approved |= is_recipient;
+
+			if pending.holder_approved && pending.recipient_approved {
+				Self::do_transfer_lease(para, recipient, &holder, offset, count)?;
+				Self::remove_pending_transfer(para, recipient);
+				Self::deposit_event(Event::<T>::LeaseTransferred {
+					para_id: para,
+					recipient,
+					period_begin,
+					period_count,
+					leaser: holder,
+				});
+			} else {
+				PendingLeaseTransfers::<T>::insert(para, recipient, pending);
+				PendingLeaseTransfersTo::<T>::insert(recipient, para, ());
+				Self::deposit_event(Event::<T>::LeaseTransferApproved {
+					para_id: para,
+					recipient,
+					period_begin,
+					period_count,
+					who,
+				});
+			}
+			Ok(())
+		}
 	}
 }
 
@@ -303,6 +451,114 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Remove the pending lease transfer from `para` to `recipient` along with its index entry.
+	fn remove_pending_transfer(
+		para: ParaId,
+		recipient: ParaId,
+	) -> Option<PendingLeaseTransfer<LeasePeriodOf<T>>> {
+		PendingLeaseTransfersTo::<T>::remove(recipient, para);
+		PendingLeaseTransfers::<T>::take(para, recipient)
+	}
+
+	/// Returns the account holding the `period_count` lease periods of `para` starting at
+	/// `period_begin`, along with the offset and number of these lease periods in `Leases`.
+	///
+	/// Fails unless all of the lease periods are held by the same account, and none of them is in
+	/// the past.
+	fn lease_holder(
+		para: ParaId,
+		period_begin: LeasePeriodOf<T>,
+		period_count: LeasePeriodOf<T>,
+	) -> Result<(T::AccountId, usize, usize), DispatchError> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let (current_lease_period, _) =
+			Self::lease_period_index(now).ok_or(Error::<T>::InvalidLeaseRange)?;
+		let offset = period_begin
+			.checked_sub(&current_lease_period)
+			.and_then(|x| x.checked_into::<usize>())
+			.ok_or(Error::<T>::InvalidLeaseRange)?;
+		let count = period_count.checked_into::<usize>().ok_or(Error::<T>::InvalidLeaseRange)?;
+		ensure!(count > 0, Error::<T>::InvalidLeaseRange);
+
+		let leases = Leases::<T>::get(para);
+		let range = leases
+			.get(offset..offset.saturating_add(count))
+			.ok_or(Error::<T>::InvalidLeaseRange)?;
+		let holder = match range.first() {
+			Some(Some((holder, _))) => holder.clone(),
+			_ => return Err(Error::<T>::InvalidLeaseRange.into()),
+		};
+		ensure!(
+			range.iter().all(|lease| matches!(lease, Some((who, _)) if who == &holder)),
+			Error::<T>::InvalidLeaseRange
+		);
+
+		Ok((holder, offset, count))
+	}
+
+	/// Move the `count` lease periods of `para` starting at `offset` in `Leases` to `recipient`.
+	///
+	/// The moved lease periods are held by the manager of `recipient` from then on, so the deposit
+	/// `holder` no longer needs is repatriated to the manager, which reserves whatever else it
+	/// needs on its own.
+	fn do_transfer_lease(
+		para: ParaId,
+		recipient: ParaId,
+		holder: &T::AccountId,
+		offset: usize,
+		count: usize,
+	) -> DispatchResult {
+		let manager = T::Registrar::manager_of(recipient).ok_or(Error::<T>::InvalidRecipient)?;
+		let mut leases = Leases::<T>::get(para);
+		let mut recipient_leases = Leases::<T>::get(recipient);
+		ensure!(
+			recipient_leases.iter().skip(offset).take(count).all(Option::is_none),
+			Error::<T>::AlreadyLeased
+		);
+
+		// The deposit of an account for a para is the maximum of its own lease periods.
+		let held = |who: &T::AccountId| {
+			Self::deposit_held(para, who).saturating_add(Self::deposit_held(recipient, who))
+		};
+		let holder_before = held(holder);
+		let manager_before = held(&manager);
+
+		if recipient_leases.len() < offset + count {
+			recipient_leases.resize_with(offset + count, || None);
+		}
+		for i in offset..offset + count {
+			recipient_leases[i] = leases[i].take().map(|(_, amount)| (manager.clone(), amount));
+		}
+		// It is illegal for a `None` value to trail in the list.
+		while let Some(None) = leases.last() {
+			leases.pop();
+		}
+		if leases.is_empty() {
+			Leases::<T>::remove(para);
+		} else {
+			Leases::<T>::insert(para, &leases);
+		}
+		Leases::<T>::insert(recipient, &recipient_leases);
+
+		let freed = holder_before.saturating_sub(held(holder));
+		let needed = held(&manager).saturating_sub(manager_before);
+		let moved = freed.min(needed);
+		let not_moved =
+			T::Currency::repatriate_reserved(holder, &manager, moved, BalanceStatus::Reserved)?;
+		debug_assert!(not_moved.is_zero());
+		let err_amount = T::Currency::unreserve(holder, freed.saturating_sub(moved));
+		debug_assert!(err_amount.is_zero());
+		T::Currency::reserve(&manager, needed.saturating_sub(moved))?;
+
+		// If the current lease period moved, so does the right to be a parachain.
+		if offset == 0 {
+			T::Registrar::make_parathread(para)?;
+			T::Registrar::make_parachain(recipient)?;
+		}
+
+		Ok(())
+	}
+
 	// Return a vector of (user, balance) for all deposits for a parachain.
 	// Useful when trying to clean up a parachain leases, as this would tell
 	// you all the balances you need to unreserve.
@@ -327,7 +583,35 @@ impl<T: Config> Pallet<T> {
 
 impl<T: Config> crate::traits::OnSwap for Pallet<T> {
 	fn on_swap(one: ParaId, other: ParaId) {
-		Leases::<T>::mutate(one, |x| Leases::<T>::mutate(other, |y| sp_std::mem::swap(x, y)))
+		Leases::<T>::mutate(one, |x| Leases::<T>::mutate(other, |y| sp_std::mem::swap(x, y)));
+
+		// Pending lease transfers follow the paras they were requested for. Only the transfers of
+		// the two paras are looked up, from either side.
+		let swapped = |id: ParaId| {
+			if id == one {
+				other
+			} else if id == other {
+				one
+			} else {
+				id
+			}
+		};
+		let mut affected = BTreeSet::new();
+		for id in [one, other] {
+			affected.extend(PendingLeaseTransfers::<T>::iter_key_prefix(id).map(|r| (id, r)));
+			affected.extend(PendingLeaseTransfersTo::<T>::iter_key_prefix(id).map(|p| (p, id)));
+		}
+		let affected: Vec<_> = affected
+			.into_iter()
+			.filter_map(|(para, recipient)| {
+				Self::remove_pending_transfer(para, recipient).map(|t| (para, recipient, t))
+			})
+			.collect();
+		for (para, recipient, transfer) in affected {
+			let (para, recipient) = (swapped(para), swapped(recipient));
+			PendingLeaseTransfers::<T>::insert(para, recipient, transfer);
+			PendingLeaseTransfersTo::<T>::insert(recipient, para, ());
+		}
 	}
 }
 
@@ -945,6 +1229,247 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn transfer_lease_works() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			assert_ok!(TestRegistrar::<Test>::register(
+				1,
+				ParaId::from(1_u32),
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+			assert_ok!(TestRegistrar::<Test>::register(
+				2,
+				ParaId::from(2_u32),
+				dummy_head_data(),
+				dummy_validation_code()
+			));
+
+			assert_ok!(Slots::lease_out(1.into(), &1, 5, 1, 4));
+			assert_eq!(Balances::reserved_balance(1), 5);
+
+			// Only the lease holder and the manager of the recipient can consent.
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(3), 1.into(), 2.into(), 3, 2),
+				Error::<Test>::NotTransferParty
+			);
+
+			// Nothing happens until both parties consented.
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 2.into(), 3, 2));
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(1_u32), ParaId::from(2_u32)),
+				Some(PendingLeaseTransfer {
+					period_begin: 3,
+					period_count: 2,
+					holder_approved: true,
+					recipient_approved: false,
+				})
+			);
+			assert_eq!(Slots::lease(ParaId::from(2_u32)), vec![]);
+
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(2), 1.into(), 2.into(), 3, 2));
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(1_u32), ParaId::from(2_u32)),
+				None
+			);
+			assert_eq!(Slots::lease(ParaId::from(1_u32)), vec![None, Some((1, 5)), Some((1, 5))]);
+			assert_eq!(
+				Slots::lease(ParaId::from(2_u32)),
+				vec![None, None, None, Some((2, 5)), Some((2, 5))]
+			);
+			// The leaser still needs its deposit for the remaining lease periods, so the manager
+			// of the recipient reserves its own.
+			assert_eq!(Slots::deposit_held(1.into(), &1), 5);
+			assert_eq!(Slots::deposit_held(2.into(), &2), 5);
+			assert_eq!(Balances::reserved_balance(1), 5);
+			assert_eq!(Balances::reserved_balance(2), 5);
+
+			run_to_block(30);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::reserved_balance(2), 5);
+			run_to_block(50);
+			assert_eq!(Balances::reserved_balance(2), 0);
+
+			assert_eq!(
+				TestRegistrar::<Test>::operations(),
+				vec![
+					(1.into(), 10, true),
+					(2.into(), 30, true),
+					(1.into(), 30, false),
+					(2.into(), 50, false),
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_lease_moves_deposit_and_parachain_status() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			for (manager, para) in [(1, 1_u32), (2, 2), (3, 3)] {
+				assert_ok!(TestRegistrar::<Test>::register(
+					manager,
+					ParaId::from(para),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+			assert_ok!(Slots::lease_out(1.into(), &1, 5, 0, 2));
+			assert_eq!(TestRegistrar::<Test>::parachains(), vec![ParaId::from(1_u32)]);
+
+			// Transferring all lease periods moves the whole deposit to the manager of the
+			// recipient, along with the right to be a parachain.
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 2.into(), 0, 2));
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(2), 1.into(), 2.into(), 0, 2));
+			assert_eq!(Slots::lease(ParaId::from(1_u32)), vec![]);
+			assert_eq!(Slots::lease(ParaId::from(2_u32)), vec![Some((2, 5)), Some((2, 5))]);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 5);
+			assert_eq!(Balances::reserved_balance(2), 5);
+			assert_eq!(Balances::free_balance(2), 20);
+			assert_eq!(TestRegistrar::<Test>::parachains(), vec![ParaId::from(2_u32)]);
+
+			// The transfer fails if the para giving up the current lease period can't be
+			// downgraded.
+			assert_ok!(TestRegistrar::<Test>::make_parathread(2.into()));
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(2), 2.into(), 3.into(), 0, 1));
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(3), 2.into(), 3.into(), 0, 1),
+				DispatchError::Other("not parachain, so cannot `make_parathread`")
+			);
+		});
+	}
+
+	#[test]
+	fn pending_lease_transfers_follow_swapped_paras() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			for (manager, para) in [(1, 1_u32), (2, 2), (3, 3)] {
+				assert_ok!(TestRegistrar::<Test>::register(
+					manager,
+					ParaId::from(para),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+			assert_ok!(Slots::lease_out(1.into(), &1, 5, 1, 2));
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 2.into(), 1, 2));
+			let pending = Slots::pending_lease_transfer(ParaId::from(1_u32), ParaId::from(2_u32));
+			assert!(pending.is_some());
+
+			<Slots as crate::traits::OnSwap>::on_swap(1.into(), 3.into());
+			assert_eq!(Slots::lease(ParaId::from(3_u32)), vec![None, Some((1, 5)), Some((1, 5))]);
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(1_u32), ParaId::from(2_u32)),
+				None
+			);
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(3_u32), ParaId::from(2_u32)),
+				pending
+			);
+
+			// Swapping the recipient moves the transfer along with its index entry.
+			<Slots as crate::traits::OnSwap>::on_swap(2.into(), 1.into());
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(3_u32), ParaId::from(1_u32)),
+				pending
+			);
+			assert!(PendingLeaseTransfersTo::<Test>::contains_key(
+				ParaId::from(1_u32),
+				ParaId::from(3_u32)
+			));
+			assert!(!PendingLeaseTransfersTo::<Test>::contains_key(
+				ParaId::from(2_u32),
+				ParaId::from(3_u32)
+			));
+			<Slots as crate::traits::OnSwap>::on_swap(1.into(), 2.into());
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(3_u32), ParaId::from(2_u32)),
+				pending
+			);
+
+			// The consent of the recipient completes the transfer of the swapped para.
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(2), 3.into(), 2.into(), 1, 2));
+			assert_eq!(Slots::lease(ParaId::from(2_u32)), vec![None, Some((2, 5)), Some((2, 5))]);
+			assert_eq!(PendingLeaseTransfers::<Test>::iter().count(), 0);
+			assert_eq!(PendingLeaseTransfersTo::<Test>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn transfer_lease_checks_work() {
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+
+			for (manager, para) in [(1, 1_u32), (2, 2), (3, 3)] {
+				assert_ok!(TestRegistrar::<Test>::register(
+					manager,
+					ParaId::from(para),
+					dummy_head_data(),
+					dummy_validation_code()
+				));
+			}
+			assert_ok!(Slots::lease_out(1.into(), &1, 5, 1, 2));
+			assert_ok!(Slots::lease_out(1.into(), &3, 5, 3, 1));
+			assert_ok!(Slots::lease_out(2.into(), &2, 5, 2, 1));
+
+			// The recipient must be another registered para.
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 1.into(), 1, 1),
+				Error::<Test>::InvalidRecipient
+			);
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 4.into(), 1, 1),
+				Error::<Test>::InvalidRecipient
+			);
+
+			// The whole range must be leased by a single account.
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 3.into(), 0, 2),
+				Error::<Test>::InvalidLeaseRange
+			);
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 3.into(), 1, 3),
+				Error::<Test>::InvalidLeaseRange
+			);
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 3.into(), 3, 2),
+				Error::<Test>::InvalidLeaseRange
+			);
+
+			// Consent for a different range doesn't count.
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 3.into(), 1, 1));
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(3), 1.into(), 3.into(), 1, 2));
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(1_u32), ParaId::from(3_u32)),
+				Some(PendingLeaseTransfer {
+					period_begin: 1,
+					period_count: 2,
+					holder_approved: false,
+					recipient_approved: true,
+				})
+			);
+
+			// A pending transfer can be cleared by either party.
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 3.into(), 0, 0));
+			assert_eq!(
+				Slots::pending_lease_transfer(ParaId::from(1_u32), ParaId::from(3_u32)),
+				None
+			);
+
+			// The recipient can't hold a lease for any of the transferred lease periods.
+			assert_ok!(Slots::transfer_lease(RuntimeOrigin::signed(1), 1.into(), 2.into(), 1, 2));
+			assert_noop!(
+				Slots::transfer_lease(RuntimeOrigin::signed(2), 1.into(), 2.into(), 1, 2),
+				Error::<Test>::AlreadyLeased
+			);
+		});
+	}
+
 	#[test]
 	fn lease_period_offset_works() {
 		new_test_ext().execute_with(|| {
@@ -1139,6 +1664,44 @@ mod benchmarking {
 			assert!(T::Registrar::is_parachain(para));
 		}
 
+		// Worst case scenario, the transfer is confirmed and includes the current lease period, so
+		// both paras change their lifecycle.
+		transfer_lease {
+			// If there is an offset, we need to be on that block to be able to do lease things.
+			frame_system::Pallet::<T>::set_block_number(T::LeaseOffset::get() + One::one());
+			let now = frame_system::Pallet::<T>::block_number();
+			let (period_begin, _) = Slots::<T>::lease_period_index(now).unwrap();
+			let period_count: LeasePeriodOf<T> = 4u32.into();
+
+			let (para, leaser) = register_a_parathread::<T>(1);
+			let (recipient, manager) = register_a_parathread::<T>(2);
+			let amount = T::Currency::minimum_balance();
+			let origin = T::ForceOrigin::successful_origin();
+			Slots::<T>::force_lease(origin, para, leaser.clone(), amount, period_begin, period_count)?;
+			T::Registrar::execute_pending_transitions();
+			assert!(T::Registrar::is_parachain(para));
+
+			Slots::<T>::transfer_lease(
+				RawOrigin::Signed(manager).into(),
+				para,
+				recipient,
+				period_begin,
+				period_count,
+			)?;
+		}: _(RawOrigin::Signed(leaser.clone()), para, recipient, period_begin, period_count)
+		verify {
+			assert_last_event::<T>(Event::<T>::LeaseTransferred {
+				para_id: para,
+				recipient,
+				period_begin,
+				period_count,
+				leaser,
+			}.into());
+			T::Registrar::execute_pending_transitions();
+			assert!(T::Registrar::is_parathread(para));
+			assert!(T::Registrar::is_parachain(recipient));
+		}
+
 		impl_benchmark_test_suite!(
 			Slots,
 			crate::integration_tests::new_test_ext(),
@@ -16,16 +16,59 @@
 
 //! XCM sender for relay chain.
 
-use parity_scale_codec::Encode;
+use frame_support::traits::Get;
+use parity_scale_codec::{Decode, Encode};
+use primitives::v2::Id as ParaId;
 use runtime_parachains::{configuration, dmp};
-use sp_std::marker::PhantomData;
-use xcm::latest::prelude::*;
+use sp_runtime::traits::Convert;
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
+use xcm_executor::traits::InspectMessageQueues;
+
+/// Price of delivering a message to a given parachain.
+pub trait PriceForParachainDelivery {
+	/// Return the assets required to deliver `message` to the given `para` destination.
+	fn price_for_parachain_delivery(para: ParaId, message: &Xcm<()>) -> MultiAssets;
+}
+
+/// Delivery is free.
+impl PriceForParachainDelivery for () {
+	fn price_for_parachain_delivery(_: ParaId, _: &Xcm<()>) -> MultiAssets {
+		MultiAssets::new()
+	}
+}
+
+/// Implementation of [`PriceForParachainDelivery`] charging a base fee for the destination, given by
+/// `BaseFee`, plus `ByteFee` for every byte of the encoded message, all paid in the asset `A`.
+pub struct SizeBasedPrice<A, BaseFee, ByteFee>(PhantomData<(A, BaseFee, ByteFee)>);
+
+impl<A: Get<AssetId>, BaseFee: Convert<ParaId, u128>, ByteFee: Get<u128>> PriceForParachainDelivery
+	for SizeBasedPrice<A, BaseFee, ByteFee>
+{
+	fn price_for_parachain_delivery(para: ParaId, message: &Xcm<()>) -> MultiAssets {
+		let amount = BaseFee::convert(para)
+			.saturating_add(ByteFee::get().saturating_mul(message.encoded_size() as u128));
+		(A::get(), amount).into()
+	}
+}
+
+/// Implementation of `Convert<ParaId, u128>` giving every destination the same base fee `B`.
+pub struct ConstantBaseFee<B>(PhantomData<B>);
+
+impl<B: Get<u128>> Convert<ParaId, u128> for ConstantBaseFee<B> {
+	fn convert(_: ParaId) -> u128 {
+		B::get()
+	}
+}
 
 /// XCM sender for relay chain. It only sends downward message.
-pub struct ChildParachainRouter<T, W>(PhantomData<(T, W)>);
+///
+/// The delivery of a message is priced by `P`. This type only quotes the price: it is up to the
+/// sender of the message to pay it.
+pub struct ChildParachainRouter<T, W, P = ()>(PhantomData<(T, W, P)>);
 
-impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion> SendXcm
-	for ChildParachainRouter<T, W>
+impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion, P> SendXcm
+	for ChildParachainRouter<T, W, P>
 {
 	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
 		let dest = dest.into();
@@ -34,11 +77,16 @@ impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion> SendXcm
 				// Downward message passing.
 				let versioned_xcm =
 					W::wrap_version(&dest, msg).map_err(|()| SendError::DestinationUnsupported)?;
+				let blob = versioned_xcm.encode();
+				// Identify the message as it is delivered, by its topic if it survived wrapping.
+				let message_id =
+					versioned_xcm.topic().unwrap_or_else(|| sp_io::hashing::blake2_256(&blob));
 				let config = <configuration::Pallet<T>>::config();
-				<dmp::Pallet<T>>::queue_downward_message(
+				<dmp::Pallet<T>>::queue_downward_message_with_id(
 					&config,
 					id.into(),
-					versioned_xcm.encode(),
+					blob,
+					message_id,
 				)
 				.map_err(Into::<SendError>::into)?;
 				Ok(())
@@ -47,3 +95,90 @@ impl<T: configuration::Config + dmp::Config, W: xcm::WrapVersion> SendXcm
 		}
 	}
 }
+
+impl<T, W, P: PriceForParachainDelivery> QuoteDelivery for ChildParachainRouter<T, W, P> {
+	fn quote_delivery(dest: &MultiLocation, msg: &Xcm<()>) -> Option<MultiAssets> {
+		match dest {
+			MultiLocation { parents: 0, interior: X1(Parachain(id)) } =>
+				Some(P::price_for_parachain_delivery((*id).into(), msg)),
+			_ => None,
+		}
+	}
+}
+
+impl<T: dmp::Config, W, P> InspectMessageQueues for ChildParachainRouter<T, W, P> {
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		<dmp::Pallet<T>>::all_dmq_contents()
+			.into_iter()
+			.map(|(para, queue)| {
+				let destination = MultiLocation::from(Parachain(para.into())).into();
+				let messages = queue
+					.into_iter()
+					.filter_map(|message| VersionedXcm::<()>::decode(&mut &message.msg[..]).ok())
+					.collect();
+				(destination, messages)
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::parameter_types;
+
+	parameter_types! {
+		pub const FeeAsset: AssetId = Concrete(Here.into());
+		pub const BaseDeliveryFee: u128 = 1_000;
+		pub const TransactionByteFee: u128 = 10;
+	}
+
+	struct PerParaBaseFee;
+	impl Convert<ParaId, u128> for PerParaBaseFee {
+		fn convert(para: ParaId) -> u128 {
+			if para == 1000.into() {
+				0
+			} else {
+				BaseDeliveryFee::get()
+			}
+		}
+	}
+
+	type Router = ChildParachainRouter<
+		(),
+		(),
+		SizeBasedPrice<FeeAsset, ConstantBaseFee<BaseDeliveryFee>, TransactionByteFee>,
+	>;
+
+	#[test]
+	fn size_based_price_works() {
+		let message = Xcm(vec![ClearOrigin]);
+		let len = message.encoded_size() as u128;
+
+		assert_eq!(
+			<Router as QuoteDelivery>::quote_delivery(&Parachain(2000).into(), &message),
+			Some((Here, 1_000 + 10 * len).into())
+		);
+		// Other destinations aren't reachable by the router.
+		assert_eq!(<Router as QuoteDelivery>::quote_delivery(&Parent.into(), &message), None);
+
+		type PerParaPrice = SizeBasedPrice<FeeAsset, PerParaBaseFee, TransactionByteFee>;
+		assert_eq!(
+			PerParaPrice::price_for_parachain_delivery(1000.into(), &message),
+			(Here, 10 * len).into()
+		);
+		assert_eq!(
+			PerParaPrice::price_for_parachain_delivery(2000.into(), &message),
+			(Here, 1_000 + 10 * len).into()
+		);
+
+		// Delivery is free by default.
+		assert_eq!(
+			<ChildParachainRouter<(), ()> as QuoteDelivery>::quote_delivery(
+				&Parachain(2000).into(),
+				&message
+			),
+			Some(MultiAssets::new())
+		);
+	}
+}
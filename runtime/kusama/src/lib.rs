@@ -1127,13 +1127,16 @@ impl parachains_inclusion::Config for Runtime {
 
 parameter_types! {
 	pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const ParasHeadHistoryLength: u32 = 256;
 }
 
 impl parachains_paras::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::runtime_parachains_paras::WeightInfo<Runtime>;
 	type UnsignedPriority = ParasUnsignedPriority;
+	type HeadHistoryLength = ParasHeadHistoryLength;
 	type NextSessionRotation = Babe;
+	type OnboardingPvfCheckHandler = Registrar;
 }
 
 parameter_types! {
@@ -1149,16 +1152,26 @@ impl parachains_ump::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+parameter_types! {
+	/// The deposit per byte of HRMP channel capacity, charged on top of the flat deposits of the
+	/// host configuration.
+	pub const HrmpChannelDepositPerByte: Balance = deposit(0, 1);
+}
 
 impl parachains_hrmp::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type ChannelDepositPerByte = HrmpChannelDepositPerByte;
 	type WeightInfo = weights::runtime_parachains_hrmp::WeightInfo<Runtime>;
 }
 
 impl parachains_paras_inherent::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
 
@@ -1170,15 +1183,21 @@ impl parachains_initializer::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_initializer::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxActiveDisputesPerSession: u32 = 64;
+}
+
 impl parachains_disputes::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
 	type SlashingHandler = ();
+	type MaxActiveDisputesPerSession = MaxActiveDisputesPerSession;
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const ParaDeposit: Balance = 40 * UNITS;
+	pub const PvfRejectionFee: Balance = 1 * UNITS;
 }
 
 impl paras_registrar::Config for Runtime {
@@ -1188,6 +1207,7 @@ impl paras_registrar::Config for Runtime {
 	type OnSwap = (Crowdloan, Slots);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
+	type PvfRejectionFee = PvfRejectionFee;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
@@ -1224,6 +1244,9 @@ impl crowdloan::Config for Runtime {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type XcmContributionOrigin = pallet_xcm::EnsureXcm<crowdloan::IsChildParachainContributor>;
+	type LocationToAccountId = xcm_config::SovereignAccountOf;
+	type XcmRouter = xcm_config::XcmRouter;
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
@@ -1233,6 +1256,7 @@ parameter_types! {
 	pub const EndingPeriod: BlockNumber = 5 * DAYS;
 	// ~ 1000 samples per day -> ~ 20 blocks per sample -> 2 minute samples
 	pub const SampleLength: BlockNumber = 2 * MINUTES;
+	pub const MaxBidHistory: u32 = 100;
 }
 
 impl auctions::Config for Runtime {
@@ -1243,6 +1267,7 @@ impl auctions::Config for Runtime {
 	type SampleLength = SampleLength;
 	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type InitiateOrigin = AuctionAdmin;
+	type MaxBidHistory = MaxBidHistory;
 	type WeightInfo = weights::runtime_common_auctions::WeightInfo<Runtime>;
 }
 
@@ -1403,11 +1428,11 @@ construct_runtime! {
 		Configuration: parachains_configuration::{Pallet, Call, Storage, Config<T>} = 51,
 		ParasShared: parachains_shared::{Pallet, Call, Storage} = 52,
 		ParaInclusion: parachains_inclusion::{Pallet, Call, Storage, Event<T>} = 53,
-		ParaInherent: parachains_paras_inherent::{Pallet, Call, Storage, Inherent} = 54,
+		ParaInherent: parachains_paras_inherent::{Pallet, Call, Storage, Inherent, Event} = 54,
 		ParaScheduler: parachains_scheduler::{Pallet, Storage} = 55,
 		Paras: parachains_paras::{Pallet, Call, Storage, Event, Config, ValidateUnsigned} = 56,
 		Initializer: parachains_initializer::{Pallet, Call, Storage} = 57,
-		Dmp: parachains_dmp::{Pallet, Call, Storage} = 58,
+		Dmp: parachains_dmp::{Pallet, Call, Storage, Event} = 58,
 		Ump: parachains_ump::{Pallet, Call, Storage, Event} = 59,
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>, Config} = 60,
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage} = 61,
@@ -1463,8 +1488,11 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	(),
+	Migrations,
 >;
+
+/// All migrations that will run on the next runtime upgrade.
+pub type Migrations = (parachains_hrmp::migration::v1::MigrateToV1<Runtime>,);
 /// The payload being signed in the transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 
@@ -1692,7 +1720,56 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_xcm::XcmDryRunApi<Block, RuntimeEvent> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::XcmDryRunEffects<RuntimeEvent>, pallet_xcm::XcmDryRunApiError> {
+			XcmPallet::dry_run_xcm::<xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets() -> Vec<xcm::latest::AssetId> {
+			XcmPallet::query_acceptable_payment_assets::<xcm_config::XcmConfig>()
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Weight, pallet_xcm::XcmPaymentApiError> {
+			XcmPallet::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: xcm::latest::Weight,
+			asset: xcm::latest::AssetId,
+		) -> Result<u128, pallet_xcm::XcmPaymentApiError> {
+			XcmPallet::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+	}
+
+	impl pallet_xcm::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(origin: xcm::VersionedMultiLocation) -> Vec<xcm::VersionedMultiAssets> {
+			XcmPallet::trapped_assets(origin)
+		}
+	}
+
+	impl crowdloan::CrowdloanApi<Block, Balance> for Runtime {
+		fn refund_status(para_id: ParaId) -> Option<crowdloan::RefundStatus<Balance>> {
+			Crowdloan::refund_status(para_id)
+		}
+	}
+
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
+	impl auctions::AuctionsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn auction_state(
+			first_sample: BlockNumber,
+			max_samples: u32,
+		) -> Option<auctions::AuctionState<AccountId, Balance, BlockNumber>> {
+			Auctions::auction_state(first_sample, max_samples)
+		}
+	}
+
 		fn validator_set() -> Option<beefy_primitives::ValidatorSet<BeefyId>> {
 			// dummy implementation due to lack of BEEFY pallet.
 			None
@@ -2002,6 +2079,22 @@ sp_api::impl_runtime_apis! {
 					let ticket = MultiLocation { parents: 0, interior: Here };
 					Ok((origin, ticket, assets))
 				}
+
+				fn unlockable_asset() -> Result<(MultiLocation, MultiLocation, MultiAsset), BenchmarkError> {
+					// Asset locking is not supported.
+					Err(BenchmarkError::Skip)
+				}
+
+				fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError> {
+					// No universal aliases are allowed.
+					Err(BenchmarkError::Skip)
+				}
+
+				fn export_message_origin_and_destination(
+				) -> Result<(MultiLocation, NetworkId, InteriorMultiLocation), BenchmarkError> {
+					// Exporting messages is not supported.
+					Err(BenchmarkError::Skip)
+				}
 			}
 
 			let whitelist: Vec<TrackedStorageKey> = vec![
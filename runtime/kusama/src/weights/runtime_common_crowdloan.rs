@@ -131,4 +131,32 @@ impl<T: frame_system::Config> runtime_common::crowdloan::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
 	}
+	// Storage: Crowdloan Funds (r:1 w:1)
+	// Storage: Slots Leases (r:1 w:0)
+	// Storage: Auctions AuctionInfo (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Crowdloan EndingsCount (r:1 w:0)
+	// Storage: Crowdloan NewRaise (r:1 w:1)
+	// Storage: unknown [0xd861ea1ebf4800d4b89f4ff787ad79ee96d9a708c85b57da7eb8f9ddeda61291] (r:1 w:1)
+	// Storage: Crowdloan XcmContributors (r:0 w:1)
+	fn contribute_from_location() -> Weight {
+		Weight::from_ref_time(123_032_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(7 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Crowdloan Funds (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: unknown [0xc85982571aa615c788ef9b2c16f54f25773fd439e8ee1ed2aa3ae43d48e880f0] (r:1 w:1)
+	// Storage: Crowdloan XcmContributors (r:1 w:1)
+	// Storage: XcmPallet SupportedVersion (r:1 w:0)
+	// Storage: XcmPallet VersionDiscoveryQueue (r:1 w:1)
+	// Storage: XcmPallet SafeXcmVersion (r:1 w:0)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
+	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	fn withdraw_to_location() -> Weight {
+		Weight::from_ref_time(114_015_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(12 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
 }
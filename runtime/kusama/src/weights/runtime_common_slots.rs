@@ -89,4 +89,16 @@ impl<T: frame_system::Config> runtime_common::slots::WeightInfo for WeightInfo<T
 			.saturating_add(T::DbWeight::get().reads(5 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// Storage: Paras ParaLifecycles (r:2 w:2)
+	// Storage: Registrar Paras (r:2 w:2)
+	// Storage: Slots Leases (r:2 w:2)
+	// Storage: Slots PendingLeaseTransfers (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	// Storage: Paras ActionsQueue (r:1 w:1)
+	fn transfer_lease() -> Weight {
+		Weight::from_ref_time(71_263_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(10 as u64))
+			.saturating_add(T::DbWeight::get().writes(9 as u64))
+	}
 }
@@ -88,4 +88,12 @@ impl<T: frame_system::Config> runtime_parachains::configuration::WeightInfo for
 			.saturating_add(T::DbWeight::get().reads(3 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	// Storage: Configuration BypassConsistencyCheck (r:1 w:0)
+	// Storage: Configuration PendingConfigs (r:1 w:1)
+	fn schedule_config_at() -> Weight {
+		Weight::from_ref_time(12_917_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
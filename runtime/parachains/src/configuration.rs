@@ -22,7 +22,10 @@ use crate::shared;
 use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_PER_MILLIS};
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
-use primitives::v2::{Balance, SessionIndex, MAX_CODE_SIZE, MAX_HEAD_DATA_SIZE, MAX_POV_SIZE};
+use primitives::{
+	v2::{Balance, SessionIndex, MAX_CODE_SIZE, MAX_HEAD_DATA_SIZE, MAX_POV_SIZE},
+	vstaging::HostConfigurationChange,
+};
 use sp_runtime::traits::Zero;
 use sp_std::prelude::*;
 
@@ -38,6 +41,10 @@ pub mod migration;
 
 const LOG_TARGET: &str = "runtime::configuration";
 
+/// The maximum number of pending configuration changes, beyond which `schedule_config_at`
+/// refuses to schedule any further.
+pub const MAX_PENDING_CONFIGS: u32 = 8;
+
 /// All configuration of the runtime with respect to parachains and parathreads.
 #[derive(Clone, Encode, Decode, PartialEq, sp_core::RuntimeDebug, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
@@ -421,6 +428,70 @@ where
 	}
 }
 
+impl<BlockNumber: Clone + PartialEq> HostConfiguration<BlockNumber> {
+	/// Returns the changes turning this configuration into `other`, in the order of the fields.
+	pub fn changes_to(&self, other: &Self) -> Vec<HostConfigurationChange<BlockNumber>> {
+		let mut changes = Vec::new();
+		macro_rules! push_changes {
+			($($field:ident => $change:ident),* $(,)?) => {
+				// Fails to compile if a field is missing.
+				let HostConfiguration { $($field: _),* } = other;
+				$(
+					if self.$field != other.$field {
+						changes.push(HostConfigurationChange::$change(other.$field.clone()));
+					}
+				)*
+			};
+		}
+		push_changes! {
+			max_code_size => MaxCodeSize,
+			max_head_data_size => MaxHeadDataSize,
+			max_upward_queue_count => MaxUpwardQueueCount,
+			max_upward_queue_size => MaxUpwardQueueSize,
+			max_upward_message_size => MaxUpwardMessageSize,
+			max_upward_message_num_per_candidate => MaxUpwardMessageNumPerCandidate,
+			hrmp_max_message_num_per_candidate => HrmpMaxMessageNumPerCandidate,
+			validation_upgrade_cooldown => ValidationUpgradeCooldown,
+			validation_upgrade_delay => ValidationUpgradeDelay,
+			max_pov_size => MaxPovSize,
+			max_downward_message_size => MaxDownwardMessageSize,
+			ump_service_total_weight => UmpServiceTotalWeight,
+			hrmp_max_parachain_outbound_channels => HrmpMaxParachainOutboundChannels,
+			hrmp_max_parathread_outbound_channels => HrmpMaxParathreadOutboundChannels,
+			hrmp_sender_deposit => HrmpSenderDeposit,
+			hrmp_recipient_deposit => HrmpRecipientDeposit,
+			hrmp_channel_max_capacity => HrmpChannelMaxCapacity,
+			hrmp_channel_max_total_size => HrmpChannelMaxTotalSize,
+			hrmp_max_parachain_inbound_channels => HrmpMaxParachainInboundChannels,
+			hrmp_max_parathread_inbound_channels => HrmpMaxParathreadInboundChannels,
+			hrmp_channel_max_message_size => HrmpChannelMaxMessageSize,
+			code_retention_period => CodeRetentionPeriod,
+			parathread_cores => ParathreadCores,
+			parathread_retries => ParathreadRetries,
+			group_rotation_frequency => GroupRotationFrequency,
+			chain_availability_period => ChainAvailabilityPeriod,
+			thread_availability_period => ThreadAvailabilityPeriod,
+			scheduling_lookahead => SchedulingLookahead,
+			max_validators_per_core => MaxValidatorsPerCore,
+			max_validators => MaxValidators,
+			dispute_period => DisputePeriod,
+			dispute_post_conclusion_acceptance_period => DisputePostConclusionAcceptancePeriod,
+			dispute_max_spam_slots => DisputeMaxSpamSlots,
+			dispute_conclusion_by_time_out_period => DisputeConclusionByTimeOutPeriod,
+			no_show_slots => NoShowSlots,
+			n_delay_tranches => NDelayTranches,
+			zeroth_delay_tranche_width => ZerothDelayTrancheWidth,
+			needed_approvals => NeededApprovals,
+			relay_vrf_modulo_samples => RelayVrfModuloSamples,
+			ump_max_individual_weight => UmpMaxIndividualWeight,
+			pvf_checking_enabled => PvfCheckingEnabled,
+			pvf_voting_ttl => PvfVotingTtl,
+			minimum_validation_upgrade_delay => MinimumValidationUpgradeDelay,
+		}
+		changes
+	}
+}
+
 pub trait WeightInfo {
 	fn set_config_with_block_number() -> Weight;
 	fn set_config_with_u32() -> Weight;
@@ -428,6 +499,7 @@ pub trait WeightInfo {
 	fn set_config_with_weight() -> Weight;
 	fn set_config_with_balance() -> Weight;
 	fn set_hrmp_open_request_ttl() -> Weight;
+	fn schedule_config_at() -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -450,6 +522,9 @@ impl WeightInfo for TestWeightInfo {
 	fn set_hrmp_open_request_ttl() -> Weight {
 		Weight::MAX
 	}
+	fn schedule_config_at() -> Weight {
+		Weight::MAX
+	}
 }
 
 #[frame_support::pallet]
@@ -472,6 +547,10 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// The new value for a configuration parameter is invalid.
 		InvalidNewValue,
+		/// The configuration can't become active before the scheduled session.
+		SessionBeforeScheduledSession,
+		/// There are too many pending configuration changes.
+		TooManyPendingConfigs,
 	}
 
 	/// The active configuration for the current session.
@@ -485,8 +564,9 @@ pub mod pallet {
 	/// This is a list of configuration changes, each with a session index at which it should
 	/// be applied.
 	///
-	/// The list is sorted ascending by session index. Also, this list can only contain at most
-	/// 2 items: for the next session and for the `scheduled_session`.
+	/// The list is sorted ascending by session index. The configuration setters only ever touch
+	/// the items for the next session and for the `scheduled_session`, any later items were
+	/// scheduled with `schedule_config_at`.
 	#[pallet::storage]
 	pub(crate) type PendingConfigs<T: Config> =
 		StorageValue<_, Vec<(SessionIndex, HostConfiguration<T::BlockNumber>)>, ValueQuery>;
@@ -1110,6 +1190,56 @@ pub mod pallet {
 			<Self as Store>::BypassConsistencyCheck::put(new);
 			Ok(())
 		}
+
+		/// Schedule the given configuration to become active at the start of `session`.
+		///
+		/// The configuration replaces any configuration pending for the same session. Changes
+		/// made with the setters apply to the configurations pending for earlier sessions only.
+		#[pallet::weight((
+			T::WeightInfo::schedule_config_at(),
+			DispatchClass::Operational,
+		))]
+		pub fn schedule_config_at(
+			origin: OriginFor<T>,
+			config: HostConfiguration<T::BlockNumber>,
+			session: SessionIndex,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				session >= Self::scheduled_session(),
+				Error::<T>::SessionBeforeScheduledSession,
+			);
+
+			if <Self as Store>::BypassConsistencyCheck::get() {
+				log::warn!(
+					target: LOG_TARGET,
+					"Bypassing the consistency check for the scheduled configuration!",
+				);
+			} else if let Err(e) = config.check_consistency() {
+				log::warn!(
+					target: LOG_TARGET,
+					"Scheduled configuration rejected due to invalid configuration: {:?}",
+					e,
+				);
+				return Err(Error::<T>::InvalidNewValue.into())
+			}
+
+			<PendingConfigs<T>>::try_mutate(|pending_configs| {
+				match pending_configs
+					.binary_search_by_key(&session, |&(apply_at_session, _)| apply_at_session)
+				{
+					Ok(i) => pending_configs[i].1 = config,
+					Err(i) => {
+						ensure!(
+							pending_configs.len() < MAX_PENDING_CONFIGS as usize,
+							Error::<T>::TooManyPendingConfigs,
+						);
+						pending_configs.insert(i, (session, config));
+					},
+				}
+				Ok(())
+			})
+		}
 	}
 
 	#[pallet::hooks]
@@ -1188,6 +1318,23 @@ impl<T: Config> Pallet<T> {
 		shared::Pallet::<T>::scheduled_session()
 	}
 
+	/// Returns the pending configuration changes, along with the sessions at which they become
+	/// active.
+	///
+	/// Each pending configuration is given by its changes to the one before it, starting with the
+	/// active configuration.
+	pub fn pending_configs() -> Vec<(SessionIndex, Vec<HostConfigurationChange<T::BlockNumber>>)> {
+		let mut base_config = Self::config();
+		<PendingConfigs<T>>::get()
+			.into_iter()
+			.map(|(session, config)| {
+				let changes = base_config.changes_to(&config);
+				base_config = config;
+				(session, changes)
+			})
+			.collect()
+	}
+
 	/// Forcibly set the active config. This should be used with extreme care, and typically
 	/// only when enabling parachains runtime pallets for the first time on a chain which has
 	/// been running without them.
@@ -1214,6 +1361,7 @@ impl<T: Config> Pallet<T> {
 		updater: impl FnOnce(&mut HostConfiguration<T::BlockNumber>),
 	) -> DispatchResult {
 		let mut pending_configs = <PendingConfigs<T>>::get();
+		let scheduled_session = Self::scheduled_session();
 
 		// 1. pending_configs = []
 		//    No pending configuration changes.
@@ -1240,10 +1388,16 @@ impl<T: Config> Pallet<T> {
 		//
 		// There cannot be (cur, X) because those are applied in the session change handler for the
 		// current session.
+		//
+		// Any of the above can be followed by configurations scheduled for later sessions with
+		// `schedule_config_at`. Those replace the whole configuration, so they are neither used as
+		// the base configuration nor updated.
 
 		// First, we need to decide what we should use as the base configuration.
 		let mut base_config = pending_configs
-			.last()
+			.iter()
+			.rev()
+			.find(|&&(apply_at_session, _)| apply_at_session <= scheduled_session)
 			.map(|&(_, ref config)| config.clone())
 			.unwrap_or_else(Self::config);
 		let base_config_consistent = base_config.check_consistency().is_ok();
@@ -1285,16 +1439,12 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		let scheduled_session = Self::scheduled_session();
-
-		if let Some(&mut (_, ref mut config)) = pending_configs
-			.iter_mut()
-			.find(|&&mut (apply_at_session, _)| apply_at_session >= scheduled_session)
+		match pending_configs
+			.binary_search_by_key(&scheduled_session, |&(apply_at_session, _)| apply_at_session)
 		{
-			*config = new_config;
-		} else {
+			Ok(i) => pending_configs[i].1 = new_config,
 			// We are scheduling a new configuration change for the scheduled session.
-			pending_configs.push((scheduled_session, new_config));
+			Err(i) => pending_configs.insert(i, (scheduled_session, new_config)),
 		}
 
 		<PendingConfigs<T>>::put(pending_configs);
@@ -36,6 +36,18 @@ benchmarks! {
 
 	set_config_with_balance {}: set_hrmp_sender_deposit(RawOrigin::Root, 100_000_000_000)
 
+	// The worst case inserts the configuration in front of all others pending.
+	schedule_config_at {
+		let session = shared::Pallet::<T>::scheduled_session();
+		let pending_configs = (1..MAX_PENDING_CONFIGS)
+			.map(|i| (session + i, HostConfiguration::default()))
+			.collect::<Vec<_>>();
+		PendingConfigs::<T>::put(pending_configs);
+	}: _(RawOrigin::Root, HostConfiguration::default(), session)
+	verify {
+		assert_eq!(PendingConfigs::<T>::get().len(), MAX_PENDING_CONFIGS as usize);
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(Default::default()),
@@ -527,6 +527,114 @@ fn setting_pending_config_members() {
 	})
 }
 
+#[test]
+fn scheduled_config_is_applied_at_the_given_session() {
+	new_test_ext(Default::default()).execute_with(|| {
+		let initial_config = Configuration::config();
+		let scheduled_config =
+			HostConfiguration { validation_upgrade_delay: 100, ..initial_config.clone() };
+		assert_ok!(Configuration::schedule_config_at(
+			RuntimeOrigin::root(),
+			scheduled_config.clone(),
+			4,
+		));
+
+		// Setter changes are scheduled in front of, and are not part of, the scheduled config.
+		assert_ok!(Configuration::set_max_code_size(RuntimeOrigin::root(), 1024));
+		let set_config = HostConfiguration { max_code_size: 1024, ..initial_config.clone() };
+		assert_eq!(
+			<Configuration as Store>::PendingConfigs::get(),
+			vec![(2, set_config.clone()), (4, scheduled_config.clone())],
+		);
+		// The scheduled config reverts the setter change, as it replaces the whole config.
+		assert_eq!(
+			Configuration::pending_configs(),
+			vec![
+				(2, vec![HostConfigurationChange::MaxCodeSize(1024)]),
+				(
+					4,
+					vec![
+						HostConfigurationChange::MaxCodeSize(initial_config.max_code_size),
+						HostConfigurationChange::ValidationUpgradeDelay(100),
+					]
+				),
+			],
+		);
+
+		on_new_session(1);
+		assert_eq!(Configuration::config(), initial_config);
+
+		// Another setter change is based on the previous one.
+		assert_ok!(Configuration::set_max_pov_size(RuntimeOrigin::root(), 1024));
+		let next_set_config = HostConfiguration { max_pov_size: 1024, ..set_config.clone() };
+		assert_eq!(
+			<Configuration as Store>::PendingConfigs::get(),
+			vec![
+				(2, set_config.clone()),
+				(3, next_set_config.clone()),
+				(4, scheduled_config.clone())
+			],
+		);
+
+		on_new_session(2);
+		assert_eq!(Configuration::config(), set_config);
+		on_new_session(3);
+		assert_eq!(Configuration::config(), next_set_config);
+
+		// The scheduled config replaces the whole configuration.
+		on_new_session(4);
+		assert_eq!(Configuration::config(), scheduled_config);
+		assert_eq!(<Configuration as Store>::PendingConfigs::get(), vec![]);
+	});
+}
+
+#[test]
+fn schedule_config_at_checks_the_request() {
+	new_test_ext(Default::default()).execute_with(|| {
+		let config = HostConfiguration::default();
+		assert!(
+			Configuration::schedule_config_at(RuntimeOrigin::signed(1), config.clone(), 2).is_err()
+		);
+
+		assert_err!(
+			Configuration::schedule_config_at(RuntimeOrigin::root(), config.clone(), 1),
+			Error::<Test>::SessionBeforeScheduledSession
+		);
+
+		assert_err!(
+			Configuration::schedule_config_at(
+				RuntimeOrigin::root(),
+				HostConfiguration { no_show_slots: 0, ..config.clone() },
+				2
+			),
+			Error::<Test>::InvalidNewValue
+		);
+
+		for session in 2..2 + MAX_PENDING_CONFIGS {
+			assert_ok!(Configuration::schedule_config_at(
+				RuntimeOrigin::root(),
+				config.clone(),
+				session
+			));
+		}
+		assert_err!(
+			Configuration::schedule_config_at(RuntimeOrigin::root(), config.clone(), 100),
+			Error::<Test>::TooManyPendingConfigs
+		);
+
+		// Replacing a pending config is fine.
+		assert_ok!(Configuration::schedule_config_at(
+			RuntimeOrigin::root(),
+			HostConfiguration { max_code_size: 1024, ..config },
+			2
+		));
+		assert_eq!(
+			<Configuration as Store>::PendingConfigs::get().len(),
+			MAX_PENDING_CONFIGS as usize
+		);
+	});
+}
+
 #[test]
 fn non_root_cannot_set_config() {
 	new_test_ext(Default::default()).execute_with(|| {
@@ -414,6 +414,9 @@ where
 
 pub trait WeightInfo {
 	fn force_unfreeze() -> Weight;
+	/// Variant over `d`, the number of active disputes tracked in the session. The worst case
+	/// is the eviction of the last of them.
+	fn evict_active_dispute(d: u32) -> Weight;
 }
 
 pub struct TestWeightInfo;
@@ -421,6 +424,11 @@ impl WeightInfo for TestWeightInfo {
 	fn force_unfreeze() -> Weight {
 		Weight::zero()
 	}
+	fn evict_active_dispute(d: u32) -> Weight {
+		// Non-zero, so the tracking of active disputes shows up in the weight limits of the
+		// inherent.
+		Weight::from_ref_time(d as u64)
+	}
 }
 
 pub use pallet::*;
@@ -435,6 +443,15 @@ pub mod pallet {
 		type RewardValidators: RewardValidators;
 		type SlashingHandler: SlashingHandler<Self::BlockNumber>;
 
+		/// The maximum number of active unconfirmed disputes per session.
+		///
+		/// Remote disputes without backing votes and with fewer than `byzantine_threshold + 1`
+		/// participants are potentially spam. Once a session has this many of them, new ones are
+		/// rejected until some got confirmed, concluded or timed out. Local and confirmed disputes
+		/// are never rejected by this limit.
+		#[pallet::constant]
+		type MaxActiveDisputesPerSession: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -479,6 +496,15 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type SpamSlots<T> = StorageMap<_, Twox64Concat, SessionIndex, Vec<u32>>;
 
+	/// The active unconfirmed remote disputes of each session, in the order they were initiated.
+	///
+	/// A dispute is evicted once it is confirmed by backing votes or by `byzantine_threshold + 1`
+	/// participants, once the candidate is included in this chain, or once it concluded or timed
+	/// out. There are at most `MaxActiveDisputesPerSession` entries per session.
+	#[pallet::storage]
+	pub(super) type ActiveDisputes<T> =
+		StorageMap<_, Twox64Concat, SessionIndex, Vec<CandidateHash>, ValueQuery>;
+
 	/// Whether the chain is frozen. Starts as `None`. When this is `Some`,
 	/// the chain will not accept any new parachain blocks for backing or inclusion,
 	/// and its value indicates the last valid block number in the chain.
@@ -829,11 +855,12 @@ impl<T: Config> Pallet<T> {
 
 				dispute.concluded_at = Some(now);
 				<Disputes<T>>::insert(session_index, candidate_hash, &dispute);
+				Self::evict_active_dispute(session_index, &candidate_hash);
 
 				if <Included<T>>::contains_key(&session_index, &candidate_hash) {
 					// Local disputes don't count towards spam.
 
-					weight += T::DbWeight::get().reads_writes(1, 1);
+					weight += T::DbWeight::get().reads_writes(2, 2);
 					continue
 				}
 
@@ -855,7 +882,7 @@ impl<T: Config> Pallet<T> {
 					let _participating = decrement_spam(spam_slots, &dispute);
 				});
 
-				weight += T::DbWeight::get().reads_writes(2, 2);
+				weight += T::DbWeight::get().reads_writes(3, 3);
 			}
 		}
 
@@ -894,6 +921,7 @@ impl<T: Config> Pallet<T> {
 				#[allow(deprecated)]
 				<Included<T>>::remove_prefix(to_prune, None);
 				SpamSlots::<T>::remove(to_prune);
+				ActiveDisputes::<T>::remove(to_prune);
 			}
 
 			*last_pruned = Some(pruning_target);
@@ -980,6 +1008,9 @@ impl<T: Config> Pallet<T> {
 			}
 		};
 
+		// Whether any of the candidate's backers voted, which confirms the dispute.
+		let mut backed = false;
+
 		// Check and import all votes.
 		let mut summary = {
 			let mut importer = DisputeStateImporter::new(dispute_state, now);
@@ -1026,6 +1057,14 @@ impl<T: Config> Pallet<T> {
 						continue
 					}
 				}
+
+				if let DisputeStatement::Valid(
+					ValidDisputeStatementKind::BackingSeconded(_) |
+					ValidDisputeStatementKind::BackingValid(_),
+				) = statement
+				{
+					backed = true;
+				}
 			}
 
 			importer.finish()
@@ -1038,8 +1077,19 @@ impl<T: Config> Pallet<T> {
 			return StatementSetFilter::RemoveAll
 		}
 
-		// Apply spam slot changes. Bail early if too many occupied.
+		// Only a bounded number of unconfirmed remote disputes may be active per session. Local
+		// disputes and disputes confirmed by backing votes or by `byzantine_threshold + 1`
+		// participants are never capped, so they can't be crowded out by spam.
 		let is_local = <Included<T>>::contains_key(&set.session, &set.candidate_hash);
+		let confirmed = backed ||
+			DisputeStateFlags::from_state(&summary.state).contains(DisputeStateFlags::CONFIRMED);
+		let capped = !is_local && !confirmed;
+		let n_active = ActiveDisputes::<T>::decode_len(&set.session).unwrap_or(0);
+		if first_votes && capped && n_active >= T::MaxActiveDisputesPerSession::get() as usize {
+			return StatementSetFilter::RemoveAll
+		}
+
+		// Apply spam slot changes. Bail early if too many occupied.
 		if !is_local {
 			let mut spam_slots: Vec<u32> =
 				SpamSlots::<T>::get(&set.session).unwrap_or_else(|| vec![0; n_validators]);
@@ -1133,6 +1183,15 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
+		// Occupy a slot right away, as sequential calls to `filter_dispute_data` must not
+		// exceed the limit either. The slot is freed once the dispute got confirmed, concluded or
+		// timed out, or once the candidate got included.
+		if first_votes && capped {
+			ActiveDisputes::<T>::append(&set.session, set.candidate_hash);
+		} else if confirmed {
+			Self::evict_active_dispute(set.session, &set.candidate_hash);
+		}
+
 		filter
 	}
 
@@ -1231,6 +1290,12 @@ impl<T: Config> Pallet<T> {
 					DisputeResult::Invalid,
 				));
 			}
+
+			if summary.new_flags.intersects(
+				DisputeStateFlags::FOR_SUPERMAJORITY | DisputeStateFlags::AGAINST_SUPERMAJORITY,
+			) {
+				Self::evict_active_dispute(session, &candidate_hash);
+			}
 		}
 
 		// Reward statements.
@@ -1290,6 +1355,7 @@ impl<T: Config> Pallet<T> {
 					decrement_spam(spam_slots, &state);
 				}
 			});
+			Self::evict_active_dispute(session, &candidate_hash);
 
 			if has_supermajority_against(&state) {
 				Self::revert_and_freeze(revert_to);
@@ -1304,6 +1370,19 @@ impl<T: Config> Pallet<T> {
 		<Included<T>>::get(session, candidate_hash)
 	}
 
+	/// Free the slot of an active dispute, if it occupies one.
+	pub(crate) fn evict_active_dispute(session: SessionIndex, candidate_hash: &CandidateHash) {
+		let mut active = ActiveDisputes::<T>::get(session);
+		if let Some(position) = active.iter().position(|c| c == candidate_hash) {
+			active.remove(position);
+			if active.is_empty() {
+				ActiveDisputes::<T>::remove(session);
+			} else {
+				ActiveDisputes::<T>::insert(session, active);
+			}
+		}
+	}
+
 	pub(crate) fn concluded_invalid(session: SessionIndex, candidate_hash: CandidateHash) -> bool {
 		<Disputes<T>>::get(&session, &candidate_hash).map_or(false, |dispute| {
 			// A dispute that has concluded with supermajority-against.
@@ -18,6 +18,7 @@ use super::*;
 
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
+use sp_core::H256;
 use sp_runtime::traits::One;
 
 benchmarks! {
@@ -28,6 +29,21 @@ benchmarks! {
 		assert!(Frozen::<T>::get().is_none())
 	}
 
+	// The worst case is the eviction of the last of `d` active disputes.
+	evict_active_dispute {
+		let d in 1 .. T::MaxActiveDisputesPerSession::get();
+
+		let active: Vec<_> =
+			(0..d).map(|i| CandidateHash(H256::from_low_u64_be(i as u64))).collect();
+		let evicted = *active.last().unwrap();
+		ActiveDisputes::<T>::insert(0, active);
+	}: {
+		Pallet::<T>::evict_active_dispute(0, &evicted);
+	}
+	verify {
+		assert_eq!(ActiveDisputes::<T>::decode_len(0).unwrap_or(0), d as usize - 1);
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(Default::default()),
@@ -19,8 +19,9 @@ use crate::{
 	configuration::HostConfiguration,
 	disputes::DisputesHandler,
 	mock::{
-		new_test_ext, AccountId, AllPalletsWithSystem, Initializer, MockGenesisConfig, System,
-		Test, PUNISH_VALIDATORS_AGAINST, PUNISH_VALIDATORS_FOR, REWARD_VALIDATORS,
+		new_test_ext, AccountId, AllPalletsWithSystem, Initializer, MaxActiveDisputesPerSession,
+		MockGenesisConfig, System, Test, PUNISH_VALIDATORS_AGAINST, PUNISH_VALIDATORS_FOR,
+		REWARD_VALIDATORS,
 	},
 };
 use assert_matches::assert_matches;
@@ -588,6 +589,7 @@ fn test_dispute_timeout() {
 
 		let stmts = update_spam_slots(stmts);
 		assert_eq!(SpamSlots::<Test>::get(start - 1), Some(vec![1, 0, 0, 0, 0, 0, 1]));
+		assert_eq!(ActiveDisputes::<Test>::get(start - 1), vec![candidate_hash]);
 
 		assert_ok!(
 			Pallet::<Test>::process_checked_multi_dispute_data(stmts),
@@ -601,6 +603,80 @@ fn test_dispute_timeout() {
 		// Run to timeout + 1 in order to executive on_finalize(timeout)
 		run_to_block(start + dispute_conclusion_by_time_out_period + 1, |_| None);
 		assert_eq!(SpamSlots::<Test>::get(start - 1), Some(vec![0, 0, 0, 0, 0, 0, 0]));
+		assert!(ActiveDisputes::<Test>::get(start - 1).is_empty());
+	});
+}
+
+#[test]
+fn filter_limits_unconfirmed_disputes_per_session() {
+	new_test_ext(Default::default()).execute_with(|| {
+		// We need 7 validators for the byzantine threshold to be 2
+		let validators: Vec<_> =
+			(0..7).map(|_| <ValidatorId as CryptoType>::Pair::generate().0).collect();
+		let accounts: Vec<AccountId> = (0..7).collect();
+
+		run_to_block(3, |b| {
+			// a new session at each block
+			let keys: Vec<_> =
+				accounts.iter().zip(&validators).map(|(a, v)| (a, v.public())).collect();
+			Some((true, b, keys.clone(), Some(keys)))
+		});
+
+		let valid = || DisputeStatement::Valid(ValidDisputeStatementKind::Explicit);
+		let invalid = || DisputeStatement::Invalid(InvalidDisputeStatementKind::Explicit);
+		let backing =
+			|| DisputeStatement::Valid(ValidDisputeStatementKind::BackingValid(Default::default()));
+
+		// Signatures are not checked by `update_spam_slots`.
+		let dispute = |byte, votes: Vec<(DisputeStatement, u32)>| DisputeStatementSet {
+			candidate_hash: CandidateHash(sp_core::H256::repeat_byte(byte)),
+			session: 1,
+			statements: votes
+				.into_iter()
+				.map(|(statement, v_i)| {
+					(statement, ValidatorIndex(v_i), validators[v_i as usize].sign(&[]))
+				})
+				.collect(),
+		};
+		let candidate_hash_a = CandidateHash(sp_core::H256::repeat_byte(0xA));
+		let candidate_hash_b = CandidateHash(sp_core::H256::repeat_byte(0xB));
+
+		// Leave room for a single unconfirmed dispute, which fills the session.
+		let max_active = MaxActiveDisputesPerSession::get();
+		ActiveDisputes::<Test>::insert(
+			1,
+			(1..max_active)
+				.map(|i| CandidateHash(sp_core::H256::from_low_u64_be(i as u64)))
+				.collect::<Vec<_>>(),
+		);
+		let checked = update_spam_slots(vec![dispute(0xA, vec![(valid(), 0), (invalid(), 1)])]);
+		assert_eq!(checked.len(), 1);
+		assert_ok!(Pallet::<Test>::process_checked_multi_dispute_data(checked));
+		assert_eq!(ActiveDisputes::<Test>::decode_len(1), Some(max_active as usize));
+
+		// Further unconfirmed disputes are rejected and take no spam slots.
+		let unconfirmed_b = dispute(0xB, vec![(valid(), 2), (invalid(), 3)]);
+		assert!(update_spam_slots(vec![unconfirmed_b.clone()]).is_empty());
+		assert_eq!(SpamSlots::<Test>::get(1), Some(vec![1, 1, 0, 0, 0, 0, 0]));
+
+		// Disputes about included candidates, backed disputes and disputes confirmed by enough
+		// participants still get through, without taking a slot.
+		let local_c = dispute(0xC, vec![(valid(), 4), (invalid(), 5)]);
+		Included::<Test>::insert(1, local_c.candidate_hash, 1);
+		let backed_d = dispute(0xD, vec![(backing(), 2), (invalid(), 3)]);
+		let confirmed_e = dispute(0xE, vec![(valid(), 4), (valid(), 5), (invalid(), 6)]);
+		let checked = update_spam_slots(vec![local_c, backed_d, confirmed_e]);
+		assert_eq!(checked.len(), 3);
+		assert_ok!(Pallet::<Test>::process_checked_multi_dispute_data(checked));
+		assert_eq!(ActiveDisputes::<Test>::decode_len(1), Some(max_active as usize));
+
+		// Confirming a dispute frees its slot for the next unconfirmed one.
+		let checked = update_spam_slots(vec![dispute(0xA, vec![(valid(), 6)])]);
+		assert_eq!(checked.len(), 1);
+		assert_ok!(Pallet::<Test>::process_checked_multi_dispute_data(checked));
+		assert!(!ActiveDisputes::<Test>::get(1).contains(&candidate_hash_a));
+		assert_eq!(update_spam_slots(vec![unconfirmed_b]).len(), 1);
+		assert_eq!(ActiveDisputes::<Test>::get(1).last(), Some(&candidate_hash_b));
 	});
 }
 
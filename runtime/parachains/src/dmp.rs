@@ -22,7 +22,7 @@ use frame_support::pallet_prelude::*;
 use primitives::v2::{DownwardMessage, Hash, Id as ParaId, InboundDownwardMessage};
 use sp_runtime::traits::{BlakeTwo256, Hash as HashT, SaturatedConversion};
 use sp_std::{fmt, prelude::*};
-use xcm::latest::SendError;
+use xcm::latest::{SendError, XcmHash};
 
 pub use pallet::*;
 
@@ -78,7 +78,17 @@ pub mod pallet {
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + configuration::Config {}
+	pub trait Config: frame_system::Config + configuration::Config {
+		/// The aggregate event.
+		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// A downward message was queued for a para. \[ para, message ID \]
+		DownwardMessageQueued(ParaId, XcmHash),
+	}
 
 	/// The downward messages addressed for a certain para.
 	#[pallet::storage]
@@ -145,10 +155,26 @@ impl<T: Config> Pallet<T> {
 	/// It is possible to send a downward message to a non-existent para. That, however, would lead
 	/// to a dangling storage. If the caller cannot statically prove that the recipient exists
 	/// then the caller should perform a runtime check.
+	///
+	/// The message is reported in events under the `blake2_256` hash of its payload.
 	pub fn queue_downward_message(
 		config: &HostConfiguration<T::BlockNumber>,
 		para: ParaId,
 		msg: DownwardMessage,
+	) -> Result<(), QueueDownwardMessageError> {
+		let id = sp_io::hashing::blake2_256(&msg);
+		Self::queue_downward_message_with_id(config, para, msg, id)
+	}
+
+	/// Enqueue a downward message to a specific recipient para, exactly as
+	/// [`Self::queue_downward_message`] does, reporting it in events under the given `id`.
+	///
+	/// This lets senders which know the XCM carried by the message identify it by its topic.
+	pub fn queue_downward_message_with_id(
+		config: &HostConfiguration<T::BlockNumber>,
+		para: ParaId,
+		msg: DownwardMessage,
+		id: XcmHash,
 	) -> Result<(), QueueDownwardMessageError> {
 		let serialized_len = msg.len() as u32;
 		if serialized_len > config.max_downward_message_size {
@@ -169,6 +195,7 @@ impl<T: Config> Pallet<T> {
 			v.push(inbound);
 		});
 
+		Self::deposit_event(Event::DownwardMessageQueued(para, id));
 		Ok(())
 	}
 
@@ -229,4 +256,11 @@ impl<T: Config> Pallet<T> {
 	pub(crate) fn dmq_contents(recipient: ParaId) -> Vec<InboundDownwardMessage<T::BlockNumber>> {
 		<Self as Store>::DownwardMessageQueues::get(&recipient)
 	}
+
+	/// Returns the contents of every non-empty downward message queue, along with its recipient.
+	///
+	/// The most recent messages are the latest in each vector.
+	pub fn all_dmq_contents() -> Vec<(ParaId, Vec<InboundDownwardMessage<T::BlockNumber>>)> {
+		<Self as Store>::DownwardMessageQueues::iter().collect()
+	}
 }
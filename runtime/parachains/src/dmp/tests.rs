@@ -15,7 +15,9 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::mock::{new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System};
+use crate::mock::{
+	assert_last_event, new_test_ext, Configuration, Dmp, MockGenesisConfig, Paras, System,
+};
 use hex_literal::hex;
 use parity_scale_codec::Encode;
 use primitives::v2::BlockNumber;
@@ -160,6 +162,26 @@ fn dmq_pruning() {
 	});
 }
 
+#[test]
+fn queued_messages_are_identified_by_hash_or_given_id() {
+	let a = ParaId::from(1312);
+
+	new_test_ext(default_genesis_config()).execute_with(|| {
+		run_to_block(1, None);
+
+		queue_downward_message(a, vec![1, 2, 3]).unwrap();
+		assert_last_event(
+			Event::DownwardMessageQueued(a, sp_io::hashing::blake2_256(&[1, 2, 3])).into(),
+		);
+
+		// The payload isn't decoded: senders which know its topic provide it.
+		Dmp::queue_downward_message_with_id(&Configuration::config(), a, vec![4, 5, 6], [7; 32])
+			.unwrap();
+		assert_last_event(Event::DownwardMessageQueued(a, [7; 32]).into());
+		assert_eq!(Dmp::dmq_length(a), 2);
+	});
+}
+
 #[test]
 fn queue_downward_message_critical() {
 	let a = ParaId::from(1312);
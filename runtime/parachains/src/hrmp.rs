@@ -16,14 +16,20 @@
 
 use crate::{
 	configuration::{self, HostConfiguration},
-	dmp, ensure_parachain, initializer, paras,
+	dmp, ensure_parachain, initializer, paras, shared,
+};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{BalanceStatus, ReservableCurrency},
 };
-use frame_support::{pallet_prelude::*, traits::ReservableCurrency};
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
-use primitives::v2::{
-	Balance, Hash, HrmpChannelId, Id as ParaId, InboundHrmpMessage, OutboundHrmpMessage,
-	SessionIndex,
+use primitives::{
+	v2::{
+		Balance, Hash, HrmpChannelId, Id as ParaId, InboundHrmpMessage, OutboundHrmpMessage,
+		SessionIndex, LOWEST_PUBLIC_ID,
+	},
+	vstaging::{HrmpChannelInfo, HrmpOpenChannelRequestInfo, HrmpTopology},
 };
 use scale_info::TypeInfo;
 use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash as HashT, UniqueSaturatedInto};
@@ -32,6 +38,7 @@ use sp_std::{
 	fmt, mem,
 	prelude::*,
 };
+use xcm::latest::XcmHash;
 
 pub use pallet::*;
 
@@ -49,6 +56,8 @@ pub(crate) mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migration;
+
 pub trait WeightInfo {
 	fn hrmp_init_open_channel() -> Weight;
 	fn hrmp_accept_open_channel() -> Weight;
@@ -59,6 +68,11 @@ pub trait WeightInfo {
 	fn hrmp_cancel_open_request(c: u32) -> Weight;
 	fn clean_open_channel_requests(c: u32) -> Weight;
 	fn force_open_hrmp_channel() -> Weight;
+	fn hrmp_accept_open_channel_with_fee() -> Weight;
+	fn hrmp_request_channel_update() -> Weight;
+	fn hrmp_accept_channel_update() -> Weight;
+	fn hrmp_cancel_channel_update(c: u32) -> Weight;
+	fn force_open_hrmp_channel_pair() -> Weight;
 }
 
 /// A weight info that is only suitable for testing.
@@ -92,6 +106,21 @@ impl WeightInfo for TestWeightInfo {
 	fn force_open_hrmp_channel() -> Weight {
 		Weight::MAX
 	}
+	fn hrmp_accept_open_channel_with_fee() -> Weight {
+		Weight::MAX
+	}
+	fn hrmp_request_channel_update() -> Weight {
+		Weight::MAX
+	}
+	fn hrmp_accept_channel_update() -> Weight {
+		Weight::MAX
+	}
+	fn hrmp_cancel_channel_update(_: u32) -> Weight {
+		Weight::MAX
+	}
+	fn force_open_hrmp_channel_pair() -> Weight {
+		Weight::MAX
+	}
 }
 
 /// A description of a request to open an HRMP channel.
@@ -110,6 +139,12 @@ pub struct HrmpOpenChannelRequest {
 	pub max_capacity: u32,
 	/// The maximum total size of the messages that can be pending in the channel at once.
 	pub max_total_size: u32,
+	/// The amount that the recipient supplied at the time of confirming this request. Zero until
+	/// the request is confirmed.
+	pub recipient_deposit: Balance,
+	/// The fee the sender pays to the recipient for each message sent over the channel. Set by
+	/// the recipient when confirming the request.
+	pub fee_per_message: Balance,
 }
 
 /// A metadata of an HRMP channel.
@@ -146,6 +181,30 @@ pub struct HrmpChannel {
 	pub sender_deposit: Balance,
 	/// The amount that the recipient supplied as a deposit when accepting opening this channel.
 	pub recipient_deposit: Balance,
+	/// The fee that is transferred from the sender's sovereign account to the recipient's
+	/// sovereign account for each message enqueued into this channel.
+	pub fee_per_message: Balance,
+}
+
+/// A request to change the parameters of an open HRMP channel.
+///
+/// The request is made by one of the participants of the channel and takes effect on the session
+/// change after the other participant confirms it.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+pub struct HrmpChannelUpdateRequest {
+	/// The participant of the channel which proposed the update.
+	pub proposer: ParaId,
+	/// Indicates if this request was confirmed by the other participant.
+	pub confirmed: bool,
+	/// The new maximum number of messages that can be pending in the channel at once.
+	pub max_capacity: u32,
+	/// The new maximum message size that could be put into the channel.
+	pub max_message_size: u32,
+	/// The deposit of the sender after the update is applied.
+	pub sender_deposit: Balance,
+	/// The deposit of the recipient after the update is applied.
+	pub recipient_deposit: Balance,
 }
 
 /// An error returned by [`check_hrmp_watermark`] that indicates an acceptance criteria check
@@ -165,6 +224,7 @@ pub enum OutboundHrmpAcceptanceErr {
 	MaxMessageSizeExceeded { idx: u32, msg_size: u32, max_size: u32 },
 	TotalSizeExceeded { idx: u32, total_size: u32, limit: u32 },
 	CapacityExceeded { idx: u32, count: u32, limit: u32 },
+	InsufficientFeeBalance { idx: u32, fees: Balance, available: Balance },
 }
 
 impl<BlockNumber> fmt::Debug for HrmpWatermarkAcceptanceErr<BlockNumber>
@@ -225,6 +285,11 @@ impl fmt::Debug for OutboundHrmpAcceptanceErr {
 				"sending the HRMP message at index {} would exceed the neogitiated channel capacity  ({} > {})",
 				idx, count, limit,
 			),
+			InsufficientFeeBalance { idx, fees, available } => write!(
+				fmt,
+				"the sender cannot afford the channel fees for the HRMP message at index {} ({} > {})",
+				idx, fees, available,
+			),
 		}
 	}
 }
@@ -235,6 +300,7 @@ pub mod pallet {
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(migration::STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -256,6 +322,15 @@ pub mod pallet {
 		/// implementation should be the same as `Balance` as used in the `Configuration`.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
+		/// The deposit charged per byte of channel capacity on top of the flat deposits defined
+		/// in the `Configuration` pallet.
+		///
+		/// The capacity of a channel is measured as `max_capacity * max_message_size`, i.e. the
+		/// maximum amount of bytes the channel can hold at once. The same rate applies to both
+		/// the sender and the recipient deposits.
+		#[pallet::constant]
+		type ChannelDepositPerByte: Get<Balance>;
+
 		/// Something that provides the weight of this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -276,6 +351,30 @@ pub mod pallet {
 		/// An HRMP channel was opened via Root origin.
 		/// `[sender, recipient, proposed_max_capacity, proposed_max_message_size]`
 		HrmpChannelForceOpened(ParaId, ParaId, u32, u32),
+		/// Open HRMP channel accepted with a per-message fee set by the recipient.
+		/// `[sender, recipient, fee_per_message]`
+		OpenChannelAcceptedWithFee(ParaId, ParaId, Balance),
+		/// An update of the parameters of an open HRMP channel was requested.
+		/// `[by_parachain, channel_id, proposed_max_capacity, proposed_max_message_size]`
+		ChannelUpdateRequested(ParaId, HrmpChannelId, u32, u32),
+		/// An update of the parameters of an open HRMP channel was accepted.
+		/// `[by_parachain, channel_id]`
+		ChannelUpdateAccepted(ParaId, HrmpChannelId),
+		/// A pending update of the parameters of an open HRMP channel was canceled.
+		/// `[by_parachain, channel_id]`
+		ChannelUpdateCanceled(ParaId, HrmpChannelId),
+		/// The parameters of an open HRMP channel were updated.
+		/// `[channel_id, max_capacity, max_message_size]`
+		ChannelUpdated(HrmpChannelId, u32, u32),
+		/// HRMP channels in both directions between two paras were opened via Root origin. The
+		/// channels take effect at the beginning of the given session.
+		/// `[para_a, para_b, max_capacity, max_message_size, session_index]`
+		HrmpChannelPairForceOpened(ParaId, ParaId, u32, u32, SessionIndex),
+		/// An accepted HRMP channel request was enacted and the channel is now open.
+		/// `[channel_id, session_index]`
+		ChannelOpened(HrmpChannelId, SessionIndex),
+		/// A message was queued on an HRMP channel. `[channel_id, message_id]`
+		OutboundMessageQueued(HrmpChannelId, XcmHash),
 	}
 
 	#[pallet::error]
@@ -318,6 +417,23 @@ pub mod pallet {
 		OpenHrmpChannelAlreadyConfirmed,
 		/// The provided witness data is wrong.
 		WrongWitness,
+		/// The origin tries to update a channel where it is neither the sender nor the recipient.
+		UpdateHrmpChannelUnauthorized,
+		/// The channel to be updated doesn't exist.
+		UpdateHrmpChannelDoesntExist,
+		/// The channel to be updated is going to be closed.
+		UpdateHrmpChannelCloseUnderway,
+		/// There is already a pending update request for the channel.
+		UpdateHrmpChannelAlreadyRequested,
+		/// The update request for the channel doesn't exist.
+		HrmpChannelUpdateDoesntExist,
+		/// The update request can only be accepted by the participant that didn't propose it.
+		AcceptHrmpChannelUpdateUnauthorized,
+		/// The update request is already confirmed.
+		HrmpChannelUpdateAlreadyConfirmed,
+		/// The para is not going to be a valid para at the next session and thus the channel
+		/// cannot be opened in time.
+		HrmpChannelParaNotValidNextSession,
 	}
 
 	/// The set of pending HRMP open channel requests.
@@ -365,6 +481,22 @@ pub mod pallet {
 	pub type HrmpCloseChannelRequestsList<T: Config> =
 		StorageValue<_, Vec<HrmpChannelId>, ValueQuery>;
 
+	/// The set of pending requests to update the parameters of open HRMP channels. Confirmed
+	/// requests are applied during the session change.
+	///
+	/// The set is accompanied by a list for iteration.
+	///
+	/// Invariant:
+	/// - There are no channels that exists in list but not in the set and vice versa.
+	/// - Each channel in the set is present in `HrmpChannels`.
+	#[pallet::storage]
+	pub type HrmpChannelUpdateRequests<T: Config> =
+		StorageMap<_, Twox64Concat, HrmpChannelId, HrmpChannelUpdateRequest>;
+
+	#[pallet::storage]
+	pub type HrmpChannelUpdateRequestsList<T: Config> =
+		StorageValue<_, Vec<HrmpChannelId>, ValueQuery>;
+
 	/// The HRMP watermark associated with each para.
 	/// Invariant:
 	/// - each para `P` used here as a key should satisfy `Paras::is_valid_para(P)` within a session.
@@ -421,6 +553,13 @@ pub mod pallet {
 	pub type HrmpChannelDigests<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, Vec<(T::BlockNumber, Vec<ParaId>)>, ValueQuery>;
 
+	/// The fees reserved from the sovereign account of each para for the outbound HRMP messages of
+	/// its candidate pending availability. They are paid to the recipients once the candidate is
+	/// enacted, and released if the candidate is dropped instead.
+	#[pallet::storage]
+	pub(super) type HrmpReservedFees<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, Balance, ValueQuery>;
+
 	/// Preopen the given HRMP channels.
 	///
 	/// The values in the tuple corresponds to
@@ -542,8 +681,7 @@ pub mod pallet {
 		#[pallet::weight(<T as Config>::WeightInfo::force_process_hrmp_open(*_channels))]
 		pub fn force_process_hrmp_open(origin: OriginFor<T>, _channels: u32) -> DispatchResult {
 			ensure_root(origin)?;
-			let host_config = configuration::Pallet::<T>::config();
-			Self::process_hrmp_open_channel_requests(&host_config);
+			Self::process_hrmp_open_channel_requests();
 			Ok(())
 		}
 
@@ -610,12 +748,150 @@ pub mod pallet {
 			));
 			Ok(())
 		}
+
+		/// Accept a pending open channel request from the given sender and set the fee that the
+		/// sender has to pay for each message sent over the channel.
+		///
+		/// The fees are transferred from the sovereign account of the sender to the sovereign
+		/// account of the recipient when the messages are enqueued.
+		///
+		/// The channel will be opened only on the next session boundary.
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_accept_open_channel_with_fee())]
+		pub fn hrmp_accept_open_channel_with_fee(
+			origin: OriginFor<T>,
+			sender: ParaId,
+			fee_per_message: Balance,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			Self::accept_open_channel_with_fee(origin, sender, fee_per_message)?;
+			Self::deposit_event(Event::OpenChannelAcceptedWithFee(sender, origin, fee_per_message));
+			Ok(())
+		}
+
+		/// Request to change the capacity and the maximum message size of an open channel. The
+		/// origin must be either the sender or the recipient in the channel being updated.
+		///
+		/// The deposits of both participants are recomputed for the new parameters. The proposer
+		/// reserves the increase of its deposit right away.
+		///
+		/// The update takes effect on the session change after the other participant accepts it.
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_request_channel_update())]
+		pub fn hrmp_request_channel_update(
+			origin: OriginFor<T>,
+			channel_id: HrmpChannelId,
+			proposed_max_capacity: u32,
+			proposed_max_message_size: u32,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			Self::request_channel_update(
+				origin,
+				channel_id.clone(),
+				proposed_max_capacity,
+				proposed_max_message_size,
+			)?;
+			Self::deposit_event(Event::ChannelUpdateRequested(
+				origin,
+				channel_id,
+				proposed_max_capacity,
+				proposed_max_message_size,
+			));
+			Ok(())
+		}
+
+		/// Accept a pending update request of the given channel. The origin must be the
+		/// participant of the channel that didn't propose the update.
+		///
+		/// The update will be applied only on the next session boundary.
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_accept_channel_update())]
+		pub fn hrmp_accept_channel_update(
+			origin: OriginFor<T>,
+			channel_id: HrmpChannelId,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			Self::accept_channel_update(origin, channel_id.clone())?;
+			Self::deposit_event(Event::ChannelUpdateAccepted(origin, channel_id));
+			Ok(())
+		}
+
+		/// Cancel a pending update request of the given channel. It can be canceled by either
+		/// participant of the channel as long as it is not yet accepted.
+		///
+		/// Total number of update requests (i.e. `HrmpChannelUpdateRequestsList`) must be provided
+		/// as witness data.
+		#[pallet::weight(<T as Config>::WeightInfo::hrmp_cancel_channel_update(*update_requests))]
+		pub fn hrmp_cancel_channel_update(
+			origin: OriginFor<T>,
+			channel_id: HrmpChannelId,
+			update_requests: u32,
+		) -> DispatchResult {
+			let origin = ensure_parachain(<T as Config>::RuntimeOrigin::from(origin))?;
+			ensure!(
+				<Self as Store>::HrmpChannelUpdateRequestsList::decode_len().unwrap_or_default()
+					as u32 <= update_requests,
+				Error::<T>::WrongWitness
+			);
+			Self::cancel_channel_update(origin, channel_id.clone())?;
+			Self::deposit_event(Event::ChannelUpdateCanceled(origin, channel_id));
+			Ok(())
+		}
+
+		/// Open channels in both directions between `para_a` and `para_b` using the Root origin.
+		/// The `max_capacity` and `max_message_size` are still subject to the Relay Chain's
+		/// configured limits and apply to both channels.
+		///
+		/// The channels are opened at the next session change. The call fails if either para is
+		/// not going to be a valid para by then. Paras registered as system chains, i.e. those
+		/// with an id below `LOWEST_PUBLIC_ID`, are not charged any deposits.
+		///
+		/// Expected use is to connect system parachains to each other.
+		#[pallet::weight(<T as Config>::WeightInfo::force_open_hrmp_channel_pair())]
+		pub fn force_open_hrmp_channel_pair(
+			origin: OriginFor<T>,
+			para_a: ParaId,
+			para_b: ParaId,
+			max_capacity: u32,
+			max_message_size: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			// A para which is neither onboarding nor offboarding is still valid at the next
+			// session change, when the channels are opened.
+			for para in [para_a, para_b] {
+				ensure!(
+					<paras::Pallet<T>>::is_valid_para(para),
+					Error::<T>::HrmpChannelParaNotValidNextSession,
+				);
+			}
+
+			for (sender, recipient) in [(para_a, para_b), (para_b, para_a)] {
+				Self::do_init_open_channel(
+					sender,
+					recipient,
+					max_capacity,
+					max_message_size,
+					!Self::is_system_chain(sender),
+				)?;
+				Self::do_accept_open_channel(
+					recipient,
+					sender,
+					0,
+					!Self::is_system_chain(recipient),
+				)?;
+			}
+
+			Self::deposit_event(Event::HrmpChannelPairForceOpened(
+				para_a,
+				para_b,
+				max_capacity,
+				max_message_size,
+				<shared::Pallet<T>>::session_index().saturating_add(1),
+			));
+			Ok(())
+		}
 	}
 }
 
 #[cfg(feature = "std")]
 fn initialize_storage<T: Config>(preopen_hrmp_channels: &[(ParaId, ParaId, u32, u32)]) {
-	let host_config = configuration::Pallet::<T>::config();
 	for &(sender, recipient, max_capacity, max_message_size) in preopen_hrmp_channels {
 		if let Err(err) =
 			preopen_hrmp_channel::<T>(sender, recipient, max_capacity, max_message_size)
@@ -623,7 +899,7 @@ fn initialize_storage<T: Config>(preopen_hrmp_channels: &[(ParaId, ParaId, u32,
 			panic!("failed to initialize the genesis storage: {:?}", err);
 		}
 	}
-	<Pallet<T>>::process_hrmp_open_channel_requests(&host_config);
+	<Pallet<T>>::process_hrmp_open_channel_requests();
 }
 
 #[cfg(feature = "std")]
@@ -653,24 +929,23 @@ impl<T: Config> Pallet<T> {
 		notification: &initializer::SessionChangeNotification<T::BlockNumber>,
 		outgoing_paras: &[ParaId],
 	) -> Weight {
-		let w1 = Self::perform_outgoing_para_cleanup(&notification.prev_config, outgoing_paras);
-		Self::process_hrmp_open_channel_requests(&notification.prev_config);
+		let w1 = Self::perform_outgoing_para_cleanup(outgoing_paras);
+		Self::process_hrmp_open_channel_requests();
+		let w2 = Self::process_hrmp_channel_update_requests();
 		Self::process_hrmp_close_channel_requests();
-		w1.saturating_add(<T as Config>::WeightInfo::force_process_hrmp_open(
-			outgoing_paras.len() as u32
-		))
-		.saturating_add(<T as Config>::WeightInfo::force_process_hrmp_close(
-			outgoing_paras.len() as u32
-		))
+		w1.saturating_add(w2)
+			.saturating_add(<T as Config>::WeightInfo::force_process_hrmp_open(
+				outgoing_paras.len() as u32,
+			))
+			.saturating_add(<T as Config>::WeightInfo::force_process_hrmp_close(
+				outgoing_paras.len() as u32,
+			))
 	}
 
 	/// Iterate over all paras that were noted for offboarding and remove all the data
 	/// associated with them.
-	fn perform_outgoing_para_cleanup(
-		config: &HostConfiguration<T::BlockNumber>,
-		outgoing: &[ParaId],
-	) -> Weight {
-		let mut w = Self::clean_open_channel_requests(config, outgoing);
+	fn perform_outgoing_para_cleanup(outgoing: &[ParaId]) -> Weight {
+		let mut w = Self::clean_open_channel_requests(outgoing);
 		for outgoing_para in outgoing {
 			Self::clean_hrmp_after_outgoing(outgoing_para);
 
@@ -691,10 +966,7 @@ impl<T: Config> Pallet<T> {
 	// Go over the HRMP open channel requests and remove all in which offboarding paras participate.
 	//
 	// This will also perform the refunds for the counterparty if it doesn't offboard.
-	pub(crate) fn clean_open_channel_requests(
-		config: &HostConfiguration<T::BlockNumber>,
-		outgoing: &[ParaId],
-	) -> Weight {
+	pub(crate) fn clean_open_channel_requests(outgoing: &[ParaId]) -> Weight {
 		// First collect all the channel ids of the open requests in which there is at least one
 		// party presents in the outgoing list.
 		//
@@ -725,16 +997,15 @@ impl<T: Config> Pallet<T> {
 				);
 			}
 
-			// If the request was confirmed, then it means it was confirmed in the finished session.
-			// Therefore, the config's hrmp_recipient_deposit represents the actual value of the
-			// deposit.
+			// If the request was confirmed, then the recipient supplied a deposit at the time of the
+			// confirmation.
 			//
 			// We still want to refund the deposit only if the para is not being offboarded.
 			if req_data.confirmed {
 				if !outgoing.contains(&req_id.recipient) {
 					T::Currency::unreserve(
 						&req_id.recipient.into_account_truncating(),
-						config.hrmp_recipient_deposit.unique_saturated_into(),
+						req_data.recipient_deposit.unique_saturated_into(),
 					);
 				}
 				Self::decrease_accepted_channel_request_count(req_id.recipient);
@@ -768,7 +1039,7 @@ impl<T: Config> Pallet<T> {
 	///
 	/// - prune the stale requests
 	/// - enact the confirmed requests
-	fn process_hrmp_open_channel_requests(config: &HostConfiguration<T::BlockNumber>) {
+	fn process_hrmp_open_channel_requests() {
 		let mut open_req_channels = <Self as Store>::HrmpOpenChannelRequestsList::get();
 		if open_req_channels.is_empty() {
 			return
@@ -797,13 +1068,14 @@ impl<T: Config> Pallet<T> {
 						&channel_id,
 						HrmpChannel {
 							sender_deposit: request.sender_deposit,
-							recipient_deposit: config.hrmp_recipient_deposit,
+							recipient_deposit: request.recipient_deposit,
 							max_capacity: request.max_capacity,
 							max_total_size: request.max_total_size,
 							max_message_size: request.max_message_size,
 							msg_count: 0,
 							total_size: 0,
 							mqc_head: None,
+							fee_per_message: request.fee_per_message,
 						},
 					);
 
@@ -817,6 +1089,11 @@ impl<T: Config> Pallet<T> {
 							v.insert(i, channel_id.recipient);
 						}
 					});
+
+					Self::deposit_event(Event::ChannelOpened(
+						channel_id.clone(),
+						<shared::Pallet<T>>::session_index(),
+					));
 				}
 
 				Self::decrease_open_channel_request_count(channel_id.sender);
@@ -830,6 +1107,73 @@ impl<T: Config> Pallet<T> {
 		<Self as Store>::HrmpOpenChannelRequestsList::put(open_req_channels);
 	}
 
+	/// Iterate over all channel update requests and apply the confirmed ones.
+	///
+	/// Returns the amount of weight consumed.
+	fn process_hrmp_channel_update_requests() -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		let update_reqs = <Self as Store>::HrmpChannelUpdateRequestsList::get();
+		if update_reqs.is_empty() {
+			return weight
+		}
+
+		let (confirmed, pending): (Vec<HrmpChannelId>, Vec<HrmpChannelId>) =
+			update_reqs.into_iter().partition(|channel_id| {
+				<Self as Store>::HrmpChannelUpdateRequests::get(channel_id)
+					.map_or(false, |req| req.confirmed)
+			});
+		weight += T::DbWeight::get().reads(pending.len() as u64 + 1);
+		<Self as Store>::HrmpChannelUpdateRequestsList::put(pending);
+
+		let config_max_total_size =
+			<configuration::Pallet<T>>::config().hrmp_channel_max_total_size;
+
+		for channel_id in confirmed {
+			let request = match <Self as Store>::HrmpChannelUpdateRequests::take(&channel_id) {
+				Some(request) => request,
+				None => continue,
+			};
+			<Self as Store>::HrmpChannels::mutate(&channel_id, |channel| {
+				if let Some(ref mut channel) = channel {
+					// The increases of the deposits were reserved at the time the participants
+					// signed off the request. Here, we only need to return the decreases.
+					T::Currency::unreserve(
+						&channel_id.sender.into_account_truncating(),
+						channel
+							.sender_deposit
+							.saturating_sub(request.sender_deposit)
+							.unique_saturated_into(),
+					);
+					T::Currency::unreserve(
+						&channel_id.recipient.into_account_truncating(),
+						channel
+							.recipient_deposit
+							.saturating_sub(request.recipient_deposit)
+							.unique_saturated_into(),
+					);
+
+					channel.max_capacity = request.max_capacity;
+					channel.max_message_size = request.max_message_size;
+					channel.max_total_size = Self::channel_max_total_size(
+						config_max_total_size,
+						request.max_capacity,
+						request.max_message_size,
+					);
+					channel.sender_deposit = request.sender_deposit;
+					channel.recipient_deposit = request.recipient_deposit;
+				}
+			});
+			Self::deposit_event(Event::ChannelUpdated(
+				channel_id,
+				request.max_capacity,
+				request.max_message_size,
+			));
+			weight += T::DbWeight::get().reads_writes(4, 4);
+		}
+
+		weight
+	}
+
 	/// Iterate over all close channel requests unconditionally closing the channels.
 	fn process_hrmp_close_channel_requests() {
 		let close_reqs = <Self as Store>::HrmpCloseChannelRequestsList::take();
@@ -849,13 +1193,20 @@ impl<T: Config> Pallet<T> {
 		if let Some(HrmpChannel { sender_deposit, recipient_deposit, .. }) =
 			<Self as Store>::HrmpChannels::take(channel_id)
 		{
+			// A pending update request dies together with the channel. Return the deposit
+			// increases that were already reserved for it.
+			let (sender_extra, recipient_extra) = Self::take_channel_update_request(channel_id)
+				.map_or((0, 0), |req| {
+					Self::reserved_for_update(channel_id, &req, sender_deposit, recipient_deposit)
+				});
+
 			T::Currency::unreserve(
 				&channel_id.sender.into_account_truncating(),
-				sender_deposit.unique_saturated_into(),
+				sender_deposit.saturating_add(sender_extra).unique_saturated_into(),
 			);
 			T::Currency::unreserve(
 				&channel_id.recipient.into_account_truncating(),
-				recipient_deposit.unique_saturated_into(),
+				recipient_deposit.saturating_add(recipient_extra).unique_saturated_into(),
 			);
 		}
 
@@ -934,6 +1285,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let mut last_recipient = None::<ParaId>;
+		let mut fees: Balance = 0;
 
 		for (idx, out_msg) in
 			out_hrmp_msgs.iter().enumerate().map(|(idx, out_msg)| (idx as u32, out_msg))
@@ -980,11 +1332,71 @@ impl<T: Config> Pallet<T> {
 					limit: channel.max_capacity,
 				})
 			}
+
+			if channel.fee_per_message > 0 {
+				fees = fees.saturating_add(channel.fee_per_message);
+				// The fees are reserved once the candidate is accepted, see `reserve_outbound_fees`.
+				let sender_account = sender.into_account_truncating();
+				if !T::Currency::can_reserve(&sender_account, fees.unique_saturated_into()) {
+					return Err(OutboundHrmpAcceptanceErr::InsufficientFeeBalance {
+						idx,
+						fees,
+						available: T::Currency::free_balance(&sender_account)
+							.unique_saturated_into(),
+					})
+				}
+			}
 		}
 
 		Ok(())
 	}
 
+	/// Reserve the fees of the outbound HRMP messages of a candidate which was just accepted, until
+	/// the candidate is either enacted or dropped.
+	///
+	/// The messages must have passed `check_outbound_hrmp` in the same block. Channels are only
+	/// opened and closed at session boundaries, where all candidates pending availability are
+	/// dropped, so the fees charged at enactment are the same as the ones reserved here.
+	pub(crate) fn reserve_outbound_fees(
+		sender: ParaId,
+		out_hrmp_msgs: &[OutboundHrmpMessage<ParaId>],
+	) {
+		let mut fees: Balance = 0;
+		for out_msg in out_hrmp_msgs {
+			let channel_id = HrmpChannelId { sender, recipient: out_msg.recipient };
+			if let Some(channel) = <Self as Store>::HrmpChannels::get(&channel_id) {
+				fees = fees.saturating_add(channel.fee_per_message);
+			}
+		}
+		if fees == 0 {
+			return
+		}
+
+		if let Err(err) =
+			T::Currency::reserve(&sender.into_account_truncating(), fees.unique_saturated_into())
+		{
+			// The fees were checked to be reservable during the candidate acceptance, so this is
+			// not expected to fail.
+			log::error!(
+				target: "runtime::hrmp",
+				"failed to reserve the fees of the HRMP messages of {:?}: {:?}",
+				sender,
+				err,
+			);
+			return
+		}
+		<Self as Store>::HrmpReservedFees::insert(sender, fees);
+	}
+
+	/// Release the fees reserved for the outbound HRMP messages of a candidate which was dropped
+	/// without being enacted.
+	pub(crate) fn release_outbound_fees(sender: ParaId) {
+		let fees = <Self as Store>::HrmpReservedFees::take(sender);
+		if fees > 0 {
+			T::Currency::unreserve(&sender.into_account_truncating(), fees.unique_saturated_into());
+		}
+	}
+
 	pub(crate) fn prune_hrmp(recipient: ParaId, new_hrmp_watermark: T::BlockNumber) -> Weight {
 		let mut weight = Weight::zero();
 
@@ -1053,8 +1465,9 @@ impl<T: Config> Pallet<T> {
 		sender: ParaId,
 		out_hrmp_msgs: Vec<OutboundHrmpMessage<ParaId>>,
 	) -> Weight {
-		let mut weight = Weight::zero();
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
 		let now = <frame_system::Pallet<T>>::block_number();
+		let mut reserved = <Self as Store>::HrmpReservedFees::take(sender);
 
 		for out_msg in out_hrmp_msgs {
 			let channel_id = HrmpChannelId { sender, recipient: out_msg.recipient };
@@ -1068,6 +1481,22 @@ impl<T: Config> Pallet<T> {
 				},
 			};
 
+			if channel.fee_per_message > 0 {
+				// The fee was reserved when the candidate was accepted, see
+				// `reserve_outbound_fees`.
+				let fee = channel.fee_per_message.min(reserved);
+				reserved -= fee;
+				let _ = T::Currency::repatriate_reserved(
+					&sender.into_account_truncating(),
+					&channel_id.recipient.into_account_truncating(),
+					fee.unique_saturated_into(),
+					BalanceStatus::Free,
+				);
+				weight += T::DbWeight::get().reads_writes(2, 2);
+			}
+
+			// The relay chain doesn't decode HRMP messages, so they are identified by their hash.
+			let id = sp_io::hashing::blake2_256(&out_msg.data);
 			let inbound = InboundHrmpMessage { sent_at: now, data: out_msg.data };
 
 			// book keeping
@@ -1085,6 +1514,7 @@ impl<T: Config> Pallet<T> {
 
 			<Self as Store>::HrmpChannels::insert(&channel_id, channel);
 			<Self as Store>::HrmpChannelContents::append(&channel_id, inbound);
+			Self::deposit_event(Event::OutboundMessageQueued(channel_id.clone(), id));
 
 			// The digests are sorted in ascending by block number order. Assuming absence of
 			// contextual execution, there are only two possible scenarios here:
@@ -1114,6 +1544,14 @@ impl<T: Config> Pallet<T> {
 			weight += T::DbWeight::get().reads_writes(2, 2);
 		}
 
+		// Defensive: whatever is left belongs to messages which were not delivered.
+		if reserved > 0 {
+			T::Currency::unreserve(
+				&sender.into_account_truncating(),
+				reserved.unique_saturated_into(),
+			);
+		}
+
 		weight
 	}
 
@@ -1127,6 +1565,23 @@ impl<T: Config> Pallet<T> {
 		recipient: ParaId,
 		proposed_max_capacity: u32,
 		proposed_max_message_size: u32,
+	) -> DispatchResult {
+		Self::do_init_open_channel(
+			origin,
+			recipient,
+			proposed_max_capacity,
+			proposed_max_message_size,
+			true,
+		)
+	}
+
+	/// Initiate opening a channel, reserving the sender deposit only if `charge_deposit` is set.
+	fn do_init_open_channel(
+		origin: ParaId,
+		recipient: ParaId,
+		proposed_max_capacity: u32,
+		proposed_max_message_size: u32,
+		charge_deposit: bool,
 	) -> DispatchResult {
 		ensure!(origin != recipient, Error::<T>::OpenHrmpChannelToSelf);
 		ensure!(
@@ -1169,9 +1624,18 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::OpenHrmpChannelLimitExceeded,
 		);
 
+		let sender_deposit = if charge_deposit {
+			Self::channel_deposit(
+				config.hrmp_sender_deposit,
+				proposed_max_capacity,
+				proposed_max_message_size,
+			)
+		} else {
+			0
+		};
 		T::Currency::reserve(
 			&origin.into_account_truncating(),
-			config.hrmp_sender_deposit.unique_saturated_into(),
+			sender_deposit.unique_saturated_into(),
 		)?;
 
 		// mutating storage directly now -- shall not bail henceforth.
@@ -1182,10 +1646,16 @@ impl<T: Config> Pallet<T> {
 			HrmpOpenChannelRequest {
 				confirmed: false,
 				_age: 0,
-				sender_deposit: config.hrmp_sender_deposit,
+				sender_deposit,
 				max_capacity: proposed_max_capacity,
 				max_message_size: proposed_max_message_size,
-				max_total_size: config.hrmp_channel_max_total_size,
+				max_total_size: Self::channel_max_total_size(
+					config.hrmp_channel_max_total_size,
+					proposed_max_capacity,
+					proposed_max_message_size,
+				),
+				recipient_deposit: 0,
+				fee_per_message: 0,
 			},
 		);
 		<Self as Store>::HrmpOpenChannelRequestsList::append(channel_id);
@@ -1221,6 +1691,31 @@ impl<T: Config> Pallet<T> {
 	/// Basically the same as [`hrmp_accept_open_channel`](Pallet::hrmp_accept_open_channel) but
 	/// intended for calling directly from other pallets rather than dispatched.
 	pub fn accept_open_channel(origin: ParaId, sender: ParaId) -> DispatchResult {
+		Self::accept_open_channel_with_fee(origin, sender, 0)
+	}
+
+	/// Accept a pending open channel request from the given sender, setting the fee charged for
+	/// each message sent over the channel.
+	///
+	/// Basically the same as
+	/// [`hrmp_accept_open_channel_with_fee`](Pallet::hrmp_accept_open_channel_with_fee) but
+	/// intended for calling directly from other pallets rather than dispatched.
+	pub fn accept_open_channel_with_fee(
+		origin: ParaId,
+		sender: ParaId,
+		fee_per_message: Balance,
+	) -> DispatchResult {
+		Self::do_accept_open_channel(origin, sender, fee_per_message, true)
+	}
+
+	/// Accept a pending open channel request, reserving the recipient deposit only if
+	/// `charge_deposit` is set.
+	fn do_accept_open_channel(
+		origin: ParaId,
+		sender: ParaId,
+		fee_per_message: Balance,
+		charge_deposit: bool,
+	) -> DispatchResult {
 		let channel_id = HrmpChannelId { sender, recipient: origin };
 		let mut channel_req = <Self as Store>::HrmpOpenChannelRequests::get(&channel_id)
 			.ok_or(Error::<T>::AcceptHrmpChannelDoesntExist)?;
@@ -1242,14 +1737,25 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::AcceptHrmpChannelLimitExceeded,
 		);
 
+		let recipient_deposit = if charge_deposit {
+			Self::channel_deposit(
+				config.hrmp_recipient_deposit,
+				channel_req.max_capacity,
+				channel_req.max_message_size,
+			)
+		} else {
+			0
+		};
 		T::Currency::reserve(
 			&origin.into_account_truncating(),
-			config.hrmp_recipient_deposit.unique_saturated_into(),
+			recipient_deposit.unique_saturated_into(),
 		)?;
 
 		// persist the updated open channel request and then increment the number of accepted
 		// channels.
 		channel_req.confirmed = true;
+		channel_req.recipient_deposit = recipient_deposit;
+		channel_req.fee_per_message = fee_per_message;
 		<Self as Store>::HrmpOpenChannelRequests::insert(&channel_id, channel_req);
 		<Self as Store>::HrmpAcceptedChannelRequestCount::insert(&origin, accepted_cnt + 1);
 
@@ -1352,6 +1858,184 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn request_channel_update(
+		origin: ParaId,
+		channel_id: HrmpChannelId,
+		proposed_max_capacity: u32,
+		proposed_max_message_size: u32,
+	) -> DispatchResult {
+		ensure!(channel_id.is_participant(origin), Error::<T>::UpdateHrmpChannelUnauthorized);
+
+		let channel = <Self as Store>::HrmpChannels::get(&channel_id)
+			.ok_or(Error::<T>::UpdateHrmpChannelDoesntExist)?;
+		ensure!(
+			!<Self as Store>::HrmpCloseChannelRequests::contains_key(&channel_id),
+			Error::<T>::UpdateHrmpChannelCloseUnderway,
+		);
+		ensure!(
+			!<Self as Store>::HrmpChannelUpdateRequests::contains_key(&channel_id),
+			Error::<T>::UpdateHrmpChannelAlreadyRequested,
+		);
+
+		let config = <configuration::Pallet<T>>::config();
+		ensure!(proposed_max_capacity > 0, Error::<T>::OpenHrmpChannelZeroCapacity);
+		ensure!(
+			proposed_max_capacity <= config.hrmp_channel_max_capacity,
+			Error::<T>::OpenHrmpChannelCapacityExceedsLimit,
+		);
+		ensure!(proposed_max_message_size > 0, Error::<T>::OpenHrmpChannelZeroMessageSize);
+		ensure!(
+			proposed_max_message_size <= config.hrmp_channel_max_message_size,
+			Error::<T>::OpenHrmpChannelMessageSizeExceedsLimit,
+		);
+
+		let request = HrmpChannelUpdateRequest {
+			proposer: origin,
+			confirmed: false,
+			max_capacity: proposed_max_capacity,
+			max_message_size: proposed_max_message_size,
+			sender_deposit: Self::channel_deposit(
+				config.hrmp_sender_deposit,
+				proposed_max_capacity,
+				proposed_max_message_size,
+			),
+			recipient_deposit: Self::channel_deposit(
+				config.hrmp_recipient_deposit,
+				proposed_max_capacity,
+				proposed_max_message_size,
+			),
+		};
+
+		let extra = if origin == channel_id.sender {
+			request.sender_deposit.saturating_sub(channel.sender_deposit)
+		} else {
+			request.recipient_deposit.saturating_sub(channel.recipient_deposit)
+		};
+		T::Currency::reserve(&origin.into_account_truncating(), extra.unique_saturated_into())?;
+
+		<Self as Store>::HrmpChannelUpdateRequests::insert(&channel_id, request);
+		<Self as Store>::HrmpChannelUpdateRequestsList::append(channel_id);
+
+		Ok(())
+	}
+
+	fn accept_channel_update(origin: ParaId, channel_id: HrmpChannelId) -> DispatchResult {
+		let mut request = <Self as Store>::HrmpChannelUpdateRequests::get(&channel_id)
+			.ok_or(Error::<T>::HrmpChannelUpdateDoesntExist)?;
+		ensure!(
+			channel_id.is_participant(origin) && origin != request.proposer,
+			Error::<T>::AcceptHrmpChannelUpdateUnauthorized,
+		);
+		ensure!(!request.confirmed, Error::<T>::HrmpChannelUpdateAlreadyConfirmed);
+
+		let channel = <Self as Store>::HrmpChannels::get(&channel_id)
+			.ok_or(Error::<T>::UpdateHrmpChannelDoesntExist)?;
+		let extra = if origin == channel_id.sender {
+			request.sender_deposit.saturating_sub(channel.sender_deposit)
+		} else {
+			request.recipient_deposit.saturating_sub(channel.recipient_deposit)
+		};
+		T::Currency::reserve(&origin.into_account_truncating(), extra.unique_saturated_into())?;
+
+		request.confirmed = true;
+		<Self as Store>::HrmpChannelUpdateRequests::insert(&channel_id, request);
+
+		Ok(())
+	}
+
+	fn cancel_channel_update(origin: ParaId, channel_id: HrmpChannelId) -> DispatchResult {
+		ensure!(channel_id.is_participant(origin), Error::<T>::UpdateHrmpChannelUnauthorized);
+
+		let request = <Self as Store>::HrmpChannelUpdateRequests::get(&channel_id)
+			.ok_or(Error::<T>::HrmpChannelUpdateDoesntExist)?;
+		ensure!(!request.confirmed, Error::<T>::HrmpChannelUpdateAlreadyConfirmed);
+
+		let _ = Self::take_channel_update_request(&channel_id);
+
+		// Only the proposer could have reserved anything for an unconfirmed request.
+		if let Some(channel) = <Self as Store>::HrmpChannels::get(&channel_id) {
+			let (sender_extra, recipient_extra) = Self::reserved_for_update(
+				&channel_id,
+				&request,
+				channel.sender_deposit,
+				channel.recipient_deposit,
+			);
+			T::Currency::unreserve(
+				&channel_id.sender.into_account_truncating(),
+				sender_extra.unique_saturated_into(),
+			);
+			T::Currency::unreserve(
+				&channel_id.recipient.into_account_truncating(),
+				recipient_extra.unique_saturated_into(),
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Remove the update request of the given channel and sync the accompanying list with the set.
+	fn take_channel_update_request(channel_id: &HrmpChannelId) -> Option<HrmpChannelUpdateRequest> {
+		let request = <Self as Store>::HrmpChannelUpdateRequests::take(channel_id)?;
+		<Self as Store>::HrmpChannelUpdateRequestsList::mutate(|update_reqs| {
+			if let Some(pos) = update_reqs.iter().position(|x| x == channel_id) {
+				update_reqs.swap_remove(pos);
+			}
+		});
+		Some(request)
+	}
+
+	/// Returns the amounts that the sender and the recipient have reserved on top of their
+	/// current channel deposits for the given update request.
+	///
+	/// A participant reserves the increase of its deposit when it signs off the request, i.e.
+	/// either by proposing or by confirming it.
+	fn reserved_for_update(
+		channel_id: &HrmpChannelId,
+		request: &HrmpChannelUpdateRequest,
+		sender_deposit: Balance,
+		recipient_deposit: Balance,
+	) -> (Balance, Balance) {
+		let sender_extra = if request.confirmed || request.proposer == channel_id.sender {
+			request.sender_deposit.saturating_sub(sender_deposit)
+		} else {
+			0
+		};
+		let recipient_extra = if request.confirmed || request.proposer == channel_id.recipient {
+			request.recipient_deposit.saturating_sub(recipient_deposit)
+		} else {
+			0
+		};
+		(sender_extra, recipient_extra)
+	}
+
+	/// Returns whether the given para is registered as a system chain.
+	///
+	/// Ids below `LOWEST_PUBLIC_ID` cannot be registered publicly and are assigned by governance.
+	fn is_system_chain(para: ParaId) -> bool {
+		para < LOWEST_PUBLIC_ID
+	}
+
+	/// Returns the deposit a participant of a channel with the given parameters has to supply.
+	///
+	/// This is the flat `base` deposit from the configuration plus
+	/// [`Config::ChannelDepositPerByte`] for each byte the channel can hold at once.
+	pub fn channel_deposit(base: Balance, max_capacity: u32, max_message_size: u32) -> Balance {
+		let capacity_bytes = Balance::from(max_capacity).saturating_mul(max_message_size.into());
+		base.saturating_add(T::ChannelDepositPerByte::get().saturating_mul(capacity_bytes))
+	}
+
+	/// Returns the maximum total size of the messages a channel with the given parameters may hold.
+	///
+	/// This is the configured limit, bounded by the bytes the [`Self::channel_deposit`] is charged
+	/// for.
+	pub fn channel_max_total_size(
+		config_max_total_size: u32,
+		max_capacity: u32,
+		max_message_size: u32,
+	) -> u32 {
+		config_max_total_size.min(max_capacity.saturating_mul(max_message_size))
+	}
+
 	/// Returns the list of MQC heads for the inbound channels of the given recipient para paired
 	/// with the sender para ids. This vector is sorted ascending by the para id and doesn't contain
 	/// multiple entries with the same sender.
@@ -1389,6 +2073,47 @@ impl<T: Config> Pallet<T> {
 
 		inbound_hrmp_channels_contents
 	}
+
+	/// Returns all open channels along with the fill levels of their queues and the watermarks of
+	/// their recipients, as well as all pending open and close requests.
+	pub(crate) fn hrmp_topology() -> HrmpTopology<T::BlockNumber> {
+		let mut channels = <Self as Store>::HrmpChannels::iter()
+			.map(|(id, channel)| HrmpChannelInfo {
+				recipient_watermark: <Self as Store>::HrmpWatermarks::get(&id.recipient),
+				id,
+				max_capacity: channel.max_capacity,
+				max_total_size: channel.max_total_size,
+				max_message_size: channel.max_message_size,
+				msg_count: channel.msg_count,
+				total_size: channel.total_size,
+				mqc_head: channel.mqc_head,
+				sender_deposit: channel.sender_deposit,
+				recipient_deposit: channel.recipient_deposit,
+				fee_per_message: channel.fee_per_message,
+			})
+			.collect::<Vec<_>>();
+		channels.sort_by(|a, b| a.id.cmp(&b.id));
+
+		let mut open_requests = <Self as Store>::HrmpOpenChannelRequests::iter()
+			.map(|(id, request)| HrmpOpenChannelRequestInfo {
+				id,
+				confirmed: request.confirmed,
+				max_capacity: request.max_capacity,
+				max_total_size: request.max_total_size,
+				max_message_size: request.max_message_size,
+				sender_deposit: request.sender_deposit,
+				recipient_deposit: request.recipient_deposit,
+				fee_per_message: request.fee_per_message,
+			})
+			.collect::<Vec<_>>();
+		open_requests.sort_by(|a, b| a.id.cmp(&b.id));
+
+		let mut close_requests =
+			<Self as Store>::HrmpCloseChannelRequests::iter_keys().collect::<Vec<_>>();
+		close_requests.sort();
+
+		HrmpTopology { channels, open_requests, close_requests }
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -1490,6 +2215,19 @@ impl<T: Config> Pallet<T> {
 				.collect::<BTreeSet<_>>(),
 		);
 
+		// Update requests are kept in sync with their list and can only target open channels.
+		assert_eq!(
+			<Self as Store>::HrmpChannelUpdateRequests::iter()
+				.map(|(k, _)| k)
+				.collect::<BTreeSet<_>>(),
+			<Self as Store>::HrmpChannelUpdateRequestsList::get()
+				.into_iter()
+				.collect::<BTreeSet<_>>(),
+		);
+		for (channel_id, _) in <Self as Store>::HrmpChannelUpdateRequests::iter() {
+			assert!(<Self as Store>::HrmpChannels::contains_key(&channel_id));
+		}
+
 		// A HRMP watermark can be None for an onboarded parachain. However, an offboarded parachain
 		// cannot have an HRMP watermark: it should've been cleanup.
 		assert_contains_only_onboarded(
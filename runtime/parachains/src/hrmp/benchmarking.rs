@@ -69,9 +69,14 @@ where
 	<T as frame_system::Config>::RuntimeOrigin: From<crate::Origin>,
 {
 	let config = Configuration::<T>::config();
-	let deposit: BalanceOf<T> = config.hrmp_sender_deposit.unique_saturated_into();
 	let capacity = config.hrmp_channel_max_capacity;
 	let message_size = config.hrmp_channel_max_message_size;
+	let deposit: BalanceOf<T> = Hrmp::<T>::channel_deposit(
+		config.hrmp_sender_deposit.max(config.hrmp_recipient_deposit),
+		capacity,
+		message_size,
+	)
+	.unique_saturated_into();
 
 	let sender: ParaId = from.into();
 	let sender_origin: crate::Origin = from.into();
@@ -105,7 +110,7 @@ where
 		return output
 	}
 
-	Hrmp::<T>::process_hrmp_open_channel_requests(&Configuration::<T>::config());
+	Hrmp::<T>::process_hrmp_open_channel_requests();
 	if matches!(until, ParachainSetupStep::Established) {
 		return output
 	}
@@ -146,13 +151,17 @@ frame_benchmarking::benchmarks! {
 
 		let recipient_id: ParaId = 2u32.into();
 
+		let capacity = Configuration::<T>::config().hrmp_channel_max_capacity;
+		let message_size = Configuration::<T>::config().hrmp_channel_max_message_size;
+
 		// make sure para is registered, and has enough balance.
-		let deposit: BalanceOf<T> = Configuration::<T>::config().hrmp_sender_deposit.unique_saturated_into();
+		let deposit: BalanceOf<T> = Hrmp::<T>::channel_deposit(
+			Configuration::<T>::config().hrmp_sender_deposit,
+			capacity,
+			message_size,
+		).unique_saturated_into();
 		register_parachain_with_balance::<T>(sender_id, deposit);
 		register_parachain_with_balance::<T>(recipient_id, deposit);
-
-		let capacity = Configuration::<T>::config().hrmp_channel_max_capacity;
-		let message_size = Configuration::<T>::config().hrmp_channel_max_message_size;
 	}: _(sender_origin, recipient_id, capacity, message_size)
 	verify {
 		assert_last_event::<T>(
@@ -196,7 +205,11 @@ frame_benchmarking::benchmarks! {
 		Configuration::<T>::initializer_on_new_session(&Shared::<T>::scheduled_session());
 
 		let config = Configuration::<T>::config();
-		let deposit: BalanceOf<T> = config.hrmp_sender_deposit.unique_saturated_into();
+		let deposit: BalanceOf<T> = Hrmp::<T>::channel_deposit(
+			config.hrmp_sender_deposit.max(config.hrmp_recipient_deposit),
+			config.hrmp_channel_max_capacity,
+			config.hrmp_channel_max_message_size,
+		).unique_saturated_into();
 
 		let para: ParaId = 1u32.into();
 		let para_origin: crate::Origin = 1u32.into();
@@ -290,9 +303,8 @@ frame_benchmarking::benchmarks! {
 
 		assert_eq!(HrmpOpenChannelRequestsList::<T>::decode_len().unwrap_or_default() as u32, c);
 		let outgoing = (0..c).map(|id| (id + PREFIX_1).into()).collect::<Vec<ParaId>>();
-		let config = Configuration::<T>::config();
 	}: {
-		Hrmp::<T>::clean_open_channel_requests(&config, &outgoing);
+		Hrmp::<T>::clean_open_channel_requests(&outgoing);
 	} verify {
 		assert_eq!(HrmpOpenChannelRequestsList::<T>::decode_len().unwrap_or_default() as u32, 0);
 	}
@@ -301,17 +313,23 @@ frame_benchmarking::benchmarks! {
 		let sender_id: ParaId = 1u32.into();
 		let recipient_id: ParaId = 2u32.into();
 
+		let capacity = Configuration::<T>::config().hrmp_channel_max_capacity;
+		let message_size = Configuration::<T>::config().hrmp_channel_max_message_size;
+
 		// make sure para is registered, and has enough balance.
-		let sender_deposit: BalanceOf<T> =
-			Configuration::<T>::config().hrmp_sender_deposit.unique_saturated_into();
-		let recipient_deposit: BalanceOf<T> =
-			Configuration::<T>::config().hrmp_recipient_deposit.unique_saturated_into();
+		let sender_deposit: BalanceOf<T> = Hrmp::<T>::channel_deposit(
+			Configuration::<T>::config().hrmp_sender_deposit,
+			capacity,
+			message_size,
+		).unique_saturated_into();
+		let recipient_deposit: BalanceOf<T> = Hrmp::<T>::channel_deposit(
+			Configuration::<T>::config().hrmp_recipient_deposit,
+			capacity,
+			message_size,
+		).unique_saturated_into();
 		register_parachain_with_balance::<T>(sender_id, sender_deposit);
 		register_parachain_with_balance::<T>(recipient_id, recipient_deposit);
 
-		let capacity = Configuration::<T>::config().hrmp_channel_max_capacity;
-		let message_size = Configuration::<T>::config().hrmp_channel_max_message_size;
-
 		// make sure this channel doesn't exist
 		let channel_id = HrmpChannelId { sender: sender_id, recipient: recipient_id };
 		assert!(HrmpOpenChannelRequests::<T>::get(&channel_id).is_none());
@@ -322,6 +340,93 @@ frame_benchmarking::benchmarks! {
 			Event::<T>::HrmpChannelForceOpened(sender_id, recipient_id, capacity, message_size).into()
 		);
 	}
+
+	hrmp_accept_open_channel_with_fee {
+		let [(sender, _), (recipient, recipient_origin)] =
+			establish_para_connection::<T>(1, 2, ParachainSetupStep::Requested);
+	}: _(recipient_origin, sender, 1_000)
+	verify {
+		assert_last_event::<T>(Event::<T>::OpenChannelAcceptedWithFee(sender, recipient, 1_000).into());
+	}
+
+	hrmp_request_channel_update {
+		let [(sender, sender_origin), (recipient, _)] =
+			establish_para_connection::<T>(1, 2, ParachainSetupStep::Established);
+		let channel_id = HrmpChannelId { sender, recipient };
+
+		// shrinking the channel avoids reserving more funds than what the paras were given.
+		let capacity = 1;
+		let message_size = 1;
+	}: _(sender_origin, channel_id.clone(), capacity, message_size)
+	verify {
+		assert_last_event::<T>(
+			Event::<T>::ChannelUpdateRequested(sender, channel_id, capacity, message_size).into()
+		);
+	}
+
+	hrmp_accept_channel_update {
+		let [(sender, sender_origin), (recipient, recipient_origin)] =
+			establish_para_connection::<T>(1, 2, ParachainSetupStep::Established);
+		let channel_id = HrmpChannelId { sender, recipient };
+		assert_ok!(Hrmp::<T>::hrmp_request_channel_update(sender_origin.into(), channel_id.clone(), 1, 1));
+	}: _(recipient_origin, channel_id.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::ChannelUpdateAccepted(recipient, channel_id).into());
+	}
+
+	hrmp_cancel_channel_update {
+		// number of items already existing in the `HrmpChannelUpdateRequestsList`, other than the
+		// one that we remove.
+		let c in 0 .. MAX_UNIQUE_CHANNELS;
+
+		for id in 0 .. c {
+			let [(sender, sender_origin), (recipient, _)] =
+				establish_para_connection::<T>(PREFIX_0 + id, PREFIX_1 + id, ParachainSetupStep::Established);
+			let channel_id = HrmpChannelId { sender, recipient };
+			assert_ok!(Hrmp::<T>::hrmp_request_channel_update(sender_origin.into(), channel_id, 1, 1));
+		}
+
+		let [(sender, sender_origin), (recipient, _)] =
+			establish_para_connection::<T>(1, 2, ParachainSetupStep::Established);
+		let channel_id = HrmpChannelId { sender, recipient };
+		assert_ok!(
+			Hrmp::<T>::hrmp_request_channel_update(sender_origin.clone().into(), channel_id.clone(), 1, 1)
+		);
+		assert_eq!(HrmpChannelUpdateRequestsList::<T>::decode_len().unwrap_or_default() as u32, c + 1);
+	}: _(sender_origin, channel_id, c + 1)
+	verify {
+		assert_eq!(HrmpChannelUpdateRequestsList::<T>::decode_len().unwrap_or_default() as u32, c);
+	}
+
+	force_open_hrmp_channel_pair {
+		// use public ids, so that both sides are charged deposits.
+		let para_a: ParaId = (u32::from(LOWEST_PUBLIC_ID) + 1).into();
+		let para_b: ParaId = (u32::from(LOWEST_PUBLIC_ID) + 2).into();
+
+		let config = Configuration::<T>::config();
+		let capacity = config.hrmp_channel_max_capacity;
+		let message_size = config.hrmp_channel_max_message_size;
+
+		// each para is both a sender and a recipient.
+		let deposit: BalanceOf<T> = Hrmp::<T>::channel_deposit(
+			config.hrmp_sender_deposit,
+			capacity,
+			message_size,
+		).saturating_add(Hrmp::<T>::channel_deposit(
+			config.hrmp_recipient_deposit,
+			capacity,
+			message_size,
+		)).unique_saturated_into();
+		register_parachain_with_balance::<T>(para_a, deposit);
+		register_parachain_with_balance::<T>(para_b, deposit);
+
+		let session = Shared::<T>::session_index() + 1;
+	}: _(frame_system::Origin::<T>::Root, para_a, para_b, capacity, message_size)
+	verify {
+		assert_last_event::<T>(
+			Event::<T>::HrmpChannelPairForceOpened(para_a, para_b, capacity, message_size, session).into()
+		);
+	}
 }
 
 frame_benchmarking::impl_benchmark_test_suite!(
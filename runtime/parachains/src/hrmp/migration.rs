@@ -0,0 +1,172 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A module that is responsible for migration of storage.
+
+use crate::{
+	configuration,
+	hrmp::{self, Config, Pallet, Store},
+};
+use frame_support::{pallet_prelude::*, traits::StorageVersion, weights::Weight};
+
+/// The current storage version.
+///
+/// v0-v1: added the recipient deposit and the per-message fee to the channels and the open
+/// channel requests.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub mod v1 {
+	use super::*;
+	use frame_support::traits::OnRuntimeUpgrade;
+	use primitives::v2::{Balance, Hash, SessionIndex};
+
+	#[derive(Encode, Decode)]
+	pub struct OldHrmpOpenChannelRequest {
+		pub confirmed: bool,
+		pub _age: SessionIndex,
+		pub sender_deposit: Balance,
+		pub max_message_size: u32,
+		pub max_capacity: u32,
+		pub max_total_size: u32,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct OldHrmpChannel {
+		pub max_capacity: u32,
+		pub max_total_size: u32,
+		pub max_message_size: u32,
+		pub msg_count: u32,
+		pub total_size: u32,
+		pub mqc_head: Option<Hash>,
+		pub sender_deposit: Balance,
+		pub recipient_deposit: Balance,
+	}
+
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get::<Pallet<T>>() == 0 {
+				let weight_consumed = migrate_to_v1::<T>();
+
+				log::info!(target: "runtime::hrmp", "MigrateToV1 executed successfully");
+				STORAGE_VERSION.put::<Pallet<T>>();
+
+				weight_consumed
+			} else {
+				log::warn!(target: "runtime::hrmp", "MigrateToV1 should be removed.");
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
+}
+
+fn migrate_to_v1<T: Config>() -> Weight {
+	// Confirmed requests were confirmed in the current session with the deposit defined by the
+	// active configuration, which is what will be unreserved when the request is processed.
+	let recipient_deposit = <configuration::Pallet<T>>::config().hrmp_recipient_deposit;
+	let mut reads_writes = 1;
+
+	<Pallet<T> as Store>::HrmpOpenChannelRequests::translate(
+		|_, old: v1::OldHrmpOpenChannelRequest| {
+			reads_writes += 1;
+			Some(hrmp::HrmpOpenChannelRequest {
+				confirmed: old.confirmed,
+				_age: old._age,
+				sender_deposit: old.sender_deposit,
+				max_message_size: old.max_message_size,
+				max_capacity: old.max_capacity,
+				max_total_size: old.max_total_size,
+				recipient_deposit: if old.confirmed { recipient_deposit } else { 0 },
+				fee_per_message: 0,
+			})
+		},
+	);
+
+	<Pallet<T> as Store>::HrmpChannels::translate(|_, old: v1::OldHrmpChannel| {
+		reads_writes += 1;
+		Some(hrmp::HrmpChannel {
+			max_capacity: old.max_capacity,
+			max_total_size: old.max_total_size,
+			max_message_size: old.max_message_size,
+			msg_count: old.msg_count,
+			total_size: old.total_size,
+			mqc_head: old.mqc_head,
+			sender_deposit: old.sender_deposit,
+			recipient_deposit: old.recipient_deposit,
+			fee_per_message: 0,
+		})
+	});
+
+	T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use primitives::v2::{HrmpChannelId, Id as ParaId};
+
+	#[test]
+	fn test_migrate_to_v1() {
+		new_test_ext(Default::default()).execute_with(|| {
+			let channel_id = HrmpChannelId { sender: ParaId::from(1), recipient: ParaId::from(2) };
+			let old_channel = v1::OldHrmpChannel {
+				max_capacity: 8,
+				max_total_size: 64,
+				max_message_size: 8,
+				msg_count: 1,
+				total_size: 3,
+				mqc_head: Some(Default::default()),
+				sender_deposit: 100,
+				recipient_deposit: 50,
+			};
+			let old_request = v1::OldHrmpOpenChannelRequest {
+				confirmed: false,
+				_age: 0,
+				sender_deposit: 100,
+				max_message_size: 8,
+				max_capacity: 8,
+				max_total_size: 64,
+			};
+
+			// Implant the v0 version in the state.
+			frame_support::storage::unhashed::put_raw(
+				&hrmp::HrmpChannels::<Test>::hashed_key_for(&channel_id),
+				&old_channel.encode(),
+			);
+			frame_support::storage::unhashed::put_raw(
+				&hrmp::HrmpOpenChannelRequests::<Test>::hashed_key_for(&channel_id),
+				&old_request.encode(),
+			);
+
+			migrate_to_v1::<Test>();
+
+			let channel = hrmp::HrmpChannels::<Test>::get(&channel_id).unwrap();
+			assert_eq!(channel.max_capacity, old_channel.max_capacity);
+			assert_eq!(channel.msg_count, old_channel.msg_count);
+			assert_eq!(channel.mqc_head, old_channel.mqc_head);
+			assert_eq!(channel.sender_deposit, old_channel.sender_deposit);
+			assert_eq!(channel.recipient_deposit, old_channel.recipient_deposit);
+			assert_eq!(channel.fee_per_message, 0);
+
+			let request = hrmp::HrmpOpenChannelRequests::<Test>::get(&channel_id).unwrap();
+			assert_eq!(request.sender_deposit, old_request.sender_deposit);
+			assert_eq!(request.max_total_size, old_request.max_total_size);
+			assert_eq!(request.recipient_deposit, 0);
+			assert_eq!(request.fee_per_message, 0);
+		});
+	}
+}
@@ -225,6 +225,107 @@ fn force_open_channel_works() {
 	});
 }
 
+#[test]
+fn force_open_channel_pair_works() {
+	let para_a = 1.into();
+	let para_b = 3.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		// System chains don't need any balance to be connected.
+		register_parachain_with_balance(para_a, 0);
+		register_parachain_with_balance(para_b, 0);
+
+		run_to_block(5, Some(vec![4, 5]));
+		let session = ParasShared::session_index();
+		Hrmp::force_open_hrmp_channel_pair(RuntimeOrigin::root(), para_a, para_b, 2, 8).unwrap();
+		Hrmp::assert_storage_consistency_exhaustive();
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::HrmpChannelPairForceOpened(
+				para_a,
+				para_b,
+				2,
+				8,
+				session + 1
+			))));
+
+		run_to_block(6, None);
+		assert!(!channel_exists(para_a, para_b));
+		assert!(!channel_exists(para_b, para_a));
+
+		// Both directions are opened at the next session change.
+		run_to_block(8, Some(vec![8]));
+		assert!(channel_exists(para_a, para_b));
+		assert!(channel_exists(para_b, para_a));
+		assert_eq!(ParasShared::session_index(), session + 1);
+		for (sender, recipient) in [(para_a, para_b), (para_b, para_a)] {
+			let channel_id = HrmpChannelId { sender, recipient };
+			assert!(System::events().iter().any(|record| record.event ==
+				MockEvent::Hrmp(Event::ChannelOpened(channel_id.clone(), session + 1))));
+
+			let channel = <Hrmp as Store>::HrmpChannels::get(&channel_id).unwrap();
+			assert_eq!(channel.sender_deposit, 0);
+			assert_eq!(channel.recipient_deposit, 0);
+		}
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn force_open_channel_pair_charges_public_paras() {
+	let system_para = 1.into();
+	let public_para = (u32::from(LOWEST_PUBLIC_ID) + 1).into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain_with_balance(system_para, 0);
+		register_parachain_with_balance(public_para, 200);
+
+		run_to_block(5, Some(vec![4, 5]));
+		Hrmp::force_open_hrmp_channel_pair(RuntimeOrigin::root(), system_para, public_para, 2, 8)
+			.unwrap();
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&public_para.into_account_truncating()),
+			0
+		);
+
+		run_to_block(8, Some(vec![8]));
+		let to_public = HrmpChannelId { sender: system_para, recipient: public_para };
+		let channel = <Hrmp as Store>::HrmpChannels::get(&to_public).unwrap();
+		assert_eq!(channel.sender_deposit, 0);
+		assert_eq!(channel.recipient_deposit, 100);
+
+		let from_public = HrmpChannelId { sender: public_para, recipient: system_para };
+		let channel = <Hrmp as Store>::HrmpChannels::get(&from_public).unwrap();
+		assert_eq!(channel.sender_deposit, 100);
+		assert_eq!(channel.recipient_deposit, 0);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn force_open_channel_pair_requires_valid_paras() {
+	let para_a = 1.into();
+	let para_b = 3.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(para_a);
+		run_to_block(5, Some(vec![4, 5]));
+
+		assert_noop!(
+			Hrmp::force_open_hrmp_channel_pair(RuntimeOrigin::root(), para_a, para_b, 2, 8),
+			Error::<Test>::HrmpChannelParaNotValidNextSession,
+		);
+
+		// A para that is going to be offboarded at the next session cannot be connected either.
+		register_parachain(para_b);
+		run_to_block(7, Some(vec![6, 7]));
+		deregister_parachain(para_b);
+		assert_noop!(
+			Hrmp::force_open_hrmp_channel_pair(RuntimeOrigin::root(), para_a, para_b, 2, 8),
+			Error::<Test>::HrmpChannelParaNotValidNextSession,
+		);
+	});
+}
+
 #[test]
 fn close_channel_works() {
 	let para_a = 5.into();
@@ -284,6 +385,11 @@ fn send_recv_messages() {
 		assert!(Hrmp::check_outbound_hrmp(&config, para_a, &msgs).is_ok());
 		let _ = Hrmp::queue_outbound_hrmp(para_a, msgs);
 		Hrmp::assert_storage_consistency_exhaustive();
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::OutboundMessageQueued(
+				HrmpChannelId { sender: para_a, recipient: para_b },
+				sp_io::hashing::blake2_256(b"this is an emergency"),
+			))));
 
 		// On Block 7:
 		// B receives the message sent by A. B sets the watermark to 6.
@@ -648,3 +754,346 @@ fn cancel_pending_open_channel_request() {
 		Hrmp::assert_storage_consistency_exhaustive();
 	});
 }
+
+#[test]
+fn deposits_scale_with_channel_capacity() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_sender_deposit = 20;
+	genesis.hrmp_recipient_deposit = 15;
+	new_test_ext(genesis.build()).execute_with(|| {
+		crate::mock::HrmpChannelDepositPerByte::set(1);
+		register_parachain_with_balance(para_a, 100);
+		register_parachain_with_balance(para_b, 110);
+		run_to_block(5, Some(vec![4, 5]));
+
+		// 2 messages of 8 bytes each, so 16 bytes of capacity on top of the flat deposits.
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 64);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()), 79);
+		run_to_block(8, Some(vec![8]));
+
+		let channel_id = HrmpChannelId { sender: para_a, recipient: para_b };
+		let channel = <Hrmp as Store>::HrmpChannels::get(&channel_id).unwrap();
+		assert_eq!(channel.sender_deposit, 36);
+		assert_eq!(channel.recipient_deposit, 31);
+
+		// Both deposits are returned in full on closure.
+		Hrmp::close_channel(para_a, channel_id).unwrap();
+		run_to_block(10, Some(vec![10]));
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()),
+			100
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()),
+			110
+		);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn message_fees_are_paid_to_recipient() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_sender_deposit = 20;
+	genesis.hrmp_recipient_deposit = 15;
+	new_test_ext(genesis.build()).execute_with(|| {
+		register_parachain_with_balance(para_a, 31);
+		register_parachain_with_balance(para_b, 110);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::hrmp_accept_open_channel_with_fee(crate::Origin::from(para_b).into(), para_a, 7)
+			.unwrap();
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::OpenChannelAcceptedWithFee(para_a, para_b, 7))));
+		run_to_block(6, Some(vec![6]));
+		assert_eq!(
+			<Hrmp as Store>::HrmpChannels::get(&HrmpChannelId {
+				sender: para_a,
+				recipient: para_b
+			})
+			.unwrap()
+			.fee_per_message,
+			7,
+		);
+
+		let msgs = vec![OutboundHrmpMessage { recipient: para_b, data: b"knock".to_vec() }];
+		let config = Configuration::config();
+		assert!(Hrmp::check_outbound_hrmp(&config, para_a, &msgs).is_ok());
+		// The fee is held from the candidate acceptance until its enactment.
+		Hrmp::reserve_outbound_fees(para_a, &msgs);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 4);
+		assert_eq!(
+			<Test as Config>::Currency::reserved_balance(&para_a.into_account_truncating()),
+			27
+		);
+		let _ = Hrmp::queue_outbound_hrmp(para_a, msgs.clone());
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 4);
+		assert_eq!(
+			<Test as Config>::Currency::reserved_balance(&para_a.into_account_truncating()),
+			20
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()),
+			102
+		);
+		assert_eq!(<Hrmp as Store>::HrmpReservedFees::get(para_a), 0);
+
+		// The sender can't afford another message.
+		run_to_block(7, None);
+		assert!(matches!(
+			Hrmp::check_outbound_hrmp(&config, para_a, &msgs),
+			Err(OutboundHrmpAcceptanceErr::InsufficientFeeBalance {
+				idx: 0,
+				fees: 7,
+				available: 4
+			})
+		));
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn message_fees_cannot_be_spent_before_enactment() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_sender_deposit = 20;
+	genesis.hrmp_recipient_deposit = 15;
+	new_test_ext(genesis.build()).execute_with(|| {
+		register_parachain_with_balance(para_a, 31);
+		register_parachain_with_balance(para_b, 110);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::hrmp_accept_open_channel_with_fee(crate::Origin::from(para_b).into(), para_a, 7)
+			.unwrap();
+		run_to_block(6, Some(vec![6]));
+
+		let msgs = vec![OutboundHrmpMessage { recipient: para_b, data: b"knock".to_vec() }];
+		let config = Configuration::config();
+
+		// A dropped candidate gets its fees back.
+		assert!(Hrmp::check_outbound_hrmp(&config, para_a, &msgs).is_ok());
+		Hrmp::reserve_outbound_fees(para_a, &msgs);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 4);
+		Hrmp::release_outbound_fees(para_a);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 11);
+		assert_eq!(<Hrmp as Store>::HrmpReservedFees::get(para_a), 0);
+
+		// The free balance of the sender is spent while its candidate is pending availability,
+		// the fee is paid nevertheless.
+		assert!(Hrmp::check_outbound_hrmp(&config, para_a, &msgs).is_ok());
+		Hrmp::reserve_outbound_fees(para_a, &msgs);
+		<Test as Config>::Currency::make_free_balance_be(&para_a.into_account_truncating(), 0);
+		let _ = Hrmp::queue_outbound_hrmp(para_a, msgs.clone());
+		assert_eq!(
+			<Test as Config>::Currency::reserved_balance(&para_a.into_account_truncating()),
+			20
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()),
+			102
+		);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn update_channel_works() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_channel_max_capacity = 4;
+	genesis.hrmp_sender_deposit = 20;
+	genesis.hrmp_recipient_deposit = 15;
+	new_test_ext(genesis.build()).execute_with(|| {
+		crate::mock::HrmpChannelDepositPerByte::set(1);
+		register_parachain_with_balance(para_a, 100);
+		register_parachain_with_balance(para_b, 110);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+		run_to_block(6, Some(vec![6]));
+
+		// The sender proposes to double the capacity and reserves the increase of its deposit.
+		let channel_id = HrmpChannelId { sender: para_a, recipient: para_b };
+		Hrmp::hrmp_request_channel_update(
+			crate::Origin::from(para_a).into(),
+			channel_id.clone(),
+			4,
+			8,
+		)
+		.unwrap();
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 48);
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// The proposer can't confirm its own request.
+		assert_noop!(
+			Hrmp::hrmp_accept_channel_update(
+				crate::Origin::from(para_a).into(),
+				channel_id.clone()
+			),
+			Error::<Test>::AcceptHrmpChannelUpdateUnauthorized,
+		);
+		Hrmp::hrmp_accept_channel_update(crate::Origin::from(para_b).into(), channel_id.clone())
+			.unwrap();
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()), 63);
+
+		// Nothing changes until the session change.
+		run_to_block(7, None);
+		assert_eq!(<Hrmp as Store>::HrmpChannels::get(&channel_id).unwrap().max_capacity, 2);
+
+		run_to_block(8, Some(vec![8]));
+		let channel = <Hrmp as Store>::HrmpChannels::get(&channel_id).unwrap();
+		assert_eq!(channel.max_capacity, 4);
+		// The configured limit is below the 32 bytes covered by the deposit.
+		assert_eq!(channel.max_total_size, 16);
+		assert_eq!(channel.sender_deposit, 52);
+		assert_eq!(channel.recipient_deposit, 47);
+		assert!(System::events().iter().any(|record| record.event ==
+			MockEvent::Hrmp(Event::ChannelUpdated(channel_id.clone(), 4, 8))));
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// Shrinking the channel returns the difference on the session change.
+		Hrmp::hrmp_request_channel_update(
+			crate::Origin::from(para_b).into(),
+			channel_id.clone(),
+			1,
+			8,
+		)
+		.unwrap();
+		Hrmp::hrmp_accept_channel_update(crate::Origin::from(para_a).into(), channel_id.clone())
+			.unwrap();
+		run_to_block(10, Some(vec![10]));
+		let channel = <Hrmp as Store>::HrmpChannels::get(&channel_id).unwrap();
+		assert_eq!(channel.max_capacity, 1);
+		assert_eq!(channel.max_total_size, 8);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 72);
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()), 87);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn pending_channel_update_is_refunded() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+
+	let mut genesis = GenesisConfigBuilder::default();
+	genesis.hrmp_channel_max_capacity = 4;
+	genesis.hrmp_sender_deposit = 20;
+	genesis.hrmp_recipient_deposit = 15;
+	new_test_ext(genesis.build()).execute_with(|| {
+		crate::mock::HrmpChannelDepositPerByte::set(1);
+		register_parachain_with_balance(para_a, 100);
+		register_parachain_with_balance(para_b, 110);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+		run_to_block(6, Some(vec![6]));
+
+		// A canceled request returns the deposit increase of the proposer.
+		let channel_id = HrmpChannelId { sender: para_a, recipient: para_b };
+		Hrmp::request_channel_update(para_a, channel_id.clone(), 4, 8).unwrap();
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 48);
+		Hrmp::hrmp_cancel_channel_update(crate::Origin::from(para_b).into(), channel_id.clone(), 1)
+			.unwrap();
+		assert_eq!(<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()), 64);
+		Hrmp::assert_storage_consistency_exhaustive();
+
+		// A confirmed request dies together with the channel when it is closed.
+		Hrmp::request_channel_update(para_a, channel_id.clone(), 4, 8).unwrap();
+		Hrmp::accept_channel_update(para_b, channel_id.clone()).unwrap();
+		assert_noop!(
+			Hrmp::cancel_channel_update(para_a, channel_id.clone()),
+			Error::<Test>::HrmpChannelUpdateAlreadyConfirmed,
+		);
+		deregister_parachain(para_a);
+		run_to_block(8, Some(vec![7, 8]));
+		assert!(!channel_exists(para_a, para_b));
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_a.into_account_truncating()),
+			100
+		);
+		assert_eq!(
+			<Test as Config>::Currency::free_balance(&para_b.into_account_truncating()),
+			110
+		);
+		Hrmp::assert_storage_consistency_exhaustive();
+	});
+}
+
+#[test]
+fn hrmp_topology_reports_channels_and_requests() {
+	let para_a = 32.into();
+	let para_b = 64.into();
+	let para_c = 97.into();
+
+	new_test_ext(GenesisConfigBuilder::default().build()).execute_with(|| {
+		register_parachain(para_a);
+		register_parachain(para_b);
+		register_parachain(para_c);
+		run_to_block(5, Some(vec![4, 5]));
+
+		Hrmp::init_open_channel(para_b, para_a, 2, 8).unwrap();
+		Hrmp::accept_open_channel(para_a, para_b).unwrap();
+		Hrmp::init_open_channel(para_a, para_b, 2, 8).unwrap();
+		Hrmp::accept_open_channel(para_b, para_a).unwrap();
+		run_to_block(6, Some(vec![6]));
+
+		let _ = Hrmp::queue_outbound_hrmp(
+			para_a,
+			vec![OutboundHrmpMessage { recipient: para_b, data: vec![1, 2, 3] }],
+		);
+		Hrmp::close_channel(para_b, HrmpChannelId { sender: para_b, recipient: para_a }).unwrap();
+		Hrmp::init_open_channel(para_c, para_a, 2, 8).unwrap();
+
+		let topology = Hrmp::hrmp_topology();
+
+		// Channels are sorted by id, so `a -> b` comes before `b -> a`.
+		assert_eq!(topology.channels.len(), 2);
+		let a_to_b = &topology.channels[0];
+		assert_eq!(a_to_b.id, HrmpChannelId { sender: para_a, recipient: para_b });
+		assert_eq!(a_to_b.max_capacity, 2);
+		assert_eq!(a_to_b.msg_count, 1);
+		assert_eq!(a_to_b.total_size, 3);
+		assert_eq!(a_to_b.sender_deposit, 100);
+		assert_eq!(a_to_b.recipient_watermark, None);
+		let b_to_a = &topology.channels[1];
+		assert_eq!(b_to_a.id, HrmpChannelId { sender: para_b, recipient: para_a });
+		assert_eq!(b_to_a.msg_count, 0);
+
+		assert_eq!(topology.open_requests.len(), 1);
+		assert_eq!(
+			topology.open_requests[0].id,
+			HrmpChannelId { sender: para_c, recipient: para_a }
+		);
+		assert!(!topology.open_requests[0].confirmed);
+
+		assert_eq!(
+			topology.close_requests,
+			vec![HrmpChannelId { sender: para_b, recipient: para_a }],
+		);
+
+		// Once the recipient processes the message, its watermark is reported.
+		run_to_block(7, None);
+		Hrmp::prune_hrmp(para_b, 6);
+		let a_to_b = &Hrmp::hrmp_topology().channels[0];
+		assert_eq!(a_to_b.msg_count, 0);
+		assert_eq!(a_to_b.total_size, 0);
+		assert_eq!(a_to_b.recipient_watermark, Some(6));
+	});
+}
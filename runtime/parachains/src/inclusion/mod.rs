@@ -316,7 +316,9 @@ impl<T: Config> Pallet<T> {
 		// unlike most drain methods, drained elements are not cleared on `Drop` of the iterator
 		// and require consumption.
 		for _ in <PendingAvailabilityCommitments<T>>::drain() {}
-		for _ in <PendingAvailability<T>>::drain() {}
+		for (para_id, _) in <PendingAvailability<T>>::drain() {
+			<hrmp::Pallet<T>>::release_outbound_fees(para_id);
+		}
 		for _ in <AvailabilityBitfields<T>>::drain() {}
 	}
 
@@ -413,6 +415,8 @@ impl<T: Config> Pallet<T> {
 						pending_availability.core,
 						pending_availability.backing_group,
 					);
+				} else {
+					<hrmp::Pallet<T>>::release_outbound_fees(para_id);
 				}
 
 				freed_cores.push((pending_availability.core, pending_availability.hash));
@@ -666,6 +670,9 @@ impl<T: Config> Pallet<T> {
 			let (descriptor, commitments) =
 				(candidate.candidate.descriptor, candidate.candidate.commitments);
 
+			// The fees of the outbound HRMP messages are held until the candidate is enacted.
+			<hrmp::Pallet<T>>::reserve_outbound_fees(para_id, &commitments.horizontal_messages);
+
 			<PendingAvailability<T>>::insert(
 				&para_id,
 				CandidatePendingAvailability {
@@ -814,6 +821,7 @@ impl<T: Config> Pallet<T> {
 		for para_id in cleaned_up_ids {
 			let pending = <PendingAvailability<T>>::take(&para_id);
 			let commitments = <PendingAvailabilityCommitments<T>>::take(&para_id);
+			<hrmp::Pallet<T>>::release_outbound_fees(para_id);
 
 			if let (Some(pending), Some(commitments)) = (pending, commitments) {
 				// defensive: this should always be true.
@@ -850,6 +858,7 @@ impl<T: Config> Pallet<T> {
 		for para_id in cleaned_up_ids {
 			let _ = <PendingAvailability<T>>::take(&para_id);
 			let _ = <PendingAvailabilityCommitments<T>>::take(&para_id);
+			<hrmp::Pallet<T>>::release_outbound_fees(para_id);
 		}
 
 		cleaned_up_cores
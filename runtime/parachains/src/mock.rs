@@ -18,7 +18,7 @@
 
 use crate::{
 	configuration, disputes, dmp, hrmp, inclusion, initializer, origin, paras, paras_inherent,
-	scheduler, session_info, shared,
+	reward_points, scheduler, session_info, shared,
 	ump::{self, MessageId, UmpSink},
 	ParaId,
 };
@@ -67,6 +67,7 @@ frame_support::construct_runtime!(
 		ParachainsOrigin: origin,
 		SessionInfo: session_info,
 		Disputes: disputes,
+		RewardPoints: reward_points,
 		Babe: pallet_babe,
 	}
 );
@@ -194,6 +195,7 @@ impl origin::Config for Test {}
 
 parameter_types! {
 	pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub static ParasHeadHistoryLength: u32 = 4;
 }
 
 /// A very dumb implementation of `EstimateNextSessionRotation`. At the moment of writing, this
@@ -218,10 +220,35 @@ impl crate::paras::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = crate::paras::TestWeightInfo;
 	type UnsignedPriority = ParasUnsignedPriority;
+	type HeadHistoryLength = ParasHeadHistoryLength;
 	type NextSessionRotation = TestNextSessionRotation;
+	type OnboardingPvfCheckHandler = Self;
 }
 
-impl crate::dmp::Config for Test {}
+thread_local! {
+	pub static ONBOARDING_PVF_CHECKS: RefCell<Vec<(ParaId, bool)>> = RefCell::new(Vec::new());
+}
+
+/// Returns the verdicts of PVF pre-checking on onboarding paras reported so far.
+pub fn onboarding_pvf_checks() -> Vec<(ParaId, bool)> {
+	ONBOARDING_PVF_CHECKS.with(|r| r.borrow().clone())
+}
+
+impl crate::paras::OnboardingPvfCheckHandler for Test {
+	fn accepted(id: ParaId) -> Weight {
+		ONBOARDING_PVF_CHECKS.with(|r| r.borrow_mut().push((id, true)));
+		Weight::zero()
+	}
+
+	fn rejected(id: ParaId) -> Weight {
+		ONBOARDING_PVF_CHECKS.with(|r| r.borrow_mut().push((id, false)));
+		Weight::zero()
+	}
+}
+
+impl crate::dmp::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
@@ -235,17 +262,27 @@ impl crate::ump::Config for Test {
 	type WeightInfo = crate::ump::TestWeightInfo;
 }
 
+parameter_types! {
+	pub static HrmpChannelDepositPerByte: Balance = 0;
+}
+
 impl crate::hrmp::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = pallet_balances::Pallet<Test>;
+	type ChannelDepositPerByte = HrmpChannelDepositPerByte;
 	type WeightInfo = crate::hrmp::TestWeightInfo;
 }
 
+parameter_types! {
+	pub const MaxActiveDisputesPerSession: u32 = 16;
+}
+
 impl crate::disputes::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = Self;
 	type SlashingHandler = Self;
+	type MaxActiveDisputesPerSession = MaxActiveDisputesPerSession;
 	type WeightInfo = crate::disputes::TestWeightInfo;
 }
 
@@ -300,6 +337,7 @@ impl crate::inclusion::Config for Test {
 }
 
 impl crate::paras_inherent::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = crate::paras_inherent::TestWeightInfo;
 }
 
@@ -339,6 +377,13 @@ impl crate::session_info::Config for Test {
 	type ValidatorSet = MockValidatorSet;
 }
 
+impl crate::reward_points::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RewardApprovals = TestRewardApprovals;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = crate::reward_points::TestWeightInfo;
+}
+
 thread_local! {
 	pub static DISCOVERY_AUTHORITIES: RefCell<Vec<AuthorityDiscoveryId>> = RefCell::new(Vec::new());
 }
@@ -363,6 +408,9 @@ thread_local! {
 
 	pub static AVAILABILITY_REWARDS: RefCell<HashMap<ValidatorIndex, usize>>
 		= RefCell::new(HashMap::new());
+
+	pub static APPROVAL_REWARDS: RefCell<Vec<(SessionIndex, Vec<(ValidatorIndex, u32)>)>>
+		= RefCell::new(Vec::new());
 }
 
 pub fn backing_rewards() -> HashMap<ValidatorIndex, usize> {
@@ -373,6 +421,10 @@ pub fn availability_rewards() -> HashMap<ValidatorIndex, usize> {
 	AVAILABILITY_REWARDS.with(|r| r.borrow().clone())
 }
 
+pub fn approval_rewards() -> Vec<(SessionIndex, Vec<(ValidatorIndex, u32)>)> {
+	APPROVAL_REWARDS.with(|r| r.borrow().clone())
+}
+
 std::thread_local! {
 	static PROCESSED: RefCell<Vec<(ParaId, UpwardMessage)>> = RefCell::new(vec![]);
 }
@@ -430,6 +482,17 @@ impl inclusion::RewardValidators for TestRewardValidators {
 	}
 }
 
+pub struct TestRewardApprovals;
+
+impl reward_points::RewardApprovals for TestRewardApprovals {
+	fn reward_approvals(
+		session: SessionIndex,
+		approvals: impl IntoIterator<Item = (ValidatorIndex, u32)>,
+	) {
+		APPROVAL_REWARDS.with(|r| r.borrow_mut().push((session, approvals.into_iter().collect())))
+	}
+}
+
 /// Create a new set of test externalities.
 pub fn new_test_ext(state: MockGenesisConfig) -> TestExternalities {
 	use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStorePtr};
@@ -439,6 +502,9 @@ pub fn new_test_ext(state: MockGenesisConfig) -> TestExternalities {
 
 	BACKING_REWARDS.with(|r| r.borrow_mut().clear());
 	AVAILABILITY_REWARDS.with(|r| r.borrow_mut().clear());
+	APPROVAL_REWARDS.with(|r| r.borrow_mut().clear());
+	HrmpChannelDepositPerByte::set(0);
+	ParasHeadHistoryLength::set(4);
 
 	let mut t = state.system.build_storage::<Test>().unwrap();
 	state.configuration.assimilate_storage(&mut t).unwrap();
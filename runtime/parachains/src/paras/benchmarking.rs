@@ -66,6 +66,18 @@ pub(crate) fn generate_disordered_upgrades<T: Config>() {
 	<Pallet<T> as Store>::UpgradeCooldowns::put(cooldowns);
 }
 
+fn generate_disordered_freezes<T: Config>() {
+	let mut freezes = Vec::new();
+
+	for i in 0..SAMPLE_SIZE {
+		let id = ParaId::from(i);
+		let block_number = T::BlockNumber::from(1000u32);
+		freezes.push((id, block_number));
+	}
+
+	<Pallet<T> as Store>::UpgradeFreezes::put(freezes);
+}
+
 fn generate_disordered_actions_queue<T: Config>() {
 	let mut queue = Vec::new();
 	let next_session = shared::Pallet::<T>::session_index().saturating_add(One::one());
@@ -107,7 +119,10 @@ benchmarks! {
 		generate_disordered_upgrades::<T>();
 	}: _(RawOrigin::Root, para_id, new_code, block)
 	verify {
-		assert_last_event::<T>(Event::CodeUpgradeScheduled(para_id).into());
+		// the upgrade is scheduled before the PVF pre-checking is kicked off.
+		frame_system::Pallet::<T>::assert_has_event(
+			<T as Config>::RuntimeEvent::from(Event::CodeUpgradeScheduled(para_id)).into(),
+		);
 	}
 	force_note_new_head {
 		let s in 1 .. MAX_HEAD_DATA_SIZE;
@@ -133,6 +148,23 @@ benchmarks! {
 		let next_session = crate::shared::Pallet::<T>::session_index().saturating_add(One::one());
 		assert_last_event::<T>(Event::ActionQueued(para_id, next_session).into());
 	}
+	force_freeze_code_upgrades {
+		let para_id = ParaId::from(SAMPLE_SIZE);
+		<Pallet<T> as Store>::ParaLifecycles::insert(&para_id, ParaLifecycle::Parachain);
+		generate_disordered_freezes::<T>();
+		let until = frame_system::Pallet::<T>::block_number() + T::BlockNumber::from(2000u32);
+	}: _(RawOrigin::Root, para_id, until)
+	verify {
+		assert_last_event::<T>(Event::CodeUpgradesFrozen(para_id).into());
+	}
+	force_unfreeze_code_upgrades {
+		let para_id = ParaId::from(SAMPLE_SIZE - 1);
+		generate_disordered_freezes::<T>();
+		generate_disordered_upgrades::<T>();
+	}: _(RawOrigin::Root, para_id)
+	verify {
+		assert_last_event::<T>(Event::CodeUpgradesUnfrozen(para_id).into());
+	}
 
 	add_trusted_validation_code {
 		let c in 1 .. MAX_CODE_SIZE;
@@ -51,6 +51,10 @@
 //! 2. The validation code was vetted by the validators and declared as non-malicious in a processes
 //!    known as PVF pre-checking.
 //!
+//! Governance can also freeze code upgrades of a para until a given relay-chain block. While the
+//! freeze is in effect, the para observes the upgrade restriction signal and cannot signal new
+//! upgrades. An upgrade that is already in progress is not affected.
+//!
 //! # Validation Code Management
 //!
 //! Potentially, one validation code can be used by several different paras. For example, during
@@ -111,9 +115,12 @@ use bitvec::{order::Lsb0 as BitOrderLsb0, vec::BitVec};
 use frame_support::{pallet_prelude::*, traits::EstimateNextSessionRotation};
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
-use primitives::v2::{
-	ConsensusLog, HeadData, Id as ParaId, PvfCheckStatement, SessionIndex, UpgradeGoAhead,
-	UpgradeRestriction, ValidationCode, ValidationCodeHash, ValidatorSignature,
+use primitives::{
+	v2::{
+		ConsensusLog, Hash, HeadData, Id as ParaId, PvfCheckStatement, SessionIndex,
+		UpgradeGoAhead, UpgradeRestriction, ValidationCode, ValidationCodeHash, ValidatorSignature,
+	},
+	vstaging::CodeUpgradePipeline,
 };
 use scale_info::{Type, TypeInfo};
 use sp_core::RuntimeDebug;
@@ -168,6 +175,20 @@ pub struct ParaPastCodeMeta<N> {
 	last_pruned: Option<N>,
 }
 
+/// The position of a para in the ring buffer holding its head history.
+#[derive(Default, Encode, Decode, TypeInfo)]
+#[cfg_attr(test, derive(Debug, Clone, PartialEq))]
+pub struct HeadHistoryCursor<N> {
+	/// The slot the next head is written to.
+	next: u32,
+	/// The number of occupied slots.
+	len: u32,
+	/// The number of slots of the ring buffer, i.e. the `HeadHistoryLength` it was laid out with.
+	capacity: u32,
+	/// The relay-chain block number at which the most recent head was noted.
+	last_noted: N,
+}
+
 /// The possible states of a para, to take into account delayed lifecycle changes.
 ///
 /// If the para is in a "transition state", it is expected that the parachain is
@@ -464,6 +485,27 @@ impl<BlockNumber> PvfCheckActiveVoteState<BlockNumber> {
 	}
 }
 
+/// Something to be notified of the verdict of PVF pre-checking on the validation code of
+/// onboarding paras.
+pub trait OnboardingPvfCheckHandler {
+	/// The validation code of the onboarding para `id` passed pre-checking.
+	fn accepted(id: ParaId) -> Weight;
+
+	/// The validation code of the onboarding para `id` was rejected by pre-checking, and the
+	/// onboarding of the para was rolled back.
+	fn rejected(id: ParaId) -> Weight;
+}
+
+impl OnboardingPvfCheckHandler for () {
+	fn accepted(_id: ParaId) -> Weight {
+		Weight::zero()
+	}
+
+	fn rejected(_id: ParaId) -> Weight {
+		Weight::zero()
+	}
+}
+
 pub trait WeightInfo {
 	fn force_set_current_code(c: u32) -> Weight;
 	fn force_set_current_head(s: u32) -> Weight;
@@ -472,6 +514,8 @@ pub trait WeightInfo {
 	fn force_queue_action() -> Weight;
 	fn add_trusted_validation_code(c: u32) -> Weight;
 	fn poke_unused_validation_code() -> Weight;
+	fn force_freeze_code_upgrades() -> Weight;
+	fn force_unfreeze_code_upgrades() -> Weight;
 
 	fn include_pvf_check_statement_finalize_upgrade_accept() -> Weight;
 	fn include_pvf_check_statement_finalize_upgrade_reject() -> Weight;
@@ -503,6 +547,12 @@ impl WeightInfo for TestWeightInfo {
 	fn poke_unused_validation_code() -> Weight {
 		Weight::MAX
 	}
+	fn force_freeze_code_upgrades() -> Weight {
+		Weight::MAX
+	}
+	fn force_unfreeze_code_upgrades() -> Weight {
+		Weight::MAX
+	}
 	fn include_pvf_check_statement_finalize_upgrade_accept() -> Weight {
 		Weight::MAX
 	}
@@ -548,6 +598,13 @@ pub mod pallet {
 
 		type NextSessionRotation: EstimateNextSessionRotation<Self::BlockNumber>;
 
+		/// The maximum number of recent heads kept in the head history of every para.
+		#[pallet::constant]
+		type HeadHistoryLength: Get<u32>;
+
+		/// Notified of the verdict of PVF pre-checking on the validation code of onboarding paras.
+		type OnboardingPvfCheckHandler: OnboardingPvfCheckHandler;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -574,6 +631,16 @@ pub mod pallet {
 		/// The given validation code was rejected by the PVF pre-checking vote.
 		/// `code_hash` `para_id`
 		PvfCheckRejected(ValidationCodeHash, ParaId),
+		/// The para was signalled to go ahead with its scheduled code upgrade. `para_id`
+		CodeUpgradeGoAhead(ParaId),
+		/// A scheduled code upgrade was applied and the given validation code is now the current
+		/// one. `para_id` `code_hash`
+		CodeUpgradeApplied(ParaId, ValidationCodeHash),
+		/// Code upgrades of a para were frozen by governance. `para_id`
+		CodeUpgradesFrozen(ParaId),
+		/// Code upgrades of a para are no longer frozen, either because the freeze expired or
+		/// because it was lifted by governance. `para_id`
+		CodeUpgradesUnfrozen(ParaId),
 	}
 
 	#[pallet::error]
@@ -605,6 +672,10 @@ pub mod pallet {
 		PvfCheckDisabled,
 		/// Parachain cannot currently schedule a code upgrade.
 		CannotUpgradeCode,
+		/// The code upgrade freeze must expire after the current block.
+		InvalidFreezeExpiry,
+		/// Code upgrades of the para are not frozen.
+		CodeUpgradesNotFrozen,
 	}
 
 	/// All currently active PVF pre-checking votes.
@@ -641,6 +712,27 @@ pub mod pallet {
 	#[pallet::getter(fn para_head)]
 	pub(super) type Heads<T: Config> = StorageMap<_, Twox64Concat, ParaId, HeadData>;
 
+	/// The hashes of the most recent heads of every registered para, along with the relay-chain
+	/// block number at which each of them was noted.
+	///
+	/// This is a ring buffer of `HeadHistoryLength` slots per para, indexed by the slot number. The
+	/// oldest entry is overwritten first, see [`HeadHistoryCursors`]. The buffer is laid out anew
+	/// when its capacity no longer matches `HeadHistoryLength`.
+	#[pallet::storage]
+	pub(super) type HeadHistory<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ParaId, Twox64Concat, u32, (T::BlockNumber, Hash)>;
+
+	/// The position of every registered para in its [`HeadHistory`] ring buffer.
+	#[pallet::storage]
+	pub(super) type HeadHistoryCursors<T: Config> =
+		StorageMap<_, Twox64Concat, ParaId, HeadHistoryCursor<T::BlockNumber>, ValueQuery>;
+
+	/// The head histories of offboarded paras along with the capacity of their ring buffers,
+	/// removed in the next block.
+	#[pallet::storage]
+	pub(super) type HeadHistoryPruneQueue<T: Config> =
+		StorageValue<_, Vec<(ParaId, u32)>, ValueQuery>;
+
 	/// The validation code hash of every live para.
 	///
 	/// Corresponding code can be retrieved with [`CodeByHash`].
@@ -723,6 +815,14 @@ pub mod pallet {
 	pub(super) type UpgradeCooldowns<T: Config> =
 		StorageValue<_, Vec<(ParaId, T::BlockNumber)>, ValueQuery>;
 
+	/// The list of paras which code upgrades are frozen by governance, along with the relay-chain
+	/// block number at which the freeze expires. A para appears at most once.
+	///
+	/// Ordered ascending by block number.
+	#[pallet::storage]
+	pub(super) type UpgradeFreezes<T: Config> =
+		StorageValue<_, Vec<(ParaId, T::BlockNumber)>, ValueQuery>;
+
 	/// The list of upcoming code upgrades. Each item is a pair of which para performs a code
 	/// upgrade and at which relay-chain block it is expected at.
 	///
@@ -837,7 +937,6 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let config = configuration::Pallet::<T>::config();
 			Self::schedule_code_upgrade(para, new_code, relay_parent_number, &config);
-			Self::deposit_event(Event::CodeUpgradeScheduled(para));
 			Ok(())
 		}
 
@@ -1040,6 +1139,58 @@ pub mod pallet {
 				Ok(Some(<T as Config>::WeightInfo::include_pvf_check_statement()).into())
 			}
 		}
+
+		/// Freeze code upgrades of the given para until the relay-chain block `until`.
+		///
+		/// While frozen, the upgrade restriction signal is set for the para, so it cannot signal
+		/// any new code upgrade. An upgrade that is already in progress is not affected. Freezing
+		/// an already frozen para replaces the expiry of the freeze.
+		#[pallet::weight(<T as Config>::WeightInfo::force_freeze_code_upgrades())]
+		pub fn force_freeze_code_upgrades(
+			origin: OriginFor<T>,
+			para: ParaId,
+			until: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(ParaLifecycles::<T>::contains_key(&para), Error::<T>::NotRegistered);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(until > now, Error::<T>::InvalidFreezeExpiry);
+
+			<Self as Store>::UpgradeFreezes::mutate(|upgrade_freezes| {
+				upgrade_freezes.retain(|&(p, _)| p != para);
+				let insert_idx = upgrade_freezes
+					.binary_search_by_key(&until, |&(_, b)| b)
+					.unwrap_or_else(|idx| idx);
+				upgrade_freezes.insert(insert_idx, (para, until));
+			});
+			UpgradeRestrictionSignal::<T>::insert(&para, UpgradeRestriction::Present);
+
+			Self::deposit_event(Event::CodeUpgradesFrozen(para));
+			Ok(())
+		}
+
+		/// Lift the code upgrade freeze of the given para before it expires.
+		///
+		/// The upgrade restriction signal is kept in case the para is still cooling down after a
+		/// previous upgrade.
+		#[pallet::weight(<T as Config>::WeightInfo::force_unfreeze_code_upgrades())]
+		pub fn force_unfreeze_code_upgrades(origin: OriginFor<T>, para: ParaId) -> DispatchResult {
+			ensure_root(origin)?;
+			let unfrozen = <Self as Store>::UpgradeFreezes::mutate(|upgrade_freezes| {
+				let len = upgrade_freezes.len();
+				upgrade_freezes.retain(|&(p, _)| p != para);
+				upgrade_freezes.len() != len
+			});
+			ensure!(unfrozen, Error::<T>::CodeUpgradesNotFrozen);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			if !Self::is_cooling_down(para, now) {
+				UpgradeRestrictionSignal::<T>::remove(&para);
+			}
+
+			Self::deposit_event(Event::CodeUpgradesUnfrozen(para));
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -1134,12 +1285,12 @@ impl<T: Config> Pallet<T> {
 		// Schedule the upgrade with a delay just like if a parachain triggered the upgrade.
 		let upgrade_block = current_block.saturating_add(config.validation_upgrade_delay);
 		Self::schedule_code_upgrade(id, new_code, upgrade_block, &config);
-		Self::deposit_event(Event::CodeUpgradeScheduled(id));
 		Ok(())
 	}
 
 	/// Set the current head of a parachain.
 	pub(crate) fn set_current_head(para: ParaId, new_head: HeadData) {
+		Self::note_head_history(para, &new_head);
 		<Self as Store>::Heads::insert(&para, new_head);
 		Self::deposit_event(Event::CurrentHeadUpdated(para));
 	}
@@ -1147,7 +1298,7 @@ impl<T: Config> Pallet<T> {
 	/// Called by the initializer to initialize the paras pallet.
 	pub(crate) fn initializer_initialize(now: T::BlockNumber) -> Weight {
 		let weight = Self::prune_old_code(now);
-		weight + Self::process_scheduled_upgrade_changes(now)
+		weight + Self::process_scheduled_upgrade_changes(now) + Self::prune_head_history()
 	}
 
 	/// Called by the initializer to finalize the paras pallet.
@@ -1221,6 +1372,15 @@ impl<T: Config> Pallet<T> {
 					parachains.remove(para);
 
 					<Self as Store>::Heads::remove(&para);
+					// The session change is not weighed, so the slots are removed in the next
+					// block instead.
+					let head_history = <Self as Store>::HeadHistoryCursors::take(&para);
+					if head_history.capacity > 0 {
+						<Self as Store>::HeadHistoryPruneQueue::append((
+							para,
+							head_history.capacity,
+						));
+					}
 					<Self as Store>::FutureCodeUpgrades::remove(&para);
 					<Self as Store>::UpgradeGoAheadSignal::remove(&para);
 					<Self as Store>::UpgradeRestrictionSignal::remove(&para);
@@ -1259,6 +1419,9 @@ impl<T: Config> Pallet<T> {
 					.filter(|&(ref para, _)| !outgoing.contains(para))
 					.collect();
 			});
+			<Self as Store>::UpgradeFreezes::mutate(|upgrade_freezes| {
+				upgrade_freezes.retain(|&(ref para, _)| !outgoing.contains(para));
+			});
 		}
 
 		// Persist parachains into the storage explicitly.
@@ -1367,6 +1530,7 @@ impl<T: Config> Pallet<T> {
 				let num = upcoming_upgrades.iter().take_while(|&(_, at)| at <= &now).count();
 				for (para, _) in upcoming_upgrades.drain(..num) {
 					<Self as Store>::UpgradeGoAheadSignal::insert(&para, UpgradeGoAhead::GoAhead);
+					Self::deposit_event(Event::CodeUpgradeGoAhead(para));
 				}
 				num
 			},
@@ -1386,22 +1550,60 @@ impl<T: Config> Pallet<T> {
 		weight += T::DbWeight::get().reads_writes(1, 1);
 		weight += T::DbWeight::get().reads(cooldowns_expired as u64);
 
+		// account weight for `UpgradeFreezes::get`.
+		weight += T::DbWeight::get().reads(1);
+		let freezes_expired = <Self as Store>::UpgradeFreezes::get()
+			.iter()
+			.take_while(|&(_, until)| until <= &now)
+			.count();
+
+		// reserve weight for `initializer_finalize`:
+		// - 1 read and 1 write for `UpgradeFreezes::mutate`.
+		// - 1 write per expired freeze.
+		weight += T::DbWeight::get().reads_writes(1, 1);
+		weight += T::DbWeight::get().writes(freezes_expired as u64);
+
 		weight
 	}
 
 	/// Actually perform unsetting the expired upgrade restrictions.
 	///
+	/// The restriction of a para stays in place as long as either its cooldown or its freeze is
+	/// not expired.
+	///
 	/// See `process_scheduled_upgrade_changes` for more details.
 	fn process_scheduled_upgrade_cooldowns(now: T::BlockNumber) {
+		let upgrade_freezes = <Self as Store>::UpgradeFreezes::mutate(
+			|upgrade_freezes: &mut Vec<(ParaId, T::BlockNumber)>| {
+				let num = upgrade_freezes.iter().take_while(|&(_, until)| until <= &now).count();
+				for (para, _) in upgrade_freezes.drain(..num) {
+					if !Self::is_cooling_down(para, now) {
+						<Self as Store>::UpgradeRestrictionSignal::remove(&para);
+					}
+					Self::deposit_event(Event::CodeUpgradesUnfrozen(para));
+				}
+				upgrade_freezes.clone()
+			},
+		);
+
 		<Self as Store>::UpgradeCooldowns::mutate(
 			|upgrade_cooldowns: &mut Vec<(ParaId, T::BlockNumber)>| {
 				for &(para, _) in upgrade_cooldowns.iter().take_while(|&(_, at)| at <= &now) {
-					<Self as Store>::UpgradeRestrictionSignal::remove(&para);
+					if !upgrade_freezes.iter().any(|&(p, _)| p == para) {
+						<Self as Store>::UpgradeRestrictionSignal::remove(&para);
+					}
 				}
 			},
 		);
 	}
 
+	/// Returns whether the upgrade cooldown of the given para is still in effect at `now`.
+	fn is_cooling_down(para: ParaId, now: T::BlockNumber) -> bool {
+		<Self as Store>::UpgradeCooldowns::get()
+			.iter()
+			.any(|&(p, at)| p == para && at > now)
+	}
+
 	/// Goes over all PVF votes in progress, reinitializes ballots, increments ages and prunes the
 	/// active votes that reached their time-to-live.
 	fn groom_ongoing_pvf_votes(
@@ -1469,6 +1671,7 @@ impl<T: Config> Pallet<T> {
 			match cause {
 				PvfCheckCause::Onboarding(id) => {
 					weight += Self::proceed_with_onboarding(*id, sessions_observed);
+					weight += T::OnboardingPvfCheckHandler::accepted(*id);
 				},
 				PvfCheckCause::Upgrade { id, relay_parent_number } => {
 					weight +=
@@ -1566,6 +1769,7 @@ impl<T: Config> Pallet<T> {
 					UpcomingParasGenesis::<T>::remove(&id);
 					CurrentCodeHash::<T>::remove(&id);
 					ParaLifecycles::<T>::remove(&id);
+					weight += T::OnboardingPvfCheckHandler::rejected(id);
 				},
 				PvfCheckCause::Upgrade { id, .. } => {
 					weight += T::DbWeight::get().writes(2);
@@ -1810,6 +2014,7 @@ impl<T: Config> Pallet<T> {
 			upgrade_cooldowns.insert(insert_idx, (id, next_possible_upgrade_at));
 		});
 
+		Self::deposit_event(Event::CodeUpgradeScheduled(id));
 		weight += Self::kick_off_pvf_check(
 			PvfCheckCause::Upgrade { id, relay_parent_number },
 			code_hash,
@@ -1913,6 +2118,7 @@ impl<T: Config> Pallet<T> {
 		new_head: HeadData,
 		execution_context: T::BlockNumber,
 	) -> Weight {
+		Self::note_head_history(id, &new_head);
 		Heads::<T>::insert(&id, new_head);
 
 		if let Some(expected_at) = <Self as Store>::FutureCodeUpgrades::get(&id) {
@@ -1925,13 +2131,14 @@ impl<T: Config> Pallet<T> {
 					new_code_hash
 				} else {
 					log::error!(target: LOG_TARGET, "Missing future code hash for {:?}", &id);
-					return T::DbWeight::get().reads_writes(3, 1 + 3)
+					return T::DbWeight::get().reads_writes(1 + 3, 3 + 3)
 				};
 				let maybe_prior_code_hash = CurrentCodeHash::<T>::get(&id);
 				CurrentCodeHash::<T>::insert(&id, &new_code_hash);
 
 				let log = ConsensusLog::ParaUpgradeCode(id, new_code_hash);
 				<frame_system::Pallet<T>>::deposit_log(log.into());
+				Self::deposit_event(Event::CodeUpgradeApplied(id, new_code_hash));
 
 				// `now` is only used for registering pruning as part of `fn note_past_code`
 				let now = <frame_system::Pallet<T>>::block_number();
@@ -1943,10 +2150,11 @@ impl<T: Config> Pallet<T> {
 					Weight::zero()
 				};
 
-				// add 1 to writes due to heads update.
-				weight + T::DbWeight::get().reads_writes(3, 1 + 3)
+				// add 3 to writes due to heads and head history update, and 1 to reads due to the
+				// head history update.
+				weight + T::DbWeight::get().reads_writes(1 + 3, 3 + 3)
 			} else {
-				T::DbWeight::get().reads_writes(1, 1 + 0)
+				T::DbWeight::get().reads_writes(1 + 1, 3 + 0)
 			}
 		} else {
 			// This means there is no upgrade scheduled.
@@ -1954,10 +2162,115 @@ impl<T: Config> Pallet<T> {
 			// In case the upgrade was aborted by the relay-chain we should reset
 			// the `Abort` signal.
 			UpgradeGoAheadSignal::<T>::remove(&id);
-			T::DbWeight::get().reads_writes(1, 2)
+			T::DbWeight::get().reads_writes(1 + 1, 3 + 1)
+		}
+	}
+
+	/// Records the hash of the given head in the head history of the para at the current block,
+	/// overwriting the oldest entry once all `HeadHistoryLength` slots are occupied.
+	///
+	/// If the head of the para was already updated in the current block the entry is replaced.
+	/// This reads and writes the cursor and writes a single slot, regardless of the history length,
+	/// unless the ring buffer needs to be resized first.
+	fn note_head_history(id: ParaId, head: &HeadData) {
+		let max_len = T::HeadHistoryLength::get();
+		let mut cursor = HeadHistoryCursors::<T>::get(&id);
+		if cursor.capacity != max_len {
+			cursor = Self::resize_head_history(id, &cursor, max_len);
+			if max_len == 0 {
+				HeadHistoryCursors::<T>::remove(&id);
+			}
+		}
+		if max_len == 0 {
+			return
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let slot = if cursor.len > 0 && cursor.last_noted == now {
+			(cursor.next + max_len - 1) % max_len
+		} else {
+			let slot = cursor.next;
+			cursor.next = (slot + 1) % max_len;
+			cursor.len = cursor.len.saturating_add(1).min(max_len);
+			slot
+		};
+		cursor.last_noted = now;
+
+		HeadHistory::<T>::insert(&id, slot, (now, head.hash()));
+		HeadHistoryCursors::<T>::insert(&id, cursor);
+	}
+
+	/// Lays out the head history of the para anew in a ring buffer of `capacity` slots, keeping
+	/// the most recent heads.
+	///
+	/// This is only needed once per para after `HeadHistoryLength` has changed and touches every
+	/// slot of both the old and the new ring buffer.
+	fn resize_head_history(
+		id: ParaId,
+		cursor: &HeadHistoryCursor<T::BlockNumber>,
+		capacity: u32,
+	) -> HeadHistoryCursor<T::BlockNumber> {
+		let history = Self::read_head_history(id, cursor);
+		for slot in 0..cursor.capacity {
+			HeadHistory::<T>::remove(&id, slot);
+		}
+
+		let kept = &history[history.len().saturating_sub(capacity as usize)..];
+		for (slot, entry) in kept.iter().enumerate() {
+			HeadHistory::<T>::insert(&id, slot as u32, entry);
+		}
+
+		let len = kept.len() as u32;
+		HeadHistoryCursor {
+			next: if capacity == 0 { 0 } else { len % capacity },
+			len,
+			capacity,
+			last_noted: cursor.last_noted,
 		}
 	}
 
+	/// Removes the head histories of the paras offboarded in the last session change.
+	///
+	/// Every para occupies at most the capacity of its ring buffer, which is charged for in full.
+	fn prune_head_history() -> Weight {
+		let queue = <Self as Store>::HeadHistoryPruneQueue::take();
+		let mut weight = T::DbWeight::get().reads(1);
+		if queue.is_empty() {
+			return weight
+		}
+
+		weight += T::DbWeight::get().writes(1);
+		for (para, capacity) in queue {
+			for slot in 0..capacity {
+				HeadHistory::<T>::remove(&para, slot);
+			}
+			weight += T::DbWeight::get().writes(capacity.into());
+		}
+
+		weight
+	}
+
+	/// Returns the head history of the given para, ordered ascending by relay-chain block number.
+	pub(crate) fn head_history(id: ParaId) -> Vec<(T::BlockNumber, Hash)> {
+		Self::read_head_history(id, &HeadHistoryCursors::<T>::get(&id))
+	}
+
+	fn read_head_history(
+		id: ParaId,
+		cursor: &HeadHistoryCursor<T::BlockNumber>,
+	) -> Vec<(T::BlockNumber, Hash)> {
+		let capacity = cursor.capacity;
+		if capacity == 0 {
+			return Vec::new()
+		}
+
+		let len = cursor.len.min(capacity);
+		let oldest = (cursor.next + capacity - len) % capacity;
+		(0..len)
+			.filter_map(|i| HeadHistory::<T>::get(&id, (oldest + i) % capacity))
+			.collect()
+	}
+
 	/// Returns the list of PVFs (aka validation code) that require casting a vote by a validator in
 	/// the active validator set.
 	pub(crate) fn pvfs_require_precheck() -> Vec<ValidationCodeHash> {
@@ -1983,6 +2296,33 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Returns the state of the code upgrade pipeline of the given para, or `None` if the para is
+	/// not registered.
+	pub(crate) fn code_upgrade_pipeline(id: ParaId) -> Option<CodeUpgradePipeline<T::BlockNumber>> {
+		ParaLifecycles::<T>::get(&id)?;
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let future_code_hash = FutureCodeHash::<T>::get(&id);
+		Some(CodeUpgradePipeline {
+			current_code_hash: CurrentCodeHash::<T>::get(&id),
+			pvf_check_in_progress: future_code_hash
+				.map_or(false, |code_hash| PvfActiveVoteMap::<T>::contains_key(&code_hash)),
+			future_code_hash,
+			expected_at: FutureCodeUpgrades::<T>::get(&id),
+			go_ahead: UpgradeGoAheadSignal::<T>::get(&id),
+			restriction: UpgradeRestrictionSignal::<T>::get(&id),
+			cooldown_until: UpgradeCooldowns::<T>::get()
+				.into_iter()
+				.filter(|&(p, at)| p == id && at > now)
+				.map(|(_, at)| at)
+				.max(),
+			frozen_until: UpgradeFreezes::<T>::get()
+				.into_iter()
+				.find(|&(p, _)| p == id)
+				.map(|(_, until)| until),
+		})
+	}
+
 	/// Returns the current lifecycle state of the para.
 	pub fn lifecycle(id: ParaId) -> Option<ParaLifecycle> {
 		ParaLifecycles::<T>::get(&id)
@@ -2108,6 +2448,7 @@ impl<T: Config> Pallet<T> {
 			CurrentCodeHash::<T>::insert(&id, code_hash);
 		}
 
+		Self::note_head_history(id, &genesis_data.genesis_head);
 		Heads::<T>::insert(&id, &genesis_data.genesis_head);
 	}
 }
@@ -26,8 +26,8 @@ use test_helpers::{dummy_head_data, dummy_validation_code};
 use crate::{
 	configuration::HostConfiguration,
 	mock::{
-		new_test_ext, Configuration, MockGenesisConfig, Paras, ParasShared, RuntimeOrigin, System,
-		Test,
+		new_test_ext, onboarding_pvf_checks, Configuration, MockGenesisConfig, Paras, ParasShared,
+		RuntimeOrigin, System, Test,
 	},
 };
 
@@ -153,6 +153,15 @@ impl EventValidator {
 		self
 	}
 
+	fn scheduled(&mut self, id: ParaId) -> &mut Self {
+		self.events.push(frame_system::EventRecord {
+			phase: frame_system::Phase::Initialization,
+			event: Event::CodeUpgradeScheduled(id).into(),
+			topics: vec![],
+		});
+		self
+	}
+
 	fn check(&self) {
 		assert_eq!(&frame_system::Pallet::<Test>::events(), &self.events);
 	}
@@ -1144,6 +1153,7 @@ fn pvf_check_coalescing_onboarding_and_upgrade() {
 
 		// Check that `b` actually onboards.
 		assert_eq!(<Paras as Store>::ActionsQueue::get(EXPECTED_SESSION + 2), vec![b]);
+		assert_eq!(onboarding_pvf_checks(), vec![(b, true)]);
 
 		// Check that the upgrade got scheduled.
 		assert_eq!(
@@ -1154,6 +1164,7 @@ fn pvf_check_coalescing_onboarding_and_upgrade() {
 		// Verify that the required events were emitted.
 		EventValidator::new()
 			.started(&validation_code, b)
+			.scheduled(a)
 			.started(&validation_code, a)
 			.accepted(&validation_code, b)
 			.accepted(&validation_code, a)
@@ -1212,6 +1223,9 @@ fn pvf_check_onboarding_reject_on_expiry() {
 
 		// Verify that at this point we can again try to initialize the same para.
 		assert!(Paras::can_schedule_para_initialize(&a));
+
+		// The rejection was reported.
+		assert_eq!(onboarding_pvf_checks(), vec![(a, false)]);
 	});
 }
 
@@ -1270,7 +1284,11 @@ fn pvf_check_upgrade_reject() {
 		assert!(<Paras as Store>::FutureCodeHash::get(&a).is_none());
 
 		// Verify that the required events were emitted.
-		EventValidator::new().started(&new_code, a).rejected(&new_code, a).check();
+		EventValidator::new()
+			.scheduled(a)
+			.started(&new_code, a)
+			.rejected(&new_code, a)
+			.check();
 	});
 }
 
@@ -1644,6 +1662,7 @@ fn add_trusted_validation_code_insta_approval() {
 
 		// Verify that the required events were emitted.
 		EventValidator::new()
+			.scheduled(para_id)
 			.started(&validation_code, para_id)
 			.accepted(&validation_code, para_id)
 			.check();
@@ -1692,6 +1711,389 @@ fn add_trusted_validation_code_enacts_existing_pvf_vote() {
 	});
 }
 
+#[test]
+fn code_upgrade_pipeline_events_and_state() {
+	let validation_upgrade_delay = 5;
+	let validation_upgrade_cooldown = 10;
+
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: dummy_head_data(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration {
+				code_retention_period: 10,
+				validation_upgrade_delay,
+				validation_upgrade_cooldown,
+				pvf_checking_enabled: false,
+				..Default::default()
+			},
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		let new_code = ValidationCode(vec![4, 5, 6]);
+
+		assert!(Paras::code_upgrade_pipeline(ParaId::from(1)).is_none());
+		assert_eq!(
+			Paras::code_upgrade_pipeline(para_id),
+			Some(CodeUpgradePipeline {
+				current_code_hash: Some(ValidationCode(vec![1, 2, 3]).hash()),
+				future_code_hash: None,
+				pvf_check_in_progress: false,
+				expected_at: None,
+				go_ahead: None,
+				restriction: None,
+				cooldown_until: None,
+				frozen_until: None,
+			}),
+		);
+
+		run_to_block(2, None);
+		Paras::schedule_code_upgrade(para_id, new_code.clone(), 1, &Configuration::config());
+		Paras::note_new_head(para_id, Default::default(), 1);
+		System::assert_has_event(Event::CodeUpgradeScheduled(para_id).into());
+
+		let expected_at = 1 + validation_upgrade_delay;
+		assert_eq!(
+			Paras::code_upgrade_pipeline(para_id),
+			Some(CodeUpgradePipeline {
+				current_code_hash: Some(ValidationCode(vec![1, 2, 3]).hash()),
+				future_code_hash: Some(new_code.hash()),
+				pvf_check_in_progress: false,
+				expected_at: Some(expected_at),
+				go_ahead: None,
+				restriction: Some(UpgradeRestriction::Present),
+				cooldown_until: Some(1 + validation_upgrade_cooldown),
+				frozen_until: None,
+			}),
+		);
+
+		run_to_block(expected_at, None);
+		System::assert_has_event(Event::CodeUpgradeGoAhead(para_id).into());
+		assert_eq!(
+			Paras::code_upgrade_pipeline(para_id).unwrap().go_ahead,
+			Some(UpgradeGoAhead::GoAhead),
+		);
+
+		Paras::note_new_head(para_id, Default::default(), expected_at);
+		System::assert_has_event(Event::CodeUpgradeApplied(para_id, new_code.hash()).into());
+
+		let pipeline = Paras::code_upgrade_pipeline(para_id).unwrap();
+		assert_eq!(pipeline.current_code_hash, Some(new_code.hash()));
+		assert_eq!(pipeline.future_code_hash, None);
+		assert_eq!(pipeline.expected_at, None);
+	});
+}
+
+#[test]
+fn force_freeze_code_upgrades_works() {
+	let validation_upgrade_delay = 5;
+	let validation_upgrade_cooldown = 10;
+
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: dummy_head_data(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration {
+				code_retention_period: 10,
+				validation_upgrade_delay,
+				validation_upgrade_cooldown,
+				pvf_checking_enabled: false,
+				..Default::default()
+			},
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+
+		run_to_block(2, None);
+		assert!(Paras::can_upgrade_validation_code(para_id));
+
+		assert_err!(
+			Paras::force_freeze_code_upgrades(RuntimeOrigin::root(), ParaId::from(1), 10),
+			Error::<Test>::NotRegistered,
+		);
+		assert_err!(
+			Paras::force_freeze_code_upgrades(RuntimeOrigin::root(), para_id, 2),
+			Error::<Test>::InvalidFreezeExpiry,
+		);
+
+		assert_ok!(Paras::force_freeze_code_upgrades(RuntimeOrigin::root(), para_id, 20));
+		System::assert_has_event(Event::CodeUpgradesFrozen(para_id).into());
+		assert_eq!(<Paras as Store>::UpgradeFreezes::get(), vec![(para_id, 20)]);
+		assert_eq!(
+			<Paras as Store>::UpgradeRestrictionSignal::get(&para_id),
+			Some(UpgradeRestriction::Present),
+		);
+		assert!(!Paras::can_upgrade_validation_code(para_id));
+		assert_eq!(Paras::code_upgrade_pipeline(para_id).unwrap().frozen_until, Some(20));
+
+		// Freezing again replaces the expiry.
+		assert_ok!(Paras::force_freeze_code_upgrades(RuntimeOrigin::root(), para_id, 15));
+		assert_eq!(<Paras as Store>::UpgradeFreezes::get(), vec![(para_id, 15)]);
+
+		run_to_block(14, None);
+		assert!(!Paras::can_upgrade_validation_code(para_id));
+
+		// The freeze expires at the end of block 15.
+		run_to_block(16, None);
+		System::assert_has_event(Event::CodeUpgradesUnfrozen(para_id).into());
+		assert!(<Paras as Store>::UpgradeFreezes::get().is_empty());
+		assert!(<Paras as Store>::UpgradeRestrictionSignal::get(&para_id).is_none());
+		assert!(Paras::can_upgrade_validation_code(para_id));
+	});
+}
+
+#[test]
+fn code_upgrade_freeze_outlives_cooldown() {
+	let validation_upgrade_delay = 5;
+	let validation_upgrade_cooldown = 10;
+
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: dummy_head_data(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration {
+				code_retention_period: 10,
+				validation_upgrade_delay,
+				validation_upgrade_cooldown,
+				pvf_checking_enabled: false,
+				..Default::default()
+			},
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		let new_code = ValidationCode(vec![4, 5, 6]);
+
+		run_to_block(2, None);
+		Paras::schedule_code_upgrade(para_id, new_code.clone(), 1, &Configuration::config());
+		Paras::note_new_head(para_id, Default::default(), 1);
+		assert_ok!(Paras::force_freeze_code_upgrades(RuntimeOrigin::root(), para_id, 30));
+
+		// The cooldown elapses, but the freeze keeps the restriction in place.
+		run_to_block(1 + validation_upgrade_cooldown + 1, None);
+		assert_eq!(
+			<Paras as Store>::UpgradeRestrictionSignal::get(&para_id),
+			Some(UpgradeRestriction::Present),
+		);
+
+		assert_ok!(Paras::force_unfreeze_code_upgrades(RuntimeOrigin::root(), para_id));
+		System::assert_has_event(Event::CodeUpgradesUnfrozen(para_id).into());
+		assert!(<Paras as Store>::UpgradeFreezes::get().is_empty());
+		assert!(<Paras as Store>::UpgradeRestrictionSignal::get(&para_id).is_none());
+
+		assert_err!(
+			Paras::force_unfreeze_code_upgrades(RuntimeOrigin::root(), para_id),
+			Error::<Test>::CodeUpgradesNotFrozen,
+		);
+	});
+}
+
+#[test]
+fn force_unfreeze_keeps_restriction_while_cooling_down() {
+	let validation_upgrade_delay = 5;
+	let validation_upgrade_cooldown = 10;
+
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: dummy_head_data(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration {
+				code_retention_period: 10,
+				validation_upgrade_delay,
+				validation_upgrade_cooldown,
+				pvf_checking_enabled: false,
+				..Default::default()
+			},
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		let new_code = ValidationCode(vec![4, 5, 6]);
+
+		run_to_block(2, None);
+		Paras::schedule_code_upgrade(para_id, new_code.clone(), 1, &Configuration::config());
+		Paras::note_new_head(para_id, Default::default(), 1);
+		assert_ok!(Paras::force_freeze_code_upgrades(RuntimeOrigin::root(), para_id, 30));
+
+		run_to_block(5, None);
+		assert_ok!(Paras::force_unfreeze_code_upgrades(RuntimeOrigin::root(), para_id));
+		assert_eq!(
+			<Paras as Store>::UpgradeRestrictionSignal::get(&para_id),
+			Some(UpgradeRestriction::Present),
+		);
+
+		// The restriction is lifted once the cooldown elapses.
+		run_to_block(1 + validation_upgrade_cooldown + 1, None);
+		assert!(<Paras as Store>::UpgradeRestrictionSignal::get(&para_id).is_none());
+	});
+}
+
+#[test]
+fn head_history_is_bounded() {
+	let genesis_head = HeadData(vec![1]);
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: genesis_head.clone(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration { pvf_checking_enabled: false, ..Default::default() },
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		let head = |n: u8| HeadData(vec![n]);
+		let history_len = <Test as Config>::HeadHistoryLength::get() as BlockNumber;
+
+		// The genesis head is noted at genesis.
+		assert_eq!(Paras::head_history(para_id), vec![(0, genesis_head.hash())]);
+
+		run_to_block(1, None);
+		Paras::note_new_head(para_id, head(2), 0);
+		// A head noted twice in the same block replaces the previous entry.
+		Paras::note_new_head(para_id, head(3), 0);
+		assert_eq!(
+			Paras::head_history(para_id),
+			vec![(0, genesis_head.hash()), (1, head(3).hash())],
+		);
+
+		for b in 2..=history_len + 1 {
+			run_to_block(b, None);
+			Paras::note_new_head(para_id, head(b as u8 + 2), b - 1);
+		}
+
+		// Only the most recent heads are kept, the oldest slots being overwritten.
+		let expected: Vec<_> =
+			(2..=history_len + 1).map(|b| (b, head(b as u8 + 2).hash())).collect();
+		assert_eq!(Paras::head_history(para_id), expected);
+		assert_eq!(
+			<Paras as Store>::HeadHistory::iter_prefix(para_id).count(),
+			history_len as usize
+		);
+		assert_eq!(Paras::para_head(&para_id), Some(head(history_len as u8 + 3)));
+
+		// The history is cleaned up when the para is offboarded.
+		assert_ok!(Paras::schedule_para_cleanup(para_id));
+		run_to_block(history_len + 4, Some(vec![history_len + 3, history_len + 4]));
+		assert!(Paras::head_history(para_id).is_empty());
+		assert_eq!(<Paras as Store>::HeadHistory::iter_prefix(para_id).count(), 0);
+	});
+}
+
+#[test]
+fn head_history_survives_history_length_changes() {
+	let genesis_head = HeadData(vec![1]);
+	let paras = vec![(
+		0u32.into(),
+		ParaGenesisArgs {
+			para_kind: ParaKind::Parachain,
+			genesis_head: genesis_head.clone(),
+			validation_code: vec![1, 2, 3].into(),
+		},
+	)];
+
+	let genesis_config = MockGenesisConfig {
+		paras: GenesisConfig { paras, ..Default::default() },
+		configuration: crate::configuration::GenesisConfig {
+			config: HostConfiguration { pvf_checking_enabled: false, ..Default::default() },
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	new_test_ext(genesis_config).execute_with(|| {
+		let para_id = ParaId::from(0);
+		let head = |n: u8| HeadData(vec![n]);
+
+		// Wrap the ring buffer of 4 slots around, so that the oldest head is not in slot 0.
+		for b in 1..=5 {
+			run_to_block(b, None);
+			Paras::note_new_head(para_id, head(b as u8 + 1), b - 1);
+		}
+		let history: Vec<_> = (2..=5).map(|b| (b, head(b as u8 + 1).hash())).collect();
+		assert_eq!(Paras::head_history(para_id), history);
+
+		// Shrinking keeps the most recent heads.
+		crate::mock::ParasHeadHistoryLength::set(2);
+		run_to_block(6, None);
+		Paras::note_new_head(para_id, head(7), 5);
+		assert_eq!(Paras::head_history(para_id), vec![(5, head(6).hash()), (6, head(7).hash())],);
+		assert_eq!(<Paras as Store>::HeadHistory::iter_prefix(para_id).count(), 2);
+
+		// Growing keeps all heads and fills the new slots.
+		crate::mock::ParasHeadHistoryLength::set(3);
+		for b in 7..=8 {
+			run_to_block(b, None);
+			Paras::note_new_head(para_id, head(b as u8 + 1), b - 1);
+		}
+		let history: Vec<_> = (6..=8).map(|b| (b, head(b as u8 + 1).hash())).collect();
+		assert_eq!(Paras::head_history(para_id), history);
+		assert_eq!(<Paras as Store>::HeadHistory::iter_prefix(para_id).count(), 3);
+
+		// The history is cleaned up in the block after the para got offboarded.
+		assert_ok!(Paras::schedule_para_cleanup(para_id));
+		run_to_block(10, Some(vec![9, 10]));
+		assert!(Paras::head_history(para_id).is_empty());
+		assert_eq!(<Paras as Store>::HeadHistory::iter_prefix(para_id).count(), 0);
+		assert!(<Paras as Store>::HeadHistoryPruneQueue::get().is_empty());
+	});
+}
+
 #[test]
 fn verify_upgrade_go_ahead_signal_is_externally_accessible() {
 	use primitives::v2::well_known_keys;
@@ -1708,6 +2110,22 @@ fn verify_upgrade_go_ahead_signal_is_externally_accessible() {
 	});
 }
 
+#[test]
+fn verify_para_head_is_externally_accessible() {
+	use primitives::v2::well_known_keys;
+
+	let a = ParaId::from(2020);
+
+	new_test_ext(Default::default()).execute_with(|| {
+		assert!(sp_io::storage::get(&well_known_keys::para_head(a)).is_none());
+		Paras::heads_insert(&a, HeadData(vec![4, 2]));
+		assert_eq!(
+			sp_io::storage::get(&well_known_keys::para_head(a)).unwrap(),
+			HeadData(vec![4, 2]).encode(),
+		);
+	});
+}
+
 #[test]
 fn verify_upgrade_restriction_signal_is_externally_accessible() {
 	use primitives::v2::well_known_keys;
@@ -35,22 +35,22 @@ use bitvec::prelude::BitVec;
 use frame_support::{
 	inherent::{InherentData, InherentIdentifier, MakeFatalError, ProvideInherent},
 	pallet_prelude::*,
-	traits::Randomness,
 };
 use frame_system::pallet_prelude::*;
-use pallet_babe::{self, ParentBlockRandomness};
 use primitives::v2::{
 	BackedCandidate, CandidateHash, CandidateReceipt, CheckedDisputeStatementSet,
-	CheckedMultiDisputeStatementSet, CoreIndex, DisputeStatementSet,
+	CheckedMultiDisputeStatementSet, CoreIndex, DisputeStatement, DisputeStatementSet,
 	InherentData as ParachainsInherentData, MultiDisputeStatementSet, ScrapedOnChainVotes,
 	SessionIndex, SigningContext, UncheckedSignedAvailabilityBitfield,
-	UncheckedSignedAvailabilityBitfields, ValidatorId, ValidatorIndex, ValidityAttestation,
-	PARACHAINS_INHERENT_IDENTIFIER,
+	UncheckedSignedAvailabilityBitfields, ValidDisputeStatementKind, ValidatorId, ValidatorIndex,
+	ValidityAttestation, PARACHAINS_INHERENT_IDENTIFIER,
 };
-use rand::{seq::SliceRandom, SeedableRng};
 
 use scale_info::TypeInfo;
-use sp_runtime::traits::{Header as HeaderT, One};
+use sp_runtime::{
+	traits::{Header as HeaderT, One},
+	SaturatedConversion,
+};
 use sp_std::{
 	cmp::Ordering,
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -113,10 +113,24 @@ pub mod pallet {
 	pub trait Config:
 		inclusion::Config + scheduler::Config + initializer::Config + pallet_babe::Config
 	{
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Backed candidates were dropped from the inherent by the runtime, as they would have
+		/// exceeded the block weight. `\[(para id, candidate hash)\]`
+		CandidatesDropped(Vec<(ParaId, CandidateHash)>),
+		/// Dispute statement sets were dropped from the inherent by the runtime, as they would have
+		/// exceeded the block weight. `\[(session index, candidate hash)\]`
+		DisputesDropped(Vec<(SessionIndex, CandidateHash)>),
+	}
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Inclusion inherent called more than once per block.
@@ -368,6 +382,10 @@ impl<T: Config> Pallet<T> {
 				)
 			};
 
+			// Only what the runtime drops itself is reported, the block author is expected to
+			// have trimmed the inherent data to fit the block weight already.
+			let mut dropped_candidates = Vec::new();
+
 			// In case of an overweight block, consume up to the entire block weight
 			// in disputes, since we will never process anything else, but invalidate
 			// the block. It's still reasonable to protect against a massive amount of disputes.
@@ -377,21 +395,30 @@ impl<T: Config> Pallet<T> {
 				.any_gt(max_block_weight)
 			{
 				log::warn!("Overweight para inherent data reached the runtime {:?}", parent_hash);
-				backed_candidates.clear();
+				dropped_candidates.extend(
+					backed_candidates
+						.drain(..)
+						.map(|candidate| (candidate.descriptor().para_id, candidate.hash())),
+				);
 				candidates_weight = Weight::zero();
 				signed_bitfields.clear();
 				bitfields_weight = Weight::zero();
 			}
 
-			let entropy = compute_entropy::<T>(parent_hash);
-			let mut rng = rand_chacha::ChaChaRng::from_seed(entropy.into());
+			let (checked_disputes, checked_disputes_weight, dropped_disputes) =
+				limit_and_sanitize_disputes::<T, _>(
+					disputes,
+					&dispute_set_validity_check,
+					max_block_weight,
+				);
+
+			if !dropped_disputes.is_empty() {
+				Self::deposit_event(Event::DisputesDropped(dropped_disputes));
+			}
+			if !dropped_candidates.is_empty() {
+				Self::deposit_event(Event::CandidatesDropped(dropped_candidates));
+			}
 
-			let (checked_disputes, checked_disputes_weight) = limit_and_sanitize_disputes::<T, _>(
-				disputes,
-				&dispute_set_validity_check,
-				max_block_weight,
-				&mut rng,
-			);
 			(
 				checked_disputes,
 				checked_disputes_weight
@@ -584,9 +611,7 @@ impl<T: Config> Pallet<T> {
 		let expected_bits = <scheduler::Pallet<T>>::availability_cores().len();
 		let validator_public = shared::Pallet::<T>::active_validator_keys();
 		let max_block_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
-
-		let entropy = compute_entropy::<T>(parent_hash);
-		let mut rng = rand_chacha::ChaChaRng::from_seed(entropy.into());
+		let now = <frame_system::Pallet<T>>::block_number();
 
 		// Filter out duplicates and continue.
 		if let Err(_) = T::DisputesHandler::deduplicate_and_sort_dispute_data(&mut disputes) {
@@ -618,13 +643,19 @@ impl<T: Config> Pallet<T> {
 			};
 
 			// Limit the disputes first, since the following statements depend on the votes include here.
-			let (checked_disputes_sets, checked_disputes_sets_consumed_weight) =
+			let (checked_disputes_sets, checked_disputes_sets_consumed_weight, dropped_disputes) =
 				limit_and_sanitize_disputes::<T, _>(
 					disputes,
 					dispute_statement_set_valid,
 					max_block_weight,
-					&mut rng,
 				);
+			if !dropped_disputes.is_empty() {
+				log::debug!(
+					target: LOG_TARGET,
+					"Dropped dispute statement sets to not exceed the block weight: {:?}",
+					dropped_disputes,
+				);
+			}
 
 			// we don't care about fresh or not disputes
 			// this writes them to storage, so let's query it via those means
@@ -705,7 +736,6 @@ impl<T: Config> Pallet<T> {
 			let freed = collect_all_freed_cores::<T, _>(freed_concluded.iter().cloned());
 
 			<scheduler::Pallet<T>>::clear();
-			let now = <frame_system::Pallet<T>>::block_number();
 			<scheduler::Pallet<T>>::schedule(freed, now);
 
 			let scheduled = <scheduler::Pallet<T>>::scheduled();
@@ -746,12 +776,19 @@ impl<T: Config> Pallet<T> {
 
 		// Assure the maximum block weight is adhered, by limiting bitfields and backed
 		// candidates. Dispute statement sets were already limited before.
-		let actual_weight = apply_weight_limit::<T>(
+		let (actual_weight, dropped_candidates) = apply_weight_limit::<T>(
 			&mut backed_candidates,
 			&mut bitfields,
 			max_block_weight.saturating_sub(checked_disputes_sets_consumed_weight),
-			&mut rng,
+			now,
 		);
+		if !dropped_candidates.is_empty() {
+			log::debug!(
+				target: LOG_TARGET,
+				"Dropped backed candidates to not exceed the block weight: {:?}",
+				dropped_candidates,
+			);
+		}
 
 		if actual_weight.any_gt(max_block_weight) {
 			log::warn!(target: LOG_TARGET, "Post weight limiting weight is still too large.");
@@ -789,56 +826,33 @@ where
 	DisputedBitfield::from(bitvec)
 }
 
-/// Select a random subset, with preference for certain indices.
+/// Select items in the given order of indices, skipping the ones which exceed
+/// the remaining weight.
 ///
-/// Adds random items to the set until all candidates
-/// are tried or the remaining weight is depleted.
+/// Tries all indices of `order` or until the remaining weight is depleted.
 ///
 /// Returns the weight of all selected items from `selectables`
 /// as well as their indices in ascending order.
-fn random_sel<X, F: Fn(&X) -> Weight>(
-	rng: &mut rand_chacha::ChaChaRng,
-	selectables: Vec<X>,
-	mut preferred_indices: Vec<usize>,
+fn ordered_sel<X, F: Fn(&X) -> Weight>(
+	selectables: &[X],
+	order: impl IntoIterator<Item = usize>,
 	weight_fn: F,
 	weight_limit: Weight,
 ) -> (Weight, Vec<usize>) {
-	if selectables.is_empty() {
-		return (Weight::zero(), Vec::new())
-	}
-	// all indices that are not part of the preferred set
-	let mut indices = (0..selectables.len())
-		.into_iter()
-		.filter(|idx| !preferred_indices.contains(idx))
-		.collect::<Vec<_>>();
-	let mut picked_indices = Vec::with_capacity(selectables.len().saturating_sub(1));
+	let mut picked_indices = Vec::with_capacity(selectables.len());
 
 	let mut weight_acc = Weight::zero();
 
-	preferred_indices.shuffle(rng);
-	for preferred_idx in preferred_indices {
-		// preferred indices originate from outside
-		if let Some(item) = selectables.get(preferred_idx) {
+	for idx in order {
+		// indices may originate from outside
+		if let Some(item) = selectables.get(idx) {
 			let updated = weight_acc.saturating_add(weight_fn(item));
 			if updated.any_gt(weight_limit) {
 				continue
 			}
 			weight_acc = updated;
-			picked_indices.push(preferred_idx);
-		}
-	}
-
-	indices.shuffle(rng);
-	for idx in indices {
-		let item = &selectables[idx];
-		let updated = weight_acc.saturating_add(weight_fn(item));
-
-		if updated.any_gt(weight_limit) {
-			continue
+			picked_indices.push(idx);
 		}
-		weight_acc = updated;
-
-		picked_indices.push(idx);
 	}
 
 	// sorting indices, so the ordering is retained
@@ -848,6 +862,35 @@ fn random_sel<X, F: Fn(&X) -> Weight>(
 	(weight_acc, picked_indices)
 }
 
+/// The order in which backed candidates are considered for inclusion.
+///
+/// Candidates are ordered by para id, starting at an offset which advances with every block,
+/// such that paras take turns in being picked first. Code upgrades go before all other
+/// candidates, as they tend to be large and hence stand no chance to be picked late. Both
+/// groups take turns among themselves, so the number of code upgrades does not skew the
+/// rotation of the other candidates.
+fn candidates_selection_order<T: Config>(
+	candidates: &[BackedCandidate<T::Hash>],
+	now: T::BlockNumber,
+) -> Vec<usize> {
+	let now = now.saturated_into::<u32>() as usize;
+	let round_robin = |mut order: Vec<usize>| {
+		order.sort_by_key(|idx| candidates[*idx].descriptor().para_id);
+		if !order.is_empty() {
+			let offset = now % order.len();
+			order.rotate_left(offset);
+		}
+		order
+	};
+
+	let (upgrades, others): (Vec<usize>, Vec<usize>) = (0..candidates.len())
+		.partition(|idx| candidates[*idx].candidate.commitments.new_validation_code.is_some());
+
+	let mut order = round_robin(upgrades);
+	order.extend(round_robin(others));
+	order
+}
+
 /// Considers an upper threshold that the inherent data must not exceed.
 ///
 /// If there is sufficient space, all bitfields and all candidates
@@ -855,21 +898,22 @@ fn random_sel<X, F: Fn(&X) -> Weight>(
 ///
 /// Otherwise tries to include all disputes, and then tries to fill the remaining space with bitfields and then candidates.
 ///
-/// The selection process is random. For candidates, there is an exception for code upgrades as they are preferred.
-/// And for disputes, local and older disputes are preferred (see `limit_and_sanitize_disputes`).
-/// for backed candidates, since with a increasing number of parachains their chances of
-/// inclusion become slim. All backed candidates  are checked beforehands in `fn create_inherent_inner`
+/// The selection process is deterministic. Backed candidates are picked round-robin over paras
+/// across blocks, with code upgrades being preferred (see `candidates_selection_order`).
+/// And for disputes, local, backed and older disputes are preferred (see `limit_and_sanitize_disputes`).
+/// All backed candidates are checked beforehands in `fn create_inherent_inner`
 /// which guarantees sanity.
 ///
 /// Assumes disputes are already filtered by the time this is called.
 ///
-/// Returns the total weight consumed by `bitfields` and `candidates`.
+/// Returns the total weight consumed by `bitfields` and `candidates`, as well as the
+/// para ids and hashes of all dropped candidates.
 fn apply_weight_limit<T: Config + inclusion::Config>(
 	candidates: &mut Vec<BackedCandidate<<T>::Hash>>,
 	bitfields: &mut UncheckedSignedAvailabilityBitfields,
 	max_consumable_weight: Weight,
-	rng: &mut rand_chacha::ChaChaRng,
-) -> Weight {
+	now: T::BlockNumber,
+) -> (Weight, Vec<(ParaId, CandidateHash)>) {
 	let total_candidates_weight = backed_candidates_weight::<T>(candidates.as_slice());
 
 	let total_bitfields_weight = signed_bitfields_weight::<T>(bitfields.len());
@@ -878,55 +922,52 @@ fn apply_weight_limit<T: Config + inclusion::Config>(
 
 	// candidates + bitfields fit into the block
 	if max_consumable_weight.all_gte(total) {
-		return total
+		return (total, Vec::new())
 	}
 
-	// Prefer code upgrades, they tend to be large and hence stand no chance to be picked
-	// late while maintaining the weight bounds.
-	let preferred_indices = candidates
-		.iter()
-		.enumerate()
-		.filter_map(|(idx, candidate)| {
-			candidate.candidate.commitments.new_validation_code.as_ref().map(|_code| idx)
-		})
-		.collect::<Vec<usize>>();
-
 	// There is weight remaining to be consumed by a subset of candidates
 	// which are going to be picked now.
 	if let Some(max_consumable_by_candidates) =
 		max_consumable_weight.checked_sub(&total_bitfields_weight)
 	{
 		let (acc_candidate_weight, indices) =
-			random_sel::<BackedCandidate<<T as frame_system::Config>::Hash>, _>(
-				rng,
-				candidates.clone(),
-				preferred_indices,
+			ordered_sel::<BackedCandidate<<T as frame_system::Config>::Hash>, _>(
+				candidates,
+				candidates_selection_order::<T>(candidates, now),
 				|c| backed_candidate_weight::<T>(c),
 				max_consumable_by_candidates,
 			);
+		let dropped = candidates
+			.iter()
+			.enumerate()
+			.filter(|(idx, _)| indices.binary_search(idx).is_err())
+			.map(|(_, candidate)| (candidate.descriptor().para_id, candidate.hash()))
+			.collect();
 		candidates.indexed_retain(|idx, _backed_candidate| indices.binary_search(&idx).is_ok());
 		// pick all bitfields, and
 		// fill the remaining space with candidates
 		let total_consumed = acc_candidate_weight.saturating_add(total_bitfields_weight);
 
-		return total_consumed
+		return (total_consumed, dropped)
 	}
 
-	candidates.clear();
+	let dropped = candidates
+		.drain(..)
+		.map(|candidate| (candidate.descriptor().para_id, candidate.hash()))
+		.collect();
 
 	// insufficient space for even the bitfields alone, so only try to fit as many of those
 	// into the block and skip the candidates entirely
-	let (total_consumed, indices) = random_sel::<UncheckedSignedAvailabilityBitfield, _>(
-		rng,
-		bitfields.clone(),
-		vec![],
+	let (total_consumed, indices) = ordered_sel::<UncheckedSignedAvailabilityBitfield, _>(
+		bitfields,
+		0..bitfields.len(),
 		|_| <<T as Config>::WeightInfo as WeightInfo>::enter_bitfields(),
 		max_consumable_weight,
 	);
 
 	bitfields.indexed_retain(|idx, _bitfield| indices.binary_search(&idx).is_ok());
 
-	total_consumed
+	(total_consumed, dropped)
 }
 
 /// Filter bitfields based on freed core indices, validity, and other sanity checks.
@@ -1190,29 +1231,6 @@ pub(crate) fn assure_sanity_backed_candidates<
 	Ok(())
 }
 
-/// Derive entropy from babe provided per block randomness.
-///
-/// In the odd case none is available, uses the `parent_hash` and
-/// a const value, while emitting a warning.
-fn compute_entropy<T: Config>(parent_hash: T::Hash) -> [u8; 32] {
-	const CANDIDATE_SEED_SUBJECT: [u8; 32] = *b"candidate-seed-selection-subject";
-	// NOTE: this is slightly gameable since this randomness was already public
-	// by the previous block, while for the block author this randomness was
-	// known 2 epochs ago. it is marginally better than using the parent block
-	// hash since it's harder to influence the VRF output than the block hash.
-	let vrf_random = ParentBlockRandomness::<T>::random(&CANDIDATE_SEED_SUBJECT[..]).0;
-	let mut entropy: [u8; 32] = CANDIDATE_SEED_SUBJECT;
-	if let Some(vrf_random) = vrf_random {
-		entropy.as_mut().copy_from_slice(vrf_random.as_ref());
-	} else {
-		// in case there is no VRF randomness present, we utilize the relay parent
-		// as seed, it's better than a static value.
-		log::warn!(target: LOG_TARGET, "ParentBlockRandomness did not provide entropy");
-		entropy.as_mut().copy_from_slice(parent_hash.as_ref());
-	}
-	entropy
-}
-
 /// Limit disputes in place.
 ///
 /// Assumes ordering of disputes, retains sorting of the statement.
@@ -1228,9 +1246,10 @@ fn compute_entropy<T: Config>(parent_hash: T::Hash) -> [u8; 32] {
 ///   1. If weight is exceeded by locals, pick the older ones (lower indices)
 ///      until the weight limit is reached.
 ///   1. If weight is exceeded by locals and remotes, pick remotes
-///      randomly and check validity one by one.
+///      with backing votes first, then the older ones, and check validity one by one.
 ///
-/// Returns the consumed weight amount, that is guaranteed to be less than the provided `max_consumable_weight`.
+/// Returns the consumed weight amount, that is guaranteed to be less than the provided `max_consumable_weight`,
+/// as well as the sessions and candidate hashes of all disputes dropped due to weight.
 fn limit_and_sanitize_disputes<
 	T: Config,
 	CheckValidityFn: FnMut(DisputeStatementSet) -> Option<CheckedDisputeStatementSet>,
@@ -1238,8 +1257,7 @@ fn limit_and_sanitize_disputes<
 	mut disputes: MultiDisputeStatementSet,
 	mut dispute_statement_set_valid: CheckValidityFn,
 	max_consumable_weight: Weight,
-	rng: &mut rand_chacha::ChaChaRng,
-) -> (Vec<CheckedDisputeStatementSet>, Weight) {
+) -> (Vec<CheckedDisputeStatementSet>, Weight, Vec<(SessionIndex, CandidateHash)>) {
 	// The total weight if all disputes would be included
 	let disputes_weight = multi_dispute_statement_sets_weight::<T, _, _>(&disputes);
 
@@ -1267,12 +1285,12 @@ fn limit_and_sanitize_disputes<
 
 		// Accumualated weight of all disputes picked, that passed the checks.
 		let mut weight_acc = Weight::zero();
+		// All disputes dropped since they would exceed the weight.
+		let mut dropped = Vec::new();
 
 		// Select disputes in-order until the remaining weight is attained
 		disputes.iter().for_each(|dss| {
-			let dispute_weight = <<T as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(
-				dss.statements.len() as u32,
-			);
+			let dispute_weight = dispute_statement_set_weight::<T, _>(dss);
 			let updated = weight_acc.saturating_add(dispute_weight);
 			if max_consumable_weight.all_gte(updated) {
 				// only apply the weight if the validity check passes
@@ -1280,37 +1298,44 @@ fn limit_and_sanitize_disputes<
 					checked_acc.push(checked);
 					weight_acc = updated;
 				}
+			} else {
+				dropped.push((dss.session, dss.candidate_hash));
 			}
 		});
 
-		// Compute the statements length of all remote disputes
-		let d = remote_disputes.iter().map(|d| d.statements.len() as u32).collect::<Vec<u32>>();
-
-		// Select remote disputes at random until the block is full
-		let (_acc_remote_disputes_weight, mut indices) = random_sel::<u32, _>(
-			rng,
-			d,
-			vec![],
-			|v| <<T as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(*v),
+		// Remote disputes with backing votes go first, as the candidate was backed on some fork
+		// and hence could have made it into a block. Otherwise retain the input order, which
+		// prefers older disputes.
+		let mut order = (0..remote_disputes.len()).collect::<Vec<_>>();
+		order.sort_by_key(|idx| !has_backing_votes(&remote_disputes[*idx]));
+
+		// Select remote disputes in order until the block is full
+		let (_acc_remote_disputes_weight, indices) = ordered_sel::<DisputeStatementSet, _>(
+			&remote_disputes,
+			order,
+			|dss| dispute_statement_set_weight::<T, _>(dss),
 			max_consumable_weight.saturating_sub(weight_acc),
 		);
 
-		// Sort the indices, to retain the same sorting as the input.
-		indices.sort();
+		dropped.extend(
+			remote_disputes
+				.iter()
+				.enumerate()
+				.filter(|(idx, _)| indices.binary_search(idx).is_err())
+				.map(|(_, dss)| (dss.session, dss.candidate_hash)),
+		);
 
 		// Add the remote disputes after checking their validity.
 		checked_acc.extend(indices.into_iter().filter_map(|idx| {
 			dispute_statement_set_valid(remote_disputes[idx].clone()).map(|cdss| {
-				let weight = <<T as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(
-					cdss.as_ref().statements.len() as u32,
-				);
+				let weight = dispute_statement_set_weight::<T, _>(&cdss);
 				weight_acc = weight_acc.saturating_add(weight);
 				cdss
 			})
 		}));
 
 		// Update the remaining weight
-		(checked_acc, weight_acc)
+		(checked_acc, weight_acc, dropped)
 	} else {
 		// Go through all of them, and just apply the filter, they would all fit
 		let checked = disputes
@@ -1319,6 +1344,19 @@ fn limit_and_sanitize_disputes<
 			.collect::<Vec<CheckedDisputeStatementSet>>();
 		// some might have been filtered out, so re-calc the weight
 		let checked_disputes_weight = multi_dispute_statement_sets_weight::<T, _, _>(&checked);
-		(checked, checked_disputes_weight)
+		(checked, checked_disputes_weight, Vec::new())
 	}
 }
+
+/// Whether the dispute statement set contains any backing votes.
+fn has_backing_votes(dss: &DisputeStatementSet) -> bool {
+	dss.statements.iter().any(|(statement, _, _)| {
+		matches!(
+			statement,
+			DisputeStatement::Valid(
+				ValidDisputeStatementKind::BackingSeconded(_) |
+					ValidDisputeStatementKind::BackingValid(_)
+			)
+		)
+	})
+}
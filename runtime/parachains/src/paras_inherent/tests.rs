@@ -119,7 +119,7 @@ mod enter {
 			// Call enter with our 2 backed candidates
 			assert_ok!(Pallet::<Test>::enter(
 				frame_system::RawOrigin::None.into(),
-				expected_para_inherent_data
+				expected_para_inherent_data,
 			));
 			assert_eq!(
 				// The length of this vec is equal to the number of candidates, so we know our 2
@@ -701,6 +701,36 @@ mod enter {
 		});
 	}
 
+	#[test]
+	// Ensure that the selected disputes, including the tracking of active disputes, never exceed
+	// the weight limit.
+	fn limit_dispute_data_within_weight_limit() {
+		new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+			let disputes = (0..4_u64)
+				.map(|i| DisputeStatementSet {
+					candidate_hash: CandidateHash(sp_core::H256::from_low_u64_be(i)),
+					session: 1,
+					statements: Vec::new(),
+				})
+				.collect::<Vec<_>>();
+
+			// All disputes would fit, if the tracking of active disputes was not accounted for.
+			let max_weight =
+				<<Test as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(0)
+					.saturating_mul(disputes.len() as u64);
+
+			let (checked, weight, dropped) = limit_and_sanitize_disputes::<Test, _>(
+				disputes,
+				|dss| Some(CheckedDisputeStatementSet::unchecked_from_unchecked(dss)),
+				max_weight,
+			);
+			assert_eq!(checked.len(), 3);
+			assert_eq!(dropped.len(), 1);
+			assert_eq!(weight, multi_dispute_statement_sets_weight::<Test, _, _>(&checked));
+			assert!(weight.all_lte(max_weight));
+		});
+	}
+
 	fn max_block_weight() -> Weight {
 		<Test as frame_system::Config>::BlockWeights::get().max_block
 	}
@@ -798,6 +828,15 @@ mod enter {
 				limit_inherent_data,
 			));
 
+			// The trimming by the block author is not reported on-chain, only what the runtime
+			// dropped itself.
+			assert!(!frame_system::Pallet::<Test>::events().iter().any(|record| matches!(
+				record.event,
+				crate::mock::RuntimeEvent::ParaInherent(
+					Event::<Test>::DisputesDropped(_) | Event::<Test>::CandidatesDropped(_)
+				)
+			)));
+
 			assert_eq!(
 				// The length of this vec is equal to the number of candidates, so we know our 2
 				// backed candidates did not get filtered out
@@ -878,7 +917,7 @@ mod sanitizers {
 	use bitvec::order::Lsb0;
 	use primitives::v2::{
 		AvailabilityBitfield, GroupIndex, Hash, Id as ParaId, SignedAvailabilityBitfield,
-		ValidatorIndex,
+		ValidationCode, ValidatorIndex,
 	};
 	use sp_core::crypto::UncheckedFrom;
 
@@ -1275,4 +1314,70 @@ mod sanitizers {
 			);
 		}
 	}
+
+	#[test]
+	fn ordered_sel_skips_what_does_not_fit() {
+		let items = [3_u64, 5, 2, 4];
+
+		// 5 and 3 fit, 4 exceeds the limit, but 2 still fits.
+		let (weight, picked) = ordered_sel(
+			&items,
+			[1, 0, 3, 2],
+			|w| Weight::from_ref_time(*w),
+			Weight::from_ref_time(10),
+		);
+		assert_eq!(weight, Weight::from_ref_time(10));
+		assert_eq!(picked, vec![0, 1, 2]);
+	}
+
+	#[test]
+	fn candidates_are_selected_round_robin() {
+		let backed_candidates = [3_u32, 1, 2, 4]
+			.into_iter()
+			.map(|para_id| BackedCandidate {
+				candidate: TestCandidateBuilder {
+					para_id: ParaId::from(para_id),
+					new_validation_code: (para_id == 4).then(|| ValidationCode(vec![1, 2, 3])),
+					..Default::default()
+				}
+				.build(),
+				validity_votes: Vec::new(),
+				validator_indices: Default::default(),
+			})
+			.collect::<Vec<_>>();
+
+		// The code upgrade of para 4 always goes first, paras 1, 2 and 3 take turns.
+		assert_eq!(candidates_selection_order::<Test>(&backed_candidates, 0), vec![3, 1, 2, 0]);
+		assert_eq!(candidates_selection_order::<Test>(&backed_candidates, 1), vec![3, 2, 0, 1]);
+		assert_eq!(candidates_selection_order::<Test>(&backed_candidates, 2), vec![3, 0, 1, 2]);
+		assert_eq!(candidates_selection_order::<Test>(&backed_candidates, 3), vec![3, 1, 2, 0]);
+		assert_eq!(candidates_selection_order::<Test>(&backed_candidates, 4), vec![3, 2, 0, 1]);
+	}
+
+	#[test]
+	fn code_upgrades_do_not_skew_the_round_robin() {
+		let backed_candidates = [1_u32, 2, 3, 4, 5]
+			.into_iter()
+			.map(|para_id| BackedCandidate {
+				candidate: TestCandidateBuilder {
+					para_id: ParaId::from(para_id),
+					new_validation_code: (para_id > 3).then(|| ValidationCode(vec![1, 2, 3])),
+					..Default::default()
+				}
+				.build(),
+				validity_votes: Vec::new(),
+				validator_indices: Default::default(),
+			})
+			.collect::<Vec<_>>();
+
+		// Paras 1, 2 and 3 take turns with every block, as do the code upgrades of paras 4 and 5.
+		for (now, expected) in [
+			(0, vec![3, 4, 0, 1, 2]),
+			(1, vec![4, 3, 1, 2, 0]),
+			(2, vec![3, 4, 2, 0, 1]),
+			(3, vec![4, 3, 0, 1, 2]),
+		] {
+			assert_eq!(candidates_selection_order::<Test>(&backed_candidates, now), expected);
+		}
+	}
 }
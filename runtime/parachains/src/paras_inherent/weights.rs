@@ -16,6 +16,8 @@
 use super::{
 	BackedCandidate, Config, DisputeStatementSet, UncheckedSignedAvailabilityBitfield, Weight,
 };
+use crate::disputes;
+use frame_support::traits::Get;
 
 pub trait WeightInfo {
 	/// Variant over `v`, the count of dispute statements in a dispute statement set. This gives the
@@ -86,6 +88,14 @@ pub fn dispute_statement_set_weight<T: Config, S: AsRef<DisputeStatementSet>>(
 	<<T as Config>::WeightInfo as WeightInfo>::enter_variable_disputes(
 		statement_set.as_ref().statements.len() as u32,
 	)
+	.saturating_add(active_dispute_weight::<T>())
+}
+
+/// The weight of tracking an active dispute, assuming a session which is at capacity.
+fn active_dispute_weight<T: Config>() -> Weight {
+	<<T as disputes::Config>::WeightInfo as disputes::WeightInfo>::evict_active_dispute(
+		<T as disputes::Config>::MaxActiveDisputesPerSession::get(),
+	)
 }
 
 pub fn multi_dispute_statement_sets_weight<
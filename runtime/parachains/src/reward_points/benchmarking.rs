@@ -0,0 +1,93 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
+use primitives::v2::{SessionInfo, ValidatorId};
+use sp_application_crypto::RuntimeAppPublic;
+
+const SESSION_INDEX: SessionIndex = 1;
+const MAX_VALIDATORS: u32 = 1_000;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	// compare to the last event record
+	let frame_system::EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+/// Note the session info of `SESSION_INDEX` with `n` validators, all of them using the same key,
+/// and make the session before the current one. Returns the key of the validators.
+fn setup_session<T: Config>(n: u32) -> ValidatorId {
+	let validator = <ValidatorId as RuntimeAppPublic>::generate_pair(None);
+	let session_info = SessionInfo {
+		active_validator_indices: (0..n).map(ValidatorIndex).collect(),
+		random_seed: [0u8; 32],
+		dispute_period: 6,
+		validators: vec![validator.clone(); n as usize].into(),
+		discovery_keys: Vec::new(),
+		assignment_keys: Vec::new(),
+		validator_groups: Default::default(),
+		n_cores: 0,
+		zeroth_delay_tranche_width: 0,
+		relay_vrf_modulo_samples: 0,
+		n_delay_tranches: 0,
+		no_show_slots: 0,
+		needed_approvals: 0,
+	};
+	session_info::Sessions::<T>::insert(SESSION_INDEX, session_info);
+	shared::Pallet::<T>::set_session_index(SESSION_INDEX + 1);
+
+	validator
+}
+
+benchmarks! {
+	submit_approvals_tally {
+		let v in 1 .. MAX_VALIDATORS;
+
+		let validator = setup_session::<T>(v);
+		let tally = ApprovalsTally {
+			session_index: SESSION_INDEX,
+			validator_index: ValidatorIndex(v - 1),
+			approvals: vec![u32::MAX; v as usize],
+		};
+		let signature = validator.sign(&tally.signing_payload()).unwrap();
+	}: _(RawOrigin::None, tally, signature)
+	verify {
+		assert_last_event::<T>(Event::ApprovalsTallyNoted(SESSION_INDEX, ValidatorIndex(v - 1)).into());
+	}
+
+	credit_approval_points {
+		let v in 1 .. MAX_VALIDATORS;
+
+		for i in 0..v {
+			ApprovalsTallies::<T>::insert(SESSION_INDEX, ValidatorIndex(i), vec![i; v as usize]);
+		}
+	}: {
+		Pallet::<T>::credit_approval_points(SESSION_INDEX);
+	}
+	verify {
+		assert_last_event::<T>(Event::ApprovalPointsCredited(SESSION_INDEX, v).into());
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(Default::default()),
+		crate::mock::Test
+	);
+}
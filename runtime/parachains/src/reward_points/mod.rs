@@ -0,0 +1,425 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! An implementation of the `RewardValidators` trait used by `inclusion` that employs
+//! `pallet-staking` to compute the rewards.
+//!
+//! Based on <https://research.web3.foundation/en/latest/polkadot/overview/2-token-economics.html>
+//! which doesn't currently mention availability bitfields. As such, we don't reward them
+//! for the time being, although we will build schemes to do so in the future.
+//!
+//! Approval checking happens off-chain, so the runtime can't observe it directly. Instead, after
+//! a session ended, validators submit signed tallies of the approval votes they observed from
+//! every validator of the session. Once the submission window of a session is over, the pallet
+//! in this module credits every validator with the median of the approval counts reported for
+//! it, provided that a supermajority of the validators of the session submitted a tally.
+
+use crate::{session_info, shared};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Defensive, ValidatorSet},
+};
+use frame_system::pallet_prelude::*;
+use primitives::{
+	v2::{supermajority_threshold, SessionIndex, ValidatorIndex, ValidatorSignature},
+	vstaging::ApprovalsTally,
+};
+use sp_runtime::traits::AppVerify;
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod tests;
+
+const LOG_TARGET: &str = "runtime::reward-points";
+
+/// The amount of era points given by backing a candidate that is included.
+pub const BACKING_POINTS: u32 = 20;
+/// The amount of era points given by dispute voting on a candidate.
+pub const DISPUTE_STATEMENT_POINTS: u32 = 20;
+/// The amount of era points given per approval vote on a candidate, as agreed on by the tallies.
+pub const APPROVAL_POINTS: u32 = 20;
+
+/// The number of sessions after a session during which approvals tallies can be submitted for it.
+pub const APPROVALS_TALLY_SESSIONS: SessionIndex = 2;
+
+/// Rewards validators for the approval checking work they did in a session.
+pub trait RewardApprovals {
+	/// Reward the validators of the given session for the given number of approval votes each.
+	fn reward_approvals(
+		session: SessionIndex,
+		approvals: impl IntoIterator<Item = (ValidatorIndex, u32)>,
+	);
+}
+
+impl RewardApprovals for () {
+	fn reward_approvals(_: SessionIndex, _: impl IntoIterator<Item = (ValidatorIndex, u32)>) {}
+}
+
+/// Rewards validators for participating in parachains with era points in pallet-staking.
+pub struct RewardValidatorsWithEraPoints<C>(sp_std::marker::PhantomData<C>);
+
+impl<C> RewardValidatorsWithEraPoints<C>
+where
+	C: pallet_staking::Config + session_info::Config,
+	C::ValidatorSet: ValidatorSet<C::AccountId, ValidatorId = C::AccountId>,
+{
+	/// Reward validators in session with points, but only if they are in the active set.
+	fn reward_only_active(
+		session_index: SessionIndex,
+		rewards: impl IntoIterator<Item = (ValidatorIndex, u32)>,
+	) {
+		let validators = session_info::Pallet::<C>::account_keys(&session_index);
+		let validators = match validators
+			.defensive_proof("account_keys are present for dispute_period sessions")
+		{
+			Some(validators) => validators,
+			None => return,
+		};
+		// limit rewards to the active validator set
+		let active_set: BTreeSet<_> = C::ValidatorSet::validators().into_iter().collect();
+
+		let rewards = rewards
+			.into_iter()
+			.filter_map(|(i, points)| validators.get(i.0 as usize).map(|v| (v.clone(), points)))
+			.filter(|(v, _)| active_set.contains(v));
+
+		<pallet_staking::Pallet<C>>::reward_by_ids(rewards);
+	}
+}
+
+impl<C> crate::inclusion::RewardValidators for RewardValidatorsWithEraPoints<C>
+where
+	C: pallet_staking::Config + shared::Config + session_info::Config,
+	C::ValidatorSet: ValidatorSet<C::AccountId, ValidatorId = C::AccountId>,
+{
+	fn reward_backing(indices: impl IntoIterator<Item = ValidatorIndex>) {
+		let session_index = shared::Pallet::<C>::session_index();
+		Self::reward_only_active(session_index, indices.into_iter().map(|i| (i, BACKING_POINTS)));
+	}
+
+	fn reward_bitfields(_validators: impl IntoIterator<Item = ValidatorIndex>) {}
+}
+
+impl<C> crate::disputes::RewardValidators for RewardValidatorsWithEraPoints<C>
+where
+	C: pallet_staking::Config + session_info::Config,
+	C::ValidatorSet: ValidatorSet<C::AccountId, ValidatorId = C::AccountId>,
+{
+	fn reward_dispute_statement(
+		session: SessionIndex,
+		validators: impl IntoIterator<Item = ValidatorIndex>,
+	) {
+		Self::reward_only_active(
+			session,
+			validators.into_iter().map(|i| (i, DISPUTE_STATEMENT_POINTS)),
+		);
+	}
+}
+
+impl<C> RewardApprovals for RewardValidatorsWithEraPoints<C>
+where
+	C: pallet_staking::Config + session_info::Config,
+	C::ValidatorSet: ValidatorSet<C::AccountId, ValidatorId = C::AccountId>,
+{
+	fn reward_approvals(
+		session: SessionIndex,
+		approvals: impl IntoIterator<Item = (ValidatorIndex, u32)>,
+	) {
+		Self::reward_only_active(
+			session,
+			approvals.into_iter().map(|(i, n)| (i, n.saturating_mul(APPROVAL_POINTS))),
+		);
+	}
+}
+
+pub trait WeightInfo {
+	fn submit_approvals_tally(v: u32) -> Weight;
+	fn credit_approval_points(v: u32) -> Weight;
+}
+
+pub struct TestWeightInfo;
+impl WeightInfo for TestWeightInfo {
+	fn submit_approvals_tally(_v: u32) -> Weight {
+		Weight::MAX
+	}
+	fn credit_approval_points(_v: u32) -> Weight {
+		Weight::MAX
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use sp_runtime::transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		ValidTransaction,
+	};
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ shared::Config
+		+ session_info::Config
+		+ frame_system::offchain::SendTransactionTypes<Call<Self>>
+	{
+		type RuntimeEvent: From<Event> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The handler crediting the approval work agreed on by the tallies.
+		type RewardApprovals: RewardApprovals;
+
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// An approvals tally has been noted. `session_index` `validator_index`
+		ApprovalsTallyNoted(SessionIndex, ValidatorIndex),
+		/// Approval points of a session have been credited from the median of the given number
+		/// of tallies. `session_index` `tallies`
+		ApprovalPointsCredited(SessionIndex, u32),
+		/// The tallies of a session have been discarded, since not enough validators submitted
+		/// one. `session_index` `tallies`
+		ApprovalsTalliesDiscarded(SessionIndex, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Tallies can't be submitted for the given session (anymore).
+		ApprovalsTallyInvalidSession,
+		/// The validator index is out of bounds of the validator set of the session.
+		ApprovalsTallyValidatorIndexOutOfBounds,
+		/// The tally doesn't have an entry for every validator of the session.
+		ApprovalsTallyInvalidLength,
+		/// The signature of the tally is invalid.
+		ApprovalsTallyInvalidSignature,
+		/// The validator already submitted a tally for the session.
+		ApprovalsTallyDuplicate,
+	}
+
+	/// The approvals tallies submitted for a session, by the index of the submitting validator.
+	#[pallet::storage]
+	pub(super) type ApprovalsTallies<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, SessionIndex, Twox64Concat, ValidatorIndex, Vec<u32>>;
+
+	/// The sessions that have tallies which are yet to be credited, sorted ascending.
+	#[pallet::storage]
+	pub(super) type TalliedSessions<T: Config> = StorageValue<_, Vec<SessionIndex>, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let current_session = shared::Pallet::<T>::session_index();
+			let mut weight = T::DbWeight::get().reads(2);
+
+			let tallied_sessions = TalliedSessions::<T>::get();
+			let (due, pending): (Vec<_>, Vec<_>) = tallied_sessions
+				.into_iter()
+				.partition(|s| s.saturating_add(APPROVALS_TALLY_SESSIONS) < current_session);
+			if due.is_empty() {
+				return weight
+			}
+
+			for session in due {
+				weight += Self::credit_approval_points(session);
+			}
+			TalliedSessions::<T>::put(pending);
+			weight + T::DbWeight::get().writes(1)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Note a tally of the approval votes a validator observed in a past session.
+		///
+		/// Can only be submitted as an unsigned transaction by a validator of the session, once
+		/// per session, during the `APPROVALS_TALLY_SESSIONS` sessions after it.
+		#[pallet::weight(<T as Config>::WeightInfo::submit_approvals_tally(tally.approvals.len() as u32))]
+		pub fn submit_approvals_tally(
+			origin: OriginFor<T>,
+			tally: ApprovalsTally,
+			signature: ValidatorSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			Self::check_approvals_tally(&tally, &signature)?;
+
+			let ApprovalsTally { session_index, validator_index, approvals } = tally;
+			ApprovalsTallies::<T>::insert(session_index, validator_index, approvals);
+			TalliedSessions::<T>::mutate(|sessions| {
+				if let Err(idx) = sessions.binary_search(&session_index) {
+					sessions.insert(idx, session_index);
+				}
+			});
+
+			Self::deposit_event(Event::ApprovalsTallyNoted(session_index, validator_index));
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (tally, signature) = match call {
+				Call::submit_approvals_tally { tally, signature } => (tally, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			match Self::check_approvals_tally(tally, signature) {
+				Ok(()) => {},
+				Err(Error::<T>::ApprovalsTallyInvalidSession) =>
+					return if tally.session_index >= shared::Pallet::<T>::session_index() {
+						InvalidTransaction::Future.into()
+					} else {
+						InvalidTransaction::Stale.into()
+					},
+				Err(Error::<T>::ApprovalsTallyValidatorIndexOutOfBounds) =>
+					return InvalidTransaction::Custom(INVALID_TX_BAD_VALIDATOR_IDX).into(),
+				Err(Error::<T>::ApprovalsTallyInvalidLength) =>
+					return InvalidTransaction::Custom(INVALID_TX_BAD_TALLY_LENGTH).into(),
+				Err(Error::<T>::ApprovalsTallyInvalidSignature) =>
+					return InvalidTransaction::BadProof.into(),
+				Err(_) => return InvalidTransaction::Custom(INVALID_TX_DUPLICATE_TALLY).into(),
+			}
+
+			ValidTransaction::with_tag_prefix("ApprovalsTally")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((tally.session_index, tally.validator_index))
+				.propagate(true)
+				.build()
+		}
+
+		fn pre_dispatch(_call: &Self::Call) -> Result<(), TransactionValidityError> {
+			// The `submit_approvals_tally` dispatchable performs the same checks as
+			// `validate_unsigned`, so there is no point in doing them twice.
+			Ok(())
+		}
+	}
+}
+
+// custom transaction error codes
+const INVALID_TX_BAD_VALIDATOR_IDX: u8 = 1;
+const INVALID_TX_BAD_TALLY_LENGTH: u8 = 2;
+const INVALID_TX_DUPLICATE_TALLY: u8 = 3;
+
+impl<T: Config> Pallet<T> {
+	/// Check that the tally is for a session tallies are accepted for, is signed by a validator of
+	/// that session, covers every validator of the session and is the first one of its submitter.
+	fn check_approvals_tally(
+		tally: &ApprovalsTally,
+		signature: &ValidatorSignature,
+	) -> Result<(), Error<T>> {
+		let current_session = shared::Pallet::<T>::session_index();
+		ensure!(
+			tally.session_index < current_session &&
+				tally.session_index.saturating_add(APPROVALS_TALLY_SESSIONS) >= current_session,
+			Error::<T>::ApprovalsTallyInvalidSession,
+		);
+
+		let validators = session_info::Pallet::<T>::session_info(tally.session_index)
+			.ok_or(Error::<T>::ApprovalsTallyInvalidSession)?
+			.validators;
+		let validator_public = validators
+			.get(tally.validator_index)
+			.ok_or(Error::<T>::ApprovalsTallyValidatorIndexOutOfBounds)?;
+		ensure!(tally.approvals.len() == validators.len(), Error::<T>::ApprovalsTallyInvalidLength);
+		ensure!(
+			signature.verify(&tally.signing_payload()[..], validator_public),
+			Error::<T>::ApprovalsTallyInvalidSignature,
+		);
+		ensure!(
+			!ApprovalsTallies::<T>::contains_key(tally.session_index, tally.validator_index),
+			Error::<T>::ApprovalsTallyDuplicate,
+		);
+
+		Ok(())
+	}
+
+	/// Credit the approval points of the given session from the submitted tallies and remove them.
+	///
+	/// Nothing is credited unless a supermajority of the validators of the session submitted a
+	/// tally, which makes sure the median is bounded by the tallies of honest validators.
+	pub(crate) fn credit_approval_points(session_index: SessionIndex) -> Weight {
+		let tallies: Vec<Vec<u32>> =
+			ApprovalsTallies::<T>::drain_prefix(session_index).map(|(_, t)| t).collect();
+		let n_tallies = tallies.len() as u32;
+		// All tallies have the length of the validator set of the session.
+		let n_validators = tallies.first().map_or(0, |t| t.len());
+
+		if n_tallies > 0 && (n_tallies as usize) >= supermajority_threshold(n_validators) {
+			T::RewardApprovals::reward_approvals(session_index, median_approvals(&tallies));
+			Self::deposit_event(Event::ApprovalPointsCredited(session_index, n_tallies));
+		} else {
+			log::debug!(
+				target: LOG_TARGET,
+				"Discarding {} approvals tallies of session {} with {} validators",
+				n_tallies,
+				session_index,
+				n_validators,
+			);
+			Self::deposit_event(Event::ApprovalsTalliesDiscarded(session_index, n_tallies));
+		}
+
+		// Benchmarked with a tally from every validator of the session.
+		<T as Config>::WeightInfo::credit_approval_points(n_validators as u32)
+	}
+
+	/// Submits the given approvals tally into the transaction pool.
+	///
+	/// This function expects an offchain context and cannot be callable from the on-chain logic.
+	pub(crate) fn submit_approvals_tally(tally: ApprovalsTally, signature: ValidatorSignature) {
+		use frame_system::offchain::SubmitTransaction;
+
+		if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(
+			Call::submit_approvals_tally { tally, signature }.into(),
+		) {
+			log::error!(target: LOG_TARGET, "Error submitting approvals tally: {:?}", e);
+		}
+	}
+}
+
+/// The lower median of the approval counts reported for every validator across the given tallies,
+/// skipping validators with a median of zero.
+pub(crate) fn median_approvals(tallies: &[Vec<u32>]) -> Vec<(ValidatorIndex, u32)> {
+	let n_validators = tallies.first().map_or(0, |t| t.len());
+	let mut counts = Vec::with_capacity(tallies.len());
+
+	(0..n_validators)
+		.filter_map(|i| {
+			counts.clear();
+			counts.extend(tallies.iter().map(|t| t.get(i).copied().unwrap_or(0)));
+			counts.sort_unstable();
+			let median = counts[(counts.len() - 1) / 2];
+			(median > 0).then(|| (ValidatorIndex(i as u32), median))
+		})
+		.collect()
+}
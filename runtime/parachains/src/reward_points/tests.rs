@@ -0,0 +1,196 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::{
+	approval_rewards, assert_last_event, new_test_ext, MockGenesisConfig, ParasShared,
+	RewardPoints, RuntimeEvent, Test,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{OnInitialize, ValidateUnsigned},
+};
+use keyring::Sr25519Keyring;
+use primitives::v2::{SessionInfo, ValidatorId};
+use sp_runtime::transaction_validity::{
+	InvalidTransaction, TransactionSource, TransactionValidity,
+};
+
+static VALIDATORS: &[Sr25519Keyring] =
+	&[Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie, Sr25519Keyring::Dave];
+
+fn note_session_info(session_index: SessionIndex) {
+	let validators: Vec<ValidatorId> = VALIDATORS.iter().map(|v| v.public().into()).collect();
+	let session_info = SessionInfo {
+		active_validator_indices: (0..validators.len() as u32).map(ValidatorIndex).collect(),
+		random_seed: [0u8; 32],
+		dispute_period: 6,
+		validators: validators.into(),
+		discovery_keys: Vec::new(),
+		assignment_keys: Vec::new(),
+		validator_groups: Default::default(),
+		n_cores: 0,
+		zeroth_delay_tranche_width: 0,
+		relay_vrf_modulo_samples: 0,
+		n_delay_tranches: 0,
+		no_show_slots: 0,
+		needed_approvals: 0,
+	};
+	session_info::Sessions::<Test>::insert(session_index, session_info);
+}
+
+fn signed_tally(
+	session_index: SessionIndex,
+	validator_index: u32,
+	approvals: Vec<u32>,
+) -> (ApprovalsTally, ValidatorSignature) {
+	let tally = ApprovalsTally {
+		session_index,
+		validator_index: ValidatorIndex(validator_index),
+		approvals,
+	};
+	let signature = VALIDATORS[validator_index as usize].sign(&tally.signing_payload()).into();
+	(tally, signature)
+}
+
+fn validate(tally: &ApprovalsTally, signature: &ValidatorSignature) -> TransactionValidity {
+	let call = Call::submit_approvals_tally { tally: tally.clone(), signature: signature.clone() };
+	<RewardPoints as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &call)
+}
+
+#[test]
+fn median_approvals_takes_lower_median_and_skips_zeroes() {
+	let tallies = vec![vec![3, 0, 1, 9], vec![5, 0, 2, 1], vec![4, 1, 0, 2], vec![100, 0, 0, 2]];
+
+	assert_eq!(median_approvals(&tallies), vec![(ValidatorIndex(0), 4), (ValidatorIndex(3), 2)],);
+	assert!(median_approvals(&[]).is_empty());
+}
+
+#[test]
+fn submit_approvals_tally_checks_the_tally() {
+	new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		note_session_info(1);
+		ParasShared::set_session_index(2);
+
+		// Tallies can only be submitted for past sessions.
+		note_session_info(2);
+		let (tally, signature) = signed_tally(2, 0, vec![1; 4]);
+		assert_eq!(validate(&tally, &signature), Err(InvalidTransaction::Future.into()));
+		assert_noop!(
+			RewardPoints::submit_approvals_tally(None.into(), tally, signature),
+			Error::<Test>::ApprovalsTallyInvalidSession,
+		);
+
+		let (tally, signature) = signed_tally(1, 4, vec![1; 4]);
+		assert_eq!(
+			validate(&tally, &signature),
+			Err(InvalidTransaction::Custom(INVALID_TX_BAD_VALIDATOR_IDX).into()),
+		);
+
+		let (tally, signature) = signed_tally(1, 0, vec![1; 3]);
+		assert_eq!(
+			validate(&tally, &signature),
+			Err(InvalidTransaction::Custom(INVALID_TX_BAD_TALLY_LENGTH).into()),
+		);
+
+		// Signed by Alice, but claims to be Bob's.
+		let (mut tally, signature) = signed_tally(1, 0, vec![1; 4]);
+		tally.validator_index = ValidatorIndex(1);
+		assert_eq!(validate(&tally, &signature), Err(InvalidTransaction::BadProof.into()));
+		assert_noop!(
+			RewardPoints::submit_approvals_tally(None.into(), tally, signature),
+			Error::<Test>::ApprovalsTallyInvalidSignature,
+		);
+
+		let (tally, signature) = signed_tally(1, 0, vec![1; 4]);
+		assert!(validate(&tally, &signature).is_ok());
+		assert_ok!(RewardPoints::submit_approvals_tally(
+			None.into(),
+			tally.clone(),
+			signature.clone()
+		));
+		assert_last_event(RuntimeEvent::RewardPoints(Event::ApprovalsTallyNoted(
+			1,
+			ValidatorIndex(0),
+		)));
+		assert_eq!(TalliedSessions::<Test>::get(), vec![1]);
+
+		assert_eq!(
+			validate(&tally, &signature),
+			Err(InvalidTransaction::Custom(INVALID_TX_DUPLICATE_TALLY).into()),
+		);
+		assert_noop!(
+			RewardPoints::submit_approvals_tally(None.into(), tally, signature),
+			Error::<Test>::ApprovalsTallyDuplicate,
+		);
+
+		// The submission window is over.
+		ParasShared::set_session_index(4);
+		let (tally, signature) = signed_tally(1, 1, vec![1; 4]);
+		assert_eq!(validate(&tally, &signature), Err(InvalidTransaction::Stale.into()));
+	});
+}
+
+#[test]
+fn approval_points_are_credited_after_the_submission_window() {
+	new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		note_session_info(1);
+		ParasShared::set_session_index(2);
+
+		for (validator_index, approvals) in
+			[(0, vec![2, 3, 0, 7]), (1, vec![3, 3, 0, 0]), (2, vec![2, 1, 0, 5])]
+		{
+			let (tally, signature) = signed_tally(1, validator_index, approvals);
+			assert_ok!(RewardPoints::submit_approvals_tally(None.into(), tally, signature));
+		}
+
+		ParasShared::set_session_index(3);
+		RewardPoints::on_initialize(1);
+		assert!(approval_rewards().is_empty());
+
+		ParasShared::set_session_index(4);
+		RewardPoints::on_initialize(2);
+		assert_eq!(
+			approval_rewards(),
+			vec![(1, vec![(ValidatorIndex(0), 2), (ValidatorIndex(1), 3), (ValidatorIndex(3), 5)])],
+		);
+		assert_last_event(RuntimeEvent::RewardPoints(Event::ApprovalPointsCredited(1, 3)));
+		assert!(TalliedSessions::<Test>::get().is_empty());
+		assert_eq!(ApprovalsTallies::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+#[test]
+fn tallies_without_supermajority_are_discarded() {
+	new_test_ext(MockGenesisConfig::default()).execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		note_session_info(1);
+		ParasShared::set_session_index(2);
+
+		for validator_index in 0..2 {
+			let (tally, signature) = signed_tally(1, validator_index, vec![100; 4]);
+			assert_ok!(RewardPoints::submit_approvals_tally(None.into(), tally, signature));
+		}
+
+		ParasShared::set_session_index(4);
+		RewardPoints::on_initialize(1);
+		assert!(approval_rewards().is_empty());
+		assert_last_event(RuntimeEvent::RewardPoints(Event::ApprovalsTalliesDiscarded(1, 2)));
+		assert!(TalliedSessions::<Test>::get().is_empty());
+	});
+}
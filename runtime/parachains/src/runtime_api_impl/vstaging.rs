@@ -16,8 +16,15 @@
 
 //! Put implementations of functions from staging APIs here.
 
-use crate::disputes;
-use primitives::v2::{CandidateHash, DisputeState, SessionIndex};
+use crate::{
+	configuration,
+	disputes::{self, slashing},
+	hrmp, paras, reward_points,
+};
+use primitives::{
+	v2::{CandidateHash, DisputeState, Hash, Id as ParaId, SessionIndex, ValidatorSignature},
+	vstaging::{self, CodeUpgradePipeline, HostConfigurationChange, HrmpTopology},
+};
 use sp_std::prelude::*;
 
 /// Implementation for `get_session_disputes` function from the runtime API
@@ -25,3 +32,48 @@ pub fn get_session_disputes<T: disputes::Config>(
 ) -> Vec<(SessionIndex, CandidateHash, DisputeState<T::BlockNumber>)> {
 	<disputes::Pallet<T>>::disputes()
 }
+
+/// Implementation for `hrmp_topology` function from the runtime API
+pub fn hrmp_topology<T: hrmp::Config>() -> HrmpTopology<T::BlockNumber> {
+	<hrmp::Pallet<T>>::hrmp_topology()
+}
+
+/// Implementation for `para_code_upgrade_pipeline` function from the runtime API
+pub fn para_code_upgrade_pipeline<T: paras::Config>(
+	para_id: ParaId,
+) -> Option<CodeUpgradePipeline<T::BlockNumber>> {
+	<paras::Pallet<T>>::code_upgrade_pipeline(para_id)
+}
+
+/// Implementation for `para_head_history` function from the runtime API
+pub fn para_head_history<T: paras::Config>(para_id: ParaId) -> Vec<(T::BlockNumber, Hash)> {
+	<paras::Pallet<T>>::head_history(para_id)
+}
+
+/// Implementation for `unapplied_slashes` function from the runtime API
+pub fn unapplied_slashes<T: slashing::Config>(
+) -> Vec<(SessionIndex, CandidateHash, vstaging::slashing::PendingSlashes)> {
+	<slashing::Pallet<T>>::unapplied_slashes()
+}
+
+/// Implementation for `submit_report_dispute_lost` function from the runtime API
+pub fn submit_unsigned_slashing_report<T: slashing::Config>(
+	dispute_proof: vstaging::slashing::DisputeProof,
+	key_ownership_proof: <T as slashing::Config>::KeyOwnerProof,
+) -> Option<()> {
+	<slashing::Pallet<T>>::submit_unsigned_slashing_report(dispute_proof, key_ownership_proof)
+}
+
+/// Implementation for `submit_approvals_tally` function from the runtime API
+pub fn submit_approvals_tally<T: reward_points::Config>(
+	tally: vstaging::ApprovalsTally,
+	signature: ValidatorSignature,
+) {
+	<reward_points::Pallet<T>>::submit_approvals_tally(tally, signature)
+}
+
+/// Implementation for `pending_configs` function from the runtime API
+pub fn pending_configs<T: configuration::Config>(
+) -> Vec<(SessionIndex, Vec<HostConfigurationChange<T::BlockNumber>>)> {
+	<configuration::Pallet<T>>::pending_configs()
+}
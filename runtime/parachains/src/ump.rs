@@ -107,13 +107,16 @@ impl<XcmExecutor: xcm::latest::ExecuteXcm<C::RuntimeCall>, C: Config> UmpSink
 			VersionedXcm,
 		};
 
-		let id = upward_message_id(data);
+		let hash = upward_message_id(data);
 		let maybe_msg_and_weight = VersionedXcm::<C::RuntimeCall>::decode_all_with_depth_limit(
 			xcm::MAX_XCM_DECODE_DEPTH,
 			&mut data,
 		)
 		.map(|xcm| {
 			(
+				// A message which sets a topic is identified by it, so that it can be followed
+				// across hops.
+				xcm.topic().unwrap_or(hash),
 				Xcm::<C::RuntimeCall>::try_from(xcm),
 				// NOTE: We are overestimating slightly here.
 				// The benchmark is timing this whole function with different message sizes and a NOOP extrinsic to
@@ -124,14 +127,14 @@ impl<XcmExecutor: xcm::latest::ExecuteXcm<C::RuntimeCall>, C: Config> UmpSink
 		});
 		match maybe_msg_and_weight {
 			Err(_) => {
-				Pallet::<C>::deposit_event(Event::InvalidFormat(id));
+				Pallet::<C>::deposit_event(Event::InvalidFormat(hash));
 				Ok(Weight::zero())
 			},
-			Ok((Err(()), weight_used)) => {
+			Ok((id, Err(()), weight_used)) => {
 				Pallet::<C>::deposit_event(Event::UnsupportedVersion(id));
 				Ok(weight_used)
 			},
-			Ok((Ok(xcm_message), weight_used)) => {
+			Ok((id, Ok(xcm_message), weight_used)) => {
 				let xcm_junction = Junction::Parachain(origin.into());
 				let outcome =
 					XcmExecutor::execute_xcm(xcm_junction, xcm_message, max_weight.ref_time());
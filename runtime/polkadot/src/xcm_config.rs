@@ -18,20 +18,24 @@
 
 use super::{
 	parachains_origin, AccountId, Balances, CouncilCollective, ParaId, Runtime, RuntimeCall,
-	RuntimeEvent, RuntimeOrigin, WeightToFee, XcmPallet,
+	RuntimeEvent, RuntimeOrigin, TransactionByteFee, Treasury, WeightToFee, XcmPallet, CENTS,
 };
 use frame_support::{
 	match_types, parameter_types,
-	traits::{Everything, Nothing},
+	traits::{ConstU32, Everything, Nothing},
+};
+use pallet_xcm::XcmPassthrough;
+use runtime_common::{
+	xcm_sender::{self, ConstantBaseFee, SizeBasedPrice},
+	ToAuthor,
 };
-use runtime_common::{xcm_sender, ToAuthor};
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	Account32Hash, AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, BackingToPlurality, ChildParachainAsNative,
 	ChildParachainConvertsVia, CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds,
-	IsConcrete, LocationInverter, SignedAccountId32AsNative, SignedToAccountId32,
-	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+	IsChildSystemParachain, IsConcrete, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit, UsingComponents, XcmFeesToAccount,
 };
 
 parameter_types! {
@@ -41,9 +45,9 @@ parameter_types! {
 	pub const DotLocation: MultiLocation = Here.into();
 	/// The Polkadot network ID. This is named.
 	pub const PolkadotNetwork: NetworkId = NetworkId::Polkadot;
-	/// Our XCM location ancestry - i.e. what, if anything, `Parent` means evaluated in our context. Since
-	/// Polkadot is a top-level relay-chain, there is no ancestry.
-	pub const Ancestry: MultiLocation = Here.into();
+	/// Our location in the universe of consensus systems. Since Polkadot is a top-level relay-chain
+	/// with its own consensus, it's just the named Polkadot network.
+	pub const UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(NetworkId::Polkadot));
 	/// The check account, which holds any native assets that have been teleported out and not back in (yet).
 	pub CheckAccount: AccountId = XcmPallet::check_account();
 }
@@ -55,6 +59,8 @@ pub type SovereignAccountOf = (
 	ChildParachainConvertsVia<ParaId, AccountId>,
 	// We can directly alias an `AccountId32` into a local account.
 	AccountId32Aliases<PolkadotNetwork, AccountId>,
+	// Any other location, such as an account on a child parachain, gets a hashed account.
+	Account32Hash<PolkadotNetwork, AccountId>,
 );
 
 /// Our asset transactor. This is what allows us to interact with the runtime assets from the point of
@@ -87,6 +93,9 @@ type LocalOriginConverter = (
 	// If the origin kind is `Native` and the XCM origin is the `AccountId32` location, then it can
 	// be expressed using the `Signed` origin variant.
 	SignedAccountId32AsNative<PolkadotNetwork, RuntimeOrigin>,
+	// If the origin kind is `Xcm`, then the XCM origin is passed through as is, for calls that
+	// accept remote locations such as crowdloan contributions.
+	XcmPassthrough<RuntimeOrigin>,
 );
 
 parameter_types! {
@@ -101,9 +110,36 @@ parameter_types! {
 /// individual routers.
 pub type XcmRouter = (
 	// Only one router so far - use DMP to communicate with child parachains.
-	xcm_sender::ChildParachainRouter<Runtime, XcmPallet>,
+	xcm_sender::ChildParachainRouter<Runtime, XcmPallet, PriceForChildParachainDelivery>,
 );
 
+parameter_types! {
+	/// The asset in which the delivery fees of messages sent from this chain are paid.
+	pub const FeeAssetId: AssetId = Concrete(DotLocation::get());
+	/// The base fee for delivering a downward message to any child parachain.
+	pub const BaseDeliveryFee: u128 = 3 * CENTS;
+	/// Delivery fees are deposited into the treasury.
+	pub TreasuryLocation: Option<MultiLocation> = Some(
+		AccountId32 { network: PolkadotNetwork::get(), id: Treasury::account_id().into() }.into()
+	);
+}
+
+/// The price of delivering a downward message: the base fee, plus the transaction byte fee for every
+/// byte of the message.
+pub type PriceForChildParachainDelivery =
+	SizeBasedPrice<FeeAssetId, ConstantBaseFee<BaseDeliveryFee>, TransactionByteFee>;
+
+match_types! {
+	pub type LocalGovernance: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 0, interior: Here } |
+		MultiLocation { parents: 0, interior: X1(Plurality { .. }) }
+	};
+}
+
+/// Locations which don't pay for the delivery of the messages they send: the relay chain itself,
+/// its governance bodies and the system parachains.
+pub type WaivedLocations = (LocalGovernance, IsChildSystemParachain<ParaId>);
+
 parameter_types! {
 	pub const Polkadot: MultiAssetFilter = Wild(AllOf { fun: WildFungible, id: Concrete(DotLocation::get()) });
 	pub const PolkadotForStatemint: (MultiAssetFilter, MultiLocation) = (Polkadot::get(), Parachain(1000).into());
@@ -141,7 +177,7 @@ impl xcm_executor::Config for XcmConfig {
 	// Polkadot Relay recognises no chains which act as reserves.
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
 	// The weight trader piggybacks on the existing transaction-fee conversion logic.
@@ -150,6 +186,11 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	// Delivery fees go to the treasury.
+	type FeeManager = XcmFeesToAccount<WaivedLocations, LocalAssetTransactor, TreasuryLocation>;
+	type AssetLocker = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 parameter_types! {
@@ -188,9 +229,10 @@ impl pallet_xcm::Config for Runtime {
 	type XcmTeleportFilter = Everything; // == Allow All
 	type XcmReserveTransferFilter = Everything; // == Allow All
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = AdvertisedXcmVersion;
+	type MaxIndexedAssetTraps = ConstU32<16>;
 }
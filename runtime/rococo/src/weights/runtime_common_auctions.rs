@@ -46,28 +46,32 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> runtime_common::auctions::WeightInfo for WeightInfo<T> {
 	// Storage: Auctions AuctionInfo (r:1 w:1)
 	// Storage: Auctions AuctionCounter (r:1 w:1)
+	// Storage: Auctions BidHistory (r:0 w:1)
+	// Storage: Auctions WinningSample (r:0 w:1)
 	fn new_auction() -> Weight {
 		Weight::from_ref_time(16_735_000 as u64)
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
-			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
 	}
 	// Storage: Paras ParaLifecycles (r:1 w:0)
 	// Storage: Auctions AuctionCounter (r:1 w:0)
 	// Storage: Auctions AuctionInfo (r:1 w:0)
 	// Storage: Slots Leases (r:1 w:0)
 	// Storage: Auctions Winning (r:1 w:1)
+	// Storage: Auctions BidHistory (r:1 w:1)
 	// Storage: Auctions ReservedAmounts (r:2 w:2)
 	// Storage: System Account (r:1 w:1)
 	fn bid() -> Weight {
 		Weight::from_ref_time(71_032_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(4 as u64))
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
 	}
 	// Storage: Auctions AuctionInfo (r:1 w:1)
 	// Storage: Babe NextRandomness (r:1 w:0)
 	// Storage: Babe EpochStart (r:1 w:0)
 	// Storage: Auctions AuctionCounter (r:1 w:0)
 	// Storage: Auctions Winning (r:600 w:600)
+	// Storage: Auctions WinningSample (r:0 w:1)
 	// Storage: Auctions ReservedAmounts (r:37 w:36)
 	// Storage: System Account (r:36 w:36)
 	// Storage: Slots Leases (r:7 w:7)
@@ -78,15 +82,17 @@ impl<T: frame_system::Config> runtime_common::auctions::WeightInfo for WeightInf
 	fn on_initialize() -> Weight {
 		Weight::from_ref_time(2_877_003_000 as u64)
 			.saturating_add(T::DbWeight::get().reads(688 as u64))
-			.saturating_add(T::DbWeight::get().writes(683 as u64))
+			.saturating_add(T::DbWeight::get().writes(684 as u64))
 	}
 	// Storage: Auctions ReservedAmounts (r:37 w:36)
 	// Storage: System Account (r:36 w:36)
 	// Storage: Auctions Winning (r:0 w:600)
 	// Storage: Auctions AuctionInfo (r:0 w:1)
+	// Storage: Auctions AuctionCounter (r:1 w:0)
+	// Storage: Auctions BidHistory (r:0 w:1)
 	fn cancel_auction() -> Weight {
 		Weight::from_ref_time(1_167_630_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(73 as u64))
-			.saturating_add(T::DbWeight::get().writes(673 as u64))
+			.saturating_add(T::DbWeight::get().reads(74 as u64))
+			.saturating_add(T::DbWeight::get().writes(674 as u64))
 	}
 }
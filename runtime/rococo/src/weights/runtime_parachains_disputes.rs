@@ -49,4 +49,13 @@ impl<T: frame_system::Config> runtime_parachains::disputes::WeightInfo for Weigh
 		Weight::from_ref_time(4_507_000 as u64)
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: ParasDisputes ActiveDisputes (r:1 w:1)
+	/// The range of component `d` is `[1, 64]`.
+	fn evict_active_dispute(d: u32, ) -> Weight {
+		Weight::from_ref_time(10_204_000 as u64)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_ref_time(70_000 as u64).saturating_mul(d as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
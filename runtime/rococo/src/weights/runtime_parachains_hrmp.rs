@@ -175,4 +175,61 @@ impl<T: frame_system::Config> runtime_parachains::hrmp::WeightInfo for WeightInf
 			.saturating_add(T::DbWeight::get().reads(13 as u64))
 			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
+	// Storage: Hrmp HrmpOpenChannelRequests (r:1 w:1)
+	// Storage: Paras ParaLifecycles (r:1 w:0)
+	// Storage: Hrmp HrmpIngressChannelsIndex (r:1 w:0)
+	// Storage: Hrmp HrmpAcceptedChannelRequestCount (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
+	fn hrmp_accept_open_channel_with_fee() -> Weight {
+		Weight::from_ref_time(39_314_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Hrmp HrmpChannels (r:1 w:0)
+	// Storage: Hrmp HrmpCloseChannelRequests (r:1 w:0)
+	// Storage: Hrmp HrmpChannelUpdateRequests (r:1 w:1)
+	// Storage: Configuration ActiveConfig (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Hrmp HrmpChannelUpdateRequestsList (r:1 w:1)
+	fn hrmp_request_channel_update() -> Weight {
+		Weight::from_ref_time(33_412_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Hrmp HrmpChannelUpdateRequests (r:1 w:1)
+	// Storage: Hrmp HrmpChannels (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn hrmp_accept_channel_update() -> Weight {
+		Weight::from_ref_time(27_105_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Hrmp HrmpChannelUpdateRequestsList (r:1 w:1)
+	// Storage: Hrmp HrmpChannelUpdateRequests (r:1 w:1)
+	// Storage: Hrmp HrmpChannels (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn hrmp_cancel_channel_update(c: u32, ) -> Weight {
+		Weight::from_ref_time(31_270_000 as u64)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_ref_time(89_000 as u64).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Paras ParaLifecycles (r:2 w:0)
+	// Storage: Hrmp HrmpOpenChannelRequests (r:2 w:2)
+	// Storage: Hrmp HrmpChannels (r:2 w:0)
+	// Storage: Hrmp HrmpEgressChannelsIndex (r:2 w:0)
+	// Storage: Hrmp HrmpOpenChannelRequestCount (r:2 w:2)
+	// Storage: Hrmp HrmpOpenChannelRequestsList (r:1 w:1)
+	// Storage: Dmp DownwardMessageQueueHeads (r:2 w:2)
+	// Storage: Dmp DownwardMessageQueues (r:2 w:2)
+	// Storage: Hrmp HrmpIngressChannelsIndex (r:2 w:0)
+	// Storage: Hrmp HrmpAcceptedChannelRequestCount (r:2 w:2)
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	fn force_open_hrmp_channel_pair() -> Weight {
+		Weight::from_ref_time(198_436_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
 }
@@ -58,13 +58,16 @@ impl<T: frame_system::Config> runtime_parachains::paras::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(4 as u64))
 			.saturating_add(T::DbWeight::get().writes(6 as u64))
 	}
+	// Storage: Paras HeadHistoryCursors (r:1 w:1)
+	// Storage: Paras HeadHistory (r:0 w:1)
 	// Storage: Paras Heads (r:0 w:1)
 	/// The range of component `s` is `[1, 1048576]`.
 	fn force_set_current_head(s: u32, ) -> Weight {
 		Weight::from_ref_time(0 as u64)
 			// Standard Error: 0
 			.saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(s as u64))
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
 	// Storage: Configuration ActiveConfig (r:1 w:0)
 	// Storage: Paras FutureCodeHash (r:1 w:1)
@@ -85,6 +88,8 @@ impl<T: frame_system::Config> runtime_parachains::paras::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(9 as u64))
 			.saturating_add(T::DbWeight::get().writes(8 as u64))
 	}
+	// Storage: Paras HeadHistoryCursors (r:1 w:1)
+	// Storage: Paras HeadHistory (r:0 w:1)
 	// Storage: Paras FutureCodeUpgrades (r:1 w:0)
 	// Storage: Paras Heads (r:0 w:1)
 	// Storage: Paras UpgradeGoAheadSignal (r:0 w:1)
@@ -93,8 +98,8 @@ impl<T: frame_system::Config> runtime_parachains::paras::WeightInfo for WeightIn
 		Weight::from_ref_time(0 as u64)
 			// Standard Error: 0
 			.saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(s as u64))
-			.saturating_add(T::DbWeight::get().reads(1 as u64))
-			.saturating_add(T::DbWeight::get().writes(2 as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
 	}
 	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
 	// Storage: Paras ActionsQueue (r:1 w:1)
@@ -103,6 +108,22 @@ impl<T: frame_system::Config> runtime_parachains::paras::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Paras ParaLifecycles (r:1 w:0)
+	// Storage: Paras UpgradeFreezes (r:1 w:1)
+	// Storage: Paras UpgradeRestrictionSignal (r:0 w:1)
+	fn force_freeze_code_upgrades() -> Weight {
+		Weight::from_ref_time(25_481_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Paras UpgradeFreezes (r:1 w:1)
+	// Storage: Paras UpgradeCooldowns (r:1 w:0)
+	// Storage: Paras UpgradeRestrictionSignal (r:0 w:1)
+	fn force_unfreeze_code_upgrades() -> Weight {
+		Weight::from_ref_time(24_127_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
 	// Storage: Paras PvfActiveVoteMap (r:1 w:0)
 	// Storage: Paras CodeByHash (r:1 w:1)
 	/// The range of component `c` is `[1, 3145728]`.
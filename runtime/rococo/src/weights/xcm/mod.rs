@@ -165,13 +165,8 @@ impl<Call> XcmWeightInfo<Call> for RococoXcmWeight<Call> {
 	) -> XCMWeight {
 		assets.weigh_multi_assets(XcmBalancesWeight::<Runtime>::initiate_teleport())
 	}
-	fn query_holding(
-		_query_id: &u64,
-		_dest: &MultiLocation,
-		_assets: &MultiAssetFilter,
-		_max_response_weight: &u64,
-	) -> XCMWeight {
-		XcmGeneric::<Runtime>::query_holding().ref_time()
+	fn report_holding(_response_info: &QueryResponseInfo, _assets: &MultiAssetFilter) -> XCMWeight {
+		XcmGeneric::<Runtime>::report_holding().ref_time()
 	}
 	fn buy_execution(_fees: &MultiAsset, _weight_limit: &WeightLimit) -> XCMWeight {
 		XcmGeneric::<Runtime>::buy_execution().ref_time()
@@ -200,4 +195,41 @@ impl<Call> XcmWeightInfo<Call> for RococoXcmWeight<Call> {
 	fn unsubscribe_version() -> XCMWeight {
 		XcmGeneric::<Runtime>::unsubscribe_version().ref_time()
 	}
+	fn expect_asset(_assets: &MultiAssets) -> XCMWeight {
+		XcmGeneric::<Runtime>::expect_asset().ref_time()
+	}
+	fn expect_origin(_origin: &Option<MultiLocation>) -> XCMWeight {
+		XcmGeneric::<Runtime>::expect_origin().ref_time()
+	}
+	fn expect_error(_error: &Option<(u32, XcmError)>) -> XCMWeight {
+		XcmGeneric::<Runtime>::expect_error().ref_time()
+	}
+	fn universal_origin(_new_global: &Junction) -> XCMWeight {
+		// Universal origins are not supported.
+		Weight::MAX.ref_time()
+	}
+	fn export_message(
+		_network: &NetworkId,
+		_destination: &InteriorMultiLocation,
+		_xcm: &Xcm<()>,
+	) -> XCMWeight {
+		// Exporting messages is not supported.
+		Weight::MAX.ref_time()
+	}
+	fn lock_asset(_asset: &MultiAsset, _unlocker: &MultiLocation) -> XCMWeight {
+		// Asset locking is not supported.
+		Weight::MAX.ref_time()
+	}
+	fn unlock_asset(_asset: &MultiAsset, _target: &MultiLocation) -> XCMWeight {
+		Weight::MAX.ref_time()
+	}
+	fn note_unlockable(_asset: &MultiAsset, _owner: &MultiLocation) -> XCMWeight {
+		Weight::MAX.ref_time()
+	}
+	fn request_unlock(_asset: &MultiAsset, _locker: &MultiLocation) -> XCMWeight {
+		Weight::MAX.ref_time()
+	}
+	fn set_topic(_topic: &[u8; 32]) -> XCMWeight {
+		XcmGeneric::<Runtime>::set_topic().ref_time()
+	}
 }
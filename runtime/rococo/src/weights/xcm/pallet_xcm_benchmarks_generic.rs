@@ -51,7 +51,7 @@ impl<T: frame_system::Config> WeightInfo<T> {
 	// Storage: Configuration ActiveConfig (r:1 w:0)
 	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
 	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
-	pub(crate) fn query_holding() -> Weight {
+	pub(crate) fn report_holding() -> Weight {
 		Weight::from_ref_time(21_822_000 as u64)
 			.saturating_add(T::DbWeight::get().reads(6 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
@@ -133,4 +133,16 @@ impl<T: frame_system::Config> WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(6 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	pub(crate) fn expect_asset() -> Weight {
+		Weight::from_ref_time(4_975_000 as u64)
+	}
+	pub(crate) fn expect_origin() -> Weight {
+		Weight::from_ref_time(3_279_000 as u64)
+	}
+	pub(crate) fn expect_error() -> Weight {
+		Weight::from_ref_time(3_157_000 as u64)
+	}
+	pub(crate) fn set_topic() -> Weight {
+		Weight::from_ref_time(3_093_000 as u64)
+	}
 }
@@ -18,18 +18,25 @@
 
 use super::{
 	parachains_origin, AccountId, Balances, CouncilCollective, ParaId, Runtime, RuntimeCall,
-	RuntimeEvent, RuntimeOrigin, WeightToFee, XcmPallet,
+	RuntimeEvent, RuntimeOrigin, TransactionByteFee, Treasury, WeightToFee, XcmPallet, CENTS,
+};
+use frame_support::{
+	match_types, parameter_types,
+	traits::{ConstU32, Everything, Nothing},
+};
+use pallet_xcm::XcmPassthrough;
+use runtime_common::{
+	xcm_sender::{self, ConstantBaseFee, SizeBasedPrice},
+	ToAuthor,
 };
-use frame_support::{match_types, parameter_types, traits::Everything};
-use runtime_common::{xcm_sender, ToAuthor};
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	Account32Hash, AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, BackingToPlurality,
 	ChildParachainAsNative, ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
 	CurrencyAdapter as XcmCurrencyAdapter, FixedWeightBounds, IsChildSystemParachain, IsConcrete,
-	LocationInverter, SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
-	TakeWeightCredit, UsingComponents, WeightInfoBounds,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	UsingComponents, WeightInfoBounds, XcmFeesToAccount,
 };
 
 parameter_types! {
@@ -40,9 +47,9 @@ parameter_types! {
 	/// The Rococo network ID. This is named.
 	pub RococoNetwork: NetworkId =
 		NetworkId::Named(b"Rococo".to_vec().try_into().expect("shorter than length limit; qed"));
-	/// Our XCM location ancestry - i.e. what, if anything, `Parent` means evaluated in our context. Since
-	/// Rococo is a top-level relay-chain, there is no ancestry.
-	pub const Ancestry: MultiLocation = Here.into();
+	/// Our location in the universe of consensus systems. Since Rococo is a top-level relay-chain
+	/// with its own consensus, it's just the named Rococo network.
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(RococoNetwork::get()));
 	/// The check account, which holds any native assets that have been teleported out and not back in (yet).
 	pub CheckAccount: AccountId = XcmPallet::check_account();
 }
@@ -54,6 +61,8 @@ pub type SovereignAccountOf = (
 	ChildParachainConvertsVia<ParaId, AccountId>,
 	// We can directly alias an `AccountId32` into a local account.
 	AccountId32Aliases<RococoNetwork, AccountId>,
+	// Any other location, such as an account on a child parachain, gets a hashed account.
+	Account32Hash<RococoNetwork, AccountId>,
 );
 
 /// Our asset transactor. This is what allows us to interest with the runtime facilities from the point of
@@ -83,6 +92,9 @@ type LocalOriginConverter = (
 	SignedAccountId32AsNative<RococoNetwork, RuntimeOrigin>,
 	// A system child parachain, expressed as a Superuser, converts to the `Root` origin.
 	ChildSystemParachainAsSuperuser<ParaId, RuntimeOrigin>,
+	// The XCM origin passed through as is, for calls that accept remote locations such as
+	// crowdloan contributions.
+	XcmPassthrough<RuntimeOrigin>,
 );
 
 parameter_types! {
@@ -96,9 +108,36 @@ parameter_types! {
 /// individual routers.
 pub type XcmRouter = (
 	// Only one router so far - use DMP to communicate with child parachains.
-	xcm_sender::ChildParachainRouter<Runtime, XcmPallet>,
+	xcm_sender::ChildParachainRouter<Runtime, XcmPallet, PriceForChildParachainDelivery>,
 );
 
+parameter_types! {
+	/// The asset in which the delivery fees of messages sent from this chain are paid.
+	pub const FeeAssetId: AssetId = Concrete(RocLocation::get());
+	/// The base fee for delivering a downward message to any child parachain.
+	pub const BaseDeliveryFee: u128 = 3 * CENTS;
+	/// Delivery fees are deposited into the treasury.
+	pub TreasuryLocation: Option<MultiLocation> = Some(
+		AccountId32 { network: RococoNetwork::get(), id: Treasury::account_id().into() }.into()
+	);
+}
+
+/// The price of delivering a downward message: the base fee, plus the transaction byte fee for every
+/// byte of the message.
+pub type PriceForChildParachainDelivery =
+	SizeBasedPrice<FeeAssetId, ConstantBaseFee<BaseDeliveryFee>, TransactionByteFee>;
+
+match_types! {
+	pub type LocalGovernance: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 0, interior: Here } |
+		MultiLocation { parents: 0, interior: X1(Plurality { .. }) }
+	};
+}
+
+/// Locations which don't pay for the delivery of the messages they send: the relay chain itself,
+/// its governance bodies and the system parachains.
+pub type WaivedLocations = (LocalGovernance, IsChildSystemParachain<ParaId>);
+
 parameter_types! {
 	pub const Rococo: MultiAssetFilter = Wild(AllOf { fun: WildFungible, id: Concrete(RocLocation::get()) });
 	pub const Statemine: MultiLocation = Parachain(1000).into();
@@ -151,7 +190,7 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = WeightInfoBounds<
 		crate::weights::xcm::RococoXcmWeight<RuntimeCall>,
@@ -164,6 +203,11 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	// Delivery fees go to the treasury.
+	type FeeManager = XcmFeesToAccount<WaivedLocations, LocalAssetTransactor, TreasuryLocation>;
+	type AssetLocker = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 parameter_types! {
@@ -204,9 +248,10 @@ impl pallet_xcm::Config for Runtime {
 	// transfer.
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type MaxIndexedAssetTraps = ConstU32<16>;
 }
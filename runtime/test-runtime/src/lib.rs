@@ -26,9 +26,11 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use polkadot_runtime_parachains::{
 	configuration as parachains_configuration, disputes as parachains_disputes,
+	disputes::slashing as parachains_slashing,
 	dmp as parachains_dmp, hrmp as parachains_hrmp, inclusion as parachains_inclusion,
 	initializer as parachains_initializer, origin as parachains_origin, paras as parachains_paras,
-	paras_inherent as parachains_paras_inherent, runtime_api_impl::v2 as runtime_impl,
+	paras_inherent as parachains_paras_inherent, reward_points as parachains_reward_points,
+	runtime_api_impl::{v2 as runtime_impl, vstaging as staging_runtime_impl},
 	scheduler as parachains_scheduler, session_info as parachains_session_info,
 	shared as parachains_shared, ump as parachains_ump,
 };
@@ -45,14 +47,15 @@ use pallet_session::historical as session_historical;
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
 use polkadot_runtime_parachains::reward_points::RewardValidatorsWithEraPoints;
 use primitives::v2::{
-	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CommittedCandidateReceipt,
-	CoreState, GroupRotationInfo, Hash as HashT, Id as ParaId, InboundDownwardMessage,
-	InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption, PersistedValidationData,
-	ScrapedOnChainVotes, SessionInfo as SessionInfoData, Signature, ValidationCode,
-	ValidationCodeHash, ValidatorId, ValidatorIndex,
+	AccountId, AccountIndex, Balance, BlockNumber, CandidateEvent, CandidateHash,
+	CommittedCandidateReceipt, CoreState, DisputeState, GroupRotationInfo, Hash as HashT,
+	Id as ParaId, InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce,
+	OccupiedCoreAssumption, PersistedValidationData, ScrapedOnChainVotes,
+	SessionInfo as SessionInfoData, Signature, ValidationCode, ValidationCodeHash, ValidatorId,
+	ValidatorIndex, ValidatorSignature, PARACHAIN_KEY_TYPE_ID,
 };
 use runtime_common::{
-	claims, impl_runtime_weights, paras_sudo_wrapper, BlockHashCount, BlockLength,
+	auctions, claims, impl_runtime_weights, paras_sudo_wrapper, BlockHashCount, BlockLength,
 	SlowAdjustingFeeUpdate,
 };
 use sp_core::OpaqueMetadata;
@@ -492,14 +495,48 @@ impl parachains_inclusion::Config for Runtime {
 	type RewardValidators = RewardValidatorsWithEraPoints<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxActiveDisputesPerSession: u32 = 64;
+}
+
 impl parachains_disputes::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = ();
-	type SlashingHandler = ();
+	type SlashingHandler = parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>;
+	type MaxActiveDisputesPerSession = MaxActiveDisputesPerSession;
 	type WeightInfo = parachains_disputes::TestWeightInfo;
 }
 
+parameter_types! {
+	pub storage ReportLongevity: u64 = EpochDuration::get() * 10;
+}
+
+impl parachains_slashing::Config for Runtime {
+	type KeyOwnerProofSystem = Historical;
+	type KeyOwnerProof =
+		<Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(KeyTypeId, ValidatorId)>>::Proof;
+	type KeyOwnerIdentification = <Self::KeyOwnerProofSystem as KeyOwnerProofSystem<(
+		KeyTypeId,
+		ValidatorId,
+	)>>::IdentificationTuple;
+	type HandleReports = parachains_slashing::SlashingReportHandler<
+		Self::KeyOwnerIdentification,
+		Offences,
+		ReportLongevity,
+	>;
+	type WeightInfo = parachains_slashing::TestWeightInfo;
+	type BenchmarkingConfig = parachains_slashing::BenchConfig<1000>;
+}
+
+impl parachains_reward_points::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RewardApprovals = RewardValidatorsWithEraPoints<Runtime>;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = parachains_reward_points::TestWeightInfo;
+}
+
 impl parachains_paras_inherent::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = parachains_paras_inherent::TestWeightInfo;
 }
 
@@ -515,16 +552,21 @@ impl parachains_session_info::Config for Runtime {
 
 parameter_types! {
 	pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const ParasHeadHistoryLength: u32 = 16;
 }
 
 impl parachains_paras::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = parachains_paras::TestWeightInfo;
 	type UnsignedPriority = ParasUnsignedPriority;
+	type HeadHistoryLength = ParasHeadHistoryLength;
 	type NextSessionRotation = Babe;
+	type OnboardingPvfCheckHandler = ();
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
 
 parameter_types! {
 	pub const FirstMessageFactorPercent: u64 = 100;
@@ -552,7 +594,7 @@ impl pallet_xcm::Config for Runtime {
 	// is `XcmExecutor`, which will be used in unit tests located in xcm-executor.
 	type RuntimeEvent = RuntimeEvent;
 	type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
-	type LocationInverter = xcm_config::InvertNothing;
+	type UniversalLocation = xcm_config::UniversalLocation;
 	type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
 	type Weigher = xcm_builder::FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
 	type XcmRouter = xcm_config::DoNothingRouter;
@@ -564,12 +606,14 @@ impl pallet_xcm::Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type MaxIndexedAssetTraps = frame_support::traits::ConstU32<16>;
 }
 
 impl parachains_hrmp::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type ChannelDepositPerByte = frame_support::traits::ConstU128<0>;
 	type WeightInfo = parachains_hrmp::TestWeightInfo;
 }
 
@@ -700,7 +744,7 @@ construct_runtime! {
 		// Parachains runtime modules
 		Configuration: parachains_configuration::{Pallet, Call, Storage, Config<T>},
 		ParaInclusion: parachains_inclusion::{Pallet, Call, Storage, Event<T>},
-		ParaInherent: parachains_paras_inherent::{Pallet, Call, Storage, Inherent},
+		ParaInherent: parachains_paras_inherent::{Pallet, Call, Storage, Inherent, Event},
 		Initializer: parachains_initializer::{Pallet, Call, Storage},
 		Paras: parachains_paras::{Pallet, Call, Storage, Event, ValidateUnsigned},
 		ParasShared: parachains_shared::{Pallet, Call, Storage},
@@ -710,9 +754,11 @@ construct_runtime! {
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage},
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>},
 		Ump: parachains_ump::{Pallet, Call, Storage, Event},
-		Dmp: parachains_dmp::{Pallet, Call, Storage},
+		Dmp: parachains_dmp::{Pallet, Call, Storage, Event},
 		Xcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
 		ParasDisputes: parachains_disputes::{Pallet, Storage, Event<T>},
+		ParasSlashing: parachains_slashing::{Pallet, Call, Storage, ValidateUnsigned},
+		ParasRewardPoints: parachains_reward_points::{Pallet, Call, Storage, Event, ValidateUnsigned},
 
 		Sudo: pallet_sudo::{Pallet, Call, Storage, Config<T>, Event<T>},
 
@@ -751,7 +797,11 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
+
+/// All migrations that will run on the next runtime upgrade.
+pub type Migrations = (parachains_hrmp::migration::v1::MigrateToV1<Runtime>,);
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 
@@ -822,6 +872,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl primitives::runtime_api::ParachainHost<Block, Hash, BlockNumber> for Runtime {
 		fn validators() -> Vec<ValidatorId> {
 			runtime_impl::validators::<Runtime>()
@@ -916,6 +967,112 @@ sp_api::impl_runtime_apis! {
 		{
 			runtime_impl::validation_code_hash::<Runtime>(para_id, assumption)
 		}
+
+		fn disputes() -> Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)> {
+			staging_runtime_impl::get_session_disputes::<Runtime>()
+		}
+
+		fn hrmp_topology() -> primitives::vstaging::HrmpTopology<BlockNumber> {
+			staging_runtime_impl::hrmp_topology::<Runtime>()
+		}
+
+		fn para_code_upgrade_pipeline(
+			para_id: ParaId,
+		) -> Option<primitives::vstaging::CodeUpgradePipeline<BlockNumber>> {
+			staging_runtime_impl::para_code_upgrade_pipeline::<Runtime>(para_id)
+		}
+
+		fn para_head_history(para_id: ParaId) -> Vec<(BlockNumber, Hash)> {
+			staging_runtime_impl::para_head_history::<Runtime>(para_id)
+		}
+
+		fn unapplied_slashes(
+		) -> Vec<(SessionIndex, CandidateHash, primitives::vstaging::slashing::PendingSlashes)> {
+			staging_runtime_impl::unapplied_slashes::<Runtime>()
+		}
+
+		fn key_ownership_proof(
+			validator_id: ValidatorId,
+		) -> Option<primitives::vstaging::slashing::OpaqueKeyOwnershipProof> {
+			Historical::prove((PARACHAIN_KEY_TYPE_ID, validator_id))
+				.map(|p| p.encode())
+				.map(primitives::vstaging::slashing::OpaqueKeyOwnershipProof::new)
+		}
+
+		fn submit_report_dispute_lost(
+			dispute_proof: primitives::vstaging::slashing::DisputeProof,
+			key_ownership_proof: primitives::vstaging::slashing::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			let key_owner_proof = key_ownership_proof.decode()?;
+
+			staging_runtime_impl::submit_unsigned_slashing_report::<Runtime>(
+				dispute_proof,
+				key_owner_proof,
+			)
+		}
+
+		fn submit_approvals_tally(
+			tally: primitives::vstaging::ApprovalsTally,
+			signature: ValidatorSignature,
+		) {
+			staging_runtime_impl::submit_approvals_tally::<Runtime>(tally, signature)
+		}
+
+		fn assigned_slots_schedule(
+			_lease_periods: u32,
+		) -> Vec<primitives::vstaging::AssignedSlotsSchedule<BlockNumber>> {
+			// The test runtime has no assigned slots.
+			Vec::new()
+		}
+
+		fn pending_configs(
+		) -> Vec<(SessionIndex, Vec<primitives::vstaging::HostConfigurationChange<BlockNumber>>)> {
+			staging_runtime_impl::pending_configs::<Runtime>()
+		}
+	}
+
+	impl auctions::AuctionsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn auction_state(
+			_first_sample: BlockNumber,
+			_max_samples: u32,
+		) -> Option<auctions::AuctionState<AccountId, Balance, BlockNumber>> {
+			// The test runtime has no slot auctions.
+			None
+		}
+	}
+
+	impl pallet_xcm::XcmDryRunApi<Block, RuntimeEvent> for Runtime {
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::XcmDryRunEffects<RuntimeEvent>, pallet_xcm::XcmDryRunApiError> {
+			Xcm::dry_run_xcm::<xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets() -> Vec<xcm::latest::AssetId> {
+			Xcm::query_acceptable_payment_assets::<xcm_config::XcmConfig>()
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Weight, pallet_xcm::XcmPaymentApiError> {
+			Xcm::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: xcm::latest::Weight,
+			asset: xcm::latest::AssetId,
+		) -> Result<u128, pallet_xcm::XcmPaymentApiError> {
+			Xcm::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+	}
+
+	impl pallet_xcm::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(origin: xcm::VersionedMultiLocation) -> Vec<xcm::VersionedMultiAssets> {
+			Xcm::trapped_assets(origin)
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -14,16 +14,24 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::{parameter_types, traits::Everything};
-use xcm::latest::{prelude::*, Weight as XCMWeight};
+use frame_support::{
+	parameter_types,
+	traits::{Everything, Nothing},
+};
+use sp_std::prelude::*;
+use xcm::{
+	latest::{prelude::*, Weight as XCMWeight},
+	VersionedMultiLocation, VersionedXcm,
+};
 use xcm_builder::{AllowUnpaidExecutionFrom, FixedWeightBounds, SignedToAccountId32};
 use xcm_executor::{
-	traits::{InvertLocation, TransactAsset, WeightTrader},
+	traits::{InspectMessageQueues, TransactAsset, WeightTrader},
 	Assets,
 };
 
 parameter_types! {
 	pub const OurNetwork: NetworkId = NetworkId::Polkadot;
+	pub const UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(NetworkId::Polkadot));
 	pub const MaxInstructions: u32 = 100;
 }
 
@@ -40,6 +48,16 @@ impl SendXcm for DoNothingRouter {
 		Ok(())
 	}
 }
+impl QuoteDelivery for DoNothingRouter {
+	fn quote_delivery(_dest: &MultiLocation, _msg: &Xcm<()>) -> Option<MultiAssets> {
+		None
+	}
+}
+impl InspectMessageQueues for DoNothingRouter {
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		Vec::new()
+	}
+}
 
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
@@ -66,16 +84,6 @@ impl WeightTrader for DummyWeightTrader {
 	}
 }
 
-pub struct InvertNothing;
-impl InvertLocation for InvertNothing {
-	fn invert_location(_: &MultiLocation) -> sp_std::result::Result<MultiLocation, ()> {
-		Ok(Here.into())
-	}
-	fn ancestry() -> MultiLocation {
-		Here.into()
-	}
-}
-
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
 	type RuntimeCall = super::RuntimeCall;
@@ -84,7 +92,7 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = pallet_xcm::XcmPassthrough<super::RuntimeOrigin>;
 	type IsReserve = ();
 	type IsTeleporter = ();
-	type LocationInverter = InvertNothing;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<super::BaseXcmWeight, super::RuntimeCall, MaxInstructions>;
 	type Trader = DummyWeightTrader;
@@ -92,4 +100,8 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = super::Xcm;
 	type AssetClaims = super::Xcm;
 	type SubscriptionService = super::Xcm;
+	type FeeManager = ();
+	type AssetLocker = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
@@ -41,6 +41,7 @@ use primitives::v2::{
 	InboundDownwardMessage, InboundHrmpMessage, Moment, Nonce, OccupiedCoreAssumption,
 	PersistedValidationData, PvfCheckStatement, ScrapedOnChainVotes, SessionInfo, Signature,
 	ValidationCode, ValidationCodeHash, ValidatorId, ValidatorIndex, ValidatorSignature,
+	PARACHAIN_KEY_TYPE_ID,
 };
 use runtime_common::{
 	assigned_slots, auctions, crowdloan, elections::OnChainAccuracy, impl_runtime_weights,
@@ -908,13 +909,16 @@ impl parachains_inclusion::Config for Runtime {
 
 parameter_types! {
 	pub const ParasUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const ParasHeadHistoryLength: u32 = 256;
 }
 
 impl parachains_paras::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::runtime_parachains_paras::WeightInfo<Runtime>;
 	type UnsignedPriority = ParasUnsignedPriority;
+	type HeadHistoryLength = ParasHeadHistoryLength;
 	type NextSessionRotation = Babe;
+	type OnboardingPvfCheckHandler = Registrar;
 }
 
 parameter_types! {
@@ -930,16 +934,26 @@ impl parachains_ump::Config for Runtime {
 	type WeightInfo = weights::runtime_parachains_ump::WeightInfo<Runtime>;
 }
 
-impl parachains_dmp::Config for Runtime {}
+impl parachains_dmp::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+parameter_types! {
+	/// The deposit per byte of HRMP channel capacity, charged on top of the flat deposits of the
+	/// host configuration.
+	pub const HrmpChannelDepositPerByte: Balance = deposit(0, 1);
+}
 
 impl parachains_hrmp::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type ChannelDepositPerByte = HrmpChannelDepositPerByte;
 	type WeightInfo = weights::runtime_parachains_hrmp::WeightInfo<Self>;
 }
 
 impl parachains_paras_inherent::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = weights::runtime_parachains_paras_inherent::WeightInfo<Runtime>;
 }
 
@@ -972,10 +986,15 @@ impl assigned_slots::Config for Runtime {
 	type MaxTemporarySlotPerLeasePeriod = MaxTemporarySlotPerLeasePeriod;
 }
 
+parameter_types! {
+	pub const MaxActiveDisputesPerSession: u32 = 64;
+}
+
 impl parachains_disputes::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RewardValidators = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
 	type SlashingHandler = parachains_slashing::SlashValidatorsForDisputes<ParasSlashing>;
+	type MaxActiveDisputesPerSession = MaxActiveDisputesPerSession;
 	type WeightInfo = weights::runtime_parachains_disputes::WeightInfo<Runtime>;
 }
 
@@ -996,8 +1015,16 @@ impl parachains_slashing::Config for Runtime {
 	type BenchmarkingConfig = parachains_slashing::BenchConfig<300>;
 }
 
+impl parachains_reward_points::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RewardApprovals = parachains_reward_points::RewardValidatorsWithEraPoints<Runtime>;
+	type UnsignedPriority = ParasUnsignedPriority;
+	type WeightInfo = weights::runtime_parachains_reward_points::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub const ParaDeposit: Balance = 2000 * CENTS;
+	pub const PvfRejectionFee: Balance = 100 * CENTS;
 	pub const DataDepositPerByte: Balance = deposit(0, 1);
 }
 
@@ -1008,6 +1035,7 @@ impl paras_registrar::Config for Runtime {
 	type OnSwap = (Crowdloan, Slots);
 	type ParaDeposit = ParaDeposit;
 	type DataDepositPerByte = DataDepositPerByte;
+	type PvfRejectionFee = PvfRejectionFee;
 	type WeightInfo = weights::runtime_common_paras_registrar::WeightInfo<Runtime>;
 }
 
@@ -1043,6 +1071,9 @@ impl crowdloan::Config for Runtime {
 	type Registrar = Registrar;
 	type Auctioneer = Auctions;
 	type MaxMemoLength = MaxMemoLength;
+	type XcmContributionOrigin = pallet_xcm::EnsureXcm<crowdloan::IsChildParachainContributor>;
+	type LocationToAccountId = xcm_config::LocationConverter;
+	type XcmRouter = xcm_config::XcmRouter;
 	type WeightInfo = weights::runtime_common_crowdloan::WeightInfo<Runtime>;
 }
 
@@ -1052,6 +1083,7 @@ parameter_types! {
 	pub const EndingPeriod: BlockNumber = 5 * DAYS;
 	// ~ 1000 samples per day -> ~ 20 blocks per sample -> 2 minute samples
 	pub const SampleLength: BlockNumber = 2 * MINUTES;
+	pub const MaxBidHistory: u32 = 100;
 }
 
 impl auctions::Config for Runtime {
@@ -1062,6 +1094,7 @@ impl auctions::Config for Runtime {
 	type SampleLength = SampleLength;
 	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type InitiateOrigin = EnsureRoot<AccountId>;
+	type MaxBidHistory = MaxBidHistory;
 	type WeightInfo = weights::runtime_common_auctions::WeightInfo<Runtime>;
 }
 
@@ -1159,16 +1192,17 @@ construct_runtime! {
 		Configuration: parachains_configuration::{Pallet, Call, Storage, Config<T>} = 42,
 		ParasShared: parachains_shared::{Pallet, Call, Storage} = 43,
 		ParaInclusion: parachains_inclusion::{Pallet, Call, Storage, Event<T>} = 44,
-		ParaInherent: parachains_paras_inherent::{Pallet, Call, Storage, Inherent} = 45,
+		ParaInherent: parachains_paras_inherent::{Pallet, Call, Storage, Inherent, Event} = 45,
 		ParaScheduler: parachains_scheduler::{Pallet, Storage} = 46,
 		Paras: parachains_paras::{Pallet, Call, Storage, Event, Config, ValidateUnsigned} = 47,
 		Initializer: parachains_initializer::{Pallet, Call, Storage} = 48,
-		Dmp: parachains_dmp::{Pallet, Call, Storage} = 49,
+		Dmp: parachains_dmp::{Pallet, Call, Storage, Event} = 49,
 		Ump: parachains_ump::{Pallet, Call, Storage, Event} = 50,
 		Hrmp: parachains_hrmp::{Pallet, Call, Storage, Event<T>, Config} = 51,
 		ParaSessionInfo: parachains_session_info::{Pallet, Storage} = 52,
 		ParasDisputes: parachains_disputes::{Pallet, Call, Storage, Event<T>} = 53,
 		ParasSlashing: parachains_slashing::{Pallet, Call, Storage, ValidateUnsigned} = 54,
+		ParasRewardPoints: parachains_reward_points::{Pallet, Call, Storage, Event, ValidateUnsigned} = 55,
 
 		// Parachain Onboarding Pallets. Start indices at 60 to leave room.
 		Registrar: paras_registrar::{Pallet, Call, Storage, Event<T>, Config} = 60,
@@ -1222,8 +1256,11 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
-	(),
+	Migrations,
 >;
+
+/// All migrations that will run on the next runtime upgrade.
+pub type Migrations = (parachains_hrmp::migration::v1::MigrateToV1<Runtime>,);
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 
@@ -1248,6 +1285,7 @@ mod benches {
 		[runtime_parachains::initializer, Initializer]
 		[runtime_parachains::paras, Paras]
 		[runtime_parachains::paras_inherent, ParaInherent]
+		[runtime_parachains::reward_points, ParasRewardPoints]
 		[runtime_parachains::ump, Ump]
 		// Substrate
 		[pallet_bags_list, VoterList]
@@ -1439,6 +1477,119 @@ sp_api::impl_runtime_apis! {
 		fn disputes() -> Vec<(SessionIndex, CandidateHash, DisputeState<BlockNumber>)> {
 			runtime_parachains::runtime_api_impl::vstaging::get_session_disputes::<Runtime>()
 		}
+
+		fn hrmp_topology() -> primitives::vstaging::HrmpTopology<BlockNumber> {
+			runtime_parachains::runtime_api_impl::vstaging::hrmp_topology::<Runtime>()
+		}
+
+		fn para_code_upgrade_pipeline(
+			para_id: ParaId,
+		) -> Option<primitives::vstaging::CodeUpgradePipeline<BlockNumber>> {
+			runtime_parachains::runtime_api_impl::vstaging::para_code_upgrade_pipeline::<Runtime>(
+				para_id,
+			)
+		}
+
+		fn para_head_history(para_id: ParaId) -> Vec<(BlockNumber, Hash)> {
+			runtime_parachains::runtime_api_impl::vstaging::para_head_history::<Runtime>(para_id)
+		}
+
+		fn unapplied_slashes(
+		) -> Vec<(SessionIndex, CandidateHash, primitives::vstaging::slashing::PendingSlashes)> {
+			runtime_parachains::runtime_api_impl::vstaging::unapplied_slashes::<Runtime>()
+		}
+
+		fn key_ownership_proof(
+			validator_id: ValidatorId,
+		) -> Option<primitives::vstaging::slashing::OpaqueKeyOwnershipProof> {
+			use parity_scale_codec::Encode;
+
+			Historical::prove((PARACHAIN_KEY_TYPE_ID, validator_id))
+				.map(|p| p.encode())
+				.map(primitives::vstaging::slashing::OpaqueKeyOwnershipProof::new)
+		}
+
+		fn submit_report_dispute_lost(
+			dispute_proof: primitives::vstaging::slashing::DisputeProof,
+			key_ownership_proof: primitives::vstaging::slashing::OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			let key_owner_proof = key_ownership_proof.decode()?;
+
+			runtime_parachains::runtime_api_impl::vstaging::submit_unsigned_slashing_report::<Runtime>(
+				dispute_proof,
+				key_owner_proof,
+			)
+		}
+
+		fn submit_approvals_tally(
+			tally: primitives::vstaging::ApprovalsTally,
+			signature: ValidatorSignature,
+		) {
+			runtime_parachains::runtime_api_impl::vstaging::submit_approvals_tally::<Runtime>(
+				tally,
+				signature,
+			)
+		}
+
+		fn assigned_slots_schedule(
+			lease_periods: u32,
+		) -> Vec<primitives::vstaging::AssignedSlotsSchedule<BlockNumber>> {
+			AssignedSlots::slot_schedule(lease_periods)
+		}
+
+		fn pending_configs(
+		) -> Vec<(SessionIndex, Vec<primitives::vstaging::HostConfigurationChange<BlockNumber>>)> {
+			runtime_parachains::runtime_api_impl::vstaging::pending_configs::<Runtime>()
+		}
+	}
+
+	impl crowdloan::CrowdloanApi<Block, Balance> for Runtime {
+		fn refund_status(para_id: ParaId) -> Option<crowdloan::RefundStatus<Balance>> {
+			Crowdloan::refund_status(para_id)
+		}
+	}
+
+	impl pallet_xcm::XcmDryRunApi<Block, RuntimeEvent> for Runtime {
+	impl auctions::AuctionsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn auction_state(
+			first_sample: BlockNumber,
+			max_samples: u32,
+		) -> Option<auctions::AuctionState<AccountId, Balance, BlockNumber>> {
+			Auctions::auction_state(first_sample, max_samples)
+		}
+	}
+
+		fn dry_run_xcm(
+			origin: xcm::VersionedMultiLocation,
+			xcm: xcm::VersionedXcm<()>,
+		) -> Result<pallet_xcm::XcmDryRunEffects<RuntimeEvent>, pallet_xcm::XcmDryRunApiError> {
+			XcmPallet::dry_run_xcm::<xcm_config::XcmConfig>(origin, xcm)
+		}
+	}
+
+	impl pallet_xcm::XcmPaymentApi<Block> for Runtime {
+		fn query_acceptable_payment_assets() -> Vec<xcm::latest::AssetId> {
+			XcmPallet::query_acceptable_payment_assets::<xcm_config::XcmConfig>()
+		}
+
+		fn query_xcm_weight(
+			message: xcm::VersionedXcm<()>,
+		) -> Result<xcm::latest::Weight, pallet_xcm::XcmPaymentApiError> {
+			XcmPallet::query_xcm_weight::<xcm_config::XcmConfig>(message)
+		}
+
+		fn query_weight_to_asset_fee(
+			weight: xcm::latest::Weight,
+			asset: xcm::latest::AssetId,
+		) -> Result<u128, pallet_xcm::XcmPaymentApiError> {
+			XcmPallet::query_weight_to_asset_fee::<xcm_config::XcmConfig>(weight, asset)
+		}
+	}
+
+	impl pallet_xcm::TrappedAssetsApi<Block> for Runtime {
+		fn trapped_assets(origin: xcm::VersionedMultiLocation) -> Vec<xcm::VersionedMultiAssets> {
+			XcmPallet::trapped_assets(origin)
+		}
 	}
 
 	impl beefy_primitives::BeefyApi<Block> for Runtime {
@@ -1691,8 +1842,8 @@ sp_api::impl_runtime_apis! {
 			impl runtime_parachains::disputes::slashing::benchmarking::Config for Runtime {}
 
 			use xcm::latest::{
-				AssetId::*, Fungibility::*, Junctions::*, MultiAsset, MultiAssets, MultiLocation,
-				Response,
+				AssetId::*, Fungibility::*, InteriorMultiLocation, Junction, Junctions::*,
+				MultiAsset, MultiAssets, MultiLocation, NetworkId, Response,
 			};
 			use xcm_config::{Westmint, WndLocation};
 
@@ -1758,6 +1909,22 @@ sp_api::impl_runtime_apis! {
 					let ticket = MultiLocation { parents: 0, interior: Here };
 					Ok((origin, ticket, assets))
 				}
+
+				fn unlockable_asset() -> Result<(MultiLocation, MultiLocation, MultiAsset), BenchmarkError> {
+					// Asset locking is not supported.
+					Err(BenchmarkError::Skip)
+				}
+
+				fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError> {
+					// No universal aliases are allowed.
+					Err(BenchmarkError::Skip)
+				}
+
+				fn export_message_origin_and_destination(
+				) -> Result<(MultiLocation, NetworkId, InteriorMultiLocation), BenchmarkError> {
+					// Exporting messages is not supported.
+					Err(BenchmarkError::Skip)
+				}
 			}
 
 			type XcmBalances = pallet_xcm_benchmarks::fungible::Pallet::<Runtime>;
@@ -57,8 +57,8 @@ fn sanity_check_teleport_assets_weight() {
 	// so this test will certainly ensure that this problem does not occur.
 	use frame_support::dispatch::GetDispatchInfo;
 	let weight = pallet_xcm::Call::<Runtime>::teleport_assets {
-		dest: Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::here())),
-		beneficiary: Box::new(xcm::VersionedMultiLocation::V1(MultiLocation::here())),
+		dest: Box::new(xcm::VersionedMultiLocation::V3(MultiLocation::here())),
+		beneficiary: Box::new(xcm::VersionedMultiLocation::V3(MultiLocation::here())),
 		assets: Box::new((Concrete(MultiLocation::here()), Fungible(200_000)).into()),
 		fee_asset_item: 0,
 	}
@@ -45,5 +45,6 @@ pub mod runtime_parachains_hrmp;
 pub mod runtime_parachains_initializer;
 pub mod runtime_parachains_paras;
 pub mod runtime_parachains_paras_inherent;
+pub mod runtime_parachains_reward_points;
 pub mod runtime_parachains_ump;
 pub mod xcm;
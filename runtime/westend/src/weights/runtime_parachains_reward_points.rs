@@ -0,0 +1,75 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+//! Autogenerated weights for `runtime_parachains::reward_points`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-11-02, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! HOSTNAME: `bm3`, CPU: `Intel(R) Core(TM) i7-7700K CPU @ 4.20GHz`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("westend-dev"), DB CACHE: 1024
+
+// Executed Command:
+// /home/benchbot/cargo_target_dir/production/polkadot
+// benchmark
+// pallet
+// --steps=50
+// --repeat=20
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --pallet=runtime_parachains::reward_points
+// --chain=westend-dev
+// --header=./file_header.txt
+// --output=./runtime/westend/src/weights/runtime_parachains_reward_points.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `runtime_parachains::reward_points`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> runtime_parachains::reward_points::WeightInfo for WeightInfo<T> {
+	// Storage: ParasShared CurrentSessionIndex (r:1 w:0)
+	// Storage: ParaSessionInfo Sessions (r:1 w:0)
+	// Storage: ParasRewardPoints ApprovalsTallies (r:1 w:1)
+	// Storage: ParasRewardPoints TalliedSessions (r:1 w:1)
+	/// The range of component `v` is `[1, 1000]`.
+	fn submit_approvals_tally(v: u32, ) -> Weight {
+		Weight::from_ref_time(104_711_000 as u64)
+			// Standard Error: 1_000
+			.saturating_add(Weight::from_ref_time(51_000 as u64).saturating_mul(v as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: ParasRewardPoints ApprovalsTallies (r:1001 w:1000)
+	// Storage: ParaSessionInfo AccountKeys (r:1 w:0)
+	// Storage: Session Validators (r:1 w:0)
+	// Storage: Staking ActiveEra (r:1 w:0)
+	// Storage: Staking ErasRewardPoints (r:1 w:1)
+	/// The range of component `v` is `[1, 1000]`.
+	fn credit_approval_points(v: u32, ) -> Weight {
+		Weight::from_ref_time(0 as u64)
+			// Standard Error: 22_000
+			.saturating_add(Weight::from_ref_time(14_310_000 as u64).saturating_mul(v as u64))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(v as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(v as u64)))
+	}
+}
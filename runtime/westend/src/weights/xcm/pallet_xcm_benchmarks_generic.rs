@@ -50,7 +50,7 @@ impl<T: frame_system::Config> WeightInfo<T> {
 	// Storage: XcmPallet SafeXcmVersion (r:1 w:0)
 	// Storage: Dmp DownwardMessageQueueHeads (r:1 w:1)
 	// Storage: Dmp DownwardMessageQueues (r:1 w:1)
-	pub(crate) fn query_holding() -> Weight {
+	pub(crate) fn report_holding() -> Weight {
 		Weight::from_ref_time(39_278_000 as u64)
 			.saturating_add(T::DbWeight::get().reads(5 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
@@ -129,4 +129,16 @@ impl<T: frame_system::Config> WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(5 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	pub(crate) fn expect_asset() -> Weight {
+		Weight::from_ref_time(8_418_000 as u64)
+	}
+	pub(crate) fn expect_origin() -> Weight {
+		Weight::from_ref_time(5_837_000 as u64)
+	}
+	pub(crate) fn expect_error() -> Weight {
+		Weight::from_ref_time(5_702_000 as u64)
+	}
+	pub(crate) fn set_topic() -> Weight {
+		Weight::from_ref_time(5_691_000 as u64)
+	}
 }
@@ -18,33 +18,41 @@
 
 use super::{
 	parachains_origin, weights, AccountId, Balances, ParaId, Runtime, RuntimeCall, RuntimeEvent,
-	RuntimeOrigin, WeightToFee, XcmPallet,
+	RuntimeOrigin, TransactionByteFee, WeightToFee, XcmPallet, CENTS,
 };
 use frame_support::{
-	parameter_types,
-	traits::{Everything, Nothing},
+	match_types, parameter_types,
+	traits::{ConstU32, Everything, Nothing},
+};
+use pallet_xcm::XcmPassthrough;
+use runtime_common::{
+	xcm_sender::{self, ConstantBaseFee, SizeBasedPrice},
+	ToAuthor,
 };
-use runtime_common::{xcm_sender, ToAuthor};
 use xcm::latest::prelude::*;
 use xcm_builder::{
-	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	Account32Hash, AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, ChildParachainAsNative,
 	ChildParachainConvertsVia, ChildSystemParachainAsSuperuser,
-	CurrencyAdapter as XcmCurrencyAdapter, IsChildSystemParachain, IsConcrete, LocationInverter,
+	CurrencyAdapter as XcmCurrencyAdapter, IsChildSystemParachain, IsConcrete,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
-	UsingComponents, WeightInfoBounds,
+	UsingComponents, WeightInfoBounds, XcmFeesToAccount,
 };
 
 parameter_types! {
 	pub const WndLocation: MultiLocation = Here.into();
-	pub const Ancestry: MultiLocation = Here.into();
 	pub WestendNetwork: NetworkId =
 		NetworkId::Named(b"Westend".to_vec().try_into().expect("shorter than length limit; qed"));
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(WestendNetwork::get()));
 	pub CheckAccount: AccountId = XcmPallet::check_account();
 }
 
-pub type LocationConverter =
-	(ChildParachainConvertsVia<ParaId, AccountId>, AccountId32Aliases<WestendNetwork, AccountId>);
+pub type LocationConverter = (
+	ChildParachainConvertsVia<ParaId, AccountId>,
+	AccountId32Aliases<WestendNetwork, AccountId>,
+	// Any other location, such as an account on a child parachain, gets a hashed account.
+	Account32Hash<WestendNetwork, AccountId>,
+);
 
 pub type LocalAssetTransactor = XcmCurrencyAdapter<
 	// Use this currency:
@@ -64,15 +72,39 @@ type LocalOriginConverter = (
 	ChildParachainAsNative<parachains_origin::Origin, RuntimeOrigin>,
 	SignedAccountId32AsNative<WestendNetwork, RuntimeOrigin>,
 	ChildSystemParachainAsSuperuser<ParaId, RuntimeOrigin>,
+	XcmPassthrough<RuntimeOrigin>,
 );
 
 /// The XCM router. When we want to send an XCM message, we use this type. It amalgamates all of our
 /// individual routers.
 pub type XcmRouter = (
 	// Only one router so far - use DMP to communicate with child parachains.
-	xcm_sender::ChildParachainRouter<Runtime, XcmPallet>,
+	xcm_sender::ChildParachainRouter<Runtime, XcmPallet, PriceForChildParachainDelivery>,
 );
 
+parameter_types! {
+	/// The asset in which the delivery fees of messages sent from this chain are paid.
+	pub const FeeAssetId: AssetId = Concrete(WndLocation::get());
+	/// The base fee for delivering a downward message to any child parachain.
+	pub const BaseDeliveryFee: u128 = 3 * CENTS;
+}
+
+/// The price of delivering a downward message: the base fee, plus the transaction byte fee for every
+/// byte of the message.
+pub type PriceForChildParachainDelivery =
+	SizeBasedPrice<FeeAssetId, ConstantBaseFee<BaseDeliveryFee>, TransactionByteFee>;
+
+match_types! {
+	pub type LocalGovernance: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 0, interior: Here } |
+		MultiLocation { parents: 0, interior: X1(Plurality { .. }) }
+	};
+}
+
+/// Locations which don't pay for the delivery of the messages they send: the relay chain itself,
+/// its governance bodies and the system parachains.
+pub type WaivedLocations = (LocalGovernance, IsChildSystemParachain<ParaId>);
+
 parameter_types! {
 	pub const Westmint: MultiLocation = Parachain(1000).into();
 	pub const Collectives: MultiLocation = Parachain(1001).into();
@@ -107,7 +139,7 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = TrustedTeleporters;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher =
 		WeightInfoBounds<weights::xcm::WestendXcmWeight<RuntimeCall>, RuntimeCall, MaxInstructions>;
@@ -116,6 +148,11 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	// Delivery fees are burned.
+	type FeeManager = XcmFeesToAccount<WaivedLocations, LocalAssetTransactor, ()>;
+	type AssetLocker = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 /// Type to convert an `Origin` type value into a `MultiLocation` value which represents an interior location
@@ -138,9 +175,10 @@ impl pallet_xcm::Config for Runtime {
 	type XcmReserveTransferFilter = Everything;
 	type Weigher =
 		WeightInfoBounds<weights::xcm::WestendXcmWeight<RuntimeCall>, RuntimeCall, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+	type MaxIndexedAssetTraps = ConstU32<16>;
 }
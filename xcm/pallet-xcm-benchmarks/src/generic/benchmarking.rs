@@ -23,18 +23,20 @@ use sp_std::vec;
 use xcm::{latest::prelude::*, DoubleEncoded};
 
 benchmarks! {
-	query_holding {
+	report_holding {
 		let holding = T::worst_case_holding();
 
 		let mut executor = new_executor::<T>(Default::default());
 		executor.holding = holding.clone().into();
 
-		let instruction = Instruction::<XcmCallOf<T>>::QueryHolding {
-			query_id: Default::default(),
-			dest: T::valid_destination()?,
+		let instruction = Instruction::<XcmCallOf<T>>::ReportHolding {
+			response_info: QueryResponseInfo {
+				destination: T::valid_destination()?,
+				query_id: Default::default(),
+				max_weight: u64::MAX,
+			},
 			// Worst case is looking through all holdings for every asset explicitly.
 			assets: Definite(holding),
-			max_response_weight: u64::MAX,
 		};
 
 		let xcm = Xcm(vec![instruction]);
@@ -271,6 +273,154 @@ benchmarks! {
 		assert!(!<T::XcmConfig as xcm_executor::Config>::SubscriptionService::is_subscribed(&origin));
 	}
 
+	expect_asset {
+		let holding = T::worst_case_holding();
+
+		let mut executor = new_executor::<T>(Default::default());
+		executor.holding = holding.clone().into();
+
+		// Worst case is checking that every asset of the holding is there.
+		let instruction = Instruction::<XcmCallOf<T>>::ExpectAsset(holding);
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	expect_origin {
+		let origin: MultiLocation = Default::default();
+		let mut executor = new_executor::<T>(origin.clone());
+		let instruction = Instruction::<XcmCallOf<T>>::ExpectOrigin(Some(origin));
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	expect_error {
+		let mut executor = new_executor::<T>(Default::default());
+		executor.error = Some((3u32, XcmError::Overflow));
+		let instruction =
+			Instruction::<XcmCallOf<T>>::ExpectError(Some((3u32, XcmError::Overflow)));
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	universal_origin {
+		let (origin, alias) = T::universal_alias()?;
+		let mut executor = new_executor::<T>(origin);
+		let instruction = Instruction::<XcmCallOf<T>>::UniversalOrigin(alias.clone());
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		assert_eq!(executor.origin.map(|o| o.interior().clone()), Some(X1(alias)));
+	}
+
+	export_message {
+		let (origin, network, destination) = T::export_message_origin_and_destination()?;
+		let mut executor = new_executor::<T>(origin);
+		// The export fee is paid from the holding, if it contains it.
+		executor.holding = T::worst_case_holding().into();
+		let instruction = Instruction::<XcmCallOf<T>>::ExportMessage {
+			network,
+			destination,
+			xcm: Xcm(vec![]),
+		};
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	lock_asset {
+		use xcm_executor::traits::TransactAsset;
+
+		let (unlocker, owner, asset) = T::unlockable_asset()?;
+		<T::XcmConfig as xcm_executor::Config>::AssetTransactor::deposit_asset(&asset, &owner)
+			.map_err(|_| "Could not deposit the asset to lock")?;
+
+		let mut executor = new_executor::<T>(owner);
+		let instruction = Instruction::<XcmCallOf<T>>::LockAsset { asset, unlocker };
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	unlock_asset {
+		use xcm_executor::traits::{AssetLock, Enact, TransactAsset};
+
+		let (unlocker, owner, asset) = T::unlockable_asset()?;
+		<T::XcmConfig as xcm_executor::Config>::AssetTransactor::deposit_asset(&asset, &owner)
+			.map_err(|_| "Could not deposit the asset to lock")?;
+		// We lock the asset first, so that it may be unlocked.
+		<T::XcmConfig as xcm_executor::Config>::AssetLocker::prepare_lock(
+			unlocker.clone(),
+			asset.clone(),
+			owner.clone(),
+		)
+		.and_then(|ticket| ticket.enact())
+		.map_err(|_| "Could not lock the asset")?;
+
+		let mut executor = new_executor::<T>(unlocker);
+		let instruction = Instruction::<XcmCallOf<T>>::UnlockAsset { asset, target: owner };
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	note_unlockable {
+		let (locker, owner, asset) = T::unlockable_asset()?;
+		let mut executor = new_executor::<T>(locker);
+		let instruction = Instruction::<XcmCallOf<T>>::NoteUnlockable { asset, owner };
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	request_unlock {
+		use xcm_executor::traits::AssetLock;
+
+		let (locker, owner, asset) = T::unlockable_asset()?;
+		// The asset must first be noted as unlockable.
+		<T::XcmConfig as xcm_executor::Config>::AssetLocker::note_unlockable(
+			locker.clone(),
+			asset.clone(),
+			owner.clone(),
+		)
+		.map_err(|_| "Could not note the asset as unlockable")?;
+
+		let mut executor = new_executor::<T>(owner);
+		let instruction = Instruction::<XcmCallOf<T>>::RequestUnlock { asset, locker };
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		// The execution succeeding is all we need to verify this xcm was successful.
+	}
+
+	set_topic {
+		let mut executor = new_executor::<T>(Default::default());
+		let instruction = Instruction::<XcmCallOf<T>>::SetTopic([1; 32]);
+		let xcm = Xcm(vec![instruction]);
+	} : {
+		executor.execute(xcm)?;
+	} verify {
+		assert_eq!(executor.topic, Some([1; 32]));
+	}
+
 	initiate_reserve_withdraw {
 		let holding = T::worst_case_holding();
 		let assets_filter = MultiAssetFilter::Definite(holding.clone());
@@ -20,7 +20,7 @@ use crate::{generic, mock::*, *};
 use codec::Decode;
 use frame_support::{
 	parameter_types,
-	traits::{Everything, OriginTrait},
+	traits::{Everything, Nothing, OriginTrait},
 	weights::Weight,
 };
 use sp_core::H256;
@@ -108,7 +108,7 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = AlwaysSignedByDefault<RuntimeOrigin>;
 	type IsReserve = AllAssetLocationsPass;
 	type IsTeleporter = ();
-	type LocationInverter = xcm_builder::LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = AllowUnpaidExecutionFrom<Everything>;
 	type Weigher = xcm_builder::FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type Trader = xcm_builder::FixedRateOfFungible<WeightPrice, ()>;
@@ -116,6 +116,10 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = TestAssetTrap;
 	type AssetClaims = TestAssetTrap;
 	type SubscriptionService = TestSubscriptionService;
+	type FeeManager = ();
+	type AssetLocker = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 impl crate::Config for Test {
@@ -153,6 +157,22 @@ impl generic::Config for Test {
 		let ticket = MultiLocation { parents: 0, interior: X1(GeneralIndex(0)) };
 		Ok((Default::default(), ticket, assets))
 	}
+
+	fn unlockable_asset() -> Result<(MultiLocation, MultiLocation, MultiAsset), BenchmarkError> {
+		// No asset locker is configured.
+		Err(BenchmarkError::Skip)
+	}
+
+	fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError> {
+		// No universal aliases are allowed.
+		Err(BenchmarkError::Skip)
+	}
+
+	fn export_message_origin_and_destination(
+	) -> Result<(MultiLocation, NetworkId, InteriorMultiLocation), BenchmarkError> {
+		// No message exporter is configured.
+		Err(BenchmarkError::Skip)
+	}
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
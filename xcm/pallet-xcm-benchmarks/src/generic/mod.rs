@@ -12,7 +12,10 @@ pub mod pallet {
 		dispatch::{Dispatchable, GetDispatchInfo},
 		pallet_prelude::Encode,
 	};
-	use xcm::latest::{MultiAssets, MultiLocation, Response};
+	use xcm::latest::{
+		InteriorMultiLocation, Junction, MultiAsset, MultiAssets, MultiLocation, NetworkId,
+		Response,
+	};
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config + crate::Config {
@@ -36,6 +39,24 @@ pub mod pallet {
 
 		/// Return an origin, ticket, and assets that can be trapped and claimed.
 		fn claimable_asset() -> Result<(MultiLocation, MultiLocation, MultiAssets), BenchmarkError>;
+
+		/// Return a locker, an owner and an asset such that the owner may lock the asset with the
+		/// locker as its unlocker, and the locker may report the asset as unlockable by the owner.
+		///
+		/// If set to `Err`, benchmarks which rely on asset locking will be skipped.
+		fn unlockable_asset() -> Result<(MultiLocation, MultiLocation, MultiAsset), BenchmarkError>;
+
+		/// Return an origin and the global junction it may alias with `UniversalOrigin`.
+		///
+		/// If set to `Err`, benchmarks which rely on a `universal_alias` will be skipped.
+		fn universal_alias() -> Result<(MultiLocation, Junction), BenchmarkError>;
+
+		/// Return an origin, along with a network and a destination within it to which the origin
+		/// may export a message.
+		///
+		/// If set to `Err`, benchmarks which rely on exporting a message will be skipped.
+		fn export_message_origin_and_destination(
+		) -> Result<(MultiLocation, NetworkId, InteriorMultiLocation), BenchmarkError>;
 	}
 
 	#[pallet::pallet]
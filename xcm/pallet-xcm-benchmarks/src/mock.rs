@@ -27,6 +27,12 @@ impl xcm::opaque::latest::SendXcm for DevNull {
 	}
 }
 
+impl xcm::opaque::latest::QuoteDelivery for DevNull {
+	fn quote_delivery(_: &MultiLocation, _: &Xcm<()>) -> Option<MultiAssets> {
+		None
+	}
+}
+
 impl xcm_executor::traits::OnResponse for DevNull {
 	fn expecting_response(_: &MultiLocation, _: u64) -> bool {
 		false
@@ -52,7 +58,7 @@ impl xcm_executor::traits::Convert<MultiLocation, u64> for AccountIdConverter {
 }
 
 parameter_types! {
-	pub Ancestry: MultiLocation = Junction::Parachain(101).into();
+	pub UniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(Kusama), Parachain(101));
 	pub UnitWeightCost: u64 = 10;
 	pub WeightPrice: (AssetId, u128) = (Concrete(Here.into()), 1_000_000);
 }
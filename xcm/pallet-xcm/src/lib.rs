@@ -23,7 +23,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
+use codec::{Codec, Decode, Encode, EncodeLike, MaxEncodedLen};
 use frame_support::traits::{Contains, EnsureOrigin, Get, OriginTrait};
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -50,8 +50,8 @@ pub mod pallet {
 	use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, BlockNumberProvider, Hash};
 	use xcm_executor::{
 		traits::{
-			ClaimAssets, DropAssets, InvertLocation, OnResponse, VersionChangeNotifier,
-			WeightBounds,
+			ClaimAssets, DropAssets, InspectMessageQueues, OnResponse, VersionChangeNotifier,
+			WeightBounds, WeightTrader,
 		},
 		Assets,
 	};
@@ -80,8 +80,9 @@ pub mod pallet {
 			Success = MultiLocation,
 		>;
 
-		/// The type used to actually dispatch an XCM to its destination.
-		type XcmRouter: SendXcm;
+		/// The type used to actually dispatch an XCM to its destination, and to quote the fees of
+		/// delivering messages sent with `send`.
+		type XcmRouter: SendXcm + QuoteDelivery;
 
 		/// Required origin for executing XCM messages, including the teleport functionality. If successful,
 		/// then it resolves to `MultiLocation` which exists as an interior location within this chain's XCM
@@ -106,8 +107,8 @@ pub mod pallet {
 		/// Means of measuring the weight consumed by an XCM message locally.
 		type Weigher: WeightBounds<<Self as SysConfig>::RuntimeCall>;
 
-		/// Means of inverting a location.
-		type LocationInverter: InvertLocation;
+		/// This chain's Universal Location.
+		type UniversalLocation: Get<InteriorMultiLocation>;
 
 		/// The outer `Origin` type.
 		type RuntimeOrigin: From<Origin> + From<<Self as SysConfig>::RuntimeOrigin>;
@@ -126,6 +127,10 @@ pub mod pallet {
 		/// The latest supported version that we advertise. Generally just set it to
 		/// `pallet_xcm::CurrentXcmVersion`.
 		type AdvertisedXcmVersion: Get<XcmVersion>;
+
+		/// The maximum number of asset traps of each origin which are indexed in
+		/// `IndexedAssetTraps`. Set to zero to disable the index.
+		type MaxIndexedAssetTraps: Get<u32>;
 	}
 
 	/// The maximum number of distinct assets allowed to be transferred in a single helper extrinsic.
@@ -136,12 +141,12 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// Execution of an XCM message was attempted.
 		///
-		/// \[ outcome \]
-		Attempted(xcm::latest::Outcome),
+		/// \[ outcome, message ID \]
+		Attempted(xcm::latest::Outcome, XcmHash),
 		/// A XCM message was sent.
 		///
-		/// \[ origin, destination, message \]
-		Sent(MultiLocation, MultiLocation, Xcm<()>),
+		/// \[ origin, destination, message, message ID \]
+		Sent(MultiLocation, MultiLocation, Xcm<()>, XcmHash),
 		/// Query response received which does not match a registered query. This may be because a
 		/// matching query was never registered, it may be because it is a duplicate response, or
 		/// because the query timed out.
@@ -269,6 +274,8 @@ pub mod pallet {
 		NoSubscription,
 		/// The location is invalid since it already has a subscription from us.
 		AlreadySubscribed,
+		/// The origin could not pay the fees of delivering the message.
+		FeesNotMet,
 	}
 
 	/// The status of a query.
@@ -330,6 +337,19 @@ pub mod pallet {
 	#[pallet::getter(fn asset_trap)]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The assets of the unclaimed asset traps of each origin, oldest first.
+	///
+	/// Only the latest `MaxIndexedAssetTraps` traps of an origin are indexed. Traps which are no
+	/// longer indexed remain in `AssetTraps` and can still be claimed.
+	#[pallet::storage]
+	pub(super) type IndexedAssetTraps<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		VersionedMultiLocation,
+		BoundedVec<VersionedMultiAssets, T::MaxIndexedAssetTraps>,
+		ValueQuery,
+	>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -470,11 +490,18 @@ pub mod pallet {
 			let dest = MultiLocation::try_from(*dest).map_err(|()| Error::<T>::BadVersion)?;
 			let message: Xcm<()> = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
 
+			let sent = Self::descended(&interior, message.clone());
+			let id = Self::delivered_message_id(&dest, &sent);
+			let fees = T::XcmRouter::quote_delivery(&dest, &sent).unwrap_or_else(MultiAssets::new);
+			if !fees.is_none() {
+				T::XcmExecutor::charge_fees(origin_location.clone(), fees)
+					.map_err(|_| Error::<T>::FeesNotMet)?;
+			}
 			Self::send_xcm(interior, dest.clone(), message.clone()).map_err(|e| match e {
 				SendError::CannotReachDestination(..) => Error::<T>::Unreachable,
 				_ => Error::<T>::SendFailure,
 			})?;
-			Self::deposit_event(Event::Sent(origin_location, dest, message));
+			Self::deposit_event(Event::Sent(origin_location, dest, message, id));
 			Ok(())
 		}
 
@@ -581,6 +608,7 @@ pub mod pallet {
 			max_weight: XcmWeight,
 		) -> DispatchResultWithPostInfo {
 			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let id = xcm_executor::message_id(&message);
 			let message = (*message).try_into().map_err(|()| Error::<T>::BadVersion)?;
 			let value = (origin_location, message);
 			ensure!(T::XcmExecuteFilter::contains(&value), Error::<T>::Filtered);
@@ -592,7 +620,7 @@ pub mod pallet {
 				max_weight,
 			);
 			let result = Ok(Some(outcome.weight_used().saturating_add(100_000_000)).into());
-			Self::deposit_event(Event::Attempted(outcome));
+			Self::deposit_event(Event::Attempted(outcome, id));
 			result
 		}
 
@@ -775,6 +803,42 @@ pub mod pallet {
 				Some(weight_limit),
 			)
 		}
+
+		/// Claim the assets of some asset traps of the origin and deposit them with a beneficiary.
+		///
+		/// - `origin`: Must be capable of withdrawing assets and executing XCM. The traps are those
+		///   of its location.
+		/// - `traps`: The assets of each trap to be claimed, as listed by `IndexedAssetTraps`. May
+		///   not be empty.
+		/// - `beneficiary`: The location, relative to this chain, of the account which receives the
+		///   claimed assets.
+		#[pallet::weight({
+			let maybe_beneficiary: Result<MultiLocation, ()> = (*beneficiary.clone()).try_into();
+			match maybe_beneficiary.and_then(|b| Pallet::<T>::claim_message(&traps, b)) {
+				Ok(mut message) =>
+					T::Weigher::weight(&mut message).map_or(Weight::MAX, |w| Weight::from_ref_time(100_000_000.saturating_add(w))),
+				Err(()) => Weight::MAX,
+			}
+		})]
+		pub fn claim_trapped_assets(
+			origin: OriginFor<T>,
+			traps: Vec<VersionedMultiAssets>,
+			beneficiary: Box<VersionedMultiLocation>,
+		) -> DispatchResult {
+			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let beneficiary: MultiLocation =
+				(*beneficiary).try_into().map_err(|()| Error::<T>::BadVersion)?;
+			ensure!(!traps.is_empty(), Error::<T>::Empty);
+			let mut message =
+				Self::claim_message(&traps, beneficiary).map_err(|()| Error::<T>::BadVersion)?;
+			let weight =
+				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			let id = xcm_executor::message_id(&VersionedXcm::from(message.clone()));
+			let outcome =
+				T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
+			Self::deposit_event(Event::Attempted(outcome, id));
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -796,12 +860,12 @@ pub mod pallet {
 			let value = (origin_location, assets.drain());
 			ensure!(T::XcmReserveTransferFilter::contains(&value), Error::<T>::Filtered);
 			let (origin_location, assets) = value;
-			let ancestry = T::LocationInverter::ancestry();
+			let context = T::UniversalLocation::get();
 			let fees = assets
 				.get(fee_asset_item as usize)
 				.ok_or(Error::<T>::Empty)?
 				.clone()
-				.reanchored(&dest, &ancestry)
+				.reanchored(&dest, &context)
 				.map_err(|_| Error::<T>::CannotReanchor)?;
 			let max_assets = assets.len() as u32;
 			let assets: MultiAssets = assets.into();
@@ -829,9 +893,10 @@ pub mod pallet {
 			let mut message = Xcm(vec![TransferReserveAsset { assets, dest, xcm }]);
 			let weight =
 				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			let id = xcm_executor::message_id(&VersionedXcm::from(message.clone()));
 			let outcome =
 				T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
-			Self::deposit_event(Event::Attempted(outcome));
+			Self::deposit_event(Event::Attempted(outcome, id));
 			Ok(())
 		}
 
@@ -853,12 +918,12 @@ pub mod pallet {
 			let value = (origin_location, assets.drain());
 			ensure!(T::XcmTeleportFilter::contains(&value), Error::<T>::Filtered);
 			let (origin_location, assets) = value;
-			let ancestry = T::LocationInverter::ancestry();
+			let context = T::UniversalLocation::get();
 			let fees = assets
 				.get(fee_asset_item as usize)
 				.ok_or(Error::<T>::Empty)?
 				.clone()
-				.reanchored(&dest, &ancestry)
+				.reanchored(&dest, &context)
 				.map_err(|_| Error::<T>::CannotReanchor)?;
 			let max_assets = assets.len() as u32;
 			let assets: MultiAssets = assets.into();
@@ -887,9 +952,10 @@ pub mod pallet {
 				Xcm(vec![WithdrawAsset(assets), InitiateTeleport { assets: Wild(All), dest, xcm }]);
 			let weight =
 				T::Weigher::weight(&mut message).map_err(|()| Error::<T>::UnweighableMessage)?;
+			let id = xcm_executor::message_id(&VersionedXcm::from(message.clone()));
 			let outcome =
 				T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
-			Self::deposit_event(Event::Attempted(outcome));
+			Self::deposit_event(Event::Attempted(outcome, id));
 			Ok(())
 		}
 
@@ -1070,22 +1136,196 @@ pub mod pallet {
 		pub fn send_xcm(
 			interior: impl Into<Junctions>,
 			dest: impl Into<MultiLocation>,
-			mut message: Xcm<()>,
+			message: Xcm<()>,
 		) -> Result<(), SendError> {
-			let interior = interior.into();
 			let dest = dest.into();
-			if interior != Junctions::Here {
-				message.0.insert(0, DescendOrigin(interior))
-			};
+			let message = Self::descended(&interior.into(), message);
 			log::trace!(target: "xcm::send_xcm", "dest: {:?}, message: {:?}", &dest, &message);
 			T::XcmRouter::send_xcm(dest, message)
 		}
 
+		/// Prefix `message` with a `DescendOrigin` into `interior`, unless `interior` is `Here`.
+		fn descended(interior: &Junctions, mut message: Xcm<()>) -> Xcm<()> {
+			if *interior != Junctions::Here {
+				message.0.insert(0, DescendOrigin(interior.clone()))
+			};
+			message
+		}
+
+		/// The ID of `message` as it is delivered to `dest`, that is once it is wrapped in the
+		/// version which `dest` is known to support, as routers wrapping their messages with this
+		/// pallet do. Since a topic can't be downgraded, this is also the ID which `dest` computes
+		/// for the message it receives.
+		fn delivered_message_id(dest: &MultiLocation, message: &Xcm<()>) -> XcmHash {
+			let versioned = VersionedXcm::from(message.clone());
+			let versioned =
+				SupportedVersion::<T>::get(XCM_VERSION, LatestVersionedMultiLocation(dest))
+					.or_else(SafeXcmVersion::<T>::get)
+					.and_then(|v| versioned.clone().into_version(v.min(XCM_VERSION)).ok())
+					.unwrap_or(versioned);
+			xcm_executor::message_id(&versioned)
+		}
+
 		pub fn check_account() -> T::AccountId {
 			const ID: PalletId = PalletId(*b"py/xcmch");
 			AccountIdConversion::<T::AccountId>::into_account_truncating(&ID)
 		}
 
+		/// Execute `xcm` from `origin` with the XCM executor configured by `XcmConfig` and report
+		/// all of its effects: the outcome, the holding register after each instruction, the
+		/// events emitted and the messages which would be sent through `XcmConfig::XcmSender`.
+		///
+		/// Nothing is reverted by this function: it is meant to back [`XcmDryRunApi`], whose
+		/// changes are never committed.
+		pub fn dry_run_xcm<XcmConfig>(
+			origin: VersionedMultiLocation,
+			xcm: VersionedXcm<()>,
+		) -> Result<XcmDryRunEffects<<T as SysConfig>::RuntimeEvent>, XcmDryRunApiError>
+		where
+			XcmConfig: xcm_executor::Config,
+			XcmConfig::XcmSender: InspectMessageQueues,
+		{
+			let origin: MultiLocation =
+				origin.try_into().map_err(|()| XcmDryRunApiError::VersionedConversionFailed)?;
+			let xcm: Xcm<()> =
+				xcm.try_into().map_err(|()| XcmDryRunApiError::VersionedConversionFailed)?;
+
+			let queued_before = XcmConfig::XcmSender::get_messages();
+			frame_system::Pallet::<T>::reset_events();
+			let (execution_result, steps) =
+				xcm_executor::XcmExecutor::<XcmConfig>::execute_xcm_with_trace(
+					origin,
+					xcm.into(),
+					XcmWeight::MAX,
+				);
+			let emitted_events = frame_system::Pallet::<T>::read_events_no_consensus()
+				.into_iter()
+				.map(|record| record.event)
+				.collect();
+			let forwarded_xcms =
+				Self::newly_queued(queued_before, XcmConfig::XcmSender::get_messages());
+
+			Ok(XcmDryRunEffects {
+				weight_used: execution_result.weight_used(),
+				execution_result,
+				holding_per_step: steps
+					.into_iter()
+					.map(|step| (step.index, step.holding.into()))
+					.collect(),
+				emitted_events,
+				forwarded_xcms,
+			})
+		}
+
+		/// The assets in which the local XCM executor accepts payment for `BuyExecution`.
+		pub fn query_acceptable_payment_assets<XcmConfig: xcm_executor::Config>() -> Vec<AssetId> {
+			<XcmConfig::Trader as WeightTrader>::acceptable_fee_assets()
+		}
+
+		/// The weight which the local XCM executor would charge for executing `message`.
+		pub fn query_xcm_weight<XcmConfig: xcm_executor::Config>(
+			message: VersionedXcm<()>,
+		) -> Result<XcmWeight, XcmPaymentApiError> {
+			let message: Xcm<()> =
+				message.try_into().map_err(|()| XcmPaymentApiError::VersionedConversionFailed)?;
+			let mut message: Xcm<XcmConfig::RuntimeCall> = message.into();
+			XcmConfig::Weigher::weight(&mut message)
+				.map_err(|()| XcmPaymentApiError::WeightNotComputable)
+		}
+
+		/// The amount of `asset` which the local XCM executor would charge for `weight`.
+		pub fn query_weight_to_asset_fee<XcmConfig: xcm_executor::Config>(
+			weight: XcmWeight,
+			asset: AssetId,
+		) -> Result<u128, XcmPaymentApiError> {
+			<XcmConfig::Trader as WeightTrader>::weight_to_fee(weight, &asset)
+				.ok_or(XcmPaymentApiError::AssetNotFound)
+		}
+
+		/// The assets of the asset traps of `origin` which are indexed in `IndexedAssetTraps`, oldest
+		/// first.
+		pub fn trapped_assets(origin: VersionedMultiLocation) -> Vec<VersionedMultiAssets> {
+			match MultiLocation::try_from(origin) {
+				Ok(origin) =>
+					IndexedAssetTraps::<T>::get(LatestVersionedMultiLocation(&origin)).into_inner(),
+				Err(()) => Vec::new(),
+			}
+		}
+
+		/// The message claiming each of `traps` and depositing their assets with `beneficiary`.
+		fn claim_message(
+			traps: &[VersionedMultiAssets],
+			beneficiary: MultiLocation,
+		) -> Result<Xcm<<T as SysConfig>::RuntimeCall>, ()> {
+			let mut message = Vec::with_capacity(traps.len() + 1);
+			let mut max_assets = 0u32;
+			for trap in traps {
+				// The ticket names the version in which the assets were trapped, as that is what
+				// their trap was recorded with.
+				let ticket: MultiLocation = match trap {
+					VersionedMultiAssets::V0(_) => GeneralIndex(0).into(),
+					VersionedMultiAssets::V1(_) => GeneralIndex(1).into(),
+					VersionedMultiAssets::V3(_) => Here.into(),
+				};
+				let assets = MultiAssets::try_from(trap.clone())?;
+				max_assets = max_assets.saturating_add(assets.len() as u32);
+				message.push(ClaimAsset { assets, ticket });
+			}
+			message.push(DepositAsset { assets: Wild(All), max_assets, beneficiary });
+			Ok(Xcm(message))
+		}
+
+		/// Add `assets` to the indexed asset traps of `origin`, evicting its oldest indexed trap if
+		/// there is no more room.
+		fn index_asset_trap(origin: &MultiLocation, assets: VersionedMultiAssets) {
+			let max = T::MaxIndexedAssetTraps::get();
+			if max == 0 {
+				return
+			}
+			IndexedAssetTraps::<T>::mutate(LatestVersionedMultiLocation(origin), |traps| {
+				if traps.len() as u32 >= max {
+					traps.remove(0);
+				}
+				// Cannot fail since we just made room.
+				let _ = traps.try_push(assets);
+			});
+		}
+
+		/// Remove one trap of `assets` from the indexed asset traps of `origin`, if it is indexed.
+		fn unindex_asset_trap(origin: &MultiLocation, assets: &VersionedMultiAssets) {
+			IndexedAssetTraps::<T>::mutate_exists(
+				LatestVersionedMultiLocation(origin),
+				|maybe_traps| {
+					if let Some(traps) = maybe_traps {
+						if let Some(index) = traps.iter().position(|trap| trap == assets) {
+							traps.remove(index);
+						}
+						if traps.is_empty() {
+							*maybe_traps = None;
+						}
+					}
+				},
+			);
+		}
+
+		/// Return the messages of `after` which were not already queued in `before`.
+		fn newly_queued(
+			before: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+			after: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+		) -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+			after
+				.into_iter()
+				.filter_map(|(destination, messages)| {
+					let already_queued = before
+						.iter()
+						.find(|(d, _)| *d == destination)
+						.map_or(0, |(_, messages)| messages.len());
+					let messages: Vec<_> = messages.into_iter().skip(already_queued).collect();
+					(!messages.is_empty()).then(|| (destination, messages))
+				})
+				.collect()
+		}
+
 		fn do_new_query(
 			responder: impl Into<MultiLocation>,
 			maybe_notify: Option<(u8, u8)>,
@@ -1124,7 +1364,8 @@ pub mod pallet {
 			timeout: T::BlockNumber,
 		) -> Result<QueryId, XcmError> {
 			let responder = responder.into();
-			let dest = T::LocationInverter::invert_location(&responder)
+			let dest = T::UniversalLocation::get()
+				.invert_target(&responder)
 				.map_err(|()| XcmError::MultiLocationNotInvertible)?;
 			let query_id = Self::new_query(responder, timeout);
 			let report_error = Xcm(vec![ReportError { dest, query_id, max_response_weight: 0 }]);
@@ -1159,7 +1400,8 @@ pub mod pallet {
 			timeout: T::BlockNumber,
 		) -> Result<(), XcmError> {
 			let responder = responder.into();
-			let dest = T::LocationInverter::invert_location(&responder)
+			let dest = T::UniversalLocation::get()
+				.invert_target(&responder)
 				.map_err(|()| XcmError::MultiLocationNotInvertible)?;
 			let notify: <T as Config>::RuntimeCall = notify.into();
 			let max_response_weight = notify.get_dispatch_info().weight;
@@ -1294,6 +1536,7 @@ pub mod pallet {
 			let versioned = VersionedMultiAssets::from(MultiAssets::from(assets));
 			let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
 			AssetTraps::<T>::mutate(hash, |n| *n += 1);
+			Self::index_asset_trap(origin, versioned.clone());
 			Self::deposit_event(Event::AssetsTrapped(hash, origin.clone(), versioned));
 			// TODO #3735: Put the real weight in there.
 			0
@@ -1322,6 +1565,7 @@ pub mod pallet {
 				1 => AssetTraps::<T>::remove(hash),
 				n => AssetTraps::<T>::insert(hash, n - 1),
 			}
+			Self::unindex_asset_trap(origin, &versioned);
 			Self::deposit_event(Event::AssetsClaimed(hash, origin.clone(), versioned));
 			return true
 		}
@@ -1584,3 +1828,73 @@ impl<RuntimeOrigin: From<crate::Origin>> ConvertOrigin<RuntimeOrigin>
 		}
 	}
 }
+
+/// The effects of executing an XCM, as reported by [`XcmDryRunApi`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct XcmDryRunEffects<RuntimeEvent> {
+	/// The outcome of the execution.
+	pub execution_result: Outcome,
+	/// The weight used by the execution.
+	pub weight_used: XcmWeight,
+	/// The contents of the holding register after each instruction which was executed, along with
+	/// the index of the instruction within the program fragment to which it belongs.
+	pub holding_per_step: Vec<(u32, VersionedMultiAssets)>,
+	/// The events emitted by the execution.
+	pub emitted_events: Vec<RuntimeEvent>,
+	/// The messages which the execution would send, grouped by destination.
+	pub forwarded_xcms: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>,
+}
+
+/// An error preventing an XCM from being dry-run.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum XcmDryRunApiError {
+	/// The origin or the message could not be converted to the latest XCM version.
+	VersionedConversionFailed,
+}
+
+/// An error preventing the weight or fee of an XCM from being determined.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum XcmPaymentApiError {
+	/// The message could not be converted to the latest XCM version.
+	VersionedConversionFailed,
+	/// The weight of the message could not be determined, for example because it has too many
+	/// instructions.
+	WeightNotComputable,
+	/// The asset is not accepted as payment for weight.
+	AssetNotFound,
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API for previewing the effects of an XCM without committing them.
+	pub trait XcmDryRunApi<RuntimeEvent: Codec> {
+		/// Execute `xcm` from `origin` as the local XCM executor would, and report its outcome,
+		/// the weight used, the holding register after each instruction, the events emitted and
+		/// the messages which would be forwarded.
+		fn dry_run_xcm(
+			origin: VersionedMultiLocation,
+			xcm: VersionedXcm<()>,
+		) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError>;
+	}
+
+	/// The API for sizing the payment of an XCM executed by this chain.
+	pub trait XcmPaymentApi {
+		/// The assets in which payment for `BuyExecution` is accepted.
+		fn query_acceptable_payment_assets() -> Vec<AssetId>;
+
+		/// The weight which would be charged for executing `message`.
+		fn query_xcm_weight(message: VersionedXcm<()>) -> Result<XcmWeight, XcmPaymentApiError>;
+
+		/// The amount of `asset` which would be charged for `weight`.
+		fn query_weight_to_asset_fee(
+			weight: XcmWeight,
+			asset: AssetId,
+		) -> Result<u128, XcmPaymentApiError>;
+	}
+
+	/// The API for finding the assets which have been trapped on this chain.
+	pub trait TrappedAssetsApi {
+		/// The assets of each indexed asset trap of `origin`, oldest first. Each trap may be claimed
+		/// through the `claim_trapped_assets` extrinsic, or a `ClaimAsset` instruction.
+		fn trapped_assets(origin: VersionedMultiLocation) -> Vec<VersionedMultiAssets>;
+	}
+}
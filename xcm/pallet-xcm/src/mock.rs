@@ -14,21 +14,24 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use frame_support::{construct_runtime, parameter_types, traits::Everything};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Nothing},
+};
 use polkadot_parachain::primitives::Id as ParaId;
 use polkadot_runtime_parachains::origin;
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
 pub use sp_std::{cell::RefCell, fmt::Debug, marker::PhantomData};
-use xcm::latest::prelude::*;
+use xcm::{latest::prelude::*, VersionedMultiLocation, VersionedXcm};
 use xcm_builder::{
 	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
 	AllowTopLevelPaidExecutionFrom, Case, ChildParachainAsNative, ChildParachainConvertsVia,
 	ChildSystemParachainAsSuperuser, CurrencyAdapter as XcmCurrencyAdapter, FixedRateOfFungible,
-	FixedWeightBounds, IsConcrete, LocationInverter, SignedAccountId32AsNative,
-	SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	FixedWeightBounds, IsConcrete, SignedAccountId32AsNative, SignedToAccountId32,
+	SovereignSignedViaLocation, TakeWeightCredit, XcmFeesToAccount,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{traits::InspectMessageQueues, XcmExecutor};
 
 use crate as pallet_xcm;
 
@@ -152,6 +155,25 @@ impl SendXcm for TestSendXcm {
 		Ok(())
 	}
 }
+impl QuoteDelivery for TestSendXcm {
+	fn quote_delivery(_dest: &MultiLocation, _msg: &Xcm<()>) -> Option<MultiAssets> {
+		Some(DeliveryPrice::get())
+	}
+}
+impl InspectMessageQueues for TestSendXcm {
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		let mut queues: Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> = Vec::new();
+		for (dest, msg) in sent_xcm() {
+			let dest = VersionedMultiLocation::from(dest);
+			let msg = VersionedXcm::from(msg);
+			match queues.iter_mut().find(|(d, _)| *d == dest) {
+				Some((_, queue)) => queue.push(msg),
+				None => queues.push((dest, vec![msg])),
+			}
+		}
+		queues
+	}
+}
 /// Sender that returns error if `X8` junction and stops routing
 pub struct TestSendXcmErrX8;
 impl SendXcm for TestSendXcmErrX8 {
@@ -165,6 +187,17 @@ impl SendXcm for TestSendXcmErrX8 {
 		}
 	}
 }
+impl QuoteDelivery for TestSendXcmErrX8 {
+	fn quote_delivery(_dest: &MultiLocation, _msg: &Xcm<()>) -> Option<MultiAssets> {
+		Some(DeliveryPrice::get())
+	}
+}
+impl InspectMessageQueues for TestSendXcmErrX8 {
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		// Messages share the queue of `TestSendXcm`, which reports them.
+		Vec::new()
+	}
+}
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
@@ -218,7 +251,7 @@ impl pallet_balances::Config for Test {
 parameter_types! {
 	pub const RelayLocation: MultiLocation = Here.into();
 	pub const AnyNetwork: NetworkId = NetworkId::Any;
-	pub Ancestry: MultiLocation = Here.into();
+	pub UniversalLocation: InteriorMultiLocation = X1(GlobalConsensus(NetworkId::Polkadot));
 	pub UnitWeightCost: u64 = 1_000;
 }
 
@@ -257,7 +290,7 @@ impl xcm_executor::Config for XcmConfig {
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = ();
 	type IsTeleporter = Case<TrustedAssets>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
 	type Trader = FixedRateOfFungible<CurrencyPerSecond, ()>;
@@ -265,12 +298,18 @@ impl xcm_executor::Config for XcmConfig {
 	type AssetTrap = XcmPallet;
 	type AssetClaims = XcmPallet;
 	type SubscriptionService = XcmPallet;
+	type FeeManager = XcmFeesToAccount<Nothing, LocalAssetTransactor, ()>;
+	type AssetLocker = ();
+	type MessageExporter = ();
+	type UniversalAliases = Nothing;
 }
 
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, AnyNetwork>;
 
 parameter_types! {
 	pub static AdvertisedXcmVersion: pallet_xcm::XcmVersion = 2;
+	// Delivery is free by default.
+	pub static DeliveryPrice: MultiAssets = MultiAssets::new();
 }
 
 impl pallet_xcm::Config for Test {
@@ -283,11 +322,12 @@ impl pallet_xcm::Config for Test {
 	type XcmTeleportFilter = Everything;
 	type XcmReserveTransferFilter = Everything;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
-	type LocationInverter = LocationInverter<Ancestry>;
+	type UniversalLocation = UniversalLocation;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 	type AdvertisedXcmVersion = AdvertisedXcmVersion;
+	type MaxIndexedAssetTraps = frame_support::traits::ConstU32<2>;
 }
 
 impl origin::Config for Test {}
@@ -302,6 +342,14 @@ pub(crate) fn last_event() -> RuntimeEvent {
 	System::events().pop().expect("RuntimeEvent expected").event
 }
 
+/// The outcome reported by the last event, which must be `Attempted`.
+pub(crate) fn last_attempted_outcome() -> Outcome {
+	match last_event() {
+		RuntimeEvent::XcmPallet(crate::Event::Attempted(outcome, _)) => outcome,
+		event => panic!("expected an `Attempted` event, got {:?}", event),
+	}
+}
+
 pub(crate) fn last_events(n: usize) -> Vec<RuntimeEvent> {
 	System::events().into_iter().map(|e| e.event).rev().take(n).rev().collect()
 }
@@ -15,13 +15,14 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	mock::*, AssetTraps, CurrentMigration, Error, LatestVersionedMultiLocation, Queries,
-	QueryStatus, VersionDiscoveryQueue, VersionNotifiers, VersionNotifyTargets,
+	mock::*, AssetTraps, CurrentMigration, Error, IndexedAssetTraps, LatestVersionedMultiLocation,
+	Queries, QueryStatus, VersionDiscoveryQueue, VersionNotifiers, VersionNotifyTargets,
+	XcmPaymentApiError,
 };
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{Currency, Hooks},
-	weights::Weight,
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
 };
 use polkadot_parachain::primitives::Id as ParaId;
 use sp_runtime::traits::{AccountIdConversion, BlakeTwo256, Hash};
@@ -186,13 +187,49 @@ fn send_works() {
 					.collect())
 			)],
 		);
+		// The message is identified as it is delivered to the relay chain, which is only known to
+		// support the safe XCM version.
+		let delivered = VersionedXcm::from(sent_xcm()[0].1.clone()).into_version(2).unwrap();
+		let id = xcm_executor::message_id(&delivered);
 		assert_eq!(
 			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Sent(sender, RelayLocation::get(), message))
+			RuntimeEvent::XcmPallet(crate::Event::Sent(sender, RelayLocation::get(), message, id))
 		);
 	});
 }
 
+/// Test that the delivery fees of messages sent with `send` are charged to the sender.
+#[test]
+fn send_charges_delivery_fees() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let message = Xcm(vec![ClearOrigin]);
+		let versioned_dest = Box::new(RelayLocation::get().into());
+		let versioned_message = Box::new(VersionedXcm::from(message.clone()));
+
+		DeliveryPrice::set((Here, INITIAL_BALANCE + 1).into());
+		assert_noop!(
+			XcmPallet::send(
+				RuntimeOrigin::signed(ALICE),
+				versioned_dest.clone(),
+				versioned_message.clone()
+			),
+			Error::<Test>::FeesNotMet
+		);
+
+		DeliveryPrice::set((Here, SEND_AMOUNT).into());
+		assert_ok!(XcmPallet::send(
+			RuntimeOrigin::signed(ALICE),
+			versioned_dest,
+			versioned_message
+		));
+		assert_eq!(sent_xcm().len(), 1);
+		// Fees are burned.
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		assert_eq!(Balances::total_issuance(), INITIAL_BALANCE - SEND_AMOUNT);
+	});
+}
+
 /// Test that sending an `XCM` message fails when the `XcmRouter` blocks the
 /// matching message format
 ///
@@ -258,10 +295,7 @@ fn teleport_assets_works() {
 		);
 		let versioned_sent = VersionedXcm::from(sent_xcm().into_iter().next().unwrap().1);
 		let _check_v0_ok: xcm::v0::Xcm<()> = versioned_sent.try_into().unwrap();
-		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
-		);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
 	});
 }
 
@@ -302,10 +336,7 @@ fn limited_teleport_assets_works() {
 		);
 		let versioned_sent = VersionedXcm::from(sent_xcm().into_iter().next().unwrap().1);
 		let _check_v0_ok: xcm::v0::Xcm<()> = versioned_sent.try_into().unwrap();
-		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
-		);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
 	});
 }
 
@@ -344,10 +375,7 @@ fn unlimited_teleport_assets_works() {
 				]),
 			)]
 		);
-		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
-		);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
 	});
 }
 
@@ -392,10 +420,7 @@ fn reserve_transfer_assets_works() {
 		);
 		let versioned_sent = VersionedXcm::from(sent_xcm().into_iter().next().unwrap().1);
 		let _check_v0_ok: xcm::v0::Xcm<()> = versioned_sent.try_into().unwrap();
-		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
-		);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
 	});
 }
 
@@ -441,10 +466,7 @@ fn limited_reserve_transfer_assets_works() {
 		);
 		let versioned_sent = VersionedXcm::from(sent_xcm().into_iter().next().unwrap().1);
 		let _check_v0_ok: xcm::v0::Xcm<()> = versioned_sent.try_into().unwrap();
-		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
-		);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
 	});
 }
 
@@ -488,10 +510,7 @@ fn unlimited_reserve_transfer_assets_works() {
 				]),
 			)]
 		);
-		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
-		);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
 	});
 }
 
@@ -521,9 +540,134 @@ fn execute_withdraw_to_deposit_works() {
 		));
 		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
 		assert_eq!(Balances::total_balance(&BOB), SEND_AMOUNT);
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(weight));
+	});
+}
+
+/// Test the message IDs of `Attempted` events
+///
+/// Asserts that a message is identified by its topic if it sets one, or by its hash otherwise.
+#[test]
+fn attempted_messages_are_identified_by_topic_or_hash() {
+	let balances = vec![(ALICE, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		use codec::Encode;
+		let alice: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let message = Xcm(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: alice },
+		]);
+		let weight = 3 * BaseXcmWeight::get();
+		let versioned = VersionedXcm::<RuntimeCall>::from(message.clone());
+		let hash = sp_io::hashing::blake2_256(&versioned.encode());
+		assert_ok!(XcmPallet::execute(RuntimeOrigin::signed(ALICE), Box::new(versioned), weight));
 		assert_eq!(
 			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight)))
+			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight), hash))
+		);
+
+		let mut message = message;
+		message.0.push(SetTopic([7; 32]));
+		let weight = 4 * BaseXcmWeight::get();
+		assert_ok!(XcmPallet::execute(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedXcm::from(message)),
+			weight
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(weight), [7; 32]))
+		);
+	});
+}
+
+/// Test `dry_run_xcm`
+///
+/// Asserts that the effects of executing an XCM are reported without it being sent through the
+/// pallet.
+#[test]
+fn dry_run_xcm_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let origin: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let dest: MultiLocation = Parachain(PARA_ID).into();
+		// A message queued beforehand is not reported as forwarded by the dry run.
+		assert_ok!(TestSendXcm::send_xcm(dest.clone(), Xcm(vec![ClearOrigin])));
+
+		let effects = XcmPallet::dry_run_xcm::<XcmConfig>(
+			origin.into(),
+			VersionedXcm::from(Xcm(vec![
+				WithdrawAsset((Here, SEND_AMOUNT).into()),
+				buy_execution((Here, SEND_AMOUNT)),
+				DepositReserveAsset {
+					assets: All.into(),
+					max_assets: 1,
+					dest: dest.clone(),
+					xcm: Xcm(vec![]),
+				},
+			])),
+		)
+		.unwrap();
+
+		let weight = 3 * BaseXcmWeight::get();
+		assert_eq!(effects.execution_result, Outcome::Complete(weight));
+		assert_eq!(effects.weight_used, weight);
+		let sent: MultiAssets = (Here, SEND_AMOUNT).into();
+		assert_eq!(
+			effects.holding_per_step,
+			vec![(0, sent.clone().into()), (1, sent.into()), (2, MultiAssets::new().into()),]
+		);
+		assert_eq!(
+			effects.forwarded_xcms,
+			vec![(
+				dest.into(),
+				vec![VersionedXcm::from(Xcm(vec![
+					ReserveAssetDeposited((Parent, SEND_AMOUNT).into()),
+					ClearOrigin,
+				]))],
+			)]
+		);
+		assert!(!effects.emitted_events.is_empty());
+		assert!(effects
+			.emitted_events
+			.iter()
+			.all(|event| matches!(event, RuntimeEvent::Balances(..))));
+	});
+}
+
+/// Test the queries of `XcmPaymentApi`
+///
+/// Asserts that the weight and fee of an XCM are those which the executor would charge.
+#[test]
+fn payment_queries_work() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let here: AssetId = Concrete(Here.into());
+		assert_eq!(XcmPallet::query_acceptable_payment_assets::<XcmConfig>(), vec![here.clone()]);
+
+		let message = VersionedXcm::from(Xcm(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Here.into() },
+		]));
+		assert_eq!(XcmPallet::query_xcm_weight::<XcmConfig>(message), Ok(3 * BaseXcmWeight::get()));
+		let too_long = VersionedXcm::from(Xcm(vec![ClearOrigin; 101]));
+		assert_eq!(
+			XcmPallet::query_xcm_weight::<XcmConfig>(too_long),
+			Err(XcmPaymentApiError::WeightNotComputable)
+		);
+
+		// `CurrencyPerSecond` charges one unit per second of weight.
+		let second = WEIGHT_PER_SECOND.ref_time();
+		assert_eq!(XcmPallet::query_weight_to_asset_fee::<XcmConfig>(5 * second, here), Ok(5));
+		assert_eq!(
+			XcmPallet::query_weight_to_asset_fee::<XcmConfig>(second, Concrete(Parent.into())),
+			Err(XcmPaymentApiError::AssetNotFound)
 		);
 	});
 }
@@ -537,20 +681,18 @@ fn trapped_assets_can_be_claimed() {
 		let dest: MultiLocation =
 			Junction::AccountId32 { network: NetworkId::Any, id: BOB.into() }.into();
 
-		assert_ok!(XcmPallet::execute(
-			RuntimeOrigin::signed(ALICE),
-			Box::new(VersionedXcm::from(Xcm(vec![
-				WithdrawAsset((Here, SEND_AMOUNT).into()),
-				buy_execution((Here, SEND_AMOUNT)),
-				// Don't propagated the error into the result.
-				SetErrorHandler(Xcm(vec![ClearError])),
-				// This will make an error.
-				Trap(0),
-				// This would succeed, but we never get to it.
-				DepositAsset { assets: All.into(), max_assets: 1, beneficiary: dest.clone() },
-			]))),
-			weight
-		));
+		let message = VersionedXcm::from(Xcm(vec![
+			WithdrawAsset((Here, SEND_AMOUNT).into()),
+			buy_execution((Here, SEND_AMOUNT)),
+			// Don't propagated the error into the result.
+			SetErrorHandler(Xcm(vec![ClearError])),
+			// This will make an error.
+			Trap(0),
+			// This would succeed, but we never get to it.
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: dest.clone() },
+		]));
+		let id = xcm_executor::message_id(&message);
+		assert_ok!(XcmPallet::execute(RuntimeOrigin::signed(ALICE), Box::new(message), weight));
 		let source: MultiLocation =
 			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
 		let trapped = AssetTraps::<Test>::iter().collect::<Vec<_>>();
@@ -560,9 +702,10 @@ fn trapped_assets_can_be_claimed() {
 			last_events(2),
 			vec![
 				RuntimeEvent::XcmPallet(crate::Event::AssetsTrapped(hash.clone(), source, vma)),
-				RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Complete(
-					5 * BaseXcmWeight::get()
-				)))
+				RuntimeEvent::XcmPallet(crate::Event::Attempted(
+					Outcome::Complete(5 * BaseXcmWeight::get()),
+					id
+				))
 			]
 		);
 		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
@@ -597,15 +740,75 @@ fn trapped_assets_can_be_claimed() {
 			weight
 		));
 		assert_eq!(
-			last_event(),
-			RuntimeEvent::XcmPallet(crate::Event::Attempted(Outcome::Incomplete(
-				BaseXcmWeight::get(),
-				XcmError::UnknownClaim
-			)))
+			last_attempted_outcome(),
+			Outcome::Incomplete(BaseXcmWeight::get(), XcmError::UnknownClaim)
 		);
 	});
 }
 
+/// Test `trapped_assets` and `claim_trapped_assets`
+///
+/// Asserts that the latest traps of an origin are indexed, and that several traps can be claimed
+/// at once, whether or not they are indexed.
+#[test]
+fn indexed_trapped_assets_can_be_claimed_in_batch() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: ALICE.into() }.into();
+		let dest: MultiLocation =
+			Junction::AccountId32 { network: NetworkId::Any, id: BOB.into() }.into();
+		let trap = |amount: u128| {
+			assert_ok!(XcmPallet::execute(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(VersionedXcm::from(Xcm(vec![
+					WithdrawAsset((Here, amount).into()),
+					buy_execution((Here, amount)),
+					SetErrorHandler(Xcm(vec![ClearError])),
+					Trap(0),
+				]))),
+				5 * BaseXcmWeight::get()
+			));
+		};
+		let vma = |amount: u128| VersionedMultiAssets::from(MultiAssets::from((Here, amount)));
+
+		trap(1);
+		trap(2);
+		trap(3);
+		// Only the latest two traps are indexed.
+		assert_eq!(XcmPallet::trapped_assets(source.clone().into()), vec![vma(2), vma(3)]);
+		assert_eq!(AssetTraps::<Test>::iter().count(), 3);
+
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::signed(ALICE),
+				vec![],
+				Box::new(dest.clone().into())
+			),
+			Error::<Test>::Empty
+		);
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(ALICE),
+			vec![vma(2), vma(1)],
+			Box::new(dest.clone().into()),
+		));
+		assert_eq!(last_attempted_outcome(), Outcome::Complete(3 * BaseXcmWeight::get()));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - 6);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + 3);
+		assert_eq!(XcmPallet::trapped_assets(source.clone().into()), vec![vma(3)]);
+
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(ALICE),
+			vec![vma(3)],
+			Box::new(dest.into()),
+		));
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + 6);
+		assert_eq!(XcmPallet::trapped_assets(source.into()), vec![]);
+		assert_eq!(AssetTraps::<Test>::iter().count(), 0);
+		assert_eq!(IndexedAssetTraps::<Test>::iter().count(), 0);
+	});
+}
+
 #[test]
 fn fake_latest_versioned_multilocation_works() {
 	use codec::Encode;
@@ -632,7 +835,7 @@ fn basic_subscription_works() {
 		);
 		assert_eq!(
 			VersionNotifiers::<Test>::iter().collect::<Vec<_>>(),
-			vec![(2, remote.clone().into(), 0)]
+			vec![(XCM_VERSION, remote.clone().into(), 0)]
 		);
 
 		assert_eq!(
@@ -813,9 +1016,9 @@ fn subscription_side_upgrades_work_with_notify() {
 		assert_eq!(
 			contents,
 			vec![
-				(2, Parachain(1000).into().versioned(), (69, 0, 2)),
-				(2, Parachain(1001).into().versioned(), (70, 0, 2)),
-				(2, Parachain(1002).into().versioned(), (71, 0, 2)),
+				(XCM_VERSION, Parachain(1000).into().versioned(), (69, 0, 2)),
+				(XCM_VERSION, Parachain(1001).into().versioned(), (70, 0, 2)),
+				(XCM_VERSION, Parachain(1002).into().versioned(), (71, 0, 2)),
 			]
 		);
 	});
@@ -842,9 +1045,9 @@ fn subscription_side_upgrades_work_without_notify() {
 		assert_eq!(
 			contents,
 			vec![
-				(2, Parachain(1000).into().versioned(), (69, 0, 2)),
-				(2, Parachain(1001).into().versioned(), (70, 0, 2)),
-				(2, Parachain(1002).into().versioned(), (71, 0, 2)),
+				(XCM_VERSION, Parachain(1000).into().versioned(), (69, 0, 2)),
+				(XCM_VERSION, Parachain(1001).into().versioned(), (70, 0, 2)),
+				(XCM_VERSION, Parachain(1002).into().versioned(), (71, 0, 2)),
 			]
 		);
 	});
@@ -1018,9 +1221,9 @@ fn subscription_side_upgrades_work_with_multistage_notify() {
 		assert_eq!(
 			contents,
 			vec![
-				(2, Parachain(1000).into().versioned(), (69, 0, 2)),
-				(2, Parachain(1001).into().versioned(), (70, 0, 2)),
-				(2, Parachain(1002).into().versioned(), (71, 0, 2)),
+				(XCM_VERSION, Parachain(1000).into().versioned(), (69, 0, 2)),
+				(XCM_VERSION, Parachain(1001).into().versioned(), (70, 0, 2)),
+				(XCM_VERSION, Parachain(1002).into().versioned(), (71, 0, 2)),
 			]
 		);
 	});
@@ -33,7 +33,7 @@ pub fn generate_conversion_functions(input: proc_macro::TokenStream) -> Result<T
 	})
 }
 
-fn generate_conversion_from_tuples(max_parents: u8) -> TokenStream {
+pub(crate) fn generate_conversion_from_tuples(max_parents: u8) -> TokenStream {
 	let mut from_tuples = (0..8usize)
 		.map(|num_junctions| {
 			let junctions =
@@ -31,9 +31,10 @@ use scale_info::TypeInfo;
 pub mod v0;
 pub mod v1;
 pub mod v2;
+pub mod v3;
 
 pub mod latest {
-	pub use super::v2::*;
+	pub use super::v3::*;
 }
 
 mod double_encoded;
@@ -71,8 +72,12 @@ pub trait IntoVersion: Sized {
 #[codec(encode_bound())]
 #[codec(decode_bound())]
 pub enum VersionedMultiLocation {
+	#[codec(index = 0)]
 	V0(v0::MultiLocation),
+	#[codec(index = 1)]
 	V1(v1::MultiLocation),
+	#[codec(index = 3)]
+	V3(v3::MultiLocation),
 }
 
 impl IntoVersion for VersionedMultiLocation {
@@ -80,6 +85,7 @@ impl IntoVersion for VersionedMultiLocation {
 		Ok(match n {
 			0 => Self::V0(self.try_into()?),
 			1 | 2 => Self::V1(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
@@ -91,9 +97,15 @@ impl From<v0::MultiLocation> for VersionedMultiLocation {
 	}
 }
 
-impl<T: Into<v1::MultiLocation>> From<T> for VersionedMultiLocation {
+impl From<v1::MultiLocation> for VersionedMultiLocation {
+	fn from(x: v1::MultiLocation) -> Self {
+		VersionedMultiLocation::V1(x)
+	}
+}
+
+impl<T: Into<v3::MultiLocation>> From<T> for VersionedMultiLocation {
 	fn from(x: T) -> Self {
-		VersionedMultiLocation::V1(x.into())
+		VersionedMultiLocation::V3(x.into())
 	}
 }
 
@@ -104,6 +116,7 @@ impl TryFrom<VersionedMultiLocation> for v0::MultiLocation {
 		match x {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
+			V3(x) => V1(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -115,6 +128,19 @@ impl TryFrom<VersionedMultiLocation> for v1::MultiLocation {
 		match x {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedMultiLocation> for v3::MultiLocation {
+	type Error = ();
+	fn try_from(x: VersionedMultiLocation) -> Result<Self, ()> {
+		use VersionedMultiLocation::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -128,6 +154,7 @@ pub enum VersionedResponse {
 	V0(v0::Response),
 	V1(v1::Response),
 	V2(v2::Response),
+	V3(v3::Response),
 }
 
 impl IntoVersion for VersionedResponse {
@@ -136,6 +163,7 @@ impl IntoVersion for VersionedResponse {
 			0 => Self::V0(self.try_into()?),
 			1 => Self::V1(self.try_into()?),
 			2 => Self::V2(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
@@ -153,9 +181,15 @@ impl From<v1::Response> for VersionedResponse {
 	}
 }
 
-impl<T: Into<v2::Response>> From<T> for VersionedResponse {
+impl From<v2::Response> for VersionedResponse {
+	fn from(x: v2::Response) -> Self {
+		VersionedResponse::V2(x)
+	}
+}
+
+impl<T: Into<v3::Response>> From<T> for VersionedResponse {
 	fn from(x: T) -> Self {
-		VersionedResponse::V2(x.into())
+		VersionedResponse::V3(x.into())
 	}
 }
 
@@ -167,6 +201,7 @@ impl TryFrom<VersionedResponse> for v0::Response {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
 			V2(x) => VersionedResponse::V1(x.try_into()?).try_into(),
+			V3(x) => VersionedResponse::V2(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -179,6 +214,7 @@ impl TryFrom<VersionedResponse> for v1::Response {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
 			V2(x) => x.try_into(),
+			V3(x) => VersionedResponse::V2(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -191,6 +227,20 @@ impl TryFrom<VersionedResponse> for v2::Response {
 			V0(x) => VersionedResponse::V1(x.try_into()?).try_into(),
 			V1(x) => x.try_into(),
 			V2(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedResponse> for v3::Response {
+	type Error = ();
+	fn try_from(x: VersionedResponse) -> Result<Self, ()> {
+		use VersionedResponse::*;
+		match x {
+			V0(x) => VersionedResponse::V1(x.try_into()?).try_into(),
+			V1(x) => VersionedResponse::V2(x.try_into()?).try_into(),
+			V2(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -201,8 +251,12 @@ impl TryFrom<VersionedResponse> for v2::Response {
 #[codec(encode_bound())]
 #[codec(decode_bound())]
 pub enum VersionedMultiAsset {
+	#[codec(index = 0)]
 	V0(v0::MultiAsset),
+	#[codec(index = 1)]
 	V1(v1::MultiAsset),
+	#[codec(index = 3)]
+	V3(v3::MultiAsset),
 }
 
 impl IntoVersion for VersionedMultiAsset {
@@ -210,6 +264,7 @@ impl IntoVersion for VersionedMultiAsset {
 		Ok(match n {
 			0 => Self::V0(self.try_into()?),
 			1 | 2 => Self::V1(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
@@ -221,9 +276,15 @@ impl From<v0::MultiAsset> for VersionedMultiAsset {
 	}
 }
 
-impl<T: Into<v1::MultiAsset>> From<T> for VersionedMultiAsset {
+impl From<v1::MultiAsset> for VersionedMultiAsset {
+	fn from(x: v1::MultiAsset) -> Self {
+		VersionedMultiAsset::V1(x)
+	}
+}
+
+impl<T: Into<v3::MultiAsset>> From<T> for VersionedMultiAsset {
 	fn from(x: T) -> Self {
-		VersionedMultiAsset::V1(x.into())
+		VersionedMultiAsset::V3(x.into())
 	}
 }
 
@@ -234,6 +295,7 @@ impl TryFrom<VersionedMultiAsset> for v0::MultiAsset {
 		match x {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
+			V3(x) => V1(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -245,6 +307,19 @@ impl TryFrom<VersionedMultiAsset> for v1::MultiAsset {
 		match x {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedMultiAsset> for v3::MultiAsset {
+	type Error = ();
+	fn try_from(x: VersionedMultiAsset) -> Result<Self, ()> {
+		use VersionedMultiAsset::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -255,8 +330,12 @@ impl TryFrom<VersionedMultiAsset> for v1::MultiAsset {
 #[codec(encode_bound())]
 #[codec(decode_bound())]
 pub enum VersionedMultiAssets {
+	#[codec(index = 0)]
 	V0(Vec<v0::MultiAsset>),
+	#[codec(index = 1)]
 	V1(v1::MultiAssets),
+	#[codec(index = 3)]
+	V3(v3::MultiAssets),
 }
 
 impl IntoVersion for VersionedMultiAssets {
@@ -264,6 +343,7 @@ impl IntoVersion for VersionedMultiAssets {
 		Ok(match n {
 			0 => Self::V0(self.try_into()?),
 			1 | 2 => Self::V1(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
@@ -275,9 +355,15 @@ impl From<Vec<v0::MultiAsset>> for VersionedMultiAssets {
 	}
 }
 
-impl<T: Into<v1::MultiAssets>> From<T> for VersionedMultiAssets {
+impl From<v1::MultiAssets> for VersionedMultiAssets {
+	fn from(x: v1::MultiAssets) -> Self {
+		VersionedMultiAssets::V1(x)
+	}
+}
+
+impl<T: Into<v3::MultiAssets>> From<T> for VersionedMultiAssets {
 	fn from(x: T) -> Self {
-		VersionedMultiAssets::V1(x.into())
+		VersionedMultiAssets::V3(x.into())
 	}
 }
 
@@ -288,6 +374,7 @@ impl TryFrom<VersionedMultiAssets> for Vec<v0::MultiAsset> {
 		match x {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
+			V3(x) => V1(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -299,6 +386,19 @@ impl TryFrom<VersionedMultiAssets> for v1::MultiAssets {
 		match x {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl TryFrom<VersionedMultiAssets> for v3::MultiAssets {
+	type Error = ();
+	fn try_from(x: VersionedMultiAssets) -> Result<Self, ()> {
+		use VersionedMultiAssets::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -313,6 +413,17 @@ pub enum VersionedXcm<RuntimeCall> {
 	V0(v0::Xcm<RuntimeCall>),
 	V1(v1::Xcm<RuntimeCall>),
 	V2(v2::Xcm<RuntimeCall>),
+	V3(v3::Xcm<RuntimeCall>),
+}
+
+impl<C> VersionedXcm<C> {
+	/// Return the topic of the message, if it is of a version which supports topics and sets one.
+	pub fn topic(&self) -> Option<v3::XcmHash> {
+		match self {
+			VersionedXcm::V3(x) => x.topic(),
+			_ => None,
+		}
+	}
 }
 
 impl<C> IntoVersion for VersionedXcm<C> {
@@ -321,6 +432,7 @@ impl<C> IntoVersion for VersionedXcm<C> {
 			0 => Self::V0(self.try_into()?),
 			1 => Self::V1(self.try_into()?),
 			2 => Self::V2(self.try_into()?),
+			3 => Self::V3(self.try_into()?),
 			_ => return Err(()),
 		})
 	}
@@ -344,6 +456,12 @@ impl<RuntimeCall> From<v2::Xcm<RuntimeCall>> for VersionedXcm<RuntimeCall> {
 	}
 }
 
+impl<RuntimeCall> From<v3::Xcm<RuntimeCall>> for VersionedXcm<RuntimeCall> {
+	fn from(x: v3::Xcm<RuntimeCall>) -> Self {
+		VersionedXcm::V3(x)
+	}
+}
+
 impl<RuntimeCall> TryFrom<VersionedXcm<RuntimeCall>> for v0::Xcm<RuntimeCall> {
 	type Error = ();
 	fn try_from(x: VersionedXcm<RuntimeCall>) -> Result<Self, ()> {
@@ -352,6 +470,7 @@ impl<RuntimeCall> TryFrom<VersionedXcm<RuntimeCall>> for v0::Xcm<RuntimeCall> {
 			V0(x) => Ok(x),
 			V1(x) => x.try_into(),
 			V2(x) => V1(x.try_into()?).try_into(),
+			V3(x) => V1(v2::Xcm::<RuntimeCall>::try_from(x)?.try_into()?).try_into(),
 		}
 	}
 }
@@ -364,6 +483,7 @@ impl<RuntimeCall> TryFrom<VersionedXcm<RuntimeCall>> for v1::Xcm<RuntimeCall> {
 			V0(x) => x.try_into(),
 			V1(x) => Ok(x),
 			V2(x) => x.try_into(),
+			V3(x) => V2(x.try_into()?).try_into(),
 		}
 	}
 }
@@ -376,6 +496,20 @@ impl<RuntimeCall> TryFrom<VersionedXcm<RuntimeCall>> for v2::Xcm<RuntimeCall> {
 			V0(x) => V1(x.try_into()?).try_into(),
 			V1(x) => x.try_into(),
 			V2(x) => Ok(x),
+			V3(x) => x.try_into(),
+		}
+	}
+}
+
+impl<RuntimeCall> TryFrom<VersionedXcm<RuntimeCall>> for v3::Xcm<RuntimeCall> {
+	type Error = ();
+	fn try_from(x: VersionedXcm<RuntimeCall>) -> Result<Self, ()> {
+		use VersionedXcm::*;
+		match x {
+			V0(x) => V1(x.try_into()?).try_into(),
+			V1(x) => V2(x.try_into()?).try_into(),
+			V2(x) => x.try_into(),
+			V3(x) => Ok(x),
 		}
 	}
 }
@@ -431,6 +565,17 @@ impl WrapVersion for AlwaysV2 {
 	}
 }
 
+/// `WrapVersion` implementation which attempts to always convert the XCM to version 3 before wrapping it.
+pub struct AlwaysV3;
+impl WrapVersion for AlwaysV3 {
+	fn wrap_version<RuntimeCall>(
+		_: &latest::MultiLocation,
+		xcm: impl Into<VersionedXcm<RuntimeCall>>,
+	) -> Result<VersionedXcm<RuntimeCall>, ()> {
+		Ok(VersionedXcm::<RuntimeCall>::V3(xcm.into().try_into()?))
+	}
+}
+
 /// `WrapVersion` implementation which attempts to always convert the XCM to the latest version before wrapping it.
 pub type AlwaysLatest = AlwaysV1;
 
@@ -439,8 +584,8 @@ pub type AlwaysRelease = AlwaysV0;
 
 pub mod prelude {
 	pub use super::{
-		latest::prelude::*, AlwaysLatest, AlwaysRelease, AlwaysV0, AlwaysV1, AlwaysV2, IntoVersion,
-		Unsupported, Version as XcmVersion, VersionedMultiAsset, VersionedMultiAssets,
+		latest::prelude::*, AlwaysLatest, AlwaysRelease, AlwaysV0, AlwaysV1, AlwaysV2, AlwaysV3,
+		IntoVersion, Unsupported, Version as XcmVersion, VersionedMultiAsset, VersionedMultiAssets,
 		VersionedMultiLocation, VersionedResponse, VersionedXcm, WrapVersion,
 	};
 }
@@ -465,8 +610,15 @@ pub mod opaque {
 		pub use crate::v2::opaque::{Instruction, Xcm};
 	}
 
+	pub mod v3 {
+		// Everything from v3
+		pub use crate::v3::*;
+		// Then override with the opaque types in v3
+		pub use crate::v3::opaque::{Instruction, Xcm};
+	}
+
 	pub mod latest {
-		pub use super::v2::*;
+		pub use super::v3::*;
 	}
 
 	/// The basic `VersionedXcm` type which just uses the `Vec<u8>` as an encoded call.
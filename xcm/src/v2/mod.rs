@@ -50,7 +50,10 @@
 //!   `DepositAsset` instructions. Failing that, dispatch calls to `teleport_assets` and
 //!   `reserve_transfer_assets` will fail with `UnweighableMessage`.
 
-use super::v1::{Order as OldOrder, Response as OldResponse, Xcm as OldXcm};
+use super::{
+	v1::{Order as OldOrder, Response as OldResponse, Xcm as OldXcm},
+	v3::{Instruction as NewInstruction, Response as NewResponse, Xcm as NewXcm},
+};
 use crate::{DoubleEncoded, GetWeight};
 use alloc::{vec, vec::Vec};
 use core::{fmt::Debug, result};
@@ -60,7 +63,9 @@ use scale_info::TypeInfo;
 
 mod traits;
 
-pub use traits::{Error, ExecuteXcm, Outcome, Result, SendError, SendResult, SendXcm};
+pub use traits::{
+	Error, ExecuteXcm, Outcome, QuoteDelivery, Result, SendError, SendResult, SendXcm,
+};
 // These parts of XCM v1 have been unchanged in XCM v2, and are re-imported here.
 pub use super::v1::{
 	Ancestor, AncestorThen, AssetId, AssetInstance, BodyId, BodyPart, Fungibility,
@@ -154,8 +159,8 @@ pub mod prelude {
 			MultiAssetFilter::{self, *},
 			MultiAssets, MultiLocation,
 			NetworkId::{self, *},
-			OriginKind, Outcome, Parent, ParentThen, QueryId, Response, Result as XcmResult,
-			SendError, SendResult, SendXcm,
+			OriginKind, Outcome, Parent, ParentThen, QueryId, QuoteDelivery, Response,
+			Result as XcmResult, SendError, SendResult, SendXcm,
 			WeightLimit::{self, *},
 			WildFungibility::{self, Fungible as WildFungible, NonFungible as WildNonFungible},
 			WildMultiAsset::{self, *},
@@ -876,6 +881,113 @@ impl<RuntimeCall> TryFrom<OldOrder<RuntimeCall>> for Instruction<RuntimeCall> {
 	}
 }
 
+// Convert from a v3 response to a v2 response.
+impl TryFrom<NewResponse> for Response {
+	type Error = ();
+	fn try_from(response: NewResponse) -> result::Result<Self, ()> {
+		Ok(match response {
+			NewResponse::Null => Self::Null,
+			NewResponse::Assets(assets) => Self::Assets(assets.try_into()?),
+			NewResponse::ExecutionResult(None) => Self::ExecutionResult(None),
+			NewResponse::ExecutionResult(Some((index, error))) =>
+				Self::ExecutionResult(Some((index, error.try_into()?))),
+			NewResponse::Version(version) => Self::Version(version),
+		})
+	}
+}
+
+impl<RuntimeCall> TryFrom<NewXcm<RuntimeCall>> for Xcm<RuntimeCall> {
+	type Error = ();
+	fn try_from(new_xcm: NewXcm<RuntimeCall>) -> result::Result<Self, ()> {
+		Ok(Xcm(new_xcm.0.into_iter().map(TryInto::try_into).collect::<result::Result<_, _>>()?))
+	}
+}
+
+impl<RuntimeCall> TryFrom<NewInstruction<RuntimeCall>> for Instruction<RuntimeCall> {
+	type Error = ();
+	fn try_from(instruction: NewInstruction<RuntimeCall>) -> result::Result<Self, ()> {
+		use NewInstruction::*;
+		Ok(match instruction {
+			WithdrawAsset(assets) => Self::WithdrawAsset(assets.try_into()?),
+			ReserveAssetDeposited(assets) => Self::ReserveAssetDeposited(assets.try_into()?),
+			ReceiveTeleportedAsset(assets) => Self::ReceiveTeleportedAsset(assets.try_into()?),
+			QueryResponse { query_id, response, max_weight } =>
+				Self::QueryResponse { query_id, response: response.try_into()?, max_weight },
+			TransferAsset { assets, beneficiary } => Self::TransferAsset {
+				assets: assets.try_into()?,
+				beneficiary: beneficiary.try_into()?,
+			},
+			TransferReserveAsset { assets, dest, xcm } => Self::TransferReserveAsset {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				Self::HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => Self::HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				Self::HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_type, require_weight_at_most, call } =>
+				Self::Transact { origin_type, require_weight_at_most, call },
+			ReportError { query_id, dest, max_response_weight } =>
+				Self::ReportError { query_id, dest: dest.try_into()?, max_response_weight },
+			DepositAsset { assets, max_assets, beneficiary } => Self::DepositAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				beneficiary: beneficiary.try_into()?,
+			},
+			DepositReserveAsset { assets, max_assets, dest, xcm } => Self::DepositReserveAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			ExchangeAsset { give, receive } =>
+				Self::ExchangeAsset { give: give.try_into()?, receive: receive.try_into()? },
+			InitiateReserveWithdraw { assets, reserve, xcm } => Self::InitiateReserveWithdraw {
+				assets: assets.try_into()?,
+				reserve: reserve.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			InitiateTeleport { assets, dest, xcm } => Self::InitiateTeleport {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			ReportHolding { response_info, assets } => Self::QueryHolding {
+				query_id: response_info.query_id,
+				dest: response_info.destination.try_into()?,
+				assets: assets.try_into()?,
+				max_response_weight: response_info.max_weight,
+			},
+			BuyExecution { fees, weight_limit } =>
+				Self::BuyExecution { fees: fees.try_into()?, weight_limit },
+			ClearOrigin => Self::ClearOrigin,
+			DescendOrigin(who) => Self::DescendOrigin(who.try_into()?),
+			RefundSurplus => Self::RefundSurplus,
+			SetErrorHandler(xcm) => Self::SetErrorHandler(xcm.try_into()?),
+			SetAppendix(xcm) => Self::SetAppendix(xcm.try_into()?),
+			ClearError => Self::ClearError,
+			ClaimAsset { assets, ticket } =>
+				Self::ClaimAsset { assets: assets.try_into()?, ticket: ticket.try_into()? },
+			Trap(code) => Self::Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				Self::SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => Self::UnsubscribeVersion,
+			ExpectAsset(..) |
+			ExpectOrigin(..) |
+			ExpectError(..) |
+			UniversalOrigin(..) |
+			ExportMessage { .. } |
+			LockAsset { .. } |
+			UnlockAsset { .. } |
+			NoteUnlockable { .. } |
+			RequestUnlock { .. } |
+			SetTopic(..) => return Err(()),
+		})
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{prelude::*, *};
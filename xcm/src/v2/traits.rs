@@ -21,6 +21,7 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 
 use super::*;
+use crate::v3::Error as NewError;
 
 #[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
 pub enum Error {
@@ -110,6 +111,42 @@ pub enum Error {
 	WeightNotComputable,
 }
 
+impl TryFrom<NewError> for Error {
+	type Error = ();
+	fn try_from(new_error: NewError) -> result::Result<Self, ()> {
+		use NewError::*;
+		Ok(match new_error {
+			Overflow => Self::Overflow,
+			Unimplemented => Self::Unimplemented,
+			UntrustedReserveLocation => Self::UntrustedReserveLocation,
+			UntrustedTeleportLocation => Self::UntrustedTeleportLocation,
+			MultiLocationFull => Self::MultiLocationFull,
+			MultiLocationNotInvertible => Self::MultiLocationNotInvertible,
+			BadOrigin => Self::BadOrigin,
+			InvalidLocation => Self::InvalidLocation,
+			AssetNotFound => Self::AssetNotFound,
+			FailedToTransactAsset(s) => Self::FailedToTransactAsset(s),
+			NotWithdrawable => Self::NotWithdrawable,
+			LocationCannotHold => Self::LocationCannotHold,
+			ExceedsMaxMessageSize => Self::ExceedsMaxMessageSize,
+			DestinationUnsupported => Self::DestinationUnsupported,
+			Transport(s) => Self::Transport(s),
+			Unroutable => Self::Unroutable,
+			UnknownClaim => Self::UnknownClaim,
+			FailedToDecode => Self::FailedToDecode,
+			MaxWeightInvalid => Self::MaxWeightInvalid,
+			NotHoldingFees => Self::NotHoldingFees,
+			TooExpensive => Self::TooExpensive,
+			Trap(code) => Self::Trap(code),
+			UnhandledXcmVersion => Self::UnhandledXcmVersion,
+			WeightLimitReached(weight) => Self::WeightLimitReached(weight),
+			Barrier => Self::Barrier,
+			WeightNotComputable => Self::WeightNotComputable,
+			ExpectationFalse | LockError | NoPermission | Unanchored => return Err(()),
+		})
+	}
+}
+
 impl From<SendError> for Error {
 	fn from(e: SendError) -> Self {
 		match e {
@@ -190,6 +227,14 @@ pub trait ExecuteXcm<RuntimeCall> {
 		weight_limit: Weight,
 		weight_credit: Weight,
 	) -> Outcome;
+
+	/// Deduct some `fees` from the account of `location`, usually to pay for the delivery of a
+	/// message sent on its behalf.
+	///
+	/// Charging fees is unsupported by default.
+	fn charge_fees(_location: impl Into<MultiLocation>, _fees: MultiAssets) -> Result {
+		Err(Error::Unimplemented)
+	}
 }
 
 impl<C> ExecuteXcm<C> for () {
@@ -303,6 +348,31 @@ pub trait SendXcm {
 	fn send_xcm(destination: impl Into<MultiLocation>, message: Xcm<()>) -> SendResult;
 }
 
+/// Utility for quoting the price of delivering an XCM message.
+///
+/// This is usually implemented by the same types as [`SendXcm`], and amalgamated in tuples the same
+/// way: in tuple format, the quote of the first item returning `Some` is used, so the items should
+/// be in the same order as the routers they price.
+pub trait QuoteDelivery {
+	/// Return the fees to be paid for delivering `message` to `destination`.
+	///
+	/// Returns `None` if `destination` is not reachable with this type, in which case other types
+	/// might be asked for a quote. Delivery is free if an empty set of assets is returned.
+	fn quote_delivery(destination: &MultiLocation, message: &Xcm<()>) -> Option<MultiAssets>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl QuoteDelivery for Tuple {
+	fn quote_delivery(destination: &MultiLocation, message: &Xcm<()>) -> Option<MultiAssets> {
+		for_tuples!( #(
+			if let Some(fees) = Tuple::quote_delivery(destination, message) {
+				return Some(fees)
+			}
+		)* );
+		None
+	}
+}
+
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 impl SendXcm for Tuple {
 	fn send_xcm(destination: impl Into<MultiLocation>, message: Xcm<()>) -> SendResult {
@@ -0,0 +1,1104 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # XCM Version 3
+//! Version 3 of the Cross-Consensus Message format data structures.
+//!
+//! ## Changes to be aware of
+//! Version 3 is a superset of version 2 for all but one instruction. Most changes are therefore
+//! automatically handled by the `try_from` and `from` conversion functions.
+//!
+//! ### Junctions
+//! - `GlobalConsensus` names the root of a consensus system other than the local one. Junctions,
+//!   locations and assets are redefined here to carry it, and any value containing it cannot be
+//!   converted to version 2.
+//!
+//! ### Instructions
+//! - `QueryHolding` is replaced by `ReportHolding`, which describes where and how to report back
+//!   using a `QueryResponseInfo`. This converts to and from `QueryHolding` losslessly.
+//! - `ExpectAsset`, `ExpectOrigin` and `ExpectError` allow a message to check the state of the
+//!   executor, throwing an `ExpectationFalse` error if it is not as expected.
+//! - `LockAsset`, `UnlockAsset`, `NoteUnlockable` and `RequestUnlock` allow assets to be locked
+//!   on one chain in favour of another.
+//! - `UniversalOrigin` allows a trusted location to act as the origin of a global consensus
+//!   system, and `ExportMessage` sends a message to another global consensus system.
+//! - `SetTopic` sets an identifier for the message, used to follow it across hops. So that the
+//!   identifier is never lost on the way, it can't be dropped when converting to version 2.
+//!
+//! None of the new instructions can be converted to version 2.
+//!
+//! ### Errors
+//! - New errors `ExpectationFalse`, `LockError`, `NoPermission` and `Unanchored` are introduced.
+//!   They cannot be converted to version 2.
+
+use super::v2::{Instruction as OldInstruction, Response as OldResponse, Xcm as OldXcm};
+use crate::{DoubleEncoded, GetWeight};
+use alloc::{vec, vec::Vec};
+use core::{fmt::Debug, result};
+use derivative::Derivative;
+use parity_scale_codec::{self, Decode, Encode};
+use scale_info::TypeInfo;
+
+mod junction;
+mod multiasset;
+mod multilocation;
+mod traits;
+
+pub use junction::Junction;
+pub use multiasset::{
+	AssetId, AssetInstance, Fungibility, MultiAsset, MultiAssetFilter, MultiAssets,
+	WildFungibility, WildMultiAsset,
+};
+pub use multilocation::{
+	Ancestor, AncestorThen, InteriorMultiLocation, Junctions, MultiLocation, Parent, ParentThen,
+};
+pub use traits::{
+	Error, ExecuteXcm, Outcome, QuoteDelivery, Result, SendError, SendResult, SendXcm,
+};
+// These parts of XCM v2 have been unchanged in XCM v3, and are re-imported here.
+pub use super::v2::{BodyId, BodyPart, NetworkId, OriginKind, QueryId, Weight, WeightLimit};
+
+/// This module's XCM version.
+pub const VERSION: super::Version = 3;
+
+/// An identifier of an XCM, used to correlate its handling across the systems which it passes
+/// through.
+pub type XcmHash = [u8; 32];
+
+#[derive(Derivative, Default, Encode, Decode, TypeInfo)]
+#[derivative(Clone(bound = ""), Eq(bound = ""), PartialEq(bound = ""), Debug(bound = ""))]
+#[codec(encode_bound())]
+#[codec(decode_bound())]
+#[scale_info(bounds(), skip_type_params(RuntimeCall))]
+pub struct Xcm<RuntimeCall>(pub Vec<Instruction<RuntimeCall>>);
+
+impl<RuntimeCall> Xcm<RuntimeCall> {
+	/// Create an empty instance.
+	pub fn new() -> Self {
+		Self(vec![])
+	}
+
+	/// Return `true` if no instructions are held in `self`.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Return the number of instructions held in `self`.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Consume and either return `self` if it contains some instructions, or if it's empty, then
+	/// instead return the result of `f`.
+	pub fn or_else(self, f: impl FnOnce() -> Self) -> Self {
+		if self.0.is_empty() {
+			f()
+		} else {
+			self
+		}
+	}
+
+	/// Return the first instruction, if any.
+	pub fn first(&self) -> Option<&Instruction<RuntimeCall>> {
+		self.0.first()
+	}
+
+	/// Return the last instruction, if any.
+	pub fn last(&self) -> Option<&Instruction<RuntimeCall>> {
+		self.0.last()
+	}
+
+	/// Return the only instruction, contained in `Self`, iff only one exists (`None` otherwise).
+	pub fn only(&self) -> Option<&Instruction<RuntimeCall>> {
+		if self.0.len() == 1 {
+			self.0.first()
+		} else {
+			None
+		}
+	}
+
+	/// Return the only instruction, contained in `Self`, iff only one exists (returns `self`
+	/// otherwise).
+	pub fn into_only(mut self) -> core::result::Result<Instruction<RuntimeCall>, Self> {
+		if self.0.len() == 1 {
+			self.0.pop().ok_or(self)
+		} else {
+			Err(self)
+		}
+	}
+
+	/// Return the topic set by the last `SetTopic` instruction, if any.
+	pub fn topic(&self) -> Option<XcmHash> {
+		self.0.iter().rev().find_map(|instruction| match instruction {
+			Instruction::SetTopic(topic) => Some(*topic),
+			_ => None,
+		})
+	}
+}
+
+/// A prelude for importing all types typically used when interacting with XCM messages.
+pub mod prelude {
+	mod contents {
+		pub use super::super::{
+			Ancestor, AncestorThen,
+			AssetId::{self, *},
+			AssetInstance::{self, *},
+			BodyId, BodyPart, Error as XcmError, ExecuteXcm,
+			Fungibility::{self, *},
+			Instruction::*,
+			InteriorMultiLocation,
+			Junction::{self, *},
+			Junctions::{self, *},
+			MultiAsset,
+			MultiAssetFilter::{self, *},
+			MultiAssets, MultiLocation,
+			NetworkId::{self, *},
+			OriginKind, Outcome, Parent, ParentThen, QueryId, QueryResponseInfo, QuoteDelivery,
+			Response, Result as XcmResult, SendError, SendResult, SendXcm,
+			WeightLimit::{self, *},
+			WildFungibility::{self, Fungible as WildFungible, NonFungible as WildNonFungible},
+			WildMultiAsset::{self, *},
+			XcmHash, XcmWeightInfo, VERSION as XCM_VERSION,
+		};
+	}
+	pub use super::{Instruction, Xcm};
+	pub use contents::*;
+	pub mod opaque {
+		pub use super::{
+			super::opaque::{Instruction, Xcm},
+			contents::*,
+		};
+	}
+}
+
+/// Response data to a query.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub enum Response {
+	/// No response. Serves as a neutral default.
+	Null,
+	/// Some assets.
+	Assets(MultiAssets),
+	/// The outcome of an XCM instruction.
+	ExecutionResult(Option<(u32, Error)>),
+	/// An XCM version.
+	Version(super::Version),
+}
+
+impl Default for Response {
+	fn default() -> Self {
+		Self::Null
+	}
+}
+
+/// Information regarding the composition of a query response.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct QueryResponseInfo {
+	/// The destination to which the query response message should be send.
+	pub destination: MultiLocation,
+	/// The `query_id` field of the `QueryResponse` message.
+	#[codec(compact)]
+	pub query_id: QueryId,
+	/// The `max_weight` field of the `QueryResponse` message.
+	#[codec(compact)]
+	pub max_weight: u64,
+}
+
+/// Cross-Consensus Message: A message from one consensus system to another.
+///
+/// Consensus systems that may send and receive messages include blockchains and smart contracts.
+///
+/// All messages are delivered from a known *origin*, expressed as a `MultiLocation`.
+///
+/// This is the inner XCM format and is version-sensitive. Messages are typically passed using the outer
+/// XCM format, known as `VersionedXcm`.
+#[derive(Derivative, Encode, Decode, TypeInfo, xcm_procedural::XcmWeightInfoTrait)]
+#[derivative(Clone(bound = ""), Eq(bound = ""), PartialEq(bound = ""), Debug(bound = ""))]
+#[codec(encode_bound())]
+#[codec(decode_bound())]
+#[scale_info(bounds(), skip_type_params(RuntimeCall))]
+pub enum Instruction<RuntimeCall> {
+	/// Withdraw asset(s) (`assets`) from the ownership of `origin` and place them into the Holding
+	/// Register.
+	///
+	/// - `assets`: The asset(s) to be withdrawn into holding.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	WithdrawAsset(MultiAssets),
+
+	/// Asset(s) (`assets`) have been received into the ownership of this system on the `origin`
+	/// system and equivalent derivatives should be placed into the Holding Register.
+	///
+	/// - `assets`: The asset(s) that are minted into holding.
+	///
+	/// Safety: `origin` must be trusted to have received and be storing `assets` such that they
+	/// may later be withdrawn should this system send a corresponding message.
+	///
+	/// Kind: *Trusted Indication*.
+	///
+	/// Errors:
+	ReserveAssetDeposited(MultiAssets),
+
+	/// Asset(s) (`assets`) have been destroyed on the `origin` system and equivalent assets should
+	/// be created and placed into the Holding Register.
+	///
+	/// - `assets`: The asset(s) that are minted into the Holding Register.
+	///
+	/// Safety: `origin` must be trusted to have irrevocably destroyed the corresponding `assets`
+	/// prior as a consequence of sending this message.
+	///
+	/// Kind: *Trusted Indication*.
+	///
+	/// Errors:
+	ReceiveTeleportedAsset(MultiAssets),
+
+	/// Respond with information that the local system is expecting.
+	///
+	/// - `query_id`: The identifier of the query that resulted in this message being sent.
+	/// - `response`: The message content.
+	/// - `max_weight`: The maximum weight that handling this response should take.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Information*.
+	///
+	/// Errors:
+	QueryResponse {
+		#[codec(compact)]
+		query_id: QueryId,
+		response: Response,
+		#[codec(compact)]
+		max_weight: u64,
+	},
+
+	/// Withdraw asset(s) (`assets`) from the ownership of `origin` and place equivalent assets
+	/// under the ownership of `beneficiary`.
+	///
+	/// - `assets`: The asset(s) to be withdrawn.
+	/// - `beneficiary`: The new owner for the assets.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	TransferAsset { assets: MultiAssets, beneficiary: MultiLocation },
+
+	/// Withdraw asset(s) (`assets`) from the ownership of `origin` and place equivalent assets
+	/// under the ownership of `dest` within this consensus system (i.e. its sovereign account).
+	///
+	/// Send an onward XCM message to `dest` of `ReserveAssetDeposited` with the given
+	/// `xcm`.
+	///
+	/// - `assets`: The asset(s) to be withdrawn.
+	/// - `dest`: The location whose sovereign account will own the assets and thus the effective
+	///   beneficiary for the assets and the notification target for the reserve asset deposit
+	///   message.
+	/// - `xcm`: The instructions that should follow the `ReserveAssetDeposited`
+	///   instruction, which is sent onwards to `dest`.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	TransferReserveAsset { assets: MultiAssets, dest: MultiLocation, xcm: Xcm<()> },
+
+	/// Apply the encoded transaction `call`, whose dispatch-origin should be `origin` as expressed
+	/// by the kind of origin `origin_type`.
+	///
+	/// - `origin_type`: The means of expressing the message origin as a dispatch origin.
+	/// - `max_weight`: The weight of `call`; this should be at least the chain's calculated weight
+	///   and will be used in the weight determination arithmetic.
+	/// - `call`: The encoded transaction to be applied.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	Transact {
+		origin_type: OriginKind,
+		#[codec(compact)]
+		require_weight_at_most: u64,
+		call: DoubleEncoded<RuntimeCall>,
+	},
+
+	/// A message to notify about a new incoming HRMP channel. This message is meant to be sent by the
+	/// relay-chain to a para.
+	///
+	/// - `sender`: The sender in the to-be opened channel. Also, the initiator of the channel opening.
+	/// - `max_message_size`: The maximum size of a message proposed by the sender.
+	/// - `max_capacity`: The maximum number of messages that can be queued in the channel.
+	///
+	/// Safety: The message should originate directly from the relay-chain.
+	///
+	/// Kind: *System Notification*
+	HrmpNewChannelOpenRequest {
+		#[codec(compact)]
+		sender: u32,
+		#[codec(compact)]
+		max_message_size: u32,
+		#[codec(compact)]
+		max_capacity: u32,
+	},
+
+	/// A message to notify about that a previously sent open channel request has been accepted by
+	/// the recipient. That means that the channel will be opened during the next relay-chain session
+	/// change. This message is meant to be sent by the relay-chain to a para.
+	///
+	/// Safety: The message should originate directly from the relay-chain.
+	///
+	/// Kind: *System Notification*
+	///
+	/// Errors:
+	HrmpChannelAccepted {
+		// NOTE: We keep this as a structured item to a) keep it consistent with the other Hrmp
+		// items; and b) because the field's meaning is not obvious/mentioned from the item name.
+		#[codec(compact)]
+		recipient: u32,
+	},
+
+	/// A message to notify that the other party in an open channel decided to close it. In particular,
+	/// `initiator` is going to close the channel opened from `sender` to the `recipient`. The close
+	/// will be enacted at the next relay-chain session change. This message is meant to be sent by
+	/// the relay-chain to a para.
+	///
+	/// Safety: The message should originate directly from the relay-chain.
+	///
+	/// Kind: *System Notification*
+	///
+	/// Errors:
+	HrmpChannelClosing {
+		#[codec(compact)]
+		initiator: u32,
+		#[codec(compact)]
+		sender: u32,
+		#[codec(compact)]
+		recipient: u32,
+	},
+
+	/// Clear the origin.
+	///
+	/// This may be used by the XCM author to ensure that later instructions cannot command the
+	/// authority of the origin (e.g. if they are being relayed from an untrusted source, as often
+	/// the case with `ReserveAssetDeposited`).
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	ClearOrigin,
+
+	/// Mutate the origin to some interior location.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	DescendOrigin(InteriorMultiLocation),
+
+	/// Immediately report the contents of the Error Register to the given destination via XCM.
+	///
+	/// A `QueryResponse` message of type `ExecutionOutcome` is sent to `dest` with the given
+	/// `query_id` and the outcome of the XCM.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ReportError {
+		#[codec(compact)]
+		query_id: QueryId,
+		dest: MultiLocation,
+		#[codec(compact)]
+		max_response_weight: u64,
+	},
+
+	/// Remove the asset(s) (`assets`) from the Holding Register and place equivalent assets under
+	/// the ownership of `beneficiary` within this consensus system.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `max_assets`: The maximum number of unique assets/asset instances to remove from holding.
+	///   Only the first `max_assets` assets/instances of those matched by `assets` will be removed,
+	///   prioritized under standard asset ordering. Any others will remain in holding.
+	/// - `beneficiary`: The new owner for the assets.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	DepositAsset {
+		assets: MultiAssetFilter,
+		#[codec(compact)]
+		max_assets: u32,
+		beneficiary: MultiLocation,
+	},
+
+	/// Remove the asset(s) (`assets`) from the Holding Register and place equivalent assets under
+	/// the ownership of `dest` within this consensus system (i.e. deposit them into its sovereign
+	/// account).
+	///
+	/// Send an onward XCM message to `dest` of `ReserveAssetDeposited` with the given `effects`.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `max_assets`: The maximum number of unique assets/asset instances to remove from holding.
+	///   Only the first `max_assets` assets/instances of those matched by `assets` will be removed,
+	///   prioritized under standard asset ordering. Any others will remain in holding.
+	/// - `dest`: The location whose sovereign account will own the assets and thus the effective
+	///   beneficiary for the assets and the notification target for the reserve asset deposit
+	///   message.
+	/// - `xcm`: The orders that should follow the `ReserveAssetDeposited` instruction
+	///   which is sent onwards to `dest`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	DepositReserveAsset {
+		assets: MultiAssetFilter,
+		#[codec(compact)]
+		max_assets: u32,
+		dest: MultiLocation,
+		xcm: Xcm<()>,
+	},
+
+	/// Remove the asset(s) (`give`) from the Holding Register and replace them with alternative
+	/// assets.
+	///
+	/// The minimum amount of assets to be received into the Holding Register for the order not to
+	/// fail may be stated.
+	///
+	/// - `give`: The asset(s) to remove from holding.
+	/// - `receive`: The minimum amount of assets(s) which `give` should be exchanged for.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ExchangeAsset { give: MultiAssetFilter, receive: MultiAssets },
+
+	/// Remove the asset(s) (`assets`) from holding and send a `WithdrawAsset` XCM message to a
+	/// reserve location.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `reserve`: A valid location that acts as a reserve for all asset(s) in `assets`. The
+	///   sovereign account of this consensus system *on the reserve location* will have appropriate
+	///   assets withdrawn and `effects` will be executed on them. There will typically be only one
+	///   valid location on any given asset/chain combination.
+	/// - `xcm`: The instructions to execute on the assets once withdrawn *on the reserve
+	///   location*.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	InitiateReserveWithdraw { assets: MultiAssetFilter, reserve: MultiLocation, xcm: Xcm<()> },
+
+	/// Remove the asset(s) (`assets`) from holding and send a `ReceiveTeleportedAsset` XCM message
+	/// to a `dest` location.
+	///
+	/// - `assets`: The asset(s) to remove from holding.
+	/// - `dest`: A valid location that respects teleports coming from this location.
+	/// - `xcm`: The instructions to execute on the assets once arrived *on the destination
+	///   location*.
+	///
+	/// NOTE: The `dest` location *MUST* respect this origin as a valid teleportation origin for all
+	/// `assets`. If it does not, then the assets may be lost.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	InitiateTeleport { assets: MultiAssetFilter, dest: MultiLocation, xcm: Xcm<()> },
+
+	/// Report to a given destination the contents of the Holding Register.
+	///
+	/// A `QueryResponse` message of type `Assets` is sent to the described destination.
+	///
+	/// - `response_info`: Information for making an appropriate query response.
+	/// - `assets`: A filter for the assets that should be reported back. The assets reported back
+	///   will be, asset-wise, *the lesser of this value and the holding register*. No wildcards
+	///   will be used when reporting assets back.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ReportHolding { response_info: QueryResponseInfo, assets: MultiAssetFilter },
+
+	/// Pay for the execution of some XCM `xcm` and `orders` with up to `weight`
+	/// picoseconds of execution time, paying for this with up to `fees` from the Holding Register.
+	///
+	/// - `fees`: The asset(s) to remove from the Holding Register to pay for fees.
+	/// - `weight_limit`: The maximum amount of weight to purchase; this must be at least the
+	///   expected maximum weight of the total XCM to be executed for the
+	///   `AllowTopLevelPaidExecutionFrom` barrier to allow the XCM be executed.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	BuyExecution { fees: MultiAsset, weight_limit: WeightLimit },
+
+	/// Refund any surplus weight previously bought with `BuyExecution`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	RefundSurplus,
+
+	/// Set the Error Handler Register. This is code that should be called in the case of an error
+	/// happening.
+	///
+	/// An error occurring within execution of this code will _NOT_ result in the error register
+	/// being set, nor will an error handler be called due to it. The error handler and appendix
+	/// may each still be set.
+	///
+	/// The apparent weight of this instruction is inclusive of the inner `Xcm`; the executing
+	/// weight however includes only the difference between the previous handler and the new
+	/// handler, which can reasonably be negative, which would result in a surplus.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	SetErrorHandler(Xcm<RuntimeCall>),
+
+	/// Set the Appendix Register. This is code that should be called after code execution
+	/// (including the error handler if any) is finished. This will be called regardless of whether
+	/// an error occurred.
+	///
+	/// Any error occurring due to execution of this code will result in the error register being
+	/// set, and the error handler (if set) firing.
+	///
+	/// The apparent weight of this instruction is inclusive of the inner `Xcm`; the executing
+	/// weight however includes only the difference between the previous appendix and the new
+	/// appendix, which can reasonably be negative, which would result in a surplus.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	SetAppendix(Xcm<RuntimeCall>),
+
+	/// Clear the Error Register.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	ClearError,
+
+	/// Create some assets which are being held on behalf of the origin.
+	///
+	/// - `assets`: The assets which are to be claimed. This must match exactly with the assets
+	///   claimable by the origin of the ticket.
+	/// - `ticket`: The ticket of the asset; this is an abstract identifier to help locate the
+	///   asset.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ClaimAsset { assets: MultiAssets, ticket: MultiLocation },
+
+	/// Always throws an error of type `Trap`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `Trap`: All circumstances, whose inner value is the same as this item's inner value.
+	Trap(#[codec(compact)] u64),
+
+	/// Ask the destination system to respond with the most recent version of XCM that they
+	/// support in a `QueryResponse` instruction. Any changes to this should also elicit similar
+	/// responses when they happen.
+	///
+	/// Kind: *Instruction*
+	SubscribeVersion {
+		#[codec(compact)]
+		query_id: QueryId,
+		#[codec(compact)]
+		max_response_weight: u64,
+	},
+
+	/// Cancel the effect of a previous `SubscribeVersion` instruction.
+	///
+	/// Kind: *Instruction*
+	UnsubscribeVersion,
+
+	/// Throw an error if Holding does not contain at least the given assets.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `ExpectationFalse`: If Holding Register does not contain the assets in the parameter.
+	ExpectAsset(MultiAssets),
+
+	/// Ensure that the Origin Register equals some given value and throw an error if not.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `ExpectationFalse`: If Origin Register is not equal to the parameter.
+	ExpectOrigin(Option<MultiLocation>),
+
+	/// Ensure that the Error Register equals some given value and throw an error if not.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `ExpectationFalse`: If the value of the Error Register is not equal to the parameter.
+	ExpectError(Option<(u32, Error)>),
+
+	/// A direct request to change the origin to the root of a global consensus system, given by
+	/// the inner junction. The current origin must be trusted by the local chain to act on behalf
+	/// of that consensus system.
+	///
+	/// - `_0`: The junction which, appended to the root of the universe, identifies the global
+	///   consensus system to be used as the new origin.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	/// - `NoPermission`: If the current origin is not trusted to act on behalf of `_0`.
+	UniversalOrigin(Junction),
+
+	/// Send a message on to a non-local consensus system.
+	///
+	/// This will tend to utilize some extra-consensus mechanism, the obvious one being a bridge.
+	/// A fee may be charged; this may be determined based on the contents of `xcm`. It will be
+	/// taken from the Holding Register.
+	///
+	/// - `network`: The remote consensus system to which the message should be exported.
+	/// - `destination`: The location relative to the remote consensus system to which the message
+	///   should be sent on arrival.
+	/// - `xcm`: The message to be exported.
+	///
+	/// As an example, to export a message for execution on Statemine (parachain #1000 in the
+	/// Kusama network), you would call with `network: NetworkId::Kusama` and
+	/// `destination: X1(Parachain(1000))`. Alternatively, to export a message for execution on
+	/// Polkadot, you would call with `network: NetworkId::Polkadot` and `destination: Here`.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors:
+	ExportMessage { network: NetworkId, destination: InteriorMultiLocation, xcm: Xcm<()> },
+
+	/// Lock the locally held asset and prevent further transfer or withdrawal.
+	///
+	/// This restriction may be removed by the `UnlockAsset` instruction being called with an
+	/// Origin of `unlocker` and a `target` equal to the current `Origin`.
+	///
+	/// If the locking is successful, then a `NoteUnlockable` instruction is sent to `unlocker`.
+	///
+	/// - `asset`: The asset(s) which should be locked.
+	/// - `unlocker`: The value which the Origin must be for a corresponding `UnlockAsset`
+	///   instruction to work.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	LockAsset { asset: MultiAsset, unlocker: MultiLocation },
+
+	/// Remove the lock over `asset` on this chain and (if nothing else is preventing it) allow the
+	/// asset to be transferred.
+	///
+	/// - `asset`: The asset to be unlocked.
+	/// - `target`: The owner of the asset on the local chain.
+	///
+	/// Safety: No concerns.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	UnlockAsset { asset: MultiAsset, target: MultiLocation },
+
+	/// Asset (`asset`) has been locked on the `origin` system and may not be transferred. It may
+	/// only be unlocked with the receipt of the `UnlockAsset` instruction from this chain.
+	///
+	/// - `asset`: The asset(s) which are now unlockable from this origin.
+	/// - `owner`: The owner of the asset on the chain in which it was locked. This may be a
+	///   location specific to the origin network.
+	///
+	/// Safety: `origin` must be trusted to have locked the corresponding `asset`
+	/// prior as a consequence of sending this message.
+	///
+	/// Kind: *Trusted Indication*.
+	///
+	/// Errors:
+	NoteUnlockable { asset: MultiAsset, owner: MultiLocation },
+
+	/// Send an `UnlockAsset` instruction to the `locker` for the given `asset`.
+	///
+	/// This may fail if the local system is making use of the fact that the asset is locked or,
+	/// of course, if there is no record that the asset actually is locked.
+	///
+	/// - `asset`: The asset(s) to be unlocked.
+	/// - `locker`: The location from which a previous `NoteUnlockable` was sent and to which an
+	///   `UnlockAsset` should be sent.
+	///
+	/// Kind: *Instruction*.
+	///
+	/// Errors:
+	RequestUnlock { asset: MultiAsset, locker: MultiLocation },
+
+	/// Set the Topic Register.
+	///
+	/// The topic is an identifier for the message which has no effect on its execution. It may be
+	/// used by the executing system to correlate the message with others, e.g. in events.
+	///
+	/// Kind: *Instruction*
+	///
+	/// Errors: None.
+	SetTopic(XcmHash),
+}
+
+impl<RuntimeCall> Xcm<RuntimeCall> {
+	pub fn into<C>(self) -> Xcm<C> {
+		Xcm::from(self)
+	}
+	pub fn from<C>(xcm: Xcm<C>) -> Self {
+		Self(xcm.0.into_iter().map(Instruction::<RuntimeCall>::from).collect())
+	}
+}
+
+impl<RuntimeCall> Instruction<RuntimeCall> {
+	pub fn into<C>(self) -> Instruction<C> {
+		Instruction::from(self)
+	}
+	pub fn from<C>(xcm: Instruction<C>) -> Self {
+		use Instruction::*;
+		match xcm {
+			WithdrawAsset(assets) => WithdrawAsset(assets),
+			ReserveAssetDeposited(assets) => ReserveAssetDeposited(assets),
+			ReceiveTeleportedAsset(assets) => ReceiveTeleportedAsset(assets),
+			QueryResponse { query_id, response, max_weight } =>
+				QueryResponse { query_id, response, max_weight },
+			TransferAsset { assets, beneficiary } => TransferAsset { assets, beneficiary },
+			TransferReserveAsset { assets, dest, xcm } =>
+				TransferReserveAsset { assets, dest, xcm },
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_type, require_weight_at_most, call } =>
+				Transact { origin_type, require_weight_at_most, call: call.into() },
+			ReportError { query_id, dest, max_response_weight } =>
+				ReportError { query_id, dest, max_response_weight },
+			DepositAsset { assets, max_assets, beneficiary } =>
+				DepositAsset { assets, max_assets, beneficiary },
+			DepositReserveAsset { assets, max_assets, dest, xcm } =>
+				DepositReserveAsset { assets, max_assets, dest, xcm },
+			ExchangeAsset { give, receive } => ExchangeAsset { give, receive },
+			InitiateReserveWithdraw { assets, reserve, xcm } =>
+				InitiateReserveWithdraw { assets, reserve, xcm },
+			InitiateTeleport { assets, dest, xcm } => InitiateTeleport { assets, dest, xcm },
+			ReportHolding { response_info, assets } => ReportHolding { response_info, assets },
+			BuyExecution { fees, weight_limit } => BuyExecution { fees, weight_limit },
+			ClearOrigin => ClearOrigin,
+			DescendOrigin(who) => DescendOrigin(who),
+			RefundSurplus => RefundSurplus,
+			SetErrorHandler(xcm) => SetErrorHandler(xcm.into()),
+			SetAppendix(xcm) => SetAppendix(xcm.into()),
+			ClearError => ClearError,
+			ClaimAsset { assets, ticket } => ClaimAsset { assets, ticket },
+			Trap(code) => Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => UnsubscribeVersion,
+			ExpectAsset(assets) => ExpectAsset(assets),
+			ExpectOrigin(origin) => ExpectOrigin(origin),
+			ExpectError(error) => ExpectError(error),
+			UniversalOrigin(j) => UniversalOrigin(j),
+			ExportMessage { network, destination, xcm } =>
+				ExportMessage { network, destination, xcm },
+			LockAsset { asset, unlocker } => LockAsset { asset, unlocker },
+			UnlockAsset { asset, target } => UnlockAsset { asset, target },
+			NoteUnlockable { asset, owner } => NoteUnlockable { asset, owner },
+			RequestUnlock { asset, locker } => RequestUnlock { asset, locker },
+			SetTopic(topic) => SetTopic(topic),
+		}
+	}
+}
+
+// TODO: Automate Generation
+impl<RuntimeCall, W: XcmWeightInfo<RuntimeCall>> GetWeight<W> for Instruction<RuntimeCall> {
+	fn weight(&self) -> Weight {
+		use Instruction::*;
+		match self {
+			WithdrawAsset(assets) => W::withdraw_asset(assets),
+			ReserveAssetDeposited(assets) => W::reserve_asset_deposited(assets),
+			ReceiveTeleportedAsset(assets) => W::receive_teleported_asset(assets),
+			QueryResponse { query_id, response, max_weight } =>
+				W::query_response(query_id, response, max_weight),
+			TransferAsset { assets, beneficiary } => W::transfer_asset(assets, beneficiary),
+			TransferReserveAsset { assets, dest, xcm } =>
+				W::transfer_reserve_asset(&assets, dest, xcm),
+			Transact { origin_type, require_weight_at_most, call } =>
+				W::transact(origin_type, require_weight_at_most, call),
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				W::hrmp_new_channel_open_request(sender, max_message_size, max_capacity),
+			HrmpChannelAccepted { recipient } => W::hrmp_channel_accepted(recipient),
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				W::hrmp_channel_closing(initiator, sender, recipient),
+			ClearOrigin => W::clear_origin(),
+			DescendOrigin(who) => W::descend_origin(who),
+			ReportError { query_id, dest, max_response_weight } =>
+				W::report_error(query_id, dest, max_response_weight),
+			DepositAsset { assets, max_assets, beneficiary } =>
+				W::deposit_asset(assets, max_assets, beneficiary),
+			DepositReserveAsset { assets, max_assets, dest, xcm } =>
+				W::deposit_reserve_asset(assets, max_assets, dest, xcm),
+			ExchangeAsset { give, receive } => W::exchange_asset(give, receive),
+			InitiateReserveWithdraw { assets, reserve, xcm } =>
+				W::initiate_reserve_withdraw(assets, reserve, xcm),
+			InitiateTeleport { assets, dest, xcm } => W::initiate_teleport(assets, dest, xcm),
+			ReportHolding { response_info, assets } => W::report_holding(response_info, assets),
+			BuyExecution { fees, weight_limit } => W::buy_execution(fees, weight_limit),
+			RefundSurplus => W::refund_surplus(),
+			SetErrorHandler(xcm) => W::set_error_handler(xcm),
+			SetAppendix(xcm) => W::set_appendix(xcm),
+			ClearError => W::clear_error(),
+			ClaimAsset { assets, ticket } => W::claim_asset(assets, ticket),
+			Trap(code) => W::trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				W::subscribe_version(query_id, max_response_weight),
+			UnsubscribeVersion => W::unsubscribe_version(),
+			ExpectAsset(assets) => W::expect_asset(assets),
+			ExpectOrigin(origin) => W::expect_origin(origin),
+			ExpectError(error) => W::expect_error(error),
+			UniversalOrigin(j) => W::universal_origin(j),
+			ExportMessage { network, destination, xcm } =>
+				W::export_message(network, destination, xcm),
+			LockAsset { asset, unlocker } => W::lock_asset(asset, unlocker),
+			UnlockAsset { asset, target } => W::unlock_asset(asset, target),
+			NoteUnlockable { asset, owner } => W::note_unlockable(asset, owner),
+			RequestUnlock { asset, locker } => W::request_unlock(asset, locker),
+			SetTopic(topic) => W::set_topic(topic),
+		}
+	}
+}
+
+pub mod opaque {
+	/// The basic concrete type of `Xcm`, which doesn't make any assumptions about the
+	/// format of a call other than it is pre-encoded.
+	pub type Xcm = super::Xcm<()>;
+
+	/// The basic concrete type of `Instruction`, which doesn't make any assumptions about the
+	/// format of a call other than it is pre-encoded.
+	pub type Instruction = super::Instruction<()>;
+}
+
+// Convert from a v2 response to a v3 response.
+impl TryFrom<OldResponse> for Response {
+	type Error = ();
+	fn try_from(old_response: OldResponse) -> result::Result<Self, ()> {
+		Ok(match old_response {
+			OldResponse::Null => Self::Null,
+			OldResponse::Assets(assets) => Self::Assets(assets.try_into()?),
+			OldResponse::ExecutionResult(result) =>
+				Self::ExecutionResult(result.map(|(index, error)| (index, error.into()))),
+			OldResponse::Version(version) => Self::Version(version),
+		})
+	}
+}
+
+impl<RuntimeCall> TryFrom<OldXcm<RuntimeCall>> for Xcm<RuntimeCall> {
+	type Error = ();
+	fn try_from(old_xcm: OldXcm<RuntimeCall>) -> result::Result<Self, ()> {
+		Ok(Xcm(old_xcm.0.into_iter().map(TryInto::try_into).collect::<result::Result<_, _>>()?))
+	}
+}
+
+impl<RuntimeCall> TryFrom<OldInstruction<RuntimeCall>> for Instruction<RuntimeCall> {
+	type Error = ();
+	fn try_from(old_instruction: OldInstruction<RuntimeCall>) -> result::Result<Self, ()> {
+		use OldInstruction::*;
+		Ok(match old_instruction {
+			WithdrawAsset(assets) => Self::WithdrawAsset(assets.try_into()?),
+			ReserveAssetDeposited(assets) => Self::ReserveAssetDeposited(assets.try_into()?),
+			ReceiveTeleportedAsset(assets) => Self::ReceiveTeleportedAsset(assets.try_into()?),
+			QueryResponse { query_id, response, max_weight } =>
+				Self::QueryResponse { query_id, response: response.try_into()?, max_weight },
+			TransferAsset { assets, beneficiary } => Self::TransferAsset {
+				assets: assets.try_into()?,
+				beneficiary: beneficiary.try_into()?,
+			},
+			TransferReserveAsset { assets, dest, xcm } => Self::TransferReserveAsset {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } =>
+				Self::HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity },
+			HrmpChannelAccepted { recipient } => Self::HrmpChannelAccepted { recipient },
+			HrmpChannelClosing { initiator, sender, recipient } =>
+				Self::HrmpChannelClosing { initiator, sender, recipient },
+			Transact { origin_type, require_weight_at_most, call } =>
+				Self::Transact { origin_type, require_weight_at_most, call },
+			ReportError { query_id, dest, max_response_weight } =>
+				Self::ReportError { query_id, dest: dest.try_into()?, max_response_weight },
+			DepositAsset { assets, max_assets, beneficiary } => Self::DepositAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				beneficiary: beneficiary.try_into()?,
+			},
+			DepositReserveAsset { assets, max_assets, dest, xcm } => Self::DepositReserveAsset {
+				assets: assets.try_into()?,
+				max_assets,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			ExchangeAsset { give, receive } =>
+				Self::ExchangeAsset { give: give.try_into()?, receive: receive.try_into()? },
+			InitiateReserveWithdraw { assets, reserve, xcm } => Self::InitiateReserveWithdraw {
+				assets: assets.try_into()?,
+				reserve: reserve.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			InitiateTeleport { assets, dest, xcm } => Self::InitiateTeleport {
+				assets: assets.try_into()?,
+				dest: dest.try_into()?,
+				xcm: xcm.try_into()?,
+			},
+			QueryHolding { query_id, dest, assets, max_response_weight } => {
+				let response_info = QueryResponseInfo {
+					destination: dest.try_into()?,
+					query_id,
+					max_weight: max_response_weight,
+				};
+				Self::ReportHolding { response_info, assets: assets.try_into()? }
+			},
+			BuyExecution { fees, weight_limit } =>
+				Self::BuyExecution { fees: fees.try_into()?, weight_limit },
+			ClearOrigin => Self::ClearOrigin,
+			DescendOrigin(who) => Self::DescendOrigin(who.try_into()?),
+			RefundSurplus => Self::RefundSurplus,
+			SetErrorHandler(xcm) => Self::SetErrorHandler(xcm.try_into()?),
+			SetAppendix(xcm) => Self::SetAppendix(xcm.try_into()?),
+			ClearError => Self::ClearError,
+			ClaimAsset { assets, ticket } =>
+				Self::ClaimAsset { assets: assets.try_into()?, ticket: ticket.try_into()? },
+			Trap(code) => Self::Trap(code),
+			SubscribeVersion { query_id, max_response_weight } =>
+				Self::SubscribeVersion { query_id, max_response_weight },
+			UnsubscribeVersion => Self::UnsubscribeVersion,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{prelude::*, *};
+	use crate::v2::{
+		Junctions::Here as OldHere, MultiAssetFilter as OldMultiAssetFilter,
+		WildMultiAsset as OldWildMultiAsset,
+	};
+
+	#[test]
+	fn basic_roundtrip_works() {
+		let xcm =
+			Xcm::<()>(vec![TransferAsset { assets: (Here, 1).into(), beneficiary: Here.into() }]);
+		let old_xcm = OldXcm::<()>(vec![OldInstruction::TransferAsset {
+			assets: (OldHere, 1).into(),
+			beneficiary: OldHere.into(),
+		}]);
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+	}
+
+	#[test]
+	fn report_holding_roundtrip_works() {
+		let xcm = Xcm::<()>(vec![
+			WithdrawAsset((Here, 1).into()),
+			ReportHolding {
+				response_info: QueryResponseInfo {
+					destination: Parent.into(),
+					query_id: 42,
+					max_weight: 1_000,
+				},
+				assets: Wild(All),
+			},
+		]);
+		let old_xcm = OldXcm::<()>(vec![
+			OldInstruction::WithdrawAsset((OldHere, 1).into()),
+			OldInstruction::QueryHolding {
+				query_id: 42,
+				dest: crate::v2::Parent.into(),
+				assets: OldMultiAssetFilter::Wild(OldWildMultiAsset::All),
+				max_response_weight: 1_000,
+			},
+		]);
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+	}
+
+	#[test]
+	fn execution_result_roundtrip_works() {
+		let xcm = Xcm::<()>(vec![QueryResponse {
+			query_id: 1,
+			response: Response::ExecutionResult(Some((3, Error::Trap(7)))),
+			max_weight: 0,
+		}]);
+		let old_xcm = OldXcm::<()>(vec![OldInstruction::QueryResponse {
+			query_id: 1,
+			response: OldResponse::ExecutionResult(Some((3, crate::v2::Error::Trap(7)))),
+			max_weight: 0,
+		}]);
+		assert_eq!(old_xcm, OldXcm::<()>::try_from(xcm.clone()).unwrap());
+		let new_xcm: Xcm<()> = old_xcm.try_into().unwrap();
+		assert_eq!(new_xcm, xcm);
+
+		// Errors which only exist in v3 cannot be expressed in v2.
+		let xcm = Xcm::<()>(vec![QueryResponse {
+			query_id: 1,
+			response: Response::ExecutionResult(Some((0, Error::ExpectationFalse))),
+			max_weight: 0,
+		}]);
+		assert!(OldXcm::<()>::try_from(xcm).is_err());
+	}
+
+	#[test]
+	fn new_instructions_are_not_downgradable() {
+		// The topic identifies the message across hops, so it must not be silently dropped.
+		let xcm = Xcm::<()>(vec![ClearOrigin, SetTopic([1; 32])]);
+		assert!(OldXcm::<()>::try_from(xcm).is_err());
+
+		for instruction in [
+			ExpectAsset((Here, 1).into()),
+			ExpectOrigin(None),
+			ExpectError(None),
+			UniversalOrigin(Parachain(1)),
+			ExportMessage { network: Any, destination: Here, xcm: Xcm::new() },
+			LockAsset { asset: (Here, 1).into(), unlocker: Parent.into() },
+			UnlockAsset { asset: (Here, 1).into(), target: Parent.into() },
+			NoteUnlockable { asset: (Here, 1).into(), owner: Parent.into() },
+			RequestUnlock { asset: (Here, 1).into(), locker: Parent.into() },
+		] {
+			assert!(OldXcm::<()>::try_from(Xcm::<()>(vec![instruction])).is_err());
+		}
+	}
+
+	#[test]
+	fn global_consensus_is_not_downgradable() {
+		let remote: MultiLocation = (Parent, Parent, GlobalConsensus(Kusama), Parachain(1)).into();
+		let xcm = Xcm::<()>(vec![
+			WithdrawAsset((Here, 1).into()),
+			DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: remote.clone() },
+		]);
+		assert!(OldXcm::<()>::try_from(xcm).is_err());
+
+		let xcm = Xcm::<()>(vec![WithdrawAsset((remote, 1).into())]);
+		assert!(OldXcm::<()>::try_from(xcm).is_err());
+
+		let xcm = Xcm::<()>(vec![DescendOrigin(X1(GlobalConsensus(Polkadot)))]);
+		assert!(OldXcm::<()>::try_from(xcm).is_err());
+	}
+}
@@ -0,0 +1,57 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use frame_support::traits::{Contains, Get};
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::{FeeManager, TransactAsset};
+
+/// A `FeeManager` which charges delivery fees to every origin except `WaivedLocations`.
+///
+/// The collected fees are deposited with `AssetTransactor` into the location returned by
+/// `Receiver`, or burned if it returns `None`.
+pub struct XcmFeesToAccount<WaivedLocations, AssetTransactor, Receiver>(
+	PhantomData<(WaivedLocations, AssetTransactor, Receiver)>,
+);
+
+impl<
+		WaivedLocations: Contains<MultiLocation>,
+		AssetTransactor: TransactAsset,
+		Receiver: Get<Option<MultiLocation>>,
+	> FeeManager for XcmFeesToAccount<WaivedLocations, AssetTransactor, Receiver>
+{
+	fn is_waived(origin: Option<&MultiLocation>) -> bool {
+		origin.map_or(false, WaivedLocations::contains)
+	}
+
+	fn handle_fee(fees: MultiAssets) {
+		let receiver = match Receiver::get() {
+			Some(receiver) => receiver,
+			None => return,
+		};
+		for asset in fees.inner() {
+			if let Err(error) = AssetTransactor::deposit_asset(asset, &receiver) {
+				log::error!(
+					target: "xcm::fees",
+					"Failed to deposit delivery fee {:?} into {:?}: {:?}",
+					asset,
+					receiver,
+					error,
+				);
+			}
+		}
+	}
+}
@@ -31,7 +31,7 @@ pub mod test_utils;
 mod location_conversion;
 pub use location_conversion::{
 	Account32Hash, AccountId32Aliases, AccountKey20Aliases, ChildParachainConvertsVia,
-	LocationInverter, ParentIsPreset, SiblingParachainConvertsVia,
+	ParentIsPreset, SiblingParachainConvertsVia,
 };
 
 mod origin_conversion;
@@ -51,6 +51,9 @@ pub use barriers::{
 mod currency_adapter;
 pub use currency_adapter::CurrencyAdapter;
 
+mod fee_handling;
+pub use fee_handling::XcmFeesToAccount;
+
 mod fungibles_adapter;
 pub use fungibles_adapter::{
 	AsPrefixedGeneralIndex, ConvertedAbstractAssetId, ConvertedConcreteAssetId, FungiblesAdapter,
@@ -61,7 +64,8 @@ mod weight;
 #[allow(deprecated)]
 pub use weight::FixedRateOfConcreteFungible;
 pub use weight::{
-	FixedRateOfFungible, FixedWeightBounds, TakeRevenue, UsingComponents, WeightInfoBounds,
+	FixedRateOfFungible, FixedWeightBounds, PriceOracle, TakeRevenue, UsingComponents,
+	UsingPriceOracle, WeightInfoBounds,
 };
 
 mod matches_fungible;
@@ -69,3 +73,6 @@ pub use matches_fungible::{IsAbstract, IsConcrete};
 
 mod filter_asset_location;
 pub use filter_asset_location::{Case, NativeAsset};
+
+mod universal_exports;
+pub use universal_exports::{ensure_is_remote, LocalExporter};
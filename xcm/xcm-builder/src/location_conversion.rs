@@ -20,7 +20,7 @@ use sp_io::hashing::blake2_256;
 use sp_runtime::traits::{AccountIdConversion, TrailingZeroInput};
 use sp_std::{borrow::Borrow, marker::PhantomData};
 use xcm::latest::{Junction::*, Junctions::*, MultiLocation, NetworkId, Parent};
-use xcm_executor::traits::{Convert, InvertLocation};
+use xcm_executor::traits::Convert;
 
 pub struct Account32Hash<Network, AccountId>(PhantomData<(Network, AccountId)>);
 impl<Network: Get<NetworkId>, AccountId: From<[u8; 32]> + Into<[u8; 32]> + Clone>
@@ -152,133 +152,3 @@ impl<Network: Get<NetworkId>, AccountId: From<[u8; 20]> + Into<[u8; 20]> + Clone
 		Ok(j.into())
 	}
 }
-
-/// Simple location inverter; give it this location's ancestry and it'll figure out the inverted
-/// location.
-///
-/// # Example
-/// ## Network Topology
-/// ```txt
-///                    v Source
-/// Relay -> Para 1 -> Account20
-///       -> Para 2 -> Account32
-///                    ^ Target
-/// ```
-/// ```rust
-/// # use frame_support::parameter_types;
-/// # use xcm::latest::{MultiLocation, Junction::*, Junctions::{self, *}, NetworkId::Any};
-/// # use xcm_builder::LocationInverter;
-/// # use xcm_executor::traits::InvertLocation;
-/// # fn main() {
-/// parameter_types!{
-///     pub Ancestry: MultiLocation = X2(
-///         Parachain(1),
-///         AccountKey20 { network: Any, key: Default::default() },
-///     ).into();
-/// }
-///
-/// let input = MultiLocation::new(2, X2(Parachain(2), AccountId32 { network: Any, id: Default::default() }));
-/// let inverted = LocationInverter::<Ancestry>::invert_location(&input);
-/// assert_eq!(inverted, Ok(MultiLocation::new(
-///     2,
-///     X2(Parachain(1), AccountKey20 { network: Any, key: Default::default() }),
-/// )));
-/// # }
-/// ```
-pub struct LocationInverter<Ancestry>(PhantomData<Ancestry>);
-impl<Ancestry: Get<MultiLocation>> InvertLocation for LocationInverter<Ancestry> {
-	fn ancestry() -> MultiLocation {
-		Ancestry::get()
-	}
-	fn invert_location(location: &MultiLocation) -> Result<MultiLocation, ()> {
-		let mut ancestry = Ancestry::get();
-		let mut junctions = Here;
-		for _ in 0..location.parent_count() {
-			junctions = junctions
-				.pushed_with(ancestry.take_first_interior().unwrap_or(OnlyChild))
-				.map_err(|_| ())?;
-		}
-		let parents = location.interior().len() as u8;
-		Ok(MultiLocation::new(parents, junctions))
-	}
-}
-
-#[cfg(test)]
-mod tests {
-	use super::*;
-
-	use frame_support::parameter_types;
-	use xcm::latest::{Junction, NetworkId::Any};
-
-	fn account20() -> Junction {
-		AccountKey20 { network: Any, key: Default::default() }
-	}
-
-	fn account32() -> Junction {
-		AccountId32 { network: Any, id: Default::default() }
-	}
-
-	// Network Topology
-	//                                     v Source
-	// Relay -> Para 1 -> SmartContract -> Account
-	//       -> Para 2 -> Account
-	//                    ^ Target
-	//
-	// Inputs and outputs written as file paths:
-	//
-	// input location (source to target): ../../../para_2/account32_default
-	// ancestry (root to source): para_1/account20_default/account20_default
-	// =>
-	// output (target to source): ../../para_1/account20_default/account20_default
-	#[test]
-	fn inverter_works_in_tree() {
-		parameter_types! {
-			pub Ancestry: MultiLocation = X3(Parachain(1), account20(), account20()).into();
-		}
-
-		let input = MultiLocation::new(3, X2(Parachain(2), account32()));
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input).unwrap();
-		assert_eq!(inverted, MultiLocation::new(2, X3(Parachain(1), account20(), account20())));
-	}
-
-	// Network Topology
-	//                                     v Source
-	// Relay -> Para 1 -> SmartContract -> Account
-	//          ^ Target
-	#[test]
-	fn inverter_uses_ancestry_as_inverted_location() {
-		parameter_types! {
-			pub Ancestry: MultiLocation = X2(account20(), account20()).into();
-		}
-
-		let input = MultiLocation::grandparent();
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input).unwrap();
-		assert_eq!(inverted, X2(account20(), account20()).into());
-	}
-
-	// Network Topology
-	//                                        v Source
-	// Relay -> Para 1 -> CollectivePallet -> Plurality
-	//          ^ Target
-	#[test]
-	fn inverter_uses_only_child_on_missing_ancestry() {
-		parameter_types! {
-			pub Ancestry: MultiLocation = X1(PalletInstance(5)).into();
-		}
-
-		let input = MultiLocation::grandparent();
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input).unwrap();
-		assert_eq!(inverted, X2(PalletInstance(5), OnlyChild).into());
-	}
-
-	#[test]
-	fn inverter_errors_when_location_is_too_large() {
-		parameter_types! {
-			pub Ancestry: MultiLocation = Here.into();
-		}
-
-		let input = MultiLocation { parents: 99, interior: X1(Parachain(88)) };
-		let inverted = LocationInverter::<Ancestry>::invert_location(&input);
-		assert_eq!(inverted, Err(()));
-	}
-}
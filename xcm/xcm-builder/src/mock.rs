@@ -17,7 +17,7 @@
 use crate::{barriers::AllowSubscriptionsFrom, test_utils::*};
 pub use crate::{
 	AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
-	FixedRateOfFungible, FixedWeightBounds, LocationInverter, TakeWeightCredit,
+	FixedRateOfFungible, FixedWeightBounds, TakeWeightCredit, XcmFeesToAccount,
 };
 pub use frame_support::{
 	dispatch::{
@@ -37,7 +37,10 @@ pub use sp_std::{
 };
 pub use xcm::latest::{prelude::*, Weight};
 pub use xcm_executor::{
-	traits::{ConvertOrigin, FilterAssetLocation, InvertLocation, OnResponse, TransactAsset},
+	traits::{
+		AssetLock, ConvertOrigin, Enact, ExportXcm, FilterAssetLocation, LockError, OnResponse,
+		TransactAsset,
+	},
 	Assets, Config,
 };
 
@@ -114,10 +117,18 @@ pub fn sent_xcm() -> Vec<(MultiLocation, opaque::Xcm)> {
 pub struct TestSendXcm;
 impl SendXcm for TestSendXcm {
 	fn send_xcm(dest: impl Into<MultiLocation>, msg: opaque::Xcm) -> SendResult {
+		if SendFails::get() {
+			return Err(SendError::Transport("Sending is disabled"))
+		}
 		SENT_XCM.with(|q| q.borrow_mut().push((dest.into(), msg)));
 		Ok(())
 	}
 }
+impl QuoteDelivery for TestSendXcm {
+	fn quote_delivery(_dest: &MultiLocation, _msg: &opaque::Xcm) -> Option<MultiAssets> {
+		Some(DeliveryPrice::get())
+	}
+}
 
 thread_local! {
 	pub static ASSETS: RefCell<BTreeMap<u64, Assets>> = RefCell::new(BTreeMap::new());
@@ -256,7 +267,7 @@ pub fn response(query_id: u64) -> Option<Response> {
 }
 
 parameter_types! {
-	pub TestAncestry: MultiLocation = X1(Parachain(42)).into();
+	pub UniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(Polkadot), Parachain(42));
 	pub UnitWeightCost: u64 = 10;
 }
 parameter_types! {
@@ -267,6 +278,136 @@ parameter_types! {
 	// 1_000_000_000_000 => 1 unit of asset for 1 unit of Weight.
 	pub static WeightPrice: (AssetId, u128) = (From::from(Here), 1_000_000_000_000);
 	pub static MaxInstructions: u32 = 100;
+	// Delivery is free by default.
+	pub static DeliveryPrice: MultiAssets = MultiAssets::new();
+	pub static SendFails: bool = false;
+	pub static WaivedLocations: Vec<MultiLocation> = vec![];
+	pub static FeeReceiver: Option<MultiLocation> = None;
+	// Nobody may alias a universal location by default.
+	pub static UniversalAliases: Vec<(MultiLocation, Junction)> = vec![];
+	// Exporting is free by default.
+	pub static ExportPrice: MultiAssets = MultiAssets::new();
+}
+
+thread_local! {
+	/// Assets locked here, as `(owner, asset, unlocker)`.
+	pub static LOCKED: RefCell<Vec<(MultiLocation, MultiAsset, MultiLocation)>> =
+		RefCell::new(Vec::new());
+	/// Assets locked elsewhere which may be unlocked from here, as `(locker, asset, owner)`.
+	pub static UNLOCKABLE: RefCell<Vec<(MultiLocation, MultiAsset, MultiLocation)>> =
+		RefCell::new(Vec::new());
+}
+pub fn locked() -> Vec<(MultiLocation, MultiAsset, MultiLocation)> {
+	LOCKED.with(|l| l.borrow().clone())
+}
+pub fn unlockable() -> Vec<(MultiLocation, MultiAsset, MultiLocation)> {
+	UNLOCKABLE.with(|l| l.borrow().clone())
+}
+
+/// A change to the lock state of the `TestAssetLocker`.
+pub enum TestLockTicket {
+	Lock(MultiLocation, MultiAsset, MultiLocation),
+	Unlock(MultiLocation, MultiAsset, MultiLocation),
+	Reduce(MultiLocation, MultiAsset, MultiLocation),
+}
+impl Enact for TestLockTicket {
+	fn enact(self) -> Result<(), LockError> {
+		match self {
+			TestLockTicket::Lock(owner, asset, unlocker) =>
+				LOCKED.with(|l| l.borrow_mut().push((owner, asset, unlocker))),
+			TestLockTicket::Unlock(owner, asset, unlocker) => LOCKED.with(|l| {
+				let entry = (owner, asset, unlocker);
+				let mut l = l.borrow_mut();
+				let index = l.iter().position(|x| x == &entry).ok_or(LockError::NotLocked)?;
+				l.remove(index);
+				Ok(())
+			})?,
+			TestLockTicket::Reduce(locker, asset, owner) => UNLOCKABLE.with(|l| {
+				let entry = (locker, asset, owner);
+				let mut l = l.borrow_mut();
+				let index = l.iter().position(|x| x == &entry).ok_or(LockError::NotLocked)?;
+				l.remove(index);
+				Ok(())
+			})?,
+		}
+		Ok(())
+	}
+}
+
+/// Asset locker which only handles locks and unlocks of whole `MultiAsset`s.
+pub struct TestAssetLocker;
+impl AssetLock for TestAssetLocker {
+	type LockTicket = TestLockTicket;
+	type UnlockTicket = TestLockTicket;
+	type ReduceTicket = TestLockTicket;
+
+	fn prepare_lock(
+		unlocker: MultiLocation,
+		asset: MultiAsset,
+		owner: MultiLocation,
+	) -> Result<TestLockTicket, LockError> {
+		let who = to_account(owner.clone()).map_err(|_| LockError::BadOwner)?;
+		ensure!(assets(who).contains(&asset), LockError::AssetNotOwned);
+		Ok(TestLockTicket::Lock(owner, asset, unlocker))
+	}
+
+	fn prepare_unlock(
+		unlocker: MultiLocation,
+		asset: MultiAsset,
+		owner: MultiLocation,
+	) -> Result<TestLockTicket, LockError> {
+		let entry = (owner, asset, unlocker);
+		ensure!(locked().contains(&entry), LockError::NotLocked);
+		let (owner, asset, unlocker) = entry;
+		Ok(TestLockTicket::Unlock(owner, asset, unlocker))
+	}
+
+	fn note_unlockable(
+		locker: MultiLocation,
+		asset: MultiAsset,
+		owner: MultiLocation,
+	) -> Result<(), LockError> {
+		UNLOCKABLE.with(|l| l.borrow_mut().push((locker, asset, owner)));
+		Ok(())
+	}
+
+	fn prepare_reduce_unlockable(
+		locker: MultiLocation,
+		asset: MultiAsset,
+		owner: MultiLocation,
+	) -> Result<TestLockTicket, LockError> {
+		let entry = (locker, asset, owner);
+		ensure!(unlockable().contains(&entry), LockError::NotLocked);
+		let (locker, asset, owner) = entry;
+		Ok(TestLockTicket::Reduce(locker, asset, owner))
+	}
+}
+
+pub type ExportedXcm = (NetworkId, u32, InteriorMultiLocation, InteriorMultiLocation, Xcm<()>);
+thread_local! {
+	pub static EXPORTED_XCM: RefCell<Vec<ExportedXcm>> = RefCell::new(Vec::new());
+}
+pub fn exported_xcm() -> Vec<ExportedXcm> {
+	EXPORTED_XCM.with(|q| (*q.borrow()).clone())
+}
+pub struct TestMessageExporter;
+impl ExportXcm for TestMessageExporter {
+	fn export_xcm(
+		network: NetworkId,
+		channel: u32,
+		universal_source: InteriorMultiLocation,
+		destination: InteriorMultiLocation,
+		message: Xcm<()>,
+	) -> SendResult {
+		EXPORTED_XCM.with(|q| {
+			q.borrow_mut().push((network, channel, universal_source, destination, message))
+		});
+		Ok(())
+	}
+
+	fn quote_export(_: &NetworkId, _: &InteriorMultiLocation, _: &Xcm<()>) -> Option<MultiAssets> {
+		Some(ExportPrice::get())
+	}
 }
 
 pub type TestBarrier = (
@@ -285,7 +426,7 @@ impl Config for TestConfig {
 	type OriginConverter = TestOriginConverter;
 	type IsReserve = TestIsReserve;
 	type IsTeleporter = TestIsTeleporter;
-	type LocationInverter = LocationInverter<TestAncestry>;
+	type UniversalLocation = UniversalLocation;
 	type Barrier = TestBarrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, TestCall, MaxInstructions>;
 	type Trader = FixedRateOfFungible<WeightPrice, ()>;
@@ -293,4 +434,8 @@ impl Config for TestConfig {
 	type AssetTrap = TestAssetTrap;
 	type AssetClaims = TestAssetTrap;
 	type SubscriptionService = TestSubscriptionService;
+	type FeeManager = XcmFeesToAccount<IsInVec<WaivedLocations>, TestAssetTransactor, FeeReceiver>;
+	type AssetLocker = TestAssetLocker;
+	type MessageExporter = TestMessageExporter;
+	type UniversalAliases = IsInVec<UniversalAliases>;
 }
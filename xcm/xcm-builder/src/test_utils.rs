@@ -21,7 +21,7 @@ use sp_std::vec::Vec;
 pub use xcm::latest::{prelude::*, Weight};
 use xcm_executor::traits::{ClaimAssets, DropAssets, VersionChangeNotifier};
 pub use xcm_executor::{
-	traits::{ConvertOrigin, FilterAssetLocation, InvertLocation, OnResponse, TransactAsset},
+	traits::{ConvertOrigin, FilterAssetLocation, OnResponse, TransactAsset},
 	Assets, Config,
 };
 
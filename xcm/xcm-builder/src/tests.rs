@@ -16,7 +16,7 @@
 
 use super::{mock::*, test_utils::*, *};
 use frame_support::{assert_err, weights::constants::WEIGHT_PER_SECOND};
-use xcm::latest::prelude::*;
+use xcm::{latest::prelude::*, VersionedXcm};
 use xcm_executor::{traits::*, Config, XcmExecutor};
 
 #[test]
@@ -461,6 +461,470 @@ fn reserve_transfer_should_work() {
 	);
 }
 
+#[test]
+fn topic_should_be_carried_over_to_sent_messages() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+	let three: MultiLocation = X1(AccountIndex64 { index: 3, network: Any }).into();
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			SetTopic([7; 32]),
+			TransferReserveAsset {
+				assets: (Here, 100).into(),
+				dest: Parachain(2).into(),
+				xcm: Xcm::<()>(vec![DepositAsset {
+					assets: All.into(),
+					max_assets: 1,
+					beneficiary: three.clone(),
+				}]),
+			},
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(20));
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			Parachain(2).into(),
+			Xcm::<()>(vec![
+				ReserveAssetDeposited((Parent, 100).into()),
+				ClearOrigin,
+				DepositAsset { assets: All.into(), max_assets: 1, beneficiary: three },
+				SetTopic([7; 32]),
+			]),
+		)]
+	);
+	let forwarded = VersionedXcm::from(sent_xcm()[0].1.clone());
+	assert_eq!(xcm_executor::message_id(&forwarded), [7; 32]);
+}
+
+#[test]
+fn delivery_fees_should_be_paid_by_origin() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	DeliveryPrice::set((Here, 10).into());
+	let treasury: MultiLocation = X1(AccountIndex64 { index: 4, network: Any }).into();
+	FeeReceiver::set(Some(treasury));
+	add_asset(1001, (Here, 1000));
+
+	// Holding is empty, so the delivery fee is withdrawn from the origin's account.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![TransferReserveAsset {
+			assets: (Here, 100).into(),
+			dest: Parachain(2).into(),
+			xcm: Xcm::<()>(vec![]),
+		}]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(assets(1001), vec![(Here, 890).into()]);
+	assert_eq!(assets(1002), vec![(Here, 100).into()]);
+	assert_eq!(assets(4), vec![(Here, 10).into()]);
+	assert_eq!(sent_xcm().len(), 1);
+
+	// The delivery fee is taken from Holding when it holds enough.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			DepositReserveAsset {
+				assets: (Here, 50).into(),
+				max_assets: 1,
+				dest: Parachain(2).into(),
+				xcm: Xcm::<()>(vec![]),
+			},
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Parachain(1).into() },
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(30));
+	assert_eq!(assets(1001), vec![(Here, 830).into()]);
+	assert_eq!(assets(1002), vec![(Here, 150).into()]);
+	assert_eq!(assets(4), vec![(Here, 20).into()]);
+	assert_eq!(sent_xcm().len(), 2);
+}
+
+#[test]
+fn delivery_fees_should_be_taken_before_assets_leave_holding() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	DeliveryPrice::set((Here, 10).into());
+	add_asset(1001, (Here, 1000));
+	let three: MultiLocation = X1(AccountIndex64 { index: 3, network: Any }).into();
+
+	// Once the origin is cleared, the fee can only come from the assets being transferred.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			ClearOrigin,
+			DepositReserveAsset {
+				assets: All.into(),
+				max_assets: 1,
+				dest: Parachain(2).into(),
+				xcm: Xcm::<()>(vec![DepositAsset {
+					assets: All.into(),
+					max_assets: 1,
+					beneficiary: three.clone(),
+				}]),
+			},
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(30));
+	assert_eq!(assets(1001), vec![(Here, 900).into()]);
+	assert_eq!(assets(1002), vec![(Here, 90).into()]);
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			Parachain(2).into(),
+			Xcm::<()>(vec![
+				ReserveAssetDeposited((Parent, 90).into()),
+				ClearOrigin,
+				DepositAsset { assets: All.into(), max_assets: 1, beneficiary: three },
+			]),
+		)]
+	);
+
+	// Nothing is deposited if the fee can't be paid.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 5).into()),
+			ClearOrigin,
+			DepositReserveAsset {
+				assets: All.into(),
+				max_assets: 1,
+				dest: Parachain(2).into(),
+				xcm: Xcm::<()>(vec![]),
+			},
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(30, XcmError::NotHoldingFees));
+	assert_eq!(assets(1002), vec![(Here, 90).into()]);
+	assert_eq!(sent_xcm().len(), 1);
+}
+
+#[test]
+fn delivery_fees_should_be_refunded_if_sending_fails() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	DeliveryPrice::set((Here, 10).into());
+	let treasury: MultiLocation = X1(AccountIndex64 { index: 4, network: Any }).into();
+	FeeReceiver::set(Some(treasury));
+	SendFails::set(true);
+	add_asset(1001, (Here, 1000));
+
+	// Fees withdrawn from the origin's account are returned to it.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![ReportError { query_id: 0, dest: Parachain(2).into(), max_response_weight: 0 }]),
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::Transport("Sending is disabled")));
+	assert_eq!(assets(1001), vec![(Here, 1000).into()]);
+	assert_eq!(assets(4), vec![]);
+
+	// Fees taken from Holding are returned to it.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			SetErrorHandler(Xcm(vec![DepositAsset {
+				assets: All.into(),
+				max_assets: 1,
+				beneficiary: Parachain(1).into(),
+			}])),
+			ReportError { query_id: 0, dest: Parachain(2).into(), max_response_weight: 0 },
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(40, XcmError::Transport("Sending is disabled")));
+	assert_eq!(assets(1001), vec![(Here, 1000).into()]);
+	assert_eq!(assets(4), vec![]);
+	assert_eq!(sent_xcm(), vec![]);
+}
+
+#[test]
+fn delivery_fees_can_be_waived_or_burned() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into(), X1(Parachain(3)).into()]);
+	DeliveryPrice::set((Here, 10).into());
+	WaivedLocations::set(vec![X1(Parachain(3)).into()]);
+	add_asset(1001, (Here, 1000));
+	add_asset(1003, (Here, 1000));
+	let message = Xcm(vec![TransferReserveAsset {
+		assets: (Here, 100).into(),
+		dest: Parachain(2).into(),
+		xcm: Xcm::<()>(vec![]),
+	}]);
+
+	// Fees are waived for parachain #3.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(3), message.clone(), 50);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(assets(1003), vec![(Here, 900).into()]);
+
+	// There is no receiver, so fees paid by parachain #1 are burned.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message, 50);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(assets(1001), vec![(Here, 890).into()]);
+	assert_eq!(assets(1002), vec![(Here, 200).into()]);
+
+	// Nobody can pay once the origin is cleared and Holding is empty.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			ClearOrigin,
+			ReportError { query_id: 0, dest: Parachain(2).into(), max_response_weight: 0 },
+		]),
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(20, XcmError::NotHoldingFees));
+	assert_eq!(sent_xcm().len(), 2);
+}
+
+#[test]
+fn expectations_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			ExpectAsset((Here, 100).into()),
+			ExpectOrigin(Some(Parachain(1).into())),
+			ExpectError(None),
+			SetTopic([1; 32]),
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Parachain(1).into() },
+		]),
+		60,
+	);
+	assert_eq!(r, Outcome::Complete(60));
+	assert_eq!(assets(1001), vec![(Here, 1000).into()]);
+
+	// Holding does not contain enough.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			ExpectAsset((Here, 101).into()),
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Parachain(1).into() },
+		]),
+		30,
+	);
+	assert_eq!(r, Outcome::Incomplete(30, XcmError::ExpectationFalse));
+
+	// The origin has been cleared.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![ClearOrigin, ExpectOrigin(Some(Parachain(1).into()))]),
+		20,
+	);
+	assert_eq!(r, Outcome::Incomplete(20, XcmError::ExpectationFalse));
+
+	// There is no error yet.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![ExpectError(Some((0, XcmError::Unimplemented)))]),
+		10,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::ExpectationFalse));
+}
+
+#[test]
+fn report_holding_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	add_asset(1001, (Here, 1000));
+
+	let response_info =
+		QueryResponseInfo { destination: Parent.into(), query_id: 42, max_weight: 5000 };
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			WithdrawAsset((Here, 100).into()),
+			ReportHolding { response_info, assets: All.into() },
+			DepositAsset { assets: All.into(), max_assets: 1, beneficiary: Parachain(1).into() },
+		]),
+		30,
+	);
+	assert_eq!(r, Outcome::Complete(30));
+	// The assets are reported as seen from the destination.
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			Parent.into(),
+			Xcm(vec![QueryResponse {
+				query_id: 42,
+				response: Response::Assets((Parachain(42), 100).into()),
+				max_weight: 5000,
+			}]),
+		)]
+	);
+}
+
+#[test]
+fn asset_locking_should_work() {
+	let owner: MultiLocation = X1(AccountIndex64 { index: 1, network: Any }).into();
+	let unlocker: MultiLocation = (Parent, Parachain(1)).into();
+	AllowUnpaidFrom::set(vec![owner.clone(), unlocker.clone()]);
+	add_asset(1, (Here, 100));
+
+	// The owner can't lock more than it owns.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		owner.clone(),
+		Xcm(vec![LockAsset { asset: (Here, 200).into(), unlocker: unlocker.clone() }]),
+		10,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::LockError));
+	assert_eq!(sent_xcm(), vec![]);
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		owner.clone(),
+		Xcm(vec![LockAsset { asset: (Here, 100).into(), unlocker: unlocker.clone() }]),
+		10,
+	);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(locked(), vec![(owner.clone(), (Here, 100).into(), unlocker.clone())]);
+	// The unlocker is notified, with the asset and owner as seen from there.
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			unlocker.clone(),
+			Xcm(vec![NoteUnlockable {
+				asset: ((Parent, Parachain(42)), 100).into(),
+				owner: (Parent, Parachain(42), AccountIndex64 { index: 1, network: Any }).into(),
+			}]),
+		)]
+	);
+
+	// Only the unlocker may unlock the asset.
+	let message = Xcm(vec![UnlockAsset { asset: (Here, 100).into(), target: owner.clone() }]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(owner.clone(), message.clone(), 10);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::LockError));
+	let r = XcmExecutor::<TestConfig>::execute_xcm(unlocker.clone(), message.clone(), 10);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(locked(), vec![]);
+
+	// The asset isn't locked anymore.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(unlocker, message, 10);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::LockError));
+}
+
+#[test]
+fn remote_unlocking_should_work() {
+	let owner: MultiLocation = X1(AccountIndex64 { index: 1, network: Any }).into();
+	let locker: MultiLocation = (Parent, Parachain(1)).into();
+	AllowUnpaidFrom::set(vec![owner.clone(), locker.clone()]);
+	let asset: MultiAsset = ((Parent, Parachain(1)), 100).into();
+
+	// Nothing has been noted as unlockable yet.
+	let message = Xcm(vec![RequestUnlock { asset: asset.clone(), locker: locker.clone() }]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(owner.clone(), message.clone(), 10);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::LockError));
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		locker.clone(),
+		Xcm(vec![NoteUnlockable { asset: asset.clone(), owner: owner.clone() }]),
+		10,
+	);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(unlockable(), vec![(locker.clone(), asset.clone(), owner.clone())]);
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(owner, message, 10);
+	assert_eq!(r, Outcome::Complete(10));
+	assert_eq!(unlockable(), vec![]);
+	// The locker is asked to unlock the asset, as seen from there.
+	assert_eq!(
+		sent_xcm(),
+		vec![(
+			locker,
+			Xcm(vec![UnlockAsset {
+				asset: (Here, 100).into(),
+				target: (Parent, Parachain(42), AccountIndex64 { index: 1, network: Any }).into(),
+			}]),
+		)]
+	);
+}
+
+#[test]
+fn universal_origin_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into(), X1(Parachain(2)).into()]);
+	UniversalAliases::set(vec![
+		(X1(Parachain(1)).into(), GlobalConsensus(Kusama)),
+		(X1(Parachain(1)).into(), GlobalConsensus(Polkadot)),
+	]);
+
+	// The new origin is relative to the root of the universe, which is our grandparent.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![
+			UniversalOrigin(GlobalConsensus(Kusama)),
+			ExpectOrigin(Some((Parent, Parent, GlobalConsensus(Kusama)).into())),
+		]),
+		20,
+	);
+	assert_eq!(r, Outcome::Complete(20));
+
+	// Parachain #2 may not alias anything, nor parachain #1 anything but the aliases given.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(2),
+		Xcm(vec![UniversalOrigin(GlobalConsensus(Kusama))]),
+		10,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::NoPermission));
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![UniversalOrigin(Parachain(3))]),
+		10,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::NoPermission));
+
+	// Nobody may alias our own global consensus, whatever the configured aliases.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![UniversalOrigin(GlobalConsensus(Polkadot))]),
+		10,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::NoPermission));
+}
+
+#[test]
+fn export_message_should_work() {
+	AllowUnpaidFrom::set(vec![X1(Parachain(1)).into()]);
+	ExportPrice::set((Here, 10).into());
+	add_asset(1001, (Here, 1000));
+
+	let message = Xcm(vec![ExportMessage {
+		network: Kusama,
+		destination: X1(Parachain(1000)),
+		xcm: Xcm(vec![ClearOrigin]),
+	}]);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Parachain(1), message.clone(), 10);
+	assert_eq!(r, Outcome::Complete(10));
+	// The export fee is paid by the origin.
+	assert_eq!(assets(1001), vec![(Here, 990).into()]);
+	assert_eq!(
+		exported_xcm(),
+		vec![(
+			Kusama,
+			0,
+			X3(GlobalConsensus(Polkadot), Parachain(42), Parachain(1)),
+			X1(Parachain(1000)),
+			Xcm(vec![ClearOrigin])
+		)]
+	);
+
+	// The export can't be paid once the origin is cleared.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		Parachain(1),
+		Xcm(vec![ClearOrigin, message.0[0].clone()]),
+		20,
+	);
+	assert_eq!(r, Outcome::Incomplete(20, XcmError::BadOrigin));
+	assert_eq!(exported_xcm().len(), 1);
+}
+
 #[test]
 fn simple_version_subscriptions_should_work() {
 	AllowSubsFrom::set(vec![Parent.into()]);
@@ -714,3 +1178,136 @@ fn weight_trader_tuple_should_work() {
 	// and no refund
 	assert_eq!(traders.refund_weight(2), None);
 }
+
+#[test]
+fn weight_trader_fee_queries_should_work() {
+	pub const PARA_1: MultiLocation = X1(Parachain(1)).into();
+	pub const PARA_2: MultiLocation = X1(Parachain(2)).into();
+
+	parameter_types! {
+		pub static HereWeightPrice: (AssetId, u128) = (Here.into().into(), WEIGHT_PER_SECOND.ref_time().into());
+		pub static PARA1WeightPrice: (AssetId, u128) = (PARA_1.into(), 2 * WEIGHT_PER_SECOND.ref_time() as u128);
+	}
+
+	type Traders = (
+		FixedRateOfFungible<HereWeightPrice, ()>,
+		FixedRateOfFungible<PARA1WeightPrice, ()>,
+		// duplicates are only listed once
+		FixedRateOfFungible<HereWeightPrice, ()>,
+	);
+
+	let here: AssetId = Here.into().into();
+	assert_eq!(Traders::acceptable_fee_assets(), vec![here.clone(), PARA_1.into()]);
+	assert_eq!(Traders::weight_to_fee(5, &here), Some(5));
+	assert_eq!(Traders::weight_to_fee(5, &PARA_1.into()), Some(10));
+	assert_eq!(Traders::weight_to_fee(5, &PARA_2.into()), None);
+	// Quotes for huge prices or weights saturate rather than overflow.
+	PARA1WeightPrice::set((PARA_1.into(), u128::MAX));
+	assert_eq!(Traders::weight_to_fee(Weight::MAX, &PARA_1.into()), Some(u128::MAX));
+	PARA1WeightPrice::set((PARA_1.into(), 2 * WEIGHT_PER_SECOND.ref_time() as u128));
+
+	// The quoted fee is exactly what buying the weight costs.
+	let mut traders = Traders::new();
+	assert_eq!(traders.buy_weight(5, fungible_multi_asset(PARA_1, 10).into()), Ok(Assets::new()),);
+}
+
+#[test]
+fn price_oracle_trader_should_work() {
+	pub const PARA_1: MultiLocation = X1(Parachain(1)).into();
+	pub const PARA_2: MultiLocation = X1(Parachain(2)).into();
+
+	parameter_types! {
+		pub static TakenRevenue: Vec<MultiAsset> = vec![];
+	}
+
+	/// Prices weight in `PARA_1` only, at two units per unit of weight.
+	struct MockOracle;
+	impl PriceOracle for MockOracle {
+		fn priced_assets() -> Vec<AssetId> {
+			vec![PARA_1.into()]
+		}
+
+		fn price_for_weight(weight: Weight, asset: &AssetId) -> Option<u128> {
+			(*asset == PARA_1.into()).then(|| 2 * weight as u128)
+		}
+	}
+
+	struct MockRevenue;
+	impl TakeRevenue for MockRevenue {
+		fn take_revenue(revenue: MultiAsset) {
+			let mut taken = TakenRevenue::get();
+			taken.push(revenue);
+			TakenRevenue::set(taken);
+		}
+	}
+
+	type Trader = UsingPriceOracle<MockOracle, MockRevenue>;
+
+	assert_eq!(Trader::acceptable_fee_assets(), vec![PARA_1.into()]);
+	assert_eq!(Trader::weight_to_fee(5, &PARA_1.into()), Some(10));
+	assert_eq!(Trader::weight_to_fee(5, &PARA_2.into()), None);
+
+	let mut trader = Trader::new();
+	// Assets without a price cannot pay.
+	assert_eq!(
+		trader.buy_weight(10, fungible_multi_asset(PARA_2, 100).into()),
+		Err(XcmError::TooExpensive),
+	);
+	// Nor can priced assets in insufficient amounts.
+	assert_eq!(
+		trader.buy_weight(10, fungible_multi_asset(PARA_1, 19).into()),
+		Err(XcmError::TooExpensive),
+	);
+
+	// The priced asset is picked out of the payment.
+	let payment: Assets =
+		vec![fungible_multi_asset(PARA_1, 100), fungible_multi_asset(PARA_2, 100)].into();
+	let expected: Assets =
+		vec![fungible_multi_asset(PARA_1, 80), fungible_multi_asset(PARA_2, 100)].into();
+	assert_eq!(trader.buy_weight(10, payment), Ok(expected));
+
+	// Refunds are made at the same rate and never exceed what was paid.
+	assert_eq!(trader.refund_weight(4), Some(fungible_multi_asset(PARA_1, 8)));
+	assert_eq!(trader.refund_weight(100), Some(fungible_multi_asset(PARA_1, 12)));
+	assert_eq!(trader.refund_weight(1), None);
+
+	// Whatever is kept is taken as revenue.
+	assert_eq!(trader.buy_weight(5, fungible_multi_asset(PARA_1, 10).into()), Ok(Assets::new()));
+	drop(trader);
+	assert_eq!(TakenRevenue::get(), vec![fungible_multi_asset(PARA_1, 10)]);
+
+	// Once locked to an asset, payments without it are refused.
+	let mut trader = Trader::new();
+	assert_eq!(trader.buy_weight(5, fungible_multi_asset(PARA_1, 10).into()), Ok(Assets::new()));
+	assert_eq!(
+		trader.buy_weight(1, fungible_multi_asset(PARA_2, 10).into()),
+		Err(XcmError::TooExpensive),
+	);
+	assert_eq!(trader.refund_weight(5), Some(fungible_multi_asset(PARA_1, 10)));
+	drop(trader);
+}
+
+#[test]
+fn price_oracle_trader_refunds_large_payments_exactly() {
+	pub const PARA_1: MultiLocation = X1(Parachain(1)).into();
+
+	/// Prices weight in `PARA_1` at an amount which overflows when multiplied by the weight.
+	struct MockOracle;
+	impl PriceOracle for MockOracle {
+		fn priced_assets() -> Vec<AssetId> {
+			vec![PARA_1.into()]
+		}
+
+		fn price_for_weight(weight: Weight, asset: &AssetId) -> Option<u128> {
+			(*asset == PARA_1.into()).then(|| (u128::MAX / 1_000) * (weight as u128 / 1_000))
+		}
+	}
+
+	let mut trader = UsingPriceOracle::<MockOracle, ()>::new();
+	let paid = (u128::MAX / 1_000) * 1_000;
+	assert_eq!(
+		trader.buy_weight(1_000_000, fungible_multi_asset(PARA_1, paid).into()),
+		Ok(Assets::new()),
+	);
+	assert_eq!(trader.refund_weight(500_000), Some(fungible_multi_asset(PARA_1, paid / 2)));
+}
@@ -0,0 +1,110 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Traits and utilities to help with origin mutation and bridging.
+
+use frame_support::traits::Get;
+use sp_std::marker::PhantomData;
+use xcm::latest::prelude::*;
+use xcm_executor::traits::ExportXcm;
+
+/// Determine whether `dest` lies within a different global consensus system to the one given by
+/// `universal_local`, the universal location of the local chain.
+///
+/// Returns the remote global consensus system together with the location of `dest` within it, or
+/// `dest` back again if it is local (or cannot be expressed universally at all).
+pub fn ensure_is_remote(
+	universal_local: impl Into<InteriorMultiLocation>,
+	dest: impl Into<MultiLocation>,
+) -> Result<(NetworkId, InteriorMultiLocation), MultiLocation> {
+	let dest = dest.into();
+	let universal_local = universal_local.into();
+	let local_net = match universal_local.global_consensus() {
+		Ok(net) => net,
+		Err(()) => return Err(dest),
+	};
+	let universal_destination = match universal_local.universal_location_of(&dest) {
+		Ok(location) => location,
+		Err(()) => return Err(dest),
+	};
+	match universal_destination.split_first() {
+		(remote_dest, Some(GlobalConsensus(remote_net))) if remote_net != local_net =>
+			Ok((remote_net, remote_dest)),
+		_ => Err(dest),
+	}
+}
+
+/// Implementation of [`SendXcm`] which routes messages destined for a different global consensus
+/// system to the given `Exporter`, as though they were exported from this chain.
+///
+/// Messages for destinations within our own consensus system are left for the next router, so
+/// this should generally be the first item of a router tuple.
+pub struct LocalExporter<Exporter, UniversalLocation>(PhantomData<(Exporter, UniversalLocation)>);
+
+impl<Exporter: ExportXcm, UniversalLocation: Get<InteriorMultiLocation>> SendXcm
+	for LocalExporter<Exporter, UniversalLocation>
+{
+	fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+		let universal_source = UniversalLocation::get();
+		let (network, destination) = match ensure_is_remote(universal_source.clone(), dest) {
+			Ok(remote) => remote,
+			Err(dest) => return Err(SendError::CannotReachDestination(dest, msg)),
+		};
+		Exporter::export_xcm(network, 0, universal_source, destination, msg)
+	}
+}
+
+impl<Exporter: ExportXcm, UniversalLocation: Get<InteriorMultiLocation>> QuoteDelivery
+	for LocalExporter<Exporter, UniversalLocation>
+{
+	fn quote_delivery(dest: &MultiLocation, msg: &Xcm<()>) -> Option<MultiAssets> {
+		let (network, destination) =
+			ensure_is_remote(UniversalLocation::get(), dest.clone()).ok()?;
+		Exporter::quote_export(&network, &destination, msg)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ensure_is_remote_works() {
+		let polkadot = X1(GlobalConsensus(Polkadot));
+		let polkadot_para = X2(GlobalConsensus(Polkadot), Parachain(1000));
+
+		// A Kusama parachain is remote from the perspective of Polkadot.
+		let x =
+			ensure_is_remote(polkadot.clone(), (Parent, GlobalConsensus(Kusama), Parachain(1000)));
+		assert_eq!(x, Ok((Kusama, X1(Parachain(1000)))));
+
+		// Polkadot Relay is local from the perspective of Polkadot.
+		let x = ensure_is_remote(polkadot.clone(), Here);
+		assert_eq!(x, Err(Here.into()));
+
+		// Our own parachains are local too.
+		let x = ensure_is_remote(polkadot_para.clone(), (Parent, Parachain(2000)));
+		assert_eq!(x, Err((Parent, Parachain(2000)).into()));
+
+		// As is anything which can't be expressed from the root of the universe.
+		let x = ensure_is_remote(polkadot, MultiLocation::new(2, Here));
+		assert_eq!(x, Err(MultiLocation::new(2, Here)));
+
+		// Kusama is remote from a Polkadot parachain as well.
+		let x = ensure_is_remote(polkadot_para, (Parent, Parent, GlobalConsensus(Kusama)));
+		assert_eq!(x, Ok((Kusama, Here)));
+	}
+}
@@ -20,14 +20,27 @@ use frame_support::{
 	weights::{constants::WEIGHT_PER_SECOND, WeightToFee as WeightToFeeT},
 };
 use parity_scale_codec::Decode;
+use sp_arithmetic::{helpers_128bit::multiply_by_rational_with_rounding, Rounding};
 use sp_runtime::traits::{SaturatedConversion, Saturating, Zero};
-use sp_std::{marker::PhantomData, result::Result};
+use sp_std::{marker::PhantomData, result::Result, vec, vec::Vec};
 use xcm::latest::{prelude::*, Weight};
 use xcm_executor::{
 	traits::{WeightBounds, WeightTrader},
 	Assets,
 };
 
+/// The amount of an asset priced at `units_per_second` which buys `weight`, saturating rather than
+/// overflowing for large prices.
+fn fee_for_weight(units_per_second: u128, weight: Weight) -> u128 {
+	multiply_by_rational_with_rounding(
+		units_per_second,
+		weight as u128,
+		WEIGHT_PER_SECOND.ref_time() as u128,
+		Rounding::Down,
+	)
+	.unwrap_or(u128::MAX)
+}
+
 pub struct FixedWeightBounds<T, C, M>(PhantomData<(T, C, M)>);
 impl<T: Get<Weight>, C: Decode + GetDispatchInfo, M: Get<u32>> WeightBounds<C>
 	for FixedWeightBounds<T, C, M>
@@ -174,6 +187,15 @@ impl<T: Get<(MultiLocation, u128)>, R: TakeRevenue> WeightTrader
 			None
 		}
 	}
+
+	fn acceptable_fee_assets() -> Vec<AssetId> {
+		vec![Concrete(T::get().0)]
+	}
+
+	fn weight_to_fee(weight: Weight, asset: &AssetId) -> Option<u128> {
+		let (id, units_per_second) = T::get();
+		(*asset == Concrete(id)).then(|| fee_for_weight(units_per_second, weight))
+	}
 }
 #[allow(deprecated)]
 impl<T: Get<(MultiLocation, u128)>, R: TakeRevenue> Drop for FixedRateOfConcreteFungible<T, R> {
@@ -229,6 +251,15 @@ impl<T: Get<(AssetId, u128)>, R: TakeRevenue> WeightTrader for FixedRateOfFungib
 			None
 		}
 	}
+
+	fn acceptable_fee_assets() -> Vec<AssetId> {
+		vec![T::get().0]
+	}
+
+	fn weight_to_fee(weight: Weight, asset: &AssetId) -> Option<u128> {
+		let (id, units_per_second) = T::get();
+		(*asset == id).then(|| fee_for_weight(units_per_second, weight))
+	}
 }
 
 impl<T: Get<(AssetId, u128)>, R: TakeRevenue> Drop for FixedRateOfFungible<T, R> {
@@ -239,6 +270,120 @@ impl<T: Get<(AssetId, u128)>, R: TakeRevenue> Drop for FixedRateOfFungible<T, R>
 	}
 }
 
+/// Source of prices for weight in terms of arbitrary assets, such as the reserves of a DEX pool or
+/// configured exchange rates.
+pub trait PriceOracle {
+	/// The assets which can currently be priced.
+	fn priced_assets() -> Vec<AssetId>;
+
+	/// Returns the amount of `asset` which buys `weight`, or `None` if `asset` cannot be priced.
+	fn price_for_weight(weight: Weight, asset: &AssetId) -> Option<u128>;
+}
+
+/// Null implementation which prices nothing.
+impl PriceOracle for () {
+	fn priced_assets() -> Vec<AssetId> {
+		Vec::new()
+	}
+
+	fn price_for_weight(_weight: Weight, _asset: &AssetId) -> Option<u128> {
+		None
+	}
+}
+
+/// Fee calculator that accepts payment in any fungible asset of the holding for which the price
+/// oracle `O` has a price.
+///
+/// The first priced asset in the payment is used, and is kept for any further weight bought by the
+/// same trader. Refunds are made in that asset, at the rate at which the weight was bought.
+pub struct UsingPriceOracle<O: PriceOracle, R: TakeRevenue>(
+	Option<(AssetId, Weight, u128)>,
+	PhantomData<(O, R)>,
+);
+impl<O: PriceOracle, R: TakeRevenue> WeightTrader for UsingPriceOracle<O, R> {
+	fn new() -> Self {
+		Self(None, PhantomData)
+	}
+
+	fn buy_weight(&mut self, weight: Weight, payment: Assets) -> Result<Assets, XcmError> {
+		log::trace!(
+			target: "xcm::weight",
+			"UsingPriceOracle::buy_weight weight: {:?}, payment: {:?}",
+			weight, payment,
+		);
+		if weight == 0 {
+			return Ok(payment)
+		}
+		let candidates = match &self.0 {
+			Some((id, ..)) => vec![id.clone()],
+			None => payment.fungible_assets_iter().map(|asset| asset.id).collect(),
+		};
+		for id in candidates {
+			if !payment.fungible.contains_key(&id) {
+				continue
+			}
+			let amount = match O::price_for_weight(weight, &id) {
+				Some(amount) => amount,
+				None => continue,
+			};
+			let unused = if amount == 0 {
+				payment.clone()
+			} else {
+				match payment.clone().checked_sub((id.clone(), amount).into()) {
+					Ok(unused) => unused,
+					Err(_) => continue,
+				}
+			};
+			let (bought, paid) = self.0.as_ref().map_or((0, 0), |(_, w, p)| (*w, *p));
+			self.0 = Some((id, bought.saturating_add(weight), paid.saturating_add(amount)));
+			return Ok(unused)
+		}
+		Err(XcmError::TooExpensive)
+	}
+
+	fn refund_weight(&mut self, weight: Weight) -> Option<MultiAsset> {
+		log::trace!(target: "xcm::weight", "UsingPriceOracle::refund_weight weight: {:?}", weight);
+		let (id, bought, paid) = self.0.as_mut()?;
+		let weight = weight.min(*bought);
+		if weight == 0 {
+			return None
+		}
+		// The result never exceeds `paid`, since `weight <= bought`.
+		let amount = multiply_by_rational_with_rounding(
+			*paid,
+			weight as u128,
+			*bought as u128,
+			Rounding::Down,
+		)
+		.unwrap_or(*paid);
+		*bought -= weight;
+		*paid -= amount;
+		if amount > 0 {
+			Some((id.clone(), amount).into())
+		} else {
+			None
+		}
+	}
+
+	fn acceptable_fee_assets() -> Vec<AssetId> {
+		O::priced_assets()
+	}
+
+	fn weight_to_fee(weight: Weight, asset: &AssetId) -> Option<u128> {
+		O::price_for_weight(weight, asset)
+	}
+}
+
+impl<O: PriceOracle, R: TakeRevenue> Drop for UsingPriceOracle<O, R> {
+	fn drop(&mut self) {
+		if let Some((id, _, paid)) = self.0.take() {
+			if paid > 0 {
+				R::take_revenue((id, paid).into());
+			}
+		}
+	}
+}
+
 /// Weight trader which uses the `TransactionPayment` pallet to set the right price for weight and then
 /// places any weight bought into the right account.
 pub struct UsingComponents<
@@ -290,6 +435,19 @@ impl<
 			None
 		}
 	}
+
+	fn acceptable_fee_assets() -> Vec<xcm::latest::AssetId> {
+		vec![Concrete(AssetId::get())]
+	}
+
+	fn weight_to_fee(weight: Weight, asset: &xcm::latest::AssetId) -> Option<u128> {
+		if *asset != Concrete(AssetId::get()) {
+			return None
+		}
+		WeightToFee::weight_to_fee(&frame_support::weights::Weight::from_ref_time(weight))
+			.try_into()
+			.ok()
+	}
 }
 impl<
 		WeightToFee: WeightToFeeT<Balance = Currency::Balance>,
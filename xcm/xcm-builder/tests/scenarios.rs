@@ -99,14 +99,14 @@ fn transfer_asset_works() {
 
 /// Scenario:
 /// A parachain wants to be notified that a transfer worked correctly.
-/// It includes a `QueryHolding` order after the deposit to get notified on success.
-/// This somewhat abuses `QueryHolding` as an indication of execution success. It works because
+/// It includes a `ReportHolding` order after the deposit to get notified on success.
+/// This somewhat abuses `ReportHolding` as an indication of execution success. It works because
 /// order execution halts on error (so no `QueryResponse` will be sent if the previous order failed).
 /// The inner response sent due to the query is not used.
 ///
 /// Asserts that the balances are updated correctly and the expected XCM is sent.
 #[test]
-fn query_holding_works() {
+fn report_holding_works() {
 	use xcm::opaque::latest::prelude::*;
 	let para_acc: AccountId = ParaId::from(PARA_ID).into_account_truncating();
 	let balances = vec![(ALICE, INITIAL_BALANCE), (para_acc.clone(), INITIAL_BALANCE)];
@@ -127,11 +127,13 @@ fn query_holding_works() {
 					beneficiary: OnlyChild.into(), // invalid destination
 				},
 				// is not triggered becasue the deposit fails
-				QueryHolding {
-					query_id,
-					dest: Parachain(PARA_ID).into(),
+				ReportHolding {
+					response_info: QueryResponseInfo {
+						destination: Parachain(PARA_ID).into(),
+						query_id,
+						max_weight: max_response_weight,
+					},
 					assets: All.into(),
-					max_response_weight,
 				},
 			]),
 			weight,
@@ -159,11 +161,13 @@ fn query_holding_works() {
 					beneficiary: Parachain(other_para_id).into(),
 				},
 				// used to get a notification in case of success
-				QueryHolding {
-					query_id,
-					dest: Parachain(PARA_ID).into(),
+				ReportHolding {
+					response_info: QueryResponseInfo {
+						destination: Parachain(PARA_ID).into(),
+						query_id,
+						max_weight: 1_000_000_000,
+					},
 					assets: All.into(),
-					max_response_weight: 1_000_000_000,
 				},
 			]),
 			weight,
@@ -64,7 +64,8 @@ fn basic_buy_fees_message_executes() {
 		assert!(polkadot_test_runtime::System::events().iter().any(|r| matches!(
 			r.event,
 			polkadot_test_runtime::RuntimeEvent::Xcm(pallet_xcm::Event::Attempted(
-				Outcome::Complete(_)
+				Outcome::Complete(_),
+				_
 			)),
 		)));
 	});
@@ -147,7 +148,7 @@ fn query_response_fires() {
 		assert_eq!(
 			polkadot_test_runtime::Xcm::query(query_id),
 			Some(QueryStatus::Ready {
-				response: VersionedResponse::V2(Response::ExecutionResult(None)),
+				response: VersionedResponse::V3(Response::ExecutionResult(None)),
 				at: 2u32.into()
 			}),
 		)
@@ -15,19 +15,22 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::traits::{
-	ClaimAssets, ConvertOrigin, DropAssets, FilterAssetLocation, InvertLocation, OnResponse,
-	ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
+	AssetLock, ClaimAssets, ConvertOrigin, DropAssets, ExportXcm, FeeManager, FilterAssetLocation,
+	OnResponse, ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
 };
-use frame_support::dispatch::{Dispatchable, GetDispatchInfo, Parameter, PostDispatchInfo};
-use xcm::latest::SendXcm;
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, Parameter, PostDispatchInfo},
+	traits::{Contains, Get},
+};
+use xcm::latest::{InteriorMultiLocation, Junction, MultiLocation, QuoteDelivery, SendXcm};
 
 /// The trait to parameterize the `XcmExecutor`.
 pub trait Config {
 	/// The outer call dispatch type.
 	type RuntimeCall: Parameter + Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo;
 
-	/// How to send an onward XCM message.
-	type XcmSender: SendXcm;
+	/// How to send an onward XCM message, and how much its delivery costs.
+	type XcmSender: SendXcm + QuoteDelivery;
 
 	/// How to withdraw and deposit an asset.
 	type AssetTransactor: TransactAsset;
@@ -41,8 +44,9 @@ pub trait Config {
 	/// Combinations of (Location, Asset) pairs which we trust as teleporters.
 	type IsTeleporter: FilterAssetLocation;
 
-	/// Means of inverting a location.
-	type LocationInverter: InvertLocation;
+	/// The location of this chain relative to the root of the universe, beginning with the
+	/// `GlobalConsensus` junction of the consensus system it is part of.
+	type UniversalLocation: Get<InteriorMultiLocation>;
 
 	/// Whether we should execute the given XCM at all.
 	type Barrier: ShouldExecute;
@@ -65,4 +69,16 @@ pub trait Config {
 
 	/// How we handle version subscription requests.
 	type SubscriptionService: VersionChangeNotifier;
+
+	/// Who pays the fees of delivering onward messages, and where the fees go.
+	type FeeManager: FeeManager;
+
+	/// The handler for asset locking and unlocking instructions.
+	type AssetLocker: AssetLock;
+
+	/// How to export messages to other global consensus systems.
+	type MessageExporter: ExportXcm;
+
+	/// The origins which may use `UniversalOrigin` to become the given global consensus system.
+	type UniversalAliases: Contains<(MultiLocation, Junction)>;
 }
@@ -19,19 +19,28 @@
 use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo},
 	ensure,
+	traits::{Contains, Get},
 };
+use parity_scale_codec::Encode;
 use sp_runtime::traits::Saturating;
 use sp_std::{marker::PhantomData, prelude::*};
-use xcm::latest::{
-	Error as XcmError, ExecuteXcm,
-	Instruction::{self, *},
-	MultiAssets, MultiLocation, Outcome, Response, SendXcm, Weight, Xcm,
+use xcm::{
+	latest::{
+		Error as XcmError, ExecuteXcm,
+		Instruction::{self, *},
+		InteriorMultiLocation,
+		Junctions::X1,
+		MultiAssets, MultiLocation, Outcome, QueryResponseInfo, QuoteDelivery, Response, SendXcm,
+		Weight, Xcm, XcmHash,
+	},
+	VersionedXcm,
 };
 
 pub mod traits;
 use traits::{
-	ClaimAssets, ConvertOrigin, DropAssets, FilterAssetLocation, InvertLocation, OnResponse,
-	ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
+	AssetLock, ClaimAssets, ConvertOrigin, DropAssets, Enact, ExportXcm, FeeManager,
+	FilterAssetLocation, OnResponse, ShouldExecute, TransactAsset, VersionChangeNotifier,
+	WeightBounds, WeightTrader,
 };
 
 mod assets;
@@ -58,20 +67,89 @@ pub struct XcmExecutor<Config: config::Config> {
 	pub error_handler_weight: u64,
 	pub appendix: Xcm<Config::RuntimeCall>,
 	pub appendix_weight: u64,
+	/// The topic of the message, if any was set with `SetTopic`.
+	pub topic: Option<[u8; 32]>,
+	/// The steps executed so far, if they are being recorded.
+	pub trace: Option<Vec<ExecutionStep>>,
 	_config: PhantomData<Config>,
 }
 
+/// The state of the holding register after an instruction has been executed, as recorded by
+/// [`XcmExecutor::execute_xcm_with_trace`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ExecutionStep {
+	/// The index of the instruction within the program fragment (the message itself, its error
+	/// handler or its appendix) to which it belongs.
+	pub index: u32,
+	/// The contents of the holding register after the instruction was executed.
+	pub holding: MultiAssets,
+}
+
+/// Fees withdrawn for a service, such as the delivery of a message, which is yet to be provided.
+struct WithdrawnFees {
+	/// The withdrawn assets.
+	assets: Assets,
+	/// The location whose account the fees were withdrawn from, or `None` if they were taken from
+	/// Holding.
+	payer: Option<MultiLocation>,
+}
+
 /// The maximum recursion limit for `execute_xcm` and `execute_effects`.
 pub const MAX_RECURSION_LIMIT: u32 = 8;
 
+/// Return the identifier of `message`: its topic, if it sets one, or otherwise the blake2-256 hash
+/// of its encoding.
+///
+/// The executor carries the topic of a message over to the messages which it sends, so a topic
+/// identifies a message across every hop it takes.
+pub fn message_id<C>(message: &VersionedXcm<C>) -> XcmHash {
+	message.topic().unwrap_or_else(|| sp_io::hashing::blake2_256(&message.encode()))
+}
+
 impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Config> {
 	fn execute_xcm_in_credit(
 		origin: impl Into<MultiLocation>,
+		message: Xcm<Config::RuntimeCall>,
+		weight_limit: Weight,
+		weight_credit: Weight,
+	) -> Outcome {
+		Self::prepare_and_execute(origin.into(), message, weight_limit, weight_credit, false).0
+	}
+
+	fn charge_fees(location: impl Into<MultiLocation>, fees: MultiAssets) -> Result<(), XcmError> {
+		let location = location.into();
+		if Config::FeeManager::is_waived(Some(&location)) {
+			return Ok(())
+		}
+		for asset in fees.inner() {
+			Config::AssetTransactor::withdraw_asset(asset, &location)?;
+		}
+		Config::FeeManager::handle_fee(fees);
+		Ok(())
+	}
+}
+
+impl<Config: config::Config> XcmExecutor<Config> {
+	/// Execute `message` from `origin` exactly as `execute_xcm` would, additionally returning the
+	/// state of the holding register after each instruction which was executed.
+	///
+	/// The effects of the execution are not reverted: this is intended for dry-running messages
+	/// in a context whose changes are discarded anyway, such as a runtime API call.
+	pub fn execute_xcm_with_trace(
+		origin: impl Into<MultiLocation>,
+		message: Xcm<Config::RuntimeCall>,
+		weight_limit: Weight,
+	) -> (Outcome, Vec<ExecutionStep>) {
+		Self::prepare_and_execute(origin.into(), message, weight_limit, 0, true)
+	}
+
+	fn prepare_and_execute(
+		origin: MultiLocation,
 		mut message: Xcm<Config::RuntimeCall>,
 		weight_limit: Weight,
 		mut weight_credit: Weight,
-	) -> Outcome {
-		let origin = origin.into();
+		record_trace: bool,
+	) -> (Outcome, Vec<ExecutionStep>) {
 		log::trace!(
 			target: "xcm::execute_xcm_in_credit",
 			"origin: {:?}, message: {:?}, weight_limit: {:?}, weight_credit: {:?}",
@@ -91,7 +169,7 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 					weight_limit,
 					weight_credit,
 				);
-				return Outcome::Error(XcmError::WeightNotComputable)
+				return (Outcome::Error(XcmError::WeightNotComputable), Vec::new())
 			},
 		};
 		if xcm_weight > weight_limit {
@@ -105,7 +183,7 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 				weight_limit,
 				weight_credit,
 			);
-			return Outcome::Error(XcmError::WeightLimitReached(xcm_weight))
+			return (Outcome::Error(XcmError::WeightLimitReached(xcm_weight)), Vec::new())
 		}
 
 		if let Err(e) =
@@ -120,10 +198,13 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 				weight_limit,
 				weight_credit,
 			);
-			return Outcome::Error(XcmError::Barrier)
+			return (Outcome::Error(XcmError::Barrier), Vec::new())
 		}
 
 		let mut vm = Self::new(origin);
+		if record_trace {
+			vm.trace = Some(Vec::new());
+		}
 
 		while !message.0.is_empty() {
 			let result = vm.execute(message);
@@ -138,7 +219,8 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 			}
 		}
 
-		vm.post_execute(xcm_weight)
+		let trace = vm.trace.take().unwrap_or_default();
+		(vm.post_execute(xcm_weight), trace)
 	}
 }
 
@@ -177,6 +259,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			error_handler_weight: 0,
 			appendix: Xcm(vec![]),
 			appendix_weight: 0,
+			topic: None,
+			trace: None,
 			_config: PhantomData,
 		}
 	}
@@ -195,10 +279,15 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		let mut result = Ok(());
 		for (i, instr) in xcm.0.into_iter().enumerate() {
 			match &mut result {
-				r @ Ok(()) =>
+				r @ Ok(()) => {
 					if let Err(e) = self.process_instruction(instr) {
 						*r = Err(ExecutorError { index: i as u32, xcm_error: e, weight: 0 });
-					},
+					}
+					if let Some(trace) = self.trace.as_mut() {
+						let holding = self.holding.clone().into();
+						trace.push(ExecutionStep { index: i as u32, holding });
+					}
+				},
 				Err(ref mut error) =>
 					if let Ok(x) = Config::Weigher::instr_weight(&instr) {
 						error.weight.saturating_accrue(x)
@@ -303,17 +392,25 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				}
 				Ok(())
 			},
-			TransferReserveAsset { mut assets, dest, xcm } => {
-				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
+			TransferReserveAsset { assets, dest, xcm } => {
+				let origin = self.origin.clone().ok_or(XcmError::BadOrigin)?;
+				let context = Config::UniversalLocation::get();
+				let mut reanchored = assets.clone();
+				reanchored.reanchor(&dest, &context).map_err(|()| XcmError::MultiLocationFull)?;
+				let message = self.transfer_message(ReserveAssetDeposited(reanchored), xcm);
+				// Pay for delivery before the assets are transferred, so that they can't be
+				// transferred without the message which notes their deposit.
+				let fees = self.take_delivery_fees(&dest, &message)?;
 				// Take `assets` from the origin account (on-chain) and place into dest account.
 				for asset in assets.inner() {
-					Config::AssetTransactor::transfer_asset(asset, origin, &dest)?;
+					if let Err(error) =
+						Config::AssetTransactor::transfer_asset(asset, &origin, &dest)
+					{
+						self.settle_fees(fees, false);
+						return Err(error)
+					}
 				}
-				let ancestry = Config::LocationInverter::ancestry();
-				assets.reanchor(&dest, &ancestry).map_err(|()| XcmError::MultiLocationFull)?;
-				let mut message = vec![ReserveAssetDeposited(assets), ClearOrigin];
-				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(dest, Xcm(message)).map_err(Into::into)
+				self.send_paid(dest, message, fees)
 			},
 			ReceiveTeleportedAsset(assets) => {
 				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
@@ -387,8 +484,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				// destination if one was registered.
 				let response = Response::ExecutionResult(self.error);
 				let message = QueryResponse { query_id, response, max_weight };
-				Config::XcmSender::send_xcm(dest, Xcm(vec![message]))?;
-				Ok(())
+				self.send(dest, Xcm(vec![message]))
 			},
 			DepositAsset { assets, max_assets, beneficiary } => {
 				let deposited = self.holding.limited_saturating_take(assets, max_assets as usize);
@@ -398,18 +494,27 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				Ok(())
 			},
 			DepositReserveAsset { assets, max_assets, dest, xcm } => {
+				// Pay for delivery while the assets are still in Holding, quoting it for the
+				// message which would carry all of them.
+				let quoted = Self::reanchored(self.holding.min(&assets), &dest, None);
+				let message = self.transfer_message(ReserveAssetDeposited(quoted), xcm);
+				let fees = self.take_delivery_fees(&dest, &message)?;
 				let deposited = self.holding.limited_saturating_take(assets, max_assets as usize);
 				for asset in deposited.assets_iter() {
-					Config::AssetTransactor::deposit_asset(&asset, &dest)?;
+					if let Err(error) = Config::AssetTransactor::deposit_asset(&asset, &dest) {
+						self.settle_fees(fees, false);
+						return Err(error)
+					}
 				}
 				// Note that we pass `None` as `maybe_failed_bin` and drop any assets which cannot
 				// be reanchored  because we have already called `deposit_asset` on all assets.
 				let assets = Self::reanchored(deposited, &dest, None);
-				let mut message = vec![ReserveAssetDeposited(assets), ClearOrigin];
-				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(dest, Xcm(message)).map_err(Into::into)
+				self.send_paid(dest, Self::with_assets(message, assets), fees)
 			},
 			InitiateReserveWithdraw { assets, reserve, xcm } => {
+				let quoted = Self::reanchored(self.holding.min(&assets), &reserve, None);
+				let message = self.transfer_message(WithdrawAsset(quoted), xcm);
+				let fees = self.take_delivery_fees(&reserve, &message)?;
 				// Note that here we are able to place any assets which could not be reanchored
 				// back into Holding.
 				let assets = Self::reanchored(
@@ -417,11 +522,12 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					&reserve,
 					Some(&mut self.holding),
 				);
-				let mut message = vec![WithdrawAsset(assets), ClearOrigin];
-				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(reserve, Xcm(message)).map_err(Into::into)
+				self.send_paid(reserve, Self::with_assets(message, assets), fees)
 			},
 			InitiateTeleport { assets, dest, xcm } => {
+				let quoted = Self::reanchored(self.holding.min(&assets), &dest, None);
+				let message = self.transfer_message(ReceiveTeleportedAsset(quoted), xcm);
+				let fees = self.take_delivery_fees(&dest, &message)?;
 				// We must do this first in order to resolve wildcards.
 				let assets = self.holding.saturating_take(assets);
 				for asset in assets.assets_iter() {
@@ -430,18 +536,14 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				// Note that we pass `None` as `maybe_failed_bin` and drop any assets which cannot
 				// be reanchored  because we have already checked all assets out.
 				let assets = Self::reanchored(assets, &dest, None);
-				let mut message = vec![ReceiveTeleportedAsset(assets), ClearOrigin];
-				message.extend(xcm.0.into_iter());
-				Config::XcmSender::send_xcm(dest, Xcm(message)).map_err(Into::into)
+				self.send_paid(dest, Self::with_assets(message, assets), fees)
 			},
-			QueryHolding { query_id, dest, assets, max_response_weight } => {
+			ReportHolding { response_info, assets } => {
 				// Note that we pass `None` as `maybe_failed_bin` since no assets were ever removed
 				// from Holding.
-				let assets = Self::reanchored(self.holding.min(&assets), &dest, None);
-				let max_weight = max_response_weight;
-				let response = Response::Assets(assets);
-				let instruction = QueryResponse { query_id, response, max_weight };
-				Config::XcmSender::send_xcm(dest, Xcm(vec![instruction])).map_err(Into::into)
+				let assets =
+					Self::reanchored(self.holding.min(&assets), &response_info.destination, None);
+				self.respond(response_info, Response::Assets(assets))
 			},
 			BuyExecution { fees, weight_limit } => {
 				// There is no need to buy any weight is `weight_limit` is `Unlimited` since it
@@ -503,6 +605,102 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				ensure!(&self.original_origin == origin, XcmError::BadOrigin);
 				Config::SubscriptionService::stop(origin)
 			},
+			ExpectAsset(assets) =>
+				self.holding.ensure_contains(&assets).map_err(|_| XcmError::ExpectationFalse),
+			ExpectOrigin(origin) => {
+				ensure!(self.origin == origin, XcmError::ExpectationFalse);
+				Ok(())
+			},
+			ExpectError(error) => {
+				ensure!(self.error == error, XcmError::ExpectationFalse);
+				Ok(())
+			},
+			UniversalOrigin(new_global) => {
+				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
+				let universal_location = Config::UniversalLocation::get();
+				// Nobody may pretend to be the root of our own consensus.
+				ensure!(universal_location.first() != Some(&new_global), XcmError::NoPermission);
+				ensure!(
+					Config::UniversalAliases::contains(&(origin.clone(), new_global.clone())),
+					XcmError::NoPermission
+				);
+				// The new origin is `new_global` within the root of the universe, as seen from
+				// here.
+				let parents =
+					u8::try_from(universal_location.len()).map_err(|_| XcmError::Unanchored)?;
+				self.origin = Some(MultiLocation::new(parents, X1(new_global)));
+				Ok(())
+			},
+			ExportMessage { network, destination, xcm } => {
+				let origin = self.origin.as_ref().ok_or(XcmError::BadOrigin)?;
+				let universal_source = Self::universal_source(origin)?;
+				// The channel is reserved for future use; only the default one is used for now.
+				let channel = 0;
+				let fees = Config::MessageExporter::quote_export(&network, &destination, &xcm)
+					.unwrap_or_else(MultiAssets::new);
+				let fees = self.take_fees(fees)?;
+				let result = Config::MessageExporter::export_xcm(
+					network,
+					channel,
+					universal_source,
+					destination,
+					xcm,
+				);
+				self.settle_fees(fees, result.is_ok());
+				result.map_err(Into::into)
+			},
+			LockAsset { asset, unlocker } => {
+				let origin = self.origin.clone().ok_or(XcmError::BadOrigin)?;
+				let context = Config::UniversalLocation::get();
+				let remote_asset = asset
+					.clone()
+					.reanchored(&unlocker, &context)
+					.map_err(|()| XcmError::MultiLocationNotInvertible)?;
+				let remote_owner = origin
+					.clone()
+					.reanchored(&unlocker, &context)
+					.map_err(|_| XcmError::MultiLocationNotInvertible)?;
+				// Take the lock before notifying the unlocker, so that it never learns of a lock
+				// which doesn't exist.
+				Config::AssetLocker::prepare_lock(unlocker.clone(), asset, origin)?.enact()?;
+				let message =
+					Xcm(vec![NoteUnlockable { asset: remote_asset, owner: remote_owner }]);
+				self.send(unlocker, message)?;
+				Ok(())
+			},
+			UnlockAsset { asset, target } => {
+				let origin = self.origin.clone().ok_or(XcmError::BadOrigin)?;
+				Config::AssetLocker::prepare_unlock(origin, asset, target)?.enact()?;
+				Ok(())
+			},
+			NoteUnlockable { asset, owner } => {
+				let origin = self.origin.clone().ok_or(XcmError::BadOrigin)?;
+				Config::AssetLocker::note_unlockable(origin, asset, owner)?;
+				Ok(())
+			},
+			RequestUnlock { asset, locker } => {
+				let origin = self.origin.clone().ok_or(XcmError::BadOrigin)?;
+				let context = Config::UniversalLocation::get();
+				let remote_asset = asset
+					.clone()
+					.reanchored(&locker, &context)
+					.map_err(|()| XcmError::MultiLocationNotInvertible)?;
+				let remote_target = origin
+					.clone()
+					.reanchored(&locker, &context)
+					.map_err(|_| XcmError::MultiLocationNotInvertible)?;
+				// Reduce what is unlockable before asking the locker to unlock it, so that the same
+				// asset can't be requested twice.
+				Config::AssetLocker::prepare_reduce_unlockable(locker.clone(), asset, origin)?
+					.enact()?;
+				let message = Xcm(vec![UnlockAsset { asset: remote_asset, target: remote_target }]);
+				self.send(locker, message)?;
+				Ok(())
+			},
+			SetTopic(topic) => {
+				self.topic = Some(topic);
+				Ok(())
+			},
 			ExchangeAsset { .. } => Err(XcmError::Unimplemented),
 			HrmpNewChannelOpenRequest { .. } => Err(XcmError::Unimplemented),
 			HrmpChannelAccepted { .. } => Err(XcmError::Unimplemented),
@@ -510,13 +708,140 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		}
 	}
 
+	/// Send `message` to `dest`, paying for its delivery unless the `FeeManager` waives the fees.
+	fn send(&mut self, dest: MultiLocation, message: Xcm<()>) -> Result<(), XcmError> {
+		let message = self.with_topic(message);
+		let fees = self.take_delivery_fees(&dest, &message)?;
+		self.send_paid(dest, message, fees)
+	}
+
+	/// Send `message` to `dest`, whose delivery has already been paid with `fees`.
+	///
+	/// The fees are refunded if the message cannot be sent.
+	fn send_paid(
+		&mut self,
+		dest: MultiLocation,
+		message: Xcm<()>,
+		fees: Option<WithdrawnFees>,
+	) -> Result<(), XcmError> {
+		let result = Config::XcmSender::send_xcm(dest, message);
+		self.settle_fees(fees, result.is_ok());
+		result.map_err(Into::into)
+	}
+
+	/// Carry the topic over to `message`, so that it can be correlated with the one being executed.
+	fn with_topic(&self, mut message: Xcm<()>) -> Xcm<()> {
+		if let Some(topic) = self.topic {
+			if message.topic() != Some(topic) {
+				message.0.push(SetTopic(topic));
+			}
+		}
+		message
+	}
+
+	/// The message which places some assets on `dest` with `instruction` and then executes `xcm`
+	/// there.
+	fn transfer_message(&self, instruction: Instruction<()>, xcm: Xcm<()>) -> Xcm<()> {
+		let mut message = vec![instruction, ClearOrigin];
+		message.extend(xcm.0.into_iter());
+		self.with_topic(Xcm(message))
+	}
+
+	/// Replace the assets carried by the first instruction of a message built with
+	/// `transfer_message`, once it is known which assets were actually transferred.
+	fn with_assets(mut message: Xcm<()>, assets: MultiAssets) -> Xcm<()> {
+		match message.0.first_mut() {
+			Some(ReserveAssetDeposited(carried)) |
+			Some(WithdrawAsset(carried)) |
+			Some(ReceiveTeleportedAsset(carried)) => *carried = assets,
+			_ => {},
+		}
+		message
+	}
+
+	/// Quote the delivery of `message` to `dest` and withdraw the fees for it.
+	fn take_delivery_fees(
+		&mut self,
+		dest: &MultiLocation,
+		message: &Xcm<()>,
+	) -> Result<Option<WithdrawnFees>, XcmError> {
+		let fees =
+			Config::XcmSender::quote_delivery(dest, message).unwrap_or_else(MultiAssets::new);
+		self.take_fees(fees)
+	}
+
+	/// Withdraw `fees` unless the `FeeManager` waives them.
+	///
+	/// The fees are taken from Holding if it contains them, otherwise they are withdrawn from the
+	/// account of the origin. They must be passed to `settle_fees` once it is known whether the
+	/// service they pay for was provided.
+	fn take_fees(&mut self, fees: MultiAssets) -> Result<Option<WithdrawnFees>, XcmError> {
+		if fees.is_none() || Config::FeeManager::is_waived(self.origin.as_ref()) {
+			return Ok(None)
+		}
+		if let Ok(assets) = self.holding.try_take(fees.clone().into()) {
+			return Ok(Some(WithdrawnFees { assets, payer: None }))
+		}
+		let origin = self.origin.clone().ok_or(XcmError::NotHoldingFees)?;
+		let mut assets = Assets::new();
+		for asset in fees.inner() {
+			match Config::AssetTransactor::withdraw_asset(asset, &origin) {
+				Ok(withdrawn) => assets.subsume_assets(withdrawn),
+				Err(_) => {
+					// Give back whatever was already withdrawn.
+					self.settle_fees(Some(WithdrawnFees { assets, payer: Some(origin) }), false);
+					return Err(XcmError::NotHoldingFees)
+				},
+			}
+		}
+		Ok(Some(WithdrawnFees { assets, payer: Some(origin) }))
+	}
+
+	/// Hand fees withdrawn by `take_fees` to the `FeeManager` if the service they pay for was
+	/// provided, or otherwise return them to where they were taken from.
+	fn settle_fees(&mut self, fees: Option<WithdrawnFees>, provided: bool) {
+		let WithdrawnFees { assets, payer } = match fees {
+			Some(fees) => fees,
+			None => return,
+		};
+		if provided {
+			Config::FeeManager::handle_fee(assets.into());
+			return
+		}
+		match payer {
+			None => self.holding.subsume_assets(assets),
+			Some(payer) =>
+				for asset in assets.into_assets_iter() {
+					// Keep whatever can't be deposited back in Holding, from where it can be
+					// trapped and later claimed.
+					if Config::AssetTransactor::deposit_asset(&asset, &payer).is_err() {
+						self.holding.subsume(asset);
+					}
+				},
+		}
+	}
+
+	/// Send a `QueryResponse` with the given `response`, as described by `info`.
+	fn respond(&mut self, info: QueryResponseInfo, response: Response) -> Result<(), XcmError> {
+		let QueryResponseInfo { destination, query_id, max_weight } = info;
+		let instruction = QueryResponse { query_id, response, max_weight };
+		self.send(destination, Xcm(vec![instruction]))
+	}
+
+	/// The location of `origin`, relative to the root of the universe.
+	fn universal_source(origin: &MultiLocation) -> Result<InteriorMultiLocation, XcmError> {
+		Config::UniversalLocation::get()
+			.universal_location_of(origin)
+			.map_err(|()| XcmError::Unanchored)
+	}
+
 	/// NOTE: Any assets which were unable to be reanchored are introduced into `failed_bin`.
 	fn reanchored(
 		mut assets: Assets,
 		dest: &MultiLocation,
 		maybe_failed_bin: Option<&mut Assets>,
 	) -> MultiAssets {
-		assets.reanchor(dest, &Config::LocationInverter::ancestry(), maybe_failed_bin);
+		assets.reanchor(dest, &Config::UniversalLocation::get(), maybe_failed_bin);
 		assets.into_assets_iter().collect::<Vec<_>>().into()
 	}
 }
@@ -0,0 +1,93 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::{InteriorMultiLocation, MultiAssets, NetworkId, SendError, SendResult, Xcm};
+
+/// Utility for delivering a message to a system under a different (non-local) consensus with a
+/// spoofed origin. This essentially defines the behaviour of the `ExportMessage` XCM instruction.
+///
+/// These can be amalgamated in tuples to form sophisticated routing systems. In tuple format, each
+/// exporter might return `CannotReachDestination` to pass the message to the next exporter.
+pub trait ExportXcm {
+	/// Export `message` to `destination` within the global consensus system `network`, over
+	/// the given `channel`.
+	///
+	/// `universal_source` is the location of the origin of the message, relative to the root of the
+	/// universe. It is up to the exporter to make the message appear to come from this location on
+	/// the remote system.
+	///
+	/// If it is not a destination which can be reached with this type but possibly could by
+	/// others, then it *MUST* return `CannotReachDestination`. Any other error will cause the
+	/// tuple implementation to exit early without trying other type fields.
+	fn export_xcm(
+		network: NetworkId,
+		channel: u32,
+		universal_source: InteriorMultiLocation,
+		destination: InteriorMultiLocation,
+		message: Xcm<()>,
+	) -> SendResult;
+
+	/// Return the fees to be paid for exporting `message` to `destination` within `network`.
+	///
+	/// Returns `None` if the exporter cannot reach the destination, in which case other types
+	/// might be asked for a quote. Exporting is free if an empty set of assets is returned.
+	fn quote_export(
+		_network: &NetworkId,
+		_destination: &InteriorMultiLocation,
+		_message: &Xcm<()>,
+	) -> Option<MultiAssets> {
+		None
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl ExportXcm for Tuple {
+	fn export_xcm(
+		network: NetworkId,
+		channel: u32,
+		universal_source: InteriorMultiLocation,
+		destination: InteriorMultiLocation,
+		message: Xcm<()>,
+	) -> SendResult {
+		for_tuples!( #(
+			// we shadow `message` in each expansion for the next one.
+			let message = match Tuple::export_xcm(
+				network.clone(),
+				channel,
+				universal_source.clone(),
+				destination.clone(),
+				message,
+			) {
+				Err(SendError::CannotReachDestination(_, m)) => m,
+				o @ _ => return o,
+			};
+		)* );
+		Err(SendError::CannotReachDestination(destination.into(), message))
+	}
+
+	fn quote_export(
+		network: &NetworkId,
+		destination: &InteriorMultiLocation,
+		message: &Xcm<()>,
+	) -> Option<MultiAssets> {
+		for_tuples!( #(
+			if let Some(fees) = Tuple::quote_export(network, destination, message) {
+				return Some(fees)
+			}
+		)* );
+		None
+	}
+}
@@ -0,0 +1,38 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::latest::{MultiAssets, MultiLocation};
+
+/// Handler for the fees of delivering messages sent by the executor, or on behalf of some location.
+pub trait FeeManager {
+	/// Determine if fees should be waived for messages sent on behalf of `origin`.
+	///
+	/// `origin` is `None` if the origin of the sending XCM was cleared.
+	fn is_waived(origin: Option<&MultiLocation>) -> bool;
+
+	/// Do something with the fees which were just collected. Fees which aren't stored anywhere are
+	/// burned.
+	fn handle_fee(fees: MultiAssets);
+}
+
+/// Delivery is free for everyone.
+impl FeeManager for () {
+	fn is_waived(_origin: Option<&MultiLocation>) -> bool {
+		true
+	}
+
+	fn handle_fee(_fees: MultiAssets) {}
+}
@@ -0,0 +1,39 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use sp_std::vec::Vec;
+use xcm::{VersionedMultiLocation, VersionedXcm};
+
+/// Inspection of the messages which a router has queued for delivery but not yet delivered.
+///
+/// This lets the messages sent by an XCM be reported without delivering them, such as when
+/// dry-running it. Implementations for tuples report the messages of every element.
+pub trait InspectMessageQueues {
+	/// Return all messages currently queued for delivery, grouped by destination. The messages
+	/// for each destination are in the order in which they were queued.
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl InspectMessageQueues for Tuple {
+	fn get_messages() -> Vec<(VersionedMultiLocation, Vec<VersionedXcm<()>>)> {
+		let mut messages = Vec::new();
+		for_tuples!( #(
+			messages.extend(Tuple::get_messages());
+		)* );
+		messages
+	}
+}
@@ -16,12 +16,20 @@
 
 //! Various traits used in configuring the executor.
 
+mod asset_lock;
+pub use asset_lock::{AssetLock, Enact, LockError};
 mod conversion;
-pub use conversion::{Convert, ConvertOrigin, Decoded, Encoded, Identity, InvertLocation, JustTry};
+pub use conversion::{Convert, ConvertOrigin, Decoded, Encoded, Identity, JustTry};
 mod drop_assets;
 pub use drop_assets::{ClaimAssets, DropAssets};
+mod export;
+pub use export::ExportXcm;
+mod fee_manager;
+pub use fee_manager::FeeManager;
 mod filter_asset_location;
 pub use filter_asset_location::FilterAssetLocation;
+mod inspect_message_queues;
+pub use inspect_message_queues::InspectMessageQueues;
 mod matches_fungible;
 pub use matches_fungible::MatchesFungible;
 mod matches_fungibles;
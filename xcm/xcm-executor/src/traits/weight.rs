@@ -15,7 +15,7 @@
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::Assets;
-use sp_std::result::Result;
+use sp_std::{result::Result, vec::Vec};
 use xcm::latest::{prelude::*, Weight};
 
 /// Determine the weight of an XCM message.
@@ -58,6 +58,21 @@ pub trait WeightTrader: Sized {
 	fn refund_weight(&mut self, _weight: Weight) -> Option<MultiAsset> {
 		None
 	}
+
+	/// Return the assets in which this trader accepts payment for weight.
+	///
+	/// Default implementation accepts nothing.
+	fn acceptable_fee_assets() -> Vec<AssetId> {
+		Vec::new()
+	}
+
+	/// Return the amount of `asset` which this trader would charge for `weight`, or `None` if it
+	/// doesn't accept payment in `asset`.
+	///
+	/// Default implementation accepts nothing.
+	fn weight_to_fee(_weight: Weight, _asset: &AssetId) -> Option<u128> {
+		None
+	}
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(30)]
@@ -87,4 +102,25 @@ impl WeightTrader for Tuple {
 		)* );
 		None
 	}
+
+	fn acceptable_fee_assets() -> Vec<AssetId> {
+		let mut assets = Vec::new();
+		for_tuples!( #(
+			for asset in Tuple::acceptable_fee_assets() {
+				if !assets.contains(&asset) {
+					assets.push(asset);
+				}
+			}
+		)* );
+		assets
+	}
+
+	fn weight_to_fee(weight: Weight, asset: &AssetId) -> Option<u128> {
+		for_tuples!( #(
+			if let Some(fee) = Tuple::weight_to_fee(weight, asset) {
+				return Some(fee);
+			}
+		)* );
+		None
+	}
 }
@@ -19,7 +19,10 @@ mod relay_chain;
 
 use polkadot_parachain::primitives::Id as ParaId;
 use sp_runtime::traits::AccountIdConversion;
-use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+use sp_std::{cell::RefCell, collections::vec_deque::VecDeque};
+use xcm::latest::prelude::*;
+use xcm_executor::{traits::ExportXcm, XcmExecutor};
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
 
 pub const ALICE: sp_runtime::AccountId32 = sp_runtime::AccountId32::new([0u8; 32]);
 pub const INITIAL_BALANCE: u128 = 1_000_000_000;
@@ -50,6 +53,14 @@ decl_test_relay_chain! {
 	}
 }
 
+decl_test_relay_chain! {
+	pub struct RemoteRelay {
+		Runtime = relay_chain::Runtime,
+		XcmConfig = relay_chain::XcmConfig,
+		new_ext = remote_relay_ext(),
+	}
+}
+
 decl_test_network! {
 	pub struct MockNet {
 		relay_chain = Relay,
@@ -97,6 +108,91 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 	ext
 }
 
+/// The relay chain of another global consensus system, `Polkadot`, reachable from the test network
+/// only over the mock bridge.
+pub fn remote_relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System, ThisNetwork};
+
+	let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		ThisNetwork::set(&NetworkId::Polkadot);
+	});
+	ext
+}
+
+thread_local! {
+	pub static BRIDGE_MESSAGE_BUS: RefCell<VecDeque<(NetworkId, InteriorMultiLocation, Xcm<()>)>>
+		= RefCell::new(VecDeque::new());
+}
+
+/// A mock bridge between the relay chain of the test network and `RemoteRelay`. Messages exported
+/// over it are queued until `process_bridged_messages` is called.
+pub struct MockBridge;
+impl ExportXcm for MockBridge {
+	fn export_xcm(
+		network: NetworkId,
+		_channel: u32,
+		universal_source: InteriorMultiLocation,
+		destination: InteriorMultiLocation,
+		message: Xcm<()>,
+	) -> SendResult {
+		// Only the relay chains themselves are at the ends of the bridge.
+		match (&network, &destination) {
+			(NetworkId::Kusama | NetworkId::Polkadot, Here) => {
+				BRIDGE_MESSAGE_BUS
+					.with(|b| b.borrow_mut().push_back((network, universal_source, message)));
+				Ok(())
+			},
+			_ => Err(SendError::CannotReachDestination(destination.into(), message)),
+		}
+	}
+
+	fn quote_export(
+		network: &NetworkId,
+		destination: &InteriorMultiLocation,
+		_message: &Xcm<()>,
+	) -> Option<MultiAssets> {
+		// Bridging is free in the simulated network.
+		match (network, destination) {
+			(NetworkId::Kusama | NetworkId::Polkadot, Here) => Some(MultiAssets::new()),
+			_ => None,
+		}
+	}
+}
+
+/// Deliver all messages queued on the mock bridge, returning the outcome of executing each of them.
+///
+/// Each message is executed by the destination relay chain with an origin aliasing the
+/// `universal_source` it was exported from.
+pub fn process_bridged_messages() -> Vec<Outcome> {
+	let mut outcomes = Vec::new();
+	while let Some((network, universal_source, message)) =
+		BRIDGE_MESSAGE_BUS.with(|b| b.borrow_mut().pop_front())
+	{
+		let (interior, global_consensus) = universal_source.split_first();
+		let mut instructions: Vec<Instruction<relay_chain::RuntimeCall>> = Vec::new();
+		if let Some(global_consensus) = global_consensus {
+			instructions.push(UniversalOrigin(global_consensus));
+		}
+		if interior != Here {
+			instructions.push(DescendOrigin(interior));
+		}
+		instructions.extend(message.0.into_iter().map(Instruction::into));
+		let message = Xcm(instructions);
+		let execute =
+			|| XcmExecutor::<relay_chain::XcmConfig>::execute_xcm(Here, message, u64::MAX);
+		let outcome = match network {
+			NetworkId::Polkadot => RemoteRelay::execute_with(execute),
+			_ => Relay::execute_with(execute),
+		};
+		outcomes.push(outcome);
+	}
+	outcomes
+}
+
 pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
 pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
 
@@ -262,11 +358,11 @@ mod tests {
 
 	/// Scenario:
 	/// A parachain wants to be notified that a transfer worked correctly.
-	/// It sends a `QueryHolding` after the deposit to get notified on success.
+	/// It sends a `ReportHolding` after the deposit to get notified on success.
 	///
 	/// Asserts that the balances are updated correctly and the expected XCM is sent.
 	#[test]
-	fn query_holding() {
+	fn report_holding() {
 		MockNet::reset();
 
 		let send_amount = 10;
@@ -282,14 +378,16 @@ mod tests {
 					max_assets: 1,
 					beneficiary: Parachain(2).into(),
 				},
-				QueryHolding {
-					query_id: query_id_set,
-					dest: Parachain(1).into(),
+				ReportHolding {
+					response_info: QueryResponseInfo {
+						destination: Parachain(1).into(),
+						query_id: query_id_set,
+						max_weight: 1_000_000_000,
+					},
 					assets: All.into(),
-					max_response_weight: 1_000_000_000,
 				},
 			]);
-			// Send withdraw and deposit with query holding
+			// Send withdraw and deposit with report holding
 			assert_ok!(ParachainPalletXcm::send_xcm(Here, Parent, message.clone(),));
 		});
 
@@ -316,4 +414,73 @@ mod tests {
 			);
 		});
 	}
+
+	/// Scenario:
+	/// A parachain sends funds to another parachain through the relay chain, tagging the message
+	/// with a topic.
+	///
+	/// Asserts that both hops of the transfer can be traced by that topic.
+	#[test]
+	fn topic_traces_message_across_hops() {
+		MockNet::reset();
+
+		let send_amount = 10;
+		let topic = [7; 32];
+
+		ParaA::execute_with(|| {
+			let message = Xcm(vec![
+				SetTopic(topic),
+				WithdrawAsset((Here, send_amount).into()),
+				buy_execution((Here, send_amount)),
+				DepositReserveAsset {
+					assets: All.into(),
+					max_assets: 1,
+					dest: Parachain(2).into(),
+					xcm: Xcm(vec![DepositAsset {
+						assets: All.into(),
+						max_assets: 1,
+						beneficiary: AccountId32 { network: Any, id: ALICE.into() }.into(),
+					}]),
+				},
+			]);
+			assert_ok!(ParachainPalletXcm::send_xcm(Here, Parent, message));
+		});
+
+		ParaB::execute_with(|| {
+			assert_eq!(
+				pallet_balances::Pallet::<parachain::Runtime>::free_balance(&ALICE),
+				INITIAL_BALANCE + send_amount
+			);
+		});
+
+		let trace = xcm_simulator::trace(topic);
+		assert_eq!(
+			trace.iter().map(|m| (m.from.clone(), m.to.clone())).collect::<Vec<_>>(),
+			vec![
+				(MultiLocation::new(0, X1(Parachain(1))), MultiLocation::here()),
+				(MultiLocation::here(), MultiLocation::new(0, X1(Parachain(2)))),
+			],
+		);
+		assert_eq!(trace[1].message.topic(), Some(topic));
+	}
+
+	#[test]
+	fn export_to_remote_consensus() {
+		MockNet::reset();
+		RemoteRelay::reset_ext();
+
+		// Parachain #1 of Kusama sends a message to the Polkadot relay chain.
+		let expected_origin = (Parent, GlobalConsensus(Kusama), Parachain(1));
+		ParaA::execute_with(|| {
+			assert_ok!(ParachainPalletXcm::send_xcm(
+				Here,
+				(Parent, Parent, GlobalConsensus(Polkadot)),
+				Xcm(vec![ExpectOrigin(Some(expected_origin.into()))]),
+			));
+		});
+
+		// It arrives with the origin of parachain #1 as seen from Polkadot.
+		assert_eq!(process_bridged_messages(), vec![Outcome::Complete(3_000)]);
+		assert!(process_bridged_messages().is_empty());
+	}
 }
@@ -77,6 +77,35 @@ pub fn encode_xcm(message: Xcm<()>, message_kind: MessageKind) -> Vec<u8> {
 	}
 }
 
+/// A message dispatched over one of the simulated message buses.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TracedMessage {
+	/// The ID of the message: its topic if it sets one, the hash of its encoding otherwise.
+	pub id: XcmHash,
+	/// The sending chain, relative to the relay chain.
+	pub from: MultiLocation,
+	/// The receiving chain, relative to the relay chain.
+	pub to: MultiLocation,
+	/// The message as it was sent.
+	pub message: Xcm<()>,
+}
+
+/// Records a message dispatched from `from` to `to`, both relative to the relay chain.
+pub fn record_message(from: MultiLocation, to: MultiLocation, message: &Xcm<()>) {
+	let id = xcm_executor::message_id(&VersionedXcm::<()>::from(message.clone()));
+	let traced = TracedMessage { id, from, to, message: message.clone() };
+	MESSAGE_TRACE.with(|t| t.borrow_mut().push(traced));
+}
+
+/// Returns every message dispatched with the given `id`, in the order in which they were
+/// delivered.
+///
+/// Messages carrying the same topic share their ID, so this is the end-to-end path of a message
+/// across the simulated chains.
+pub fn trace(id: XcmHash) -> Vec<TracedMessage> {
+	MESSAGE_TRACE.with(|t| t.borrow().iter().filter(|m| m.id == id).cloned().collect())
+}
+
 /// The macro is implementing upward message passing(UMP) for the provided relay
 /// chain struct. The struct has to provide the XCM configuration for the relay
 /// chain.
@@ -230,6 +259,7 @@ thread_local! {
 		= RefCell::new(VecDeque::new());
 	pub static RELAY_MESSAGE_BUS: RefCell<VecDeque<(MultiLocation, Xcm<()>)>>
 		= RefCell::new(VecDeque::new());
+	pub static MESSAGE_TRACE: RefCell<Vec<TracedMessage>> = RefCell::new(Vec::new());
 }
 
 /// Declares a test network that consists of a relay chain and multiple
@@ -266,6 +296,8 @@ macro_rules! decl_test_network {
 				$crate::RELAY_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
 				// Reset parachain message bus.
 				$crate::PARA_MESSAGE_BUS.with(|b| b.replace(VecDeque::new()));
+				// Reset the trace of dispatched messages.
+				$crate::MESSAGE_TRACE.with(|t| t.replace(Vec::new()));
 				<$relay_chain>::reset_ext();
 				$( <$parachain>::reset_ext(); )*
 			}
@@ -287,6 +319,11 @@ macro_rules! decl_test_network {
 				|b| b.borrow_mut().pop_front()) {
 				match destination.interior() {
 					$crate::Junctions::Here if destination.parent_count() == 1 => {
+						$crate::record_message(
+							$crate::Parachain(para_id.into()).into(),
+							$crate::MultiLocation::here(),
+							&message,
+						);
 						let encoded = $crate::encode_xcm(message, $crate::MessageKind::Ump);
 						let r = <$relay_chain>::process_upward_message(
 							para_id, &encoded[..],
@@ -298,6 +335,11 @@ macro_rules! decl_test_network {
 					},
 					$(
 						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 1 => {
+							$crate::record_message(
+								$crate::Parachain(para_id.into()).into(),
+								$crate::Parachain(*id).into(),
+								&message,
+							);
 							let encoded = $crate::encode_xcm(message, $crate::MessageKind::Xcmp);
 							let messages = vec![(para_id, 1, &encoded[..])];
 							let _weight = <$parachain>::handle_xcmp_messages(
@@ -324,6 +366,11 @@ macro_rules! decl_test_network {
 				match destination.interior() {
 					$(
 						$crate::X1($crate::Parachain(id)) if *id == $para_id && destination.parent_count() == 0 => {
+							$crate::record_message(
+								$crate::MultiLocation::here(),
+								$crate::Parachain(*id).into(),
+								&message,
+							);
 							let encoded = $crate::encode_xcm(message, $crate::MessageKind::Dmp);
 							// NOTE: RelayChainBlockNumber is hard-coded to 1
 							let messages = vec![(1, encoded)];
@@ -365,6 +412,13 @@ macro_rules! decl_test_network {
 			}
 		}
 
+		impl<T> $crate::QuoteDelivery for ParachainXcmRouter<T> {
+			fn quote_delivery(_destination: &$crate::MultiLocation, _message: &$crate::Xcm<()>) -> Option<$crate::MultiAssets> {
+				// Delivery is free in the simulated network.
+				None
+			}
+		}
+
 		/// XCM router for relay chain.
 		pub struct RelayChainXcmRouter;
 		impl $crate::SendXcm for RelayChainXcmRouter {
@@ -384,5 +438,12 @@ macro_rules! decl_test_network {
 				}
 			}
 		}
+
+		impl $crate::QuoteDelivery for RelayChainXcmRouter {
+			fn quote_delivery(_destination: &$crate::MultiLocation, _message: &$crate::Xcm<()>) -> Option<$crate::MultiAssets> {
+				// Delivery is free in the simulated network.
+				None
+			}
+		}
 	};
 }